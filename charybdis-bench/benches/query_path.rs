@@ -0,0 +1,173 @@
+use charybdis::fragment::QueryFragment;
+use charybdis::named_values;
+use charybdis::operations::Find;
+use charybdis::query::QueryValue;
+use charybdis::scylla::FromRow;
+use charybdis::types::{Int, Uuid};
+use charybdis_bench::{
+    columns, live_cluster, models, primary_key_columns, rows, serialize, BenchModel, LiveCluster, MediumRow, NarrowRow,
+    WideRow,
+};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use tokio::runtime::Runtime;
+
+/// Number of rows deserialized by single iteration, and of rows in each partition read by stream benchmarks.
+const ROWS: [usize; 2] = [100, 1000];
+
+fn serialize_model<M: BenchModel>(c: &mut Criterion, name: &str) {
+    let model = models::<M>(Uuid::new_v4(), 1).remove(0);
+    let columns = columns::<M>();
+    let primary_key_columns = primary_key_columns::<M>();
+
+    let mut group = c.benchmark_group(format!("serialize/{}", name));
+
+    group.bench_function("model", |b| {
+        b.iter(|| serialize(&QueryValue::<M, M>::Model(black_box(&model)), &columns))
+    });
+
+    group.bench_function("primary_key", |b| {
+        b.iter(|| {
+            let value = QueryValue::<M, M>::PrimaryKey(black_box(&model).primary_key_values());
+            serialize(&value, &primary_key_columns)
+        })
+    });
+
+    group.finish();
+}
+
+fn serialize_values(c: &mut Criterion) {
+    serialize_model::<NarrowRow>(c, "narrow");
+    serialize_model::<MediumRow>(c, "medium");
+    serialize_model::<WideRow>(c, "wide");
+
+    let partition_id = Uuid::new_v4();
+    let id: Int = 42;
+    let primary_key_columns = primary_key_columns::<NarrowRow>();
+    let partition_key_columns = &primary_key_columns[..1];
+
+    let mut group = c.benchmark_group("serialize/values");
+
+    group.bench_function("owned", |b| {
+        b.iter(|| {
+            let value = QueryValue::<_, NarrowRow>::Owned((black_box(partition_id),));
+            serialize(&value, partition_key_columns)
+        })
+    });
+
+    let values = (partition_id, id);
+    group.bench_function("ref", |b| {
+        b.iter(|| {
+            serialize(
+                &QueryValue::<_, NarrowRow>::Ref(black_box(&values)),
+                &primary_key_columns,
+            )
+        })
+    });
+
+    group.bench_function("named", |b| {
+        b.iter(|| {
+            let value = QueryValue::<(), NarrowRow>::Named(named_values! {
+                "partition_id" => black_box(partition_id),
+                "id" => black_box(id),
+            });
+            serialize(&value, &primary_key_columns)
+        })
+    });
+
+    group.bench_function("fragment", |b| {
+        b.iter(|| {
+            let value = QueryValue::<_, NarrowRow>::Fragment(
+                Box::new(QueryValue::Owned((black_box(partition_id),))),
+                QueryFragment::new("id > ?", (black_box(id),)),
+            );
+            serialize(&value, &primary_key_columns)
+        })
+    });
+
+    group.finish();
+}
+
+fn deserialize_model<M: BenchModel>(c: &mut Criterion, name: &str) {
+    let mut group = c.benchmark_group(format!("deserialize/{}", name));
+
+    for count in ROWS {
+        let partition_rows = rows::<M>(Uuid::new_v4(), count);
+
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(count),
+            &partition_rows,
+            |b, partition_rows| {
+                b.iter_batched(
+                    || partition_rows.clone(),
+                    |partition_rows| {
+                        partition_rows
+                            .into_iter()
+                            .map(|row| M::from_row(row).unwrap())
+                            .collect::<Vec<M>>()
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn deserialize_rows(c: &mut Criterion) {
+    deserialize_model::<NarrowRow>(c, "narrow");
+    deserialize_model::<MediumRow>(c, "medium");
+    deserialize_model::<WideRow>(c, "wide");
+}
+
+fn stream_model<M: BenchModel<PartitionKey = (Uuid,)>>(
+    c: &mut Criterion,
+    runtime: &Runtime,
+    cluster: &LiveCluster,
+    name: &str,
+) {
+    let session = cluster.session;
+    let mut group = c.benchmark_group(format!("stream/{}", name));
+
+    for count in ROWS {
+        let partition_id = Uuid::new_v4();
+
+        runtime
+            .block_on(cluster.seed::<M>(partition_id, count))
+            .expect("failed to seed partition");
+
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_function(BenchmarkId::from_parameter(count), |b| {
+            b.to_async(runtime).iter(|| async move {
+                M::find_by_partition_key_value((partition_id,))
+                    .execute(session)
+                    .await
+                    .unwrap()
+                    .try_collect()
+                    .await
+                    .unwrap()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn stream_partitions(c: &mut Criterion) {
+    let runtime = Runtime::new().expect("failed to build tokio runtime");
+
+    let cluster = match runtime.block_on(live_cluster()) {
+        Some(cluster) => cluster,
+        None => return,
+    };
+
+    stream_model::<NarrowRow>(c, &runtime, &cluster, "narrow");
+    stream_model::<MediumRow>(c, &runtime, &cluster, "medium");
+    stream_model::<WideRow>(c, &runtime, &cluster, "wide");
+
+    runtime.block_on(cluster.teardown());
+}
+
+criterion_group!(benches, serialize_values, deserialize_rows, stream_partitions);
+criterion_main!(benches);
@@ -0,0 +1,53 @@
+use std::env;
+
+use charybdis::errors::CharybdisError;
+use charybdis::operations::Insert;
+use charybdis::scylla::CachingSession;
+use charybdis::types::Uuid;
+use charybdis_testing::TestKeyspace;
+use migrate::MigrationBuilder;
+use scylla::SessionBuilder;
+
+use crate::rows::{models, BenchModel};
+
+const DEFAULT_SCYLLA_URI: &str = "127.0.0.1:9042";
+const STATEMENT_CACHE_SIZE: usize = 1000;
+const SEED_CHUNK_SIZE: usize = 100;
+
+/// Ephemeral keyspace of benchmarked models on Scylla at `SCYLLA_URI`, e.g. container started with
+/// `charybdis-testing/docker-compose.yml`.
+pub struct LiveCluster {
+    pub session: &'static CachingSession,
+    keyspace: TestKeyspace<'static>,
+}
+
+/// Connects to Scylla at `SCYLLA_URI` (defaults to `127.0.0.1:9042`) and migrates benchmarked models into new
+/// keyspace. `None` if cluster is not reachable, so benchmarks against live cluster can be skipped.
+pub async fn live_cluster() -> Option<LiveCluster> {
+    let uri = env::var("SCYLLA_URI").unwrap_or_else(|_| DEFAULT_SCYLLA_URI.to_string());
+
+    let session = match SessionBuilder::new().known_node(&uri).build().await {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("Scylla at {} is not reachable, skipping live benchmarks: {}", uri, e);
+            return None;
+        }
+    };
+
+    // session outlives benchmarks, as they are run until the process exits
+    let session: &'static CachingSession = Box::leak(Box::new(CachingSession::from(session, STATEMENT_CACHE_SIZE)));
+    let keyspace = TestKeyspace::ephemeral_with(session, MigrationBuilder::new().use_registry(true)).await;
+
+    Some(LiveCluster { session, keyspace })
+}
+
+impl LiveCluster {
+    /// Inserts `count` sample rows into given partition.
+    pub async fn seed<M: BenchModel>(&self, partition_id: Uuid, count: usize) -> Result<(), CharybdisError> {
+        M::insert_many(&mut models::<M>(partition_id, count), self.session, SEED_CHUNK_SIZE).await
+    }
+
+    pub async fn teardown(self) {
+        self.keyspace.teardown().await;
+    }
+}
@@ -0,0 +1,7 @@
+pub use cluster::{live_cluster, LiveCluster};
+pub use models::{MediumRow, NarrowRow, WideRow};
+pub use rows::{columns, models, primary_key_columns, rows, serialize, BenchModel};
+
+mod cluster;
+mod models;
+mod rows;
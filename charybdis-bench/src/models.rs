@@ -0,0 +1,123 @@
+use charybdis::macros::charybdis_model;
+use charybdis::scylla::{CqlValue, Row};
+use charybdis::types::{BigInt, Boolean, Double, Int, Text, Uuid};
+
+use crate::rows::BenchModel;
+
+/// Rows of benchmarked models are partitioned by `partition_id` and clustered by `id`, so whole partition is
+/// read by `find_by_partition_key_value`.
+#[charybdis_model(
+    table_name = bench_narrow_rows,
+    partition_keys = [partition_id],
+    clustering_keys = [id],
+    global_secondary_indexes = []
+)]
+#[derive(Default, Clone)]
+pub struct NarrowRow {
+    pub partition_id: Uuid,
+    pub id: Int,
+    pub value: Text,
+}
+
+#[charybdis_model(
+    table_name = bench_medium_rows,
+    partition_keys = [partition_id],
+    clustering_keys = [id],
+    global_secondary_indexes = []
+)]
+#[derive(Default, Clone)]
+pub struct MediumRow {
+    pub partition_id: Uuid,
+    pub id: Int,
+    pub name: Text,
+    pub email: Text,
+    pub description: Option<Text>,
+    pub age: Int,
+    pub score: Double,
+    pub active: Boolean,
+    pub views: BigInt,
+    pub likes: BigInt,
+}
+
+#[charybdis_model(
+    table_name = bench_wide_rows,
+    partition_keys = [partition_id],
+    clustering_keys = [id],
+    global_secondary_indexes = []
+)]
+#[derive(Default, Clone)]
+pub struct WideRow {
+    pub partition_id: Uuid,
+    pub id: Int,
+    pub text_1: Text,
+    pub text_2: Text,
+    pub text_3: Text,
+    pub text_4: Text,
+    pub text_5: Text,
+    pub text_6: Text,
+    pub text_7: Text,
+    pub text_8: Text,
+    pub text_9: Text,
+    pub text_10: Text,
+    pub text_11: Text,
+    pub text_12: Text,
+    pub text_13: Text,
+    pub text_14: Text,
+    pub text_15: Text,
+    pub number_1: BigInt,
+    pub number_2: BigInt,
+    pub number_3: BigInt,
+    pub number_4: BigInt,
+    pub number_5: BigInt,
+    pub number_6: BigInt,
+    pub number_7: BigInt,
+    pub number_8: BigInt,
+    pub number_9: BigInt,
+    pub number_10: BigInt,
+    pub number_11: BigInt,
+    pub number_12: BigInt,
+    pub number_13: BigInt,
+    pub number_14: BigInt,
+    pub number_15: BigInt,
+}
+
+fn key_columns(partition_id: Uuid, index: usize) -> Vec<Option<CqlValue>> {
+    vec![Some(CqlValue::Uuid(partition_id)), Some(CqlValue::Int(index as Int))]
+}
+
+impl BenchModel for NarrowRow {
+    fn sample_row(partition_id: Uuid, index: usize) -> Row {
+        let mut columns = key_columns(partition_id, index);
+        columns.push(Some(CqlValue::Text(format!("value {}", index))));
+
+        Row { columns }
+    }
+}
+
+impl BenchModel for MediumRow {
+    fn sample_row(partition_id: Uuid, index: usize) -> Row {
+        let mut columns = key_columns(partition_id, index);
+        columns.extend([
+            Some(CqlValue::Text(format!("user {}", index))),
+            Some(CqlValue::Text(format!("user{}@example.com", index))),
+            (index % 2 == 0).then(|| CqlValue::Text(format!("description of user {}", index))),
+            Some(CqlValue::Int((index % 100) as Int)),
+            Some(CqlValue::Double(index as Double / 10.0)),
+            Some(CqlValue::Boolean(index % 3 != 0)),
+            Some(CqlValue::BigInt(index as BigInt * 1000)),
+            Some(CqlValue::BigInt(index as BigInt * 10)),
+        ]);
+
+        Row { columns }
+    }
+}
+
+impl BenchModel for WideRow {
+    fn sample_row(partition_id: Uuid, index: usize) -> Row {
+        let mut columns = key_columns(partition_id, index);
+        columns.extend((1..=15).map(|n| Some(CqlValue::Text(format!("text {} of row {}", n, index)))));
+        columns.extend((1..=15).map(|n| Some(CqlValue::BigInt(n * index as BigInt))));
+
+        Row { columns }
+    }
+}
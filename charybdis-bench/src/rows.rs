@@ -0,0 +1,86 @@
+use charybdis::model::{BaseModel, Model};
+use charybdis::scylla::{CqlValue, Row, RowSerializationContext, RowWriter, SerializeRow};
+use charybdis::types::Uuid;
+use scylla::frame::response::result::{ColumnSpec, ColumnType, PreparedMetadata, TableSpec};
+
+const KEYSPACE: &str = "charybdis_bench";
+
+/// Model with sample rows, so its deserialization, serialization and reads can be benchmarked at the same data.
+pub trait BenchModel: Model + Send + Sync + 'static {
+    /// `index`-th row of partition, with columns in order of `SELECT_FIELDS_CLAUSE`.
+    fn sample_row(partition_id: Uuid, index: usize) -> Row;
+}
+
+pub fn rows<M: BenchModel>(partition_id: Uuid, count: usize) -> Vec<Row> {
+    (0..count).map(|index| M::sample_row(partition_id, index)).collect()
+}
+
+/// Models deserialized from [rows], so they hold the same values.
+pub fn models<M: BenchModel>(partition_id: Uuid, count: usize) -> Vec<M> {
+    rows::<M>(partition_id, count)
+        .into_iter()
+        .map(|row| M::from_row(row).expect("sample row must match model"))
+        .collect()
+}
+
+fn column_type(value: &Option<CqlValue>) -> ColumnType {
+    match value {
+        Some(CqlValue::Uuid(_)) => ColumnType::Uuid,
+        Some(CqlValue::Int(_)) => ColumnType::Int,
+        Some(CqlValue::BigInt(_)) => ColumnType::BigInt,
+        Some(CqlValue::Double(_)) => ColumnType::Double,
+        Some(CqlValue::Boolean(_)) => ColumnType::Boolean,
+        _ => ColumnType::Text,
+    }
+}
+
+/// Bind markers of statement that binds all columns of the model, named by its fields, as prepared statement
+/// reports them to serialization.
+pub fn columns<M: BenchModel>() -> Vec<ColumnSpec> {
+    let row = M::sample_row(Uuid::nil(), 0);
+
+    M::SELECT_FIELDS_CLAUSE
+        .split(',')
+        .map(str::trim)
+        .zip(row.columns.iter())
+        .map(|(name, value)| ColumnSpec {
+            table_spec: TableSpec::borrowed(KEYSPACE, M::DB_MODEL_NAME),
+            name: name.to_string(),
+            typ: column_type(value),
+        })
+        .collect()
+}
+
+/// Bind markers of statement by primary key, e.g. `FIND_BY_PRIMARY_KEY_QUERY`.
+pub fn primary_key_columns<M: BenchModel>() -> Vec<ColumnSpec> {
+    let primary_key = M::PRIMARY_KEY_FIELDS_CLAUSE
+        .split(',')
+        .map(str::trim)
+        .collect::<Vec<&str>>();
+
+    columns::<M>()
+        .into_iter()
+        .filter(|column| primary_key.contains(&column.name.as_str()))
+        .collect()
+}
+
+/// Serializes values as driver does for execution of prepared statement with given bind markers.
+pub fn serialize(values: &impl SerializeRow, columns: &[ColumnSpec]) -> Vec<u8> {
+    let metadata = PreparedMetadata {
+        flags: 0,
+        col_count: columns.len(),
+        pk_indexes: vec![],
+        col_specs: columns.to_vec(),
+    };
+
+    let mut buffer = vec![];
+
+    values
+        .serialize(
+            &RowSerializationContext::from_prepared(&metadata),
+            &mut RowWriter::new(&mut buffer),
+        )
+        .expect("values must match columns");
+
+    buffer
+}
@@ -7,18 +7,25 @@ use syn::parse_macro_input;
 use syn::DeriveInput;
 
 use charybdis_parser::fields::CharybdisFields;
+use charybdis_parser::schema::code_schema::ModelMacro;
 use charybdis_parser::traits::CharybdisMacroArgs;
 
 use crate::model::*;
 use crate::native::{
-    decrement_counter_methods, delete_by_primary_key_functions, find_all_function, find_by_global_secondary_index,
-    find_by_local_secondary_index, find_by_primary_keys_functions, increment_counter_methods,
-    pull_from_collection_consts, pull_from_collection_consts_if_exists, pull_from_collection_methods,
-    pull_from_collection_methods_if_exists, push_to_collection_consts, push_to_collection_consts_if_exists,
-    push_to_collection_methods, push_to_collection_methods_if_exists,
+    association_functions, decrement_counter_methods, delete_by_primary_key_functions, diff_model_impl,
+    find_all_function, find_by_collection_index, find_by_filtering_fields, find_by_global_secondary_index,
+    find_by_local_secondary_index, find_by_partition_key_in_function, find_by_primary_keys_functions,
+    find_via_view_functions, increment_counter_methods, list_element_consts, list_element_methods, lookup_models,
+    lookup_table_methods, map_entry_consts, map_entry_methods, metadata_fields_impl, model_json_impl,
+    prepare_all_function, pull_from_collection_cb_methods, pull_from_collection_consts,
+    pull_from_collection_consts_if_exists, pull_from_collection_methods, pull_from_collection_methods_if_exists,
+    push_to_collection_cb_methods, push_to_collection_consts, push_to_collection_consts_if_exists,
+    push_to_collection_methods, push_to_collection_methods_if_exists, register_model, register_statements,
+    select_with_metadata_function, setter_methods, tenant_scoped_impl, time_series_functions, tracked_model_impl,
+    udt_json_impl,
 };
 use crate::rules::*;
-use crate::scylla::from_row;
+use crate::scylla::{from_row, serialize_row_derive, serialize_row_impl};
 
 mod model;
 mod native;
@@ -47,39 +54,89 @@ pub fn charybdis_model(args: TokenStream, input: TokenStream) -> TokenStream {
 
     // Charybdis::BaseModel consts
     let db_model_name_const = db_model_name_const(&args);
+    let select_fields_clause_const = select_fields_clause_const(fields);
+    let primary_key_fields_clause_const = primary_key_fields_clause_const(fields);
+    let partition_key_fields_clause_const = partition_key_fields_clause_const(fields);
     let find_by_primary_key_query_const = find_by_primary_key_query_const(&args, fields);
     let find_by_partition_key_query_consts = find_by_partition_key_query_consts(&args, fields);
     let find_first_by_partition_key_query_const = find_first_by_partition_key_query_const(&args, fields);
+    let find_by_token_range_query_const = find_by_token_range_query_const(&args, fields);
+    let count_by_partition_key_query_const = count_by_partition_key_query_const(&args, fields);
     let insert_query_const = insert_query_const(&args, fields);
 
     // Charybdis::Model consts
     let insert_if_not_exists_query_const = insert_if_not_exists_query_const(&args, fields);
     let update_query_const = update_query_const(&args, fields);
     let delete_query_const = delete_query_const(&args, fields);
+    let hard_delete_query_const = hard_delete_query_const(&args, fields);
     let delete_by_partition_key_query_const = delete_by_partition_key_query_const(&args, fields);
 
     // Charybdis::BaseModel methods
     let primary_key_values_method = primary_key_values_method(fields);
     let partition_key_values_method = partition_key_values_method(fields);
+    let is_soft_deleted_method = is_soft_deleted_method(fields);
+    let tenant_column_const = tenant_column_const(fields);
+
+    // Charybdis::Model methods
+    let generate_ids_method = generate_ids_method(fields);
+    let soft_delete_insert_values_method = soft_delete_insert_values_method(fields);
+    let versioned_update_methods = versioned_update_methods(fields);
+    let validate_method = validate_method(fields);
 
     // Collection consts
     let push_to_collection_consts = push_to_collection_consts(&args, fields);
     let push_to_collection_consts_if_exists = push_to_collection_consts_if_exists(&args, fields);
     let pull_from_collection_consts = pull_from_collection_consts(&args, fields);
     let pull_from_collection_consts_if_exists = pull_from_collection_consts_if_exists(&args, fields);
+    let map_entry_consts = map_entry_consts(&args, fields);
+    let list_element_consts = list_element_consts(&args, fields);
+    let collection_consts = quote! {
+        #push_to_collection_consts
+        #push_to_collection_consts_if_exists
+
+        #pull_from_collection_consts
+        #pull_from_collection_consts_if_exists
+
+        #map_entry_consts
+        #list_element_consts
+    };
+
+    // consts are generated either in model impl or in `queries_module`
+    let (impl_collection_consts, queries_module) = match &args.queries_module {
+        Some(queries_module) => {
+            let vis = &input.vis;
+            let queries_module = syn::parse_str::<syn::Ident>(queries_module).unwrap();
+
+            (quote!(), quote!(#vis mod #queries_module { #collection_consts }))
+        }
+        None => (collection_consts, quote!()),
+    };
 
     // Collection methods
-    let push_to_collection_methods = push_to_collection_methods(fields);
-    let push_to_collection_methods_if_exists = push_to_collection_methods_if_exists(fields);
-    let pull_from_collection_methods = pull_from_collection_methods(fields);
-    let pull_from_collection_methods_if_exists = pull_from_collection_methods_if_exists(fields);
+    let push_to_collection_methods = push_to_collection_methods(&args, fields);
+    let push_to_collection_methods_if_exists = push_to_collection_methods_if_exists(&args, fields);
+    let pull_from_collection_methods = pull_from_collection_methods(&args, fields);
+    let pull_from_collection_methods_if_exists = pull_from_collection_methods_if_exists(&args, fields);
+    let push_to_collection_cb_methods = push_to_collection_cb_methods(&args, fields);
+    let pull_from_collection_cb_methods = pull_from_collection_cb_methods(&args, fields);
+    let map_entry_methods = map_entry_methods(&args, fields);
+    let list_element_methods = list_element_methods(&args, fields);
 
     // Counter methods
     let increment_counter_methods = increment_counter_methods(&args, fields);
     let decrement_counter_methods = decrement_counter_methods(&args, fields);
 
-    // FromRow trait
+    // Change tracking
+    let setter_methods = setter_methods(&args, fields);
+    let tracked_model_impl = tracked_model_impl(struct_name, &args, fields);
+    let tenant_scoped_impl = tenant_scoped_impl(struct_name, fields);
+    let diff_model_impl = diff_model_impl(struct_name, &args, fields);
+
+    // FromRow and SerializeRow traits
     let from_row = from_row(struct_name, fields);
+    let serialize_row_derive = serialize_row_derive(fields);
+    let serialize_row_impl = serialize_row_impl(struct_name, fields);
+    let derives_serialize_row = !fields.has_codecs();
 
     // Current model macro rules
     let find_model_query_rule = find_model_query_rule(struct_name, &args, fields);
@@ -91,31 +148,58 @@ pub fn charybdis_model(args: TokenStream, input: TokenStream) -> TokenStream {
 
     // Associated functions
     let find_by_key_funs = find_by_primary_keys_functions(struct_name, &args, fields);
+    let find_by_partition_key_in_fun = find_by_partition_key_in_function(struct_name, &args, fields);
     let find_all_fun = find_all_function(struct_name, &args, &fields);
     let find_by_local_secondary_index_funs = find_by_local_secondary_index(struct_name, &args, fields);
     let find_by_global_secondary_index_funs = find_by_global_secondary_index(struct_name, &args, fields);
+    let find_by_filtering_fields_funs = find_by_filtering_fields(struct_name, &args, fields);
+    let find_by_collection_index_funs = find_by_collection_index(struct_name, &args, fields);
+    let time_series_funs = time_series_functions(struct_name, &args, fields);
+    let select_with_metadata_fun = select_with_metadata_function(&args, fields);
+    let prepare_all_fun = prepare_all_function(&args, fields, true);
     let delete_by_cks_funs = delete_by_primary_key_functions(&args, fields);
+    let association_funs = association_functions(&args, fields);
 
-    CharybdisFields::proxy_charybdis_attrs_to_scylla(&mut input);
+    // Lookup tables
+    let lookup_models = lookup_models(&input, &args, fields);
+    let lookup_table_methods = lookup_table_methods(struct_name, &args, fields);
+
+    // CQL aware JSON
+    let model_json_impl = model_json_impl(struct_name, &args, fields);
+
+    // TTL and WRITETIME columns
+    let metadata_fields_impl = metadata_fields_impl(struct_name, fields);
+
+    // Global model registry
+    let register_model = register_model(&input, &args, ModelMacro::Table);
+    let register_statements = register_statements(struct_name);
+
+    // `scylla` attributes are known only to derived SerializeRow
+    if derives_serialize_row {
+        CharybdisFields::proxy_charybdis_attrs_to_scylla(&mut input);
+    }
     CharybdisFields::strip_charybdis_attributes(&mut input);
 
     let expanded = quote! {
-        #[derive(charybdis::macros::scylla::SerializeRow)]
+        #serialize_row_derive
         #input
 
         impl #struct_name {
             #find_by_key_funs
+            #find_by_partition_key_in_fun
             #find_all_fun
             #delete_by_cks_funs
 
             #find_by_local_secondary_index_funs
             #find_by_global_secondary_index_funs
+            #find_by_filtering_fields_funs
+            #find_by_collection_index_funs
+            #time_series_funs
+            #select_with_metadata_fun
+            #prepare_all_fun
+            #association_funs
 
-            #push_to_collection_consts
-            #push_to_collection_consts_if_exists
-
-            #pull_from_collection_consts
-            #pull_from_collection_consts_if_exists
+            #impl_collection_consts
 
             // methods
             #push_to_collection_methods
@@ -124,8 +208,18 @@ pub fn charybdis_model(args: TokenStream, input: TokenStream) -> TokenStream {
             #pull_from_collection_methods
             #pull_from_collection_methods_if_exists
 
+            #push_to_collection_cb_methods
+            #pull_from_collection_cb_methods
+
+            #map_entry_methods
+            #list_element_methods
+
             #increment_counter_methods
             #decrement_counter_methods
+
+            #setter_methods
+
+            #lookup_table_methods
         }
 
        impl charybdis::model::BaseModel for #struct_name {
@@ -135,13 +229,20 @@ pub fn charybdis_model(args: TokenStream, input: TokenStream) -> TokenStream {
 
             // consts
             #db_model_name_const
+            #select_fields_clause_const
+            #primary_key_fields_clause_const
+            #partition_key_fields_clause_const
             #find_by_primary_key_query_const
             #find_by_partition_key_query_consts
             #find_first_by_partition_key_query_const
+            #find_by_token_range_query_const
+            #count_by_partition_key_query_const
 
             // methods
             #primary_key_values_method
             #partition_key_values_method
+            #is_soft_deleted_method
+            #tenant_column_const
         }
 
         impl charybdis::model::Model for #struct_name {
@@ -150,14 +251,29 @@ pub fn charybdis_model(args: TokenStream, input: TokenStream) -> TokenStream {
             #insert_if_not_exists_query_const
             #update_query_const
             #delete_query_const
+            #hard_delete_query_const
             #delete_by_partition_key_query_const
 
+            // methods
+            #generate_ids_method
+            #soft_delete_insert_values_method
+            #versioned_update_methods
+            #validate_method
         }
 
+        #tracked_model_impl
+        #tenant_scoped_impl
+        #diff_model_impl
+
         impl charybdis::scylla::FromRow for #struct_name {
             #from_row
         }
 
+        #serialize_row_impl
+
+        #model_json_impl
+        #metadata_fields_impl
+
         #find_model_query_rule
         #find_model_rule
         #find_first_model_rule
@@ -165,6 +281,12 @@ pub fn charybdis_model(args: TokenStream, input: TokenStream) -> TokenStream {
         #partial_model_generator
         #delete_model_query_rule
         #delete_model_rule
+
+        #lookup_models
+        #queries_module
+
+        #register_model
+        #register_statements
     };
 
     TokenStream::from(expanded)
@@ -185,9 +307,14 @@ pub fn charybdis_view_model(args: TokenStream, input: TokenStream) -> TokenStrea
 
     // Charybdis::MaterializedView consts
     let db_model_name_const = db_model_name_const(&args);
+    let select_fields_clause_const = select_fields_clause_const(fields);
+    let primary_key_fields_clause_const = primary_key_fields_clause_const(fields);
+    let partition_key_fields_clause_const = partition_key_fields_clause_const(fields);
     let find_by_primary_key_query_const = find_by_primary_key_query_const(&args, fields);
     let find_by_partition_key_query_consts = find_by_partition_key_query_consts(&args, fields);
     let find_first_by_partition_key_query_const = find_first_by_partition_key_query_const(&args, fields);
+    let find_by_token_range_query_const = find_by_token_range_query_const(&args, fields);
+    let count_by_partition_key_query_const = count_by_partition_key_query_const(&args, fields);
 
     // Charybdis::BaseModel methods
     let primary_key_values_method = primary_key_values_method(fields);
@@ -198,17 +325,39 @@ pub fn charybdis_view_model(args: TokenStream, input: TokenStream) -> TokenStrea
 
     // Associated functions
     let find_by_key_funs = find_by_primary_keys_functions(struct_name, &args, fields);
+    let find_by_partition_key_in_fun = find_by_partition_key_in_function(struct_name, &args, fields);
+    let prepare_all_fun = prepare_all_function(&args, fields, false);
 
-    CharybdisFields::proxy_charybdis_attrs_to_scylla(&mut input);
+    // FromRow and SerializeRow traits
+    let from_row = from_row(struct_name, fields);
+    let serialize_row_derive = serialize_row_derive(fields);
+    let serialize_row_impl = serialize_row_impl(struct_name, fields);
+    let derives_serialize_row = !fields.has_codecs();
+
+    // CQL aware JSON
+    let model_json_impl = model_json_impl(struct_name, &args, fields);
+
+    // Finders of base model that query the view
+    let find_via_view_funs = find_via_view_functions(struct_name, &args, fields);
+
+    // Global model registry
+    let register_model = register_model(&input, &args, ModelMacro::MaterializedView);
+    let register_statements = register_statements(struct_name);
+
+    // `scylla` attributes are known only to derived SerializeRow
+    if derives_serialize_row {
+        CharybdisFields::proxy_charybdis_attrs_to_scylla(&mut input);
+    }
     CharybdisFields::strip_charybdis_attributes(&mut input);
 
     let expanded = quote! {
-        #[derive(charybdis::macros::scylla::SerializeRow)]
-        #[derive(charybdis::macros::scylla::FromRow)]
+        #serialize_row_derive
         #input
 
         impl #struct_name {
             #find_by_key_funs
+            #find_by_partition_key_in_fun
+            #prepare_all_fun
         }
 
         impl charybdis::model::BaseModel for #struct_name {
@@ -218,9 +367,14 @@ pub fn charybdis_view_model(args: TokenStream, input: TokenStream) -> TokenStrea
 
             // consts
             #db_model_name_const
+            #select_fields_clause_const
+            #primary_key_fields_clause_const
+            #partition_key_fields_clause_const
             #find_by_primary_key_query_const
             #find_by_partition_key_query_consts
             #find_first_by_partition_key_query_const
+            #find_by_token_range_query_const
+            #count_by_partition_key_query_const
 
             // methods
             #primary_key_values_method
@@ -229,19 +383,168 @@ pub fn charybdis_view_model(args: TokenStream, input: TokenStream) -> TokenStrea
 
         impl charybdis::model::MaterializedView for #struct_name {}
 
+        #find_via_view_funs
+
+        impl charybdis::scylla::FromRow for #struct_name {
+            #from_row
+        }
+
+        #serialize_row_impl
+
+        #model_json_impl
+
         #find_model_query_rule
+
+        #register_model
+        #register_statements
     };
 
     TokenStream::from(expanded)
 }
 
+/// This macro generates the implementation of the [CounterModel] trait for the given struct. Counter models
+/// don't implement [Model], so they can't be inserted or deleted.
 #[proc_macro_attribute]
-pub fn charybdis_udt_model(_: TokenStream, input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
+pub fn charybdis_counter_model(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args: CharybdisMacroArgs = parse_macro_input!(args);
+    let mut input: DeriveInput = parse_macro_input!(input);
+    let mut new_fields = CharybdisFields::from_input(&input, &args);
+    let fields = new_fields.populate(&args);
+
+    fields.panic_on_counter_model_misuse(&args);
+
+    let struct_name = &input.ident.clone();
+
+    // Charybdis::BaseModel types
+    let primary_key_type = primary_key_type(fields);
+    let partition_key_type = partition_key_type(fields);
+
+    // Charybdis::BaseModel consts
+    let db_model_name_const = db_model_name_const(&args);
+    let select_fields_clause_const = select_fields_clause_const(fields);
+    let primary_key_fields_clause_const = primary_key_fields_clause_const(fields);
+    let partition_key_fields_clause_const = partition_key_fields_clause_const(fields);
+    let find_by_primary_key_query_const = find_by_primary_key_query_const(&args, fields);
+    let find_by_partition_key_query_consts = find_by_partition_key_query_consts(&args, fields);
+    let find_first_by_partition_key_query_const = find_first_by_partition_key_query_const(&args, fields);
+    let find_by_token_range_query_const = find_by_token_range_query_const(&args, fields);
+    let count_by_partition_key_query_const = count_by_partition_key_query_const(&args, fields);
+
+    // Charybdis::BaseModel methods
+    let primary_key_values_method = primary_key_values_method(fields);
+    let partition_key_values_method = partition_key_values_method(fields);
+
+    // Charybdis::CounterModel methods
+    let with_zero_counters_method = with_zero_counters_method(fields);
+
+    // Counter methods
+    let increment_counter_methods = increment_counter_methods(&args, fields);
+    let decrement_counter_methods = decrement_counter_methods(&args, fields);
+
+    // Current model rules
+    let find_model_query_rule = find_model_query_rule(struct_name, &args, fields);
+    let find_model_rule = find_model_rule(struct_name, &args, fields);
+    let find_first_model_rule = find_first_model_rule(struct_name, &args, fields);
+
+    // Associated functions
+    let find_by_key_funs = find_by_primary_keys_functions(struct_name, &args, fields);
+    let find_by_partition_key_in_fun = find_by_partition_key_in_function(struct_name, &args, fields);
+    let find_all_fun = find_all_function(struct_name, &args, fields);
+    let prepare_all_fun = prepare_all_function(&args, fields, false);
+
+    // FromRow and SerializeRow traits
+    let from_row = from_row(struct_name, fields);
+    let serialize_row_derive = serialize_row_derive(fields);
+    let serialize_row_impl = serialize_row_impl(struct_name, fields);
+
+    // CQL aware JSON
+    let model_json_impl = model_json_impl(struct_name, &args, fields);
+
+    // Global model registry
+    let register_model = register_model(&input, &args, ModelMacro::CounterTable);
+    let register_statements = register_statements(struct_name);
+
+    // counter fields can't declare codecs, so SerializeRow is always derived
+    CharybdisFields::proxy_charybdis_attrs_to_scylla(&mut input);
+    CharybdisFields::strip_charybdis_attributes(&mut input);
+
+    let expanded = quote! {
+        #serialize_row_derive
+        #input
+
+        impl #struct_name {
+            #find_by_key_funs
+            #find_by_partition_key_in_fun
+            #find_all_fun
+            #prepare_all_fun
+
+            #increment_counter_methods
+            #decrement_counter_methods
+        }
+
+        impl charybdis::model::BaseModel for #struct_name {
+            // types
+            #primary_key_type
+            #partition_key_type
+
+            // consts
+            #db_model_name_const
+            #select_fields_clause_const
+            #primary_key_fields_clause_const
+            #partition_key_fields_clause_const
+            #find_by_primary_key_query_const
+            #find_by_partition_key_query_consts
+            #find_first_by_partition_key_query_const
+            #find_by_token_range_query_const
+            #count_by_partition_key_query_const
+
+            // methods
+            #primary_key_values_method
+            #partition_key_values_method
+        }
+
+        impl charybdis::model::CounterModel for #struct_name {
+            #with_zero_counters_method
+        }
+
+        impl charybdis::scylla::FromRow for #struct_name {
+            #from_row
+        }
+
+        #serialize_row_impl
+
+        #model_json_impl
+
+        #find_model_query_rule
+        #find_model_rule
+        #find_first_model_rule
+
+        #register_model
+        #register_statements
+    };
+
+    TokenStream::from(expanded)
+}
+
+#[proc_macro_attribute]
+pub fn charybdis_udt_model(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args: CharybdisMacroArgs = parse_macro_input!(args);
+    let mut input = parse_macro_input!(input as DeriveInput);
+
+    CharybdisFields::panic_on_udt_field_attributes(&input);
+
+    let udt_json_impl = udt_json_impl(&input, &args);
+    let register_model = register_model(&input, &args, ModelMacro::Udt);
+
+    CharybdisFields::strip_charybdis_attributes(&mut input);
 
     let gen = quote! {
         #[derive(charybdis::macros::scylla::FromUserType, charybdis::macros::scylla::SerializeValue)]
         #input
+
+        #udt_json_impl
+
+        #register_model
     };
 
     gen.into()
@@ -1,3 +1,4 @@
+use proc_macro2::TokenStream;
 use quote::quote;
 use syn::ImplItem;
 
@@ -6,12 +7,23 @@ use charybdis_parser::traits::CharybdisMacroArgs;
 
 use crate::traits::fields::FieldsQuery;
 
+/// For models with `soft_delete` field, `DELETE_QUERY` sets the field to current timestamp instead of
+/// removing the row, and row is removed by `HARD_DELETE_QUERY`. Soft delete is conditioned on existence of the
+/// row, so `UPDATE` doesn't create row that was never inserted.
 pub(crate) fn delete_query_const(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> ImplItem {
-    let query_str: String = format!(
-        "DELETE FROM {} WHERE {}",
-        ch_args.table_name(),
-        fields.primary_key_fields.where_placeholders(),
-    );
+    let query_str: String = match &fields.soft_delete_column {
+        Some(soft_delete_column) => format!(
+            "UPDATE {} SET {} = toTimestamp(now()) WHERE {} IF EXISTS",
+            ch_args.table_name(),
+            soft_delete_column,
+            fields.primary_key_fields.where_placeholders(),
+        ),
+        None => format!(
+            "DELETE FROM {} WHERE {}",
+            ch_args.table_name(),
+            fields.primary_key_fields.where_placeholders(),
+        ),
+    };
 
     let generated = quote! {
         const DELETE_QUERY: &'static str = #query_str;
@@ -20,6 +32,22 @@ pub(crate) fn delete_query_const(ch_args: &CharybdisMacroArgs, fields: &Charybdi
     syn::parse_quote!(#generated)
 }
 
+pub(crate) fn hard_delete_query_const(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> TokenStream {
+    if ch_args.soft_delete.is_none() {
+        return quote!();
+    }
+
+    let query_str: String = format!(
+        "DELETE FROM {} WHERE {}",
+        ch_args.table_name(),
+        fields.primary_key_fields.where_placeholders(),
+    );
+
+    quote! {
+        const HARD_DELETE_QUERY: &'static str = #query_str;
+    }
+}
+
 pub(crate) fn delete_by_partition_key_query_const(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> ImplItem {
     let query_str: String = format!(
         "DELETE FROM {} WHERE {}",
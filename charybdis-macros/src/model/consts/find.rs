@@ -1,11 +1,60 @@
+use proc_macro2::TokenStream;
 use quote::quote;
 use syn::ImplItem;
 
 use charybdis_parser::fields::CharybdisFields;
+use charybdis_parser::statement;
 use charybdis_parser::traits::CharybdisMacroArgs;
 
 use crate::traits::fields::FieldsQuery;
 
+/// Filters out rows that are marked as deleted by models with `soft_delete` field, see
+/// [soft_delete_filtered](statement::soft_delete_filtered). Statements of other models are returned as they are.
+pub(crate) fn soft_delete_filtered(fields: &CharybdisFields, query: String) -> String {
+    match &fields.soft_delete_column {
+        Some(soft_delete_column) => statement::soft_delete_filtered(&query, soft_delete_column),
+        None => query,
+    }
+}
+
+/// Limits statement to the first row, before `ALLOW FILTERING` that has to be the last clause.
+pub(crate) fn first_row_query(query: &str) -> String {
+    match query.strip_suffix(" ALLOW FILTERING") {
+        Some(query) => format!("{} LIMIT 1 ALLOW FILTERING", query),
+        None => format!("{} LIMIT 1", query),
+    }
+}
+
+pub(crate) fn select_fields_clause_const(fields: &CharybdisFields) -> ImplItem {
+    let fields_str = fields.db_fields.comma_sep_cols();
+
+    let generated = quote! {
+        const SELECT_FIELDS_CLAUSE: &'static str = #fields_str;
+    };
+
+    syn::parse_quote!(#generated)
+}
+
+pub(crate) fn primary_key_fields_clause_const(fields: &CharybdisFields) -> ImplItem {
+    let fields_str = fields.primary_key_fields.comma_sep_cols();
+
+    let generated = quote! {
+        const PRIMARY_KEY_FIELDS_CLAUSE: &'static str = #fields_str;
+    };
+
+    syn::parse_quote!(#generated)
+}
+
+pub(crate) fn partition_key_fields_clause_const(fields: &CharybdisFields) -> ImplItem {
+    let fields_str = fields.partition_key_fields.comma_sep_cols();
+
+    let generated = quote! {
+        const PARTITION_KEY_FIELDS_CLAUSE: &'static str = #fields_str;
+    };
+
+    syn::parse_quote!(#generated)
+}
+
 pub(crate) fn find_by_primary_key_query_const(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> ImplItem {
     let query_str = format!(
         "SELECT {} FROM {} WHERE {}",
@@ -13,6 +62,7 @@ pub(crate) fn find_by_primary_key_query_const(ch_args: &CharybdisMacroArgs, fiel
         ch_args.table_name(),
         fields.primary_key_fields.where_placeholders(),
     );
+    let query_str = soft_delete_filtered(fields, query_str);
 
     let generated = quote! {
         const FIND_BY_PRIMARY_KEY_QUERY: &'static str = #query_str;
@@ -21,16 +71,82 @@ pub(crate) fn find_by_primary_key_query_const(ch_args: &CharybdisMacroArgs, fiel
     syn::parse_quote!(#generated)
 }
 
-pub(crate) fn find_by_partition_key_query_consts(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> ImplItem {
-    let query_str = format!(
+/// `FIND_BY_PARTITION_KEY_QUERY` with optional `ORDER BY` of first clustering key and optional `LIMIT ?`, e.g.
+/// `SELECT ... FROM posts WHERE category_id = ? ORDER BY id DESC LIMIT ?`. Ordering by first clustering key
+/// orders rows by all clustering keys, as following keys keep their clustering order relative to it.
+pub(crate) fn find_by_partition_key_query(
+    ch_args: &CharybdisMacroArgs,
+    fields: &CharybdisFields,
+    order: Option<&str>,
+    limit: bool,
+) -> String {
+    let mut query_str = format!(
         "SELECT {} FROM {} WHERE {}",
         fields.db_fields.comma_sep_cols(),
         ch_args.table_name(),
         fields.partition_key_fields.where_placeholders(),
     );
 
-    let generated = quote! {
+    if let Some(order) = order {
+        let first_clustering_key = fields
+            .clustering_key_fields
+            .first()
+            .expect("ordered partition query requires clustering key");
+
+        query_str.push_str(&format!(" ORDER BY {} {}", first_clustering_key.cql_name(), order));
+    }
+
+    if limit {
+        query_str.push_str(" LIMIT ?");
+    }
+
+    soft_delete_filtered(fields, query_str)
+}
+
+/// `FIND_BY_PARTITION_KEY_QUERY` and its variants used by `order_by_clustering` and `limit` of partition finders.
+/// Ordered variants are `None` for models without clustering keys.
+pub(crate) fn find_by_partition_key_query_consts(
+    ch_args: &CharybdisMacroArgs,
+    fields: &CharybdisFields,
+) -> TokenStream {
+    let query_str = find_by_partition_key_query(ch_args, fields, None, false);
+    let limit_query_str = find_by_partition_key_query(ch_args, fields, None, true);
+
+    let ordered_query = |order: &str, limit: bool| {
+        if fields.clustering_key_fields.is_empty() {
+            return quote!(None);
+        }
+
+        let query_str = find_by_partition_key_query(ch_args, fields, Some(order), limit);
+
+        quote!(Some(#query_str))
+    };
+
+    let asc_query = ordered_query("ASC", false);
+    let desc_query = ordered_query("DESC", false);
+    let asc_limit_query = ordered_query("ASC", true);
+    let desc_limit_query = ordered_query("DESC", true);
+
+    quote! {
         const FIND_BY_PARTITION_KEY_QUERY: &'static str = #query_str;
+        const FIND_BY_PARTITION_KEY_LIMIT_QUERY: &'static str = #limit_query_str;
+        const FIND_BY_PARTITION_KEY_ASC_QUERY: Option<&'static str> = #asc_query;
+        const FIND_BY_PARTITION_KEY_DESC_QUERY: Option<&'static str> = #desc_query;
+        const FIND_BY_PARTITION_KEY_ASC_LIMIT_QUERY: Option<&'static str> = #asc_limit_query;
+        const FIND_BY_PARTITION_KEY_DESC_LIMIT_QUERY: Option<&'static str> = #desc_limit_query;
+    }
+}
+
+pub(crate) fn count_by_partition_key_query_const(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> ImplItem {
+    let query_str = format!(
+        "SELECT COUNT(*) FROM {} WHERE {}",
+        ch_args.table_name(),
+        fields.partition_key_fields.where_placeholders(),
+    );
+    let query_str = soft_delete_filtered(fields, query_str);
+
+    let generated = quote! {
+        const COUNT_BY_PARTITION_KEY_QUERY: &'static str = #query_str;
     };
 
     syn::parse_quote!(#generated)
@@ -46,6 +162,7 @@ pub(crate) fn find_first_by_partition_key_query_const(
         ch_args.table_name(),
         fields.partition_key_fields.where_placeholders(),
     );
+    let query_str = soft_delete_filtered(fields, query_str);
 
     let generated = quote! {
         const FIND_FIRST_BY_PARTITION_KEY_QUERY: &'static str = #query_str;
@@ -53,3 +170,22 @@ pub(crate) fn find_first_by_partition_key_query_const(
 
     syn::parse_quote!(#generated)
 }
+
+/// token range bounds are inclusive, so ranges can cover the whole token ring including its maximal token
+pub(crate) fn find_by_token_range_query_const(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> ImplItem {
+    let token = format!("token({})", fields.partition_key_fields.comma_sep_cols());
+    let query_str = format!(
+        "SELECT {} FROM {} WHERE {} >= ? AND {} <= ?",
+        fields.db_fields.comma_sep_cols(),
+        ch_args.table_name(),
+        token,
+        token,
+    );
+    let query_str = soft_delete_filtered(fields, query_str);
+
+    let generated = quote! {
+        const FIND_BY_TOKEN_RANGE_QUERY: &'static str = #query_str;
+    };
+
+    syn::parse_quote!(#generated)
+}
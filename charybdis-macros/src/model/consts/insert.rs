@@ -4,15 +4,32 @@ use syn::ImplItem;
 use charybdis_parser::fields::CharybdisFields;
 use charybdis_parser::traits::CharybdisMacroArgs;
 
-use crate::traits::fields::FieldsQuery;
+/// Columns and values of `INSERT` statements. Inserted rows of models with `soft_delete` field are live, so its
+/// column is set to Unix epoch instead of being bound, also by partial models that don't select the field.
+fn insert_columns_and_values(fields: &CharybdisFields) -> (String, String) {
+    let mut columns = vec![];
+    let mut values = vec![];
+
+    for field in &fields.db_fields {
+        if fields.soft_delete_column.as_ref() == Some(&field.cql_name()) {
+            continue;
+        }
+
+        columns.push(field.cql_name());
+        values.push(field.bind_marker());
+    }
+
+    if let Some(soft_delete_column) = &fields.soft_delete_column {
+        columns.push(soft_delete_column.clone());
+        values.push("0".to_string());
+    }
+
+    (columns.join(", "), values.join(", "))
+}
 
 pub(crate) fn insert_query_const(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> ImplItem {
-    let query_str: String = format!(
-        "INSERT INTO {} ({}) VALUES ({})",
-        ch_args.table_name(),
-        fields.db_fields.comma_sep_cols(),
-        fields.db_fields.insert_bind_markers(),
-    );
+    let (columns, values) = insert_columns_and_values(fields);
+    let query_str: String = format!("INSERT INTO {} ({}) VALUES ({})", ch_args.table_name(), columns, values);
 
     let generated = quote! {
         const INSERT_QUERY: &'static str = #query_str;
@@ -22,11 +39,12 @@ pub(crate) fn insert_query_const(ch_args: &CharybdisMacroArgs, fields: &Charybdi
 }
 
 pub(crate) fn insert_if_not_exists_query_const(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> ImplItem {
+    let (columns, values) = insert_columns_and_values(fields);
     let query_str: String = format!(
         "INSERT INTO {} ({}) VALUES ({}) IF NOT EXISTS",
         ch_args.table_name(),
-        fields.db_fields.comma_sep_cols(),
-        fields.db_fields.insert_bind_markers(),
+        columns,
+        values
     );
 
     let generated = quote! {
@@ -6,13 +6,41 @@ use charybdis_parser::traits::CharybdisMacroArgs;
 
 use crate::traits::fields::FieldsQuery;
 
+pub(crate) const NEXT_VERSION_BIND_MARKER: &str = "charybdis_next_version";
+
+/// For versioned models update is conditional on current version:
+/// `UPDATE ... SET ..., version = :charybdis_next_version WHERE ... IF version = :version`.
+/// Next version is bound by client for all version types, so it can be assigned to the model after update.
 pub(crate) fn update_query_const(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> ImplItem {
-    let query_str: String = format!(
-        "UPDATE {} SET {} WHERE {}",
-        ch_args.table_name(),
-        fields.non_primary_key_db_fields().set_bind_markers(),
-        fields.primary_key_fields.where_bind_markers(),
-    );
+    let query_str: String = match fields.version_field {
+        Some(version_field) => {
+            let mut set_fields = fields.non_primary_key_db_fields();
+            set_fields.retain(|field| !field.is_version);
+
+            let mut set_clause = set_fields.set_bind_markers();
+
+            if !set_clause.is_empty() {
+                set_clause.push_str(", ");
+            }
+
+            format!(
+                "UPDATE {} SET {}{} = :{} WHERE {} IF {} = {}",
+                ch_args.table_name(),
+                set_clause,
+                version_field.cql_name(),
+                NEXT_VERSION_BIND_MARKER,
+                fields.primary_key_fields.where_bind_markers(),
+                version_field.cql_name(),
+                version_field.bind_marker(),
+            )
+        }
+        None => format!(
+            "UPDATE {} SET {} WHERE {}",
+            ch_args.table_name(),
+            fields.non_primary_key_db_fields().set_bind_markers(),
+            fields.primary_key_fields.where_bind_markers(),
+        ),
+    };
 
     let generated = quote! {
         const UPDATE_QUERY: &'static str = #query_str;
@@ -1,8 +1,11 @@
-use quote::quote;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
 use syn::ImplItem;
 
-use charybdis_parser::fields::CharybdisFields;
+use charybdis_parser::fields::{CharybdisFields, IdStrategy, Validator};
 
+use crate::model::consts::NEXT_VERSION_BIND_MARKER;
+use crate::scylla::{bound_field_types, bound_field_values};
 use crate::traits::tuple::Tuple;
 
 /// returns tuple of primary key types
@@ -51,3 +54,244 @@ pub(crate) fn partition_key_values_method(fields: &CharybdisFields) -> ImplItem
 
     syn::parse_quote!(#partition_key_values_method)
 }
+
+/// rows with `soft_delete` field set after Unix epoch are filtered out by model queries
+pub(crate) fn is_soft_deleted_method(fields: &CharybdisFields) -> TokenStream {
+    let soft_delete_column = match &fields.soft_delete_column {
+        Some(soft_delete_column) => soft_delete_column,
+        None => return quote!(),
+    };
+
+    // partial models that don't select the field still filter rows by its column
+    let is_soft_deleted = fields.soft_delete_field.map(|soft_delete_field| {
+        let soft_delete_ident = &soft_delete_field.ident;
+
+        quote! {
+            fn is_soft_deleted(&self) -> bool {
+                self.#soft_delete_ident.timestamp_millis() != 0
+            }
+        }
+    });
+
+    quote! {
+        const SOFT_DELETE_COLUMN: Option<&'static str> = Some(#soft_delete_column);
+
+        #is_soft_deleted
+    }
+}
+
+/// `INSERT` statements of models with `soft_delete` field set it to Unix epoch, so insert values are serialized from
+/// local struct holding references to the other model fields.
+pub(crate) fn soft_delete_insert_values_method(fields: &CharybdisFields) -> TokenStream {
+    let soft_delete_field = match fields.soft_delete_field {
+        Some(soft_delete_field) => soft_delete_field,
+        None => return quote!(),
+    };
+
+    let mut idents = vec![];
+    let mut types = vec![];
+    let mut values = vec![];
+
+    let bound_fields = fields
+        .db_fields
+        .iter()
+        .zip(bound_field_types(fields))
+        .zip(bound_field_values(fields));
+
+    for ((field, ty), value) in bound_fields {
+        if field.ident != soft_delete_field.ident {
+            idents.push(&field.ident);
+            types.push(ty);
+            values.push(value);
+        }
+    }
+
+    quote! {
+        fn insert_values<Val: charybdis::scylla::SerializeRow>(&self) -> charybdis::query::QueryValue<'_, Val, Self> {
+            #[derive(charybdis::macros::scylla::SerializeRow)]
+            struct InsertValues<'a> {
+                #(#idents: #types,)*
+            }
+
+            charybdis::query::QueryValue::Row(Box::new(InsertValues {
+                #(#idents: #values,)*
+            }))
+        }
+    }
+}
+
+/// column of `tenant_key` field, see `TenantScoped`
+pub(crate) fn tenant_column_const(fields: &CharybdisFields) -> TokenStream {
+    match fields.tenant_field {
+        Some(tenant_field) => {
+            let tenant_column = tenant_field.cql_name();
+
+            quote! {
+                const TENANT_COLUMN: Option<&'static str> = Some(#tenant_column);
+            }
+        }
+        None => quote!(),
+    }
+}
+
+/// model with the same primary key, counters set to zero and ignored fields set to default
+pub(crate) fn with_zero_counters_method(fields: &CharybdisFields) -> TokenStream {
+    let field_values = fields.all_fields.iter().map(|field| {
+        let field_name = &field.ident;
+
+        if field.is_primary_key() {
+            quote!(#field_name: self.#field_name.clone())
+        } else if !field.ignore && field.is_option() {
+            quote!(#field_name: Some(Default::default()))
+        } else {
+            quote!(#field_name: Default::default())
+        }
+    });
+
+    quote! {
+        fn with_zero_counters(&self) -> Self {
+            Self {
+                #(#field_values),*
+            }
+        }
+    }
+}
+
+/// populates unset key fields that declare `#[charybdis(id_strategy = "...")]`
+pub(crate) fn generate_ids_method(fields: &CharybdisFields) -> ImplItem {
+    let generators = fields.primary_key_fields.iter().filter_map(|field| {
+        let field_name = &field.ident;
+        let generator = match field.id_strategy.as_ref()? {
+            IdStrategy::UuidV7 => quote!(charybdis::id::uuid_v7()),
+            IdStrategy::Snowflake => quote!(charybdis::id::snowflake()),
+            IdStrategy::Custom(path) => quote!(#path()),
+        };
+
+        Some(quote! {
+            if charybdis::id::IdValue::is_unset(&self.#field_name) {
+                self.#field_name = #generator.into();
+            }
+        })
+    });
+
+    let generate_ids_method = quote! {
+        fn generate_ids(&mut self) {
+            #(#generators)*
+        }
+    };
+
+    syn::parse_quote!(#generate_ids_method)
+}
+
+/// collects errors of checks declared with `#[charybdis(validate = "...")]`
+pub(crate) fn validate_method(fields: &CharybdisFields) -> TokenStream {
+    let checks = fields
+        .db_fields
+        .iter()
+        .flat_map(|field| field.validators.iter().map(move |validator| (field, validator)))
+        .map(|(field, validator)| {
+            let field_ident = &field.ident;
+            let field_name = &field.name;
+
+            let (code, check) = match validator {
+                Validator::Email => ("email", quote!(charybdis::validation::email(&self.#field_ident))),
+                Validator::NotEmpty => (
+                    "not_empty",
+                    quote!(charybdis::validation::not_empty(&self.#field_ident)),
+                ),
+                Validator::Length { min, max } => {
+                    let min = optional_bound(min);
+                    let max = optional_bound(max);
+
+                    (
+                        "length",
+                        quote!(charybdis::validation::length(&self.#field_ident, #min, #max)),
+                    )
+                }
+                Validator::Range { min, max } => {
+                    let min = optional_bound(min);
+                    let max = optional_bound(max);
+
+                    (
+                        "range",
+                        quote!(charybdis::validation::range(&self.#field_ident, #min, #max)),
+                    )
+                }
+                Validator::Custom(path) => ("custom", quote!(#path(&self.#field_ident))),
+            };
+
+            quote! {
+                if let Err(message) = #check {
+                    errors.add(#field_name, #code, message);
+                }
+            }
+        })
+        .collect::<Vec<TokenStream>>();
+
+    if checks.is_empty() {
+        return quote!();
+    }
+
+    quote! {
+        fn validate(&self) -> Result<(), charybdis::validation::ValidationErrors> {
+            let mut errors = charybdis::validation::ValidationErrors::default();
+
+            #(#checks)*
+
+            errors.into_result()
+        }
+    }
+}
+
+fn optional_bound(bound: &Option<syn::Expr>) -> TokenStream {
+    match bound {
+        Some(bound) => quote!(Some(#bound)),
+        None => quote!(None),
+    }
+}
+
+/// Versioned models bind `charybdis_next_version` in addition to model fields, so update values are serialized
+/// from local struct holding references to model fields. Next version is generated once per update values, and
+/// carried by `QueryValue::Versioned`, so the same version is assigned by `increment_version` after update.
+pub(crate) fn versioned_update_methods(fields: &CharybdisFields) -> TokenStream {
+    let version_field = match fields.version_field {
+        Some(version_field) => version_field,
+        None => return quote!(),
+    };
+
+    let version_ident = &version_field.ident;
+    let version_type = &version_field.ty;
+    let next_version_ident = format_ident!("{}", NEXT_VERSION_BIND_MARKER);
+    let idents = fields.db_fields.iter().map(|field| &field.ident).collect::<Vec<_>>();
+    let types = bound_field_types(fields);
+    let values = bound_field_values(fields);
+
+    quote! {
+        const VERSIONED: bool = true;
+
+        fn update_values<Val: charybdis::scylla::SerializeRow>(&self) -> charybdis::query::QueryValue<'_, Val, Self> {
+            #[derive(charybdis::macros::scylla::SerializeRow)]
+            struct VersionedUpdateValues<'a> {
+                #(#idents: #types,)*
+                #next_version_ident: #version_type,
+            }
+
+            let next_version = charybdis::model::VersionValue::next_version(&self.#version_ident);
+            let values = charybdis::query::QueryValue::Row(Box::new(VersionedUpdateValues {
+                #(#idents: #values,)*
+                #next_version_ident: next_version,
+            }));
+
+            charybdis::query::QueryValue::Versioned(
+                Box::new(values),
+                charybdis::model::VersionValue::into_version(next_version),
+            )
+        }
+
+        fn increment_version(&mut self, next_version: charybdis::model::Version) {
+            if let Some(next_version) = charybdis::model::VersionValue::from_version(next_version) {
+                self.#version_ident = next_version;
+            }
+        }
+    }
+}
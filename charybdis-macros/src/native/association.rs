@@ -0,0 +1,143 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_str;
+
+use charybdis_parser::fields::CharybdisFields;
+use charybdis_parser::traits::{Association, CharybdisMacroArgs};
+
+use crate::traits::string::ToIdent;
+
+/// Generates `load_<association>` and `load_<association>_for` functions for `belongs_to` and `has_many`
+/// declarations, e.g. for `belongs_to = [(author, User, [author_id])]`:
+/// ```rust ignore
+/// let author: Option<User> = post.load_author(&session).await?;
+/// let authors: Vec<(&Post, Option<User>)> = Post::load_author_for(&posts, &session).await?;
+/// ```
+/// `belongs_to` fields are matched with primary key of associated model, `has_many` fields with its
+/// partition key. `load_<association>_for` loads each distinct key once, with `IN` statements for associations
+/// of single field.
+pub(crate) fn association_functions(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> TokenStream {
+    let belongs_to = ch_args.belongs_to().iter().map(|association| {
+        let model = association_model(association, fields);
+        let key_values = association_key_values(association, quote!(self));
+
+        association_fns(
+            association,
+            quote!(Option<#model>),
+            quote! {
+                <#model as charybdis::operations::Find>::maybe_find_by_primary_key_value(#key_values)
+                    .execute(session)
+                    .await
+            },
+            quote!(load_belonging_for),
+        )
+    });
+
+    let has_many = ch_args.has_many().iter().map(|association| {
+        let model = association_model(association, fields);
+        let key_values = association_key_values(association, quote!(self));
+
+        association_fns(
+            association,
+            quote!(Vec<#model>),
+            quote! {
+                <#model as charybdis::operations::Find>::find_by_partition_key_value(#key_values)
+                    .execute(session)
+                    .await?
+                    .try_collect()
+                    .await
+            },
+            quote!(load_many_for),
+        )
+    });
+
+    quote! {
+        #(#belongs_to)*
+        #(#has_many)*
+    }
+}
+
+fn association_model(association: &Association, fields: &CharybdisFields) -> TokenStream {
+    for field_name in &association.fields {
+        let field = fields
+            .db_fields
+            .iter()
+            .find(|field| &field.name == field_name)
+            .unwrap_or_else(|| {
+                panic!(
+                    "Field {} of association {} not found in struct fields",
+                    field_name, association.name
+                )
+            });
+
+        if field.is_option() {
+            panic!(
+                "Field {} of association {} must not be Option, as it's used as key of associated model",
+                field_name, association.name
+            );
+        }
+    }
+
+    parse_str::<TokenStream>(&association.model)
+        .unwrap_or_else(|_| panic!("Invalid model path of association {}", association.name))
+}
+
+fn association_key_values(association: &Association, model: TokenStream) -> TokenStream {
+    let values = association.fields.iter().map(|field| {
+        let field = field.to_ident();
+
+        quote!(#model.#field.clone())
+    });
+
+    quote!((#(#values,)*))
+}
+
+/// `load_in_fn` of runtime loads association of single field with `IN` statements, associations of multiple fields
+/// are loaded by `load_<association>` of first model of each distinct key.
+fn association_fns(
+    association: &Association,
+    output: TokenStream,
+    load: TokenStream,
+    load_in_fn: TokenStream,
+) -> TokenStream {
+    let load_fn_name = format!("load_{}", association.name).to_ident();
+    let load_for_fn_name = format!("load_{}_for", association.name).to_ident();
+
+    let load_for = match association.fields.as_slice() {
+        [field] => {
+            let field = field.to_ident();
+
+            quote! {
+                charybdis::association::#load_in_fn(models, |model| model.#field.clone(), session).await
+            }
+        }
+        _ => {
+            let key_values = association_key_values(association, quote!(model));
+
+            quote! {
+                charybdis::association::load_for(
+                    models,
+                    |model| #key_values,
+                    |model| model.#load_fn_name(session),
+                )
+                .await
+            }
+        }
+    };
+
+    quote! {
+        pub async fn #load_fn_name(
+            &self,
+            session: &impl charybdis::session::CharybdisSession,
+        ) -> Result<#output, charybdis::errors::CharybdisError> {
+            #load
+        }
+
+        pub async fn #load_for_fn_name<'a>(
+            models: &'a [Self],
+            session: &impl charybdis::session::CharybdisSession,
+        ) -> Result<Vec<(&'a Self, #output)>, charybdis::errors::CharybdisError> {
+            #load_for
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use charybdis_parser::fields::{CharybdisFields, Field};
+use charybdis_parser::traits::CharybdisMacroArgs;
+
+use crate::model::consts::NEXT_VERSION_BIND_MARKER;
+use crate::traits::fields::FieldsQuery;
+
+const CURRENT_BIND_MARKER_PREFIX: &str = "charybdis_current_";
+
+/// Fields that can be changed by setters: primary key can't be updated, version is set by update and
+/// counters are changed only by increments.
+fn tracked_fields<'a>(fields: &'a CharybdisFields) -> Vec<&'a Field<'a>> {
+    fields
+        .non_primary_key_db_fields()
+        .into_iter()
+        .filter(|field| !field.is_version && !field.is_counter())
+        .collect()
+}
+
+/// Value of field of `model`, e.g. `self`, boxed to be bound by name.
+fn bound_value(field: &Field, model: TokenStream) -> TokenStream {
+    let ident = &field.ident;
+
+    match &field.codec {
+        Some(codec) => {
+            let path = &codec.path;
+            quote!(::std::boxed::Box::new(#path::encode(&#model.#ident)))
+        }
+        None => quote!(::std::boxed::Box::new(&#model.#ident)),
+    }
+}
+
+pub(crate) fn setter_methods(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> TokenStream {
+    let changes_field = match fields.changes_field {
+        Some(changes_field) => &changes_field.ident,
+        None => return quote!(),
+    };
+
+    let setters = tracked_fields(fields).into_iter().map(|field| {
+        let ident = &field.ident;
+        let ty = &field.ty;
+        let name = &field.name;
+        let setter = format_ident!("{}", ch_args.method_name(&format!("set_{}", field.name)));
+
+        quote! {
+            pub fn #setter(&mut self, #ident: #ty) {
+                self.#ident = #ident;
+                self.#changes_field.mark(#name);
+            }
+        }
+    });
+
+    quote! {
+        #(#setters)*
+    }
+}
+
+/// `UPDATE` of changed fields is built at runtime from `SET` assignments of changed fields, followed by
+/// version assignment and conditions that are the same as in `UPDATE_QUERY`.
+pub(crate) fn tracked_model_impl(
+    struct_name: &syn::Ident,
+    ch_args: &CharybdisMacroArgs,
+    fields: &CharybdisFields,
+) -> TokenStream {
+    let changes_field = match fields.changes_field {
+        Some(changes_field) => &changes_field.ident,
+        None => return quote!(),
+    };
+
+    let set_changed_fields = tracked_fields(fields).into_iter().map(|field| {
+        let name = &field.name;
+        let assignment = format!("{} = {}", field.cql_name(), field.bind_marker());
+        let value = bound_value(field, quote!(self));
+
+        quote! {
+            if self.#changes_field.is_changed(#name) {
+                assignments.push(#assignment);
+                values.insert(#name, #value);
+            }
+        }
+    });
+
+    let set_version = fields.version_field.map(|version_field| {
+        let ident = &version_field.ident;
+        let name = &version_field.name;
+
+        let assignment = format!("{} = :{}", version_field.cql_name(), NEXT_VERSION_BIND_MARKER);
+
+        quote! {
+            let next_version = charybdis::model::VersionValue::next_version(&self.#ident);
+
+            assignments.push(#assignment);
+            values.insert(#name, ::std::boxed::Box::new(&self.#ident));
+            values.insert(#NEXT_VERSION_BIND_MARKER, ::std::boxed::Box::new(next_version));
+        }
+    });
+
+    let query_values = match fields.version_field {
+        Some(_) => quote! {
+            charybdis::query::QueryValue::Versioned(
+                ::std::boxed::Box::new(charybdis::query::QueryValue::Named(values)),
+                charybdis::model::VersionValue::into_version(next_version),
+            )
+        },
+        None => quote!(charybdis::query::QueryValue::Named(values)),
+    };
+
+    let set_primary_key = fields.primary_key_fields.iter().map(|field| {
+        let name = &field.name;
+        let value = bound_value(field, quote!(self));
+
+        quote!(values.insert(#name, #value);)
+    });
+
+    let statement_start = format!("UPDATE {} SET ", ch_args.table_name());
+    let statement_end = match fields.version_field {
+        Some(version_field) => format!(
+            " WHERE {} IF {} = {}",
+            fields.primary_key_fields.where_bind_markers(),
+            version_field.cql_name(),
+            version_field.bind_marker()
+        ),
+        None => format!(" WHERE {}", fields.primary_key_fields.where_bind_markers()),
+    };
+
+    quote! {
+        impl charybdis::changes::TrackedModel for #struct_name {
+            fn changes(&self) -> &charybdis::changes::Changes {
+                &self.#changes_field
+            }
+
+            fn changes_mut(&mut self) -> &mut charybdis::changes::Changes {
+                &mut self.#changes_field
+            }
+
+            fn changes_update_statement(&self) -> Option<(String, charybdis::query::QueryValue<'_, Self, Self>)> {
+                let mut assignments: Vec<&str> = vec![];
+                let mut values = charybdis::query::NamedValues::new();
+
+                #(#set_changed_fields)*
+
+                if assignments.is_empty() {
+                    return None;
+                }
+
+                #set_version
+                #(#set_primary_key)*
+
+                let statement = format!("{}{}{}", #statement_start, assignments.join(", "), #statement_end);
+
+                Some((statement, #query_values))
+            }
+        }
+    }
+}
+
+/// Fields of stored row are bound to `:charybdis_current_<field>` markers, so they don't clash with values of the
+/// model bound to `:<field>` markers.
+fn current_bind_marker(field: &Field) -> String {
+    format!("{}{}", CURRENT_BIND_MARKER_PREFIX, field.name)
+}
+
+/// `UPDATE` of fields that differ from stored row is built at runtime like in `tracked_model_impl`. Version
+/// condition and next version are taken from stored row, so model doesn't have to be read before it's saved.
+///
+/// Fields are compared by `PartialEq`, so it's generated only for models declared with `diff_model`.
+pub(crate) fn diff_model_impl(
+    struct_name: &syn::Ident,
+    ch_args: &CharybdisMacroArgs,
+    fields: &CharybdisFields,
+) -> TokenStream {
+    if !ch_args.diff_model.unwrap_or(false) {
+        return quote!();
+    }
+
+    let compared_fields = tracked_fields(fields);
+
+    let diff_fields = compared_fields.iter().map(|field| {
+        let ident = &field.ident;
+        let name = &field.name;
+
+        quote! {
+            if self.#ident != other.#ident {
+                changes.mark(#name);
+            }
+        }
+    });
+
+    let set_changed_fields = compared_fields.iter().map(|field| {
+        let name = &field.name;
+        let assignment = format!("{} = {}", field.cql_name(), field.bind_marker());
+        let value = bound_value(field, quote!(self));
+        let current_marker = current_bind_marker(field);
+        let condition = format!("{} = :{}", field.cql_name(), current_marker);
+        let current_value = bound_value(field, quote!(current));
+
+        quote! {
+            if changes.is_changed(#name) {
+                assignments.push(#assignment);
+                values.insert(#name, #value);
+
+                if lwt {
+                    conditions.push(#condition);
+                    values.insert(#current_marker, #current_value);
+                }
+            }
+        }
+    });
+
+    let set_version = fields.version_field.map(|version_field| {
+        let ident = &version_field.ident;
+        let name = &version_field.name;
+        let condition = format!("{} = {}", version_field.cql_name(), version_field.bind_marker());
+
+        let assignment = format!("{} = :{}", version_field.cql_name(), NEXT_VERSION_BIND_MARKER);
+
+        quote! {
+            assignments.push(#assignment);
+            values.insert(
+                #NEXT_VERSION_BIND_MARKER,
+                ::std::boxed::Box::new(charybdis::model::VersionValue::next_version(&current.#ident)),
+            );
+            conditions.insert(0, #condition);
+            values.insert(#name, ::std::boxed::Box::new(&current.#ident));
+        }
+    });
+
+    let set_primary_key = fields.primary_key_fields.iter().map(|field| {
+        let name = &field.name;
+        let value = bound_value(field, quote!(self));
+
+        quote!(values.insert(#name, #value);)
+    });
+
+    let statement_start = format!("UPDATE {} SET ", ch_args.table_name());
+    let statement_where = format!(" WHERE {}", fields.primary_key_fields.where_bind_markers());
+
+    quote! {
+        impl charybdis::changes::DiffModel for #struct_name {
+            #[allow(unused_variables, unused_mut)]
+            fn diff(&self, other: &Self) -> charybdis::changes::Changes {
+                let mut changes = charybdis::changes::Changes::default();
+
+                #(#diff_fields)*
+
+                changes
+            }
+
+            #[allow(unused_variables, unused_mut)]
+            fn diff_update_statement<'a>(
+                &'a self,
+                changes: &charybdis::changes::Changes,
+                current: &'a Self,
+                lwt: bool,
+            ) -> (String, charybdis::query::NamedValues<'a>) {
+                let mut assignments: Vec<&str> = vec![];
+                let mut conditions: Vec<&str> = vec![];
+                let mut values = charybdis::query::NamedValues::new();
+
+                #(#set_changed_fields)*
+                #set_version
+                #(#set_primary_key)*
+
+                let mut statement = format!("{}{}{}", #statement_start, assignments.join(", "), #statement_where);
+
+                if !conditions.is_empty() {
+                    statement.push_str(" IF ");
+                    statement.push_str(&conditions.join(" AND "));
+                }
+
+                (statement, values)
+            }
+        }
+    }
+}
@@ -20,12 +20,12 @@ pub(crate) fn push_to_collection_consts(ch_args: &CharybdisMacroArgs, fields: &C
             let query_str = format!(
                 "UPDATE {} SET {} = {} + ? WHERE {}",
                 ch_args.table_name(),
-                field.name,
-                field.name,
+                field.cql_name(),
+                field.cql_name(),
                 fields.primary_key_fields.where_placeholders(),
             );
 
-            let const_name = format!("PUSH_{}_QUERY", field.name.to_uppercase());
+            let const_name = ch_args.const_name(&format!("PUSH_{}_QUERY", field.name.to_uppercase()));
             let const_name: TokenStream = parse_str::<TokenStream>(&const_name).unwrap();
 
             let expanded = quote! {
@@ -58,12 +58,12 @@ pub(crate) fn push_to_collection_consts_if_exists(
             let query_str = format!(
                 "UPDATE {} SET {} = {} + ? WHERE {} IF EXISTS",
                 ch_args.table_name(),
-                field.name,
-                field.name,
+                field.cql_name(),
+                field.cql_name(),
                 fields.primary_key_fields.where_placeholders(),
             );
 
-            let const_name = format!("PUSH_{}_IF_EXISTS_QUERY", field.name.to_uppercase());
+            let const_name = ch_args.const_name(&format!("PUSH_{}_IF_EXISTS_QUERY", field.name.to_uppercase()));
             let const_name: TokenStream = parse_str::<TokenStream>(&const_name).unwrap();
 
             let expanded = quote! {
@@ -93,12 +93,12 @@ pub(crate) fn pull_from_collection_consts(ch_args: &CharybdisMacroArgs, fields:
             let query_str = format!(
                 "UPDATE {} SET {} = {} - ? WHERE {}",
                 ch_args.table_name(),
-                field.name,
-                field.name,
+                field.cql_name(),
+                field.cql_name(),
                 fields.primary_key_fields.where_placeholders(),
             );
 
-            let const_name = format!("PULL_{}_QUERY", field.name.to_uppercase());
+            let const_name = ch_args.const_name(&format!("PULL_{}_QUERY", field.name.to_uppercase()));
             let const_name: TokenStream = parse_str::<TokenStream>(&const_name).unwrap();
 
             let expanded = quote! {
@@ -131,12 +131,12 @@ pub(crate) fn pull_from_collection_consts_if_exists(
             let query_str = format!(
                 "UPDATE {} SET {} = {} - ? WHERE {} IF EXISTS",
                 ch_args.table_name(),
-                field.name,
-                field.name,
+                field.cql_name(),
+                field.cql_name(),
                 fields.primary_key_fields.where_placeholders(),
             );
 
-            let const_name = format!("PULL_{}_IF_EXISTS_QUERY", field.name.to_uppercase());
+            let const_name = ch_args.const_name(&format!("PULL_{}_IF_EXISTS_QUERY", field.name.to_uppercase()));
             let const_name: TokenStream = parse_str::<TokenStream>(&const_name).unwrap();
 
             let expanded = quote! {
@@ -154,7 +154,7 @@ pub(crate) fn pull_from_collection_consts_if_exists(
     expanded
 }
 
-pub(crate) fn push_to_collection_methods(fields: &CharybdisFields) -> TokenStream {
+pub(crate) fn push_to_collection_methods(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> TokenStream {
     let push_to_collection_rules: Vec<TokenStream> = fields
         .db_fields
         .iter()
@@ -163,9 +163,9 @@ pub(crate) fn push_to_collection_methods(fields: &CharybdisFields) -> TokenStrea
                 return None;
             }
 
-            let push_to_query_str = format!("Self::PUSH_{}_QUERY", field.name.to_uppercase());
+            let push_to_query_str = ch_args.const_path(&format!("PUSH_{}_QUERY", field.name.to_uppercase()));
             let push_to_query = parse_str::<TokenStream>(&push_to_query_str).unwrap();
-            let fun_name_str = format!("push_{}", field.name);
+            let fun_name_str = ch_args.method_name(&format!("push_{}", field.name));
             let fun_name = parse_str::<TokenStream>(&fun_name_str).unwrap();
             let types = fields.primary_key_fields.types();
             let values = fields.primary_key_fields.values();
@@ -193,7 +193,10 @@ pub(crate) fn push_to_collection_methods(fields: &CharybdisFields) -> TokenStrea
     expanded
 }
 
-pub(crate) fn push_to_collection_methods_if_exists(fields: &CharybdisFields) -> TokenStream {
+pub(crate) fn push_to_collection_methods_if_exists(
+    ch_args: &CharybdisMacroArgs,
+    fields: &CharybdisFields,
+) -> TokenStream {
     let push_to_collection_rules: Vec<TokenStream> = fields
         .db_fields
         .iter()
@@ -202,9 +205,9 @@ pub(crate) fn push_to_collection_methods_if_exists(fields: &CharybdisFields) ->
                 return None;
             }
 
-            let push_to_query_str = format!("Self::PUSH_{}_IF_EXISTS_QUERY", field.name.to_uppercase());
+            let push_to_query_str = ch_args.const_path(&format!("PUSH_{}_IF_EXISTS_QUERY", field.name.to_uppercase()));
             let push_to_query = parse_str::<TokenStream>(&push_to_query_str).unwrap();
-            let fun_name_str = format!("push_{}_if_exists", field.name);
+            let fun_name_str = ch_args.method_name(&format!("push_{}_if_exists", field.name));
             let fun_name = parse_str::<TokenStream>(&fun_name_str).unwrap();
             let types = fields.primary_key_fields.types();
             let values = fields.primary_key_fields.values();
@@ -232,7 +235,7 @@ pub(crate) fn push_to_collection_methods_if_exists(fields: &CharybdisFields) ->
     expanded
 }
 
-pub(crate) fn pull_from_collection_methods(fields: &CharybdisFields) -> TokenStream {
+pub(crate) fn pull_from_collection_methods(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> TokenStream {
     let pull_from_collection_rules: Vec<TokenStream> = fields
         .db_fields
         .iter()
@@ -241,9 +244,9 @@ pub(crate) fn pull_from_collection_methods(fields: &CharybdisFields) -> TokenStr
                 return None;
             }
 
-            let pull_from_query_str = format!("Self::PULL_{}_QUERY", field.name.to_uppercase());
+            let pull_from_query_str = ch_args.const_path(&format!("PULL_{}_QUERY", field.name.to_uppercase()));
             let pull_from_query = parse_str::<TokenStream>(&pull_from_query_str).unwrap();
-            let fun_name_str = format!("pull_{}", field.name);
+            let fun_name_str = ch_args.method_name(&format!("pull_{}", field.name));
             let fun_name = parse_str::<TokenStream>(&fun_name_str).unwrap();
             let types = fields.primary_key_fields.types();
             let values = fields.primary_key_fields.values();
@@ -271,7 +274,10 @@ pub(crate) fn pull_from_collection_methods(fields: &CharybdisFields) -> TokenStr
     expanded
 }
 
-pub(crate) fn pull_from_collection_methods_if_exists(fields: &CharybdisFields) -> TokenStream {
+pub(crate) fn pull_from_collection_methods_if_exists(
+    ch_args: &CharybdisMacroArgs,
+    fields: &CharybdisFields,
+) -> TokenStream {
     let pull_from_collection_rules: Vec<TokenStream> = fields
         .db_fields
         .iter()
@@ -280,9 +286,10 @@ pub(crate) fn pull_from_collection_methods_if_exists(fields: &CharybdisFields) -
                 return None;
             }
 
-            let pull_from_query_str = format!("Self::PULL_{}_IF_EXISTS_QUERY", field.name.to_uppercase());
+            let pull_from_query_str =
+                ch_args.const_path(&format!("PULL_{}_IF_EXISTS_QUERY", field.name.to_uppercase()));
             let pull_from_query = parse_str::<TokenStream>(&pull_from_query_str).unwrap();
-            let fun_name_str = format!("pull_{}_if_exists", field.name);
+            let fun_name_str = ch_args.method_name(&format!("pull_{}_if_exists", field.name));
             let fun_name = parse_str::<TokenStream>(&fun_name_str).unwrap();
             let types = fields.primary_key_fields.types();
             let values = fields.primary_key_fields.values();
@@ -309,3 +316,330 @@ pub(crate) fn pull_from_collection_methods_if_exists(fields: &CharybdisFields) -
 
     expanded
 }
+
+pub(crate) fn push_to_collection_cb_methods(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> TokenStream {
+    let push_to_collection_rules: Vec<TokenStream> = fields
+        .db_fields
+        .iter()
+        .filter_map(|field| {
+            if !field.is_collection() {
+                return None;
+            }
+
+            let push_to_query_str = ch_args.const_path(&format!("PUSH_{}_QUERY", field.name.to_uppercase()));
+            let push_to_query = parse_str::<TokenStream>(&push_to_query_str).unwrap();
+            let fun_name_str = ch_args.method_name(&format!("push_{}_cb", field.name));
+            let fun_name = parse_str::<TokenStream>(&fun_name_str).unwrap();
+            let registered_fun_name = parse_str::<TokenStream>(&format!("{}_registered", fun_name_str)).unwrap();
+            let types = fields.primary_key_fields.types();
+            let values = fields.primary_key_fields.values();
+
+            let expanded = quote! {
+                pub fn #fun_name<'a, V: charybdis::scylla::SerializeValue, Ext>(
+                    &'a mut self,
+                    value: V,
+                    extension: &'a Ext,
+                ) -> charybdis::query::CharybdisCbQuery<
+                    'a,
+                    Self,
+                    charybdis::callbacks::CollectionUpdateAction<Self>,
+                    (V, #(#types),*),
+                >
+                where
+                    Self: charybdis::callbacks::Callbacks<Extension = Ext>,
+                {
+                    let values = charybdis::query::QueryValue::Owned((value, #(#values),*));
+
+                    charybdis::query::CharybdisCbQuery::with_values(#push_to_query, values, self, extension)
+                }
+
+                pub fn #registered_fun_name<'a, V: charybdis::scylla::SerializeValue>(
+                    &'a mut self,
+                    value: V,
+                ) -> charybdis::query::CharybdisCbQuery<
+                    'a,
+                    Self,
+                    charybdis::callbacks::CollectionUpdateAction<Self>,
+                    (V, #(#types),*),
+                >
+                where
+                    Self: charybdis::callbacks::Callbacks,
+                    <Self as charybdis::callbacks::Callbacks>::Extension: Send + Sync + 'static,
+                {
+                    let values = charybdis::query::QueryValue::Owned((value, #(#values),*));
+
+                    charybdis::query::CharybdisCbQuery::with_registered_values(#push_to_query, values, self)
+                }
+            };
+
+            Some(expanded)
+        })
+        .collect();
+
+    let expanded = quote! {
+        #(#push_to_collection_rules)*
+    };
+
+    expanded
+}
+
+pub(crate) fn pull_from_collection_cb_methods(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> TokenStream {
+    let pull_from_collection_rules: Vec<TokenStream> = fields
+        .db_fields
+        .iter()
+        .filter_map(|field| {
+            if !field.is_collection() {
+                return None;
+            }
+
+            let pull_from_query_str = ch_args.const_path(&format!("PULL_{}_QUERY", field.name.to_uppercase()));
+            let pull_from_query = parse_str::<TokenStream>(&pull_from_query_str).unwrap();
+            let fun_name_str = ch_args.method_name(&format!("pull_{}_cb", field.name));
+            let fun_name = parse_str::<TokenStream>(&fun_name_str).unwrap();
+            let registered_fun_name = parse_str::<TokenStream>(&format!("{}_registered", fun_name_str)).unwrap();
+            let types = fields.primary_key_fields.types();
+            let values = fields.primary_key_fields.values();
+
+            let expanded = quote! {
+                pub fn #fun_name<'a, V: charybdis::scylla::SerializeValue, Ext>(
+                    &'a mut self,
+                    value: V,
+                    extension: &'a Ext,
+                ) -> charybdis::query::CharybdisCbQuery<
+                    'a,
+                    Self,
+                    charybdis::callbacks::CollectionUpdateAction<Self>,
+                    (V, #(#types),*),
+                >
+                where
+                    Self: charybdis::callbacks::Callbacks<Extension = Ext>,
+                {
+                    let values = charybdis::query::QueryValue::Owned((value, #(#values),*));
+
+                    charybdis::query::CharybdisCbQuery::with_values(#pull_from_query, values, self, extension)
+                }
+
+                pub fn #registered_fun_name<'a, V: charybdis::scylla::SerializeValue>(
+                    &'a mut self,
+                    value: V,
+                ) -> charybdis::query::CharybdisCbQuery<
+                    'a,
+                    Self,
+                    charybdis::callbacks::CollectionUpdateAction<Self>,
+                    (V, #(#types),*),
+                >
+                where
+                    Self: charybdis::callbacks::Callbacks,
+                    <Self as charybdis::callbacks::Callbacks>::Extension: Send + Sync + 'static,
+                {
+                    let values = charybdis::query::QueryValue::Owned((value, #(#values),*));
+
+                    charybdis::query::CharybdisCbQuery::with_registered_values(#pull_from_query, values, self)
+                }
+            };
+
+            Some(expanded)
+        })
+        .collect();
+
+    let expanded = quote! {
+        #(#pull_from_collection_rules)*
+    };
+
+    expanded
+}
+
+pub(crate) fn map_entry_consts(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> TokenStream {
+    let queries: Vec<TokenStream> = fields
+        .db_fields
+        .iter()
+        .filter_map(|field| {
+            if !field.is_map() {
+                return None;
+            }
+
+            let set_query_str = format!(
+                "UPDATE {} SET {}[?] = ? WHERE {}",
+                ch_args.table_name(),
+                field.cql_name(),
+                fields.primary_key_fields.where_placeholders(),
+            );
+            let remove_query_str = format!(
+                "DELETE {}[?] FROM {} WHERE {}",
+                field.cql_name(),
+                ch_args.table_name(),
+                fields.primary_key_fields.where_placeholders(),
+            );
+
+            let set_const_name = ch_args.const_name(&format!("SET_{}_ENTRY_QUERY", field.name.to_uppercase()));
+            let set_const_name: TokenStream = parse_str::<TokenStream>(&set_const_name).unwrap();
+            let remove_const_name = ch_args.const_name(&format!("REMOVE_{}_ENTRY_QUERY", field.name.to_uppercase()));
+            let remove_const_name: TokenStream = parse_str::<TokenStream>(&remove_const_name).unwrap();
+
+            let expanded = quote! {
+                pub const #set_const_name: &'static str = #set_query_str;
+                pub const #remove_const_name: &'static str = #remove_query_str;
+            };
+
+            Some(expanded)
+        })
+        .collect();
+
+    let expanded = quote! {
+        #(#queries)*
+    };
+
+    expanded
+}
+
+pub(crate) fn map_entry_methods(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> TokenStream {
+    let map_entry_rules: Vec<TokenStream> = fields
+        .db_fields
+        .iter()
+        .filter_map(|field| {
+            if !field.is_map() {
+                return None;
+            }
+
+            let set_query_str = ch_args.const_path(&format!("SET_{}_ENTRY_QUERY", field.name.to_uppercase()));
+            let set_query = parse_str::<TokenStream>(&set_query_str).unwrap();
+            let remove_query_str = ch_args.const_path(&format!("REMOVE_{}_ENTRY_QUERY", field.name.to_uppercase()));
+            let remove_query = parse_str::<TokenStream>(&remove_query_str).unwrap();
+            let set_fun_name_str = ch_args.method_name(&format!("set_{}_entry", field.name));
+            let set_fun_name = parse_str::<TokenStream>(&set_fun_name_str).unwrap();
+            let remove_fun_name_str = ch_args.method_name(&format!("remove_{}_entry", field.name));
+            let remove_fun_name = parse_str::<TokenStream>(&remove_fun_name_str).unwrap();
+            let types = fields.primary_key_fields.types();
+            let values = fields.primary_key_fields.values();
+
+            let expanded = quote! {
+                pub fn #set_fun_name<K: charybdis::scylla::SerializeValue, V: charybdis::scylla::SerializeValue>(
+                    &self,
+                    key: K,
+                    value: V,
+                ) -> charybdis::query::CharybdisQuery<(K, V, #(#types),*), Self, charybdis::query::ModelMutation> {
+                    charybdis::query::CharybdisQuery::new(
+                        #set_query,
+                        charybdis::query::QueryValue::Owned((key, value, #(#values),*)),
+                    )
+                }
+
+                pub fn #remove_fun_name<K: charybdis::scylla::SerializeValue>(
+                    &self,
+                    key: K,
+                ) -> charybdis::query::CharybdisQuery<(K, #(#types),*), Self, charybdis::query::ModelMutation> {
+                    charybdis::query::CharybdisQuery::new(
+                        #remove_query,
+                        charybdis::query::QueryValue::Owned((key, #(#values),*)),
+                    )
+                }
+            };
+
+            Some(expanded)
+        })
+        .collect();
+
+    let expanded = quote! {
+        #(#map_entry_rules)*
+    };
+
+    expanded
+}
+
+pub(crate) fn list_element_consts(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> TokenStream {
+    let queries: Vec<TokenStream> = fields
+        .db_fields
+        .iter()
+        .filter_map(|field| {
+            if !field.is_list() {
+                return None;
+            }
+
+            let set_at_query_str = format!(
+                "UPDATE {} SET {}[?] = ? WHERE {}",
+                ch_args.table_name(),
+                field.cql_name(),
+                fields.primary_key_fields.where_placeholders(),
+            );
+            let prepend_query_str = format!(
+                "UPDATE {} SET {} = ? + {} WHERE {}",
+                ch_args.table_name(),
+                field.cql_name(),
+                field.cql_name(),
+                fields.primary_key_fields.where_placeholders(),
+            );
+
+            let set_at_const_name = ch_args.const_name(&format!("SET_{}_AT_QUERY", field.name.to_uppercase()));
+            let set_at_const_name: TokenStream = parse_str::<TokenStream>(&set_at_const_name).unwrap();
+            let prepend_const_name = ch_args.const_name(&format!("PREPEND_{}_QUERY", field.name.to_uppercase()));
+            let prepend_const_name: TokenStream = parse_str::<TokenStream>(&prepend_const_name).unwrap();
+
+            let expanded = quote! {
+                pub const #set_at_const_name: &'static str = #set_at_query_str;
+                pub const #prepend_const_name: &'static str = #prepend_query_str;
+            };
+
+            Some(expanded)
+        })
+        .collect();
+
+    let expanded = quote! {
+        #(#queries)*
+    };
+
+    expanded
+}
+
+pub(crate) fn list_element_methods(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> TokenStream {
+    let list_element_rules: Vec<TokenStream> = fields
+        .db_fields
+        .iter()
+        .filter_map(|field| {
+            if !field.is_list() {
+                return None;
+            }
+
+            let set_at_query_str = ch_args.const_path(&format!("SET_{}_AT_QUERY", field.name.to_uppercase()));
+            let set_at_query = parse_str::<TokenStream>(&set_at_query_str).unwrap();
+            let prepend_query_str = ch_args.const_path(&format!("PREPEND_{}_QUERY", field.name.to_uppercase()));
+            let prepend_query = parse_str::<TokenStream>(&prepend_query_str).unwrap();
+            let set_at_fun_name_str = ch_args.method_name(&format!("set_{}_at", field.name));
+            let set_at_fun_name = parse_str::<TokenStream>(&set_at_fun_name_str).unwrap();
+            let prepend_fun_name_str = ch_args.method_name(&format!("prepend_{}", field.name));
+            let prepend_fun_name = parse_str::<TokenStream>(&prepend_fun_name_str).unwrap();
+            let types = fields.primary_key_fields.types();
+            let values = fields.primary_key_fields.values();
+
+            let expanded = quote! {
+                pub fn #set_at_fun_name<V: charybdis::scylla::SerializeValue>(
+                    &self,
+                    index: i32,
+                    value: V,
+                ) -> charybdis::query::CharybdisQuery<(i32, V, #(#types),*), Self, charybdis::query::ModelMutation> {
+                    charybdis::query::CharybdisQuery::new(
+                        #set_at_query,
+                        charybdis::query::QueryValue::Owned((index, value, #(#values),*)),
+                    )
+                }
+
+                pub fn #prepend_fun_name<V: charybdis::scylla::SerializeValue>(
+                    &self,
+                    value: V,
+                ) -> charybdis::query::CharybdisQuery<(V, #(#types),*), Self, charybdis::query::ModelMutation> {
+                    charybdis::query::CharybdisQuery::new(
+                        #prepend_query,
+                        charybdis::query::QueryValue::Owned((value, #(#values),*)),
+                    )
+                }
+            };
+
+            Some(expanded)
+        })
+        .collect();
+
+    let expanded = quote! {
+        #(#list_element_rules)*
+    };
+
+    expanded
+}
@@ -20,12 +20,12 @@ pub(crate) fn increment_counter_methods(ch_args: &CharybdisMacroArgs, fields: &C
             let increment_query = format!(
                 "UPDATE {} SET {} = {} + ? WHERE {}",
                 ch_args.table_name(),
-                field.name,
-                field.name,
+                field.cql_name(),
+                field.cql_name(),
                 fields.primary_key_fields.where_placeholders()
             );
 
-            let fun_name_str = format!("increment_{}", field.name);
+            let fun_name_str = ch_args.method_name(&format!("increment_{}", field.name));
             let fun_name = parse_str::<TokenStream>(&fun_name_str).unwrap();
             let types = fields.primary_key_fields.types();
             let values = fields.primary_key_fields.values();
@@ -65,12 +65,12 @@ pub(crate) fn decrement_counter_methods(ch_args: &CharybdisMacroArgs, fields: &C
             let decrement_query = format!(
                 "UPDATE {} SET {} = {} - ? WHERE {}",
                 ch_args.table_name(),
-                field.name,
-                field.name,
+                field.cql_name(),
+                field.cql_name(),
                 fields.primary_key_fields.where_placeholders()
             );
 
-            let fun_name_str = format!("decrement_{}", field.name);
+            let fun_name_str = ch_args.method_name(&format!("decrement_{}", field.name));
             let fun_name = parse_str::<TokenStream>(&fun_name_str).unwrap();
             let types = fields.primary_key_fields.types();
             let values = fields.primary_key_fields.values();
@@ -23,10 +23,17 @@ const MAX_DELETE_BY_FUNCTIONS: usize = 3;
 /// ```ignore
 ///  User::delete_by_id_and_org_id(session: &Session, org_id: Uuid) -> Result<Vec<User>, errors::CharybdisError>
 ///  User::delete_by_id_and_org_id_and_created_at(session: &Session, org_id: Uuid, created_at: Timestamp) -> Result<Vec<User>, errors::CharybdisError>
+/// ```
+/// Functions of models with `soft_delete` field are named `hard_delete_by_...`, as they remove rows, while rows
+/// selected by partial primary key can't be soft deleted by single `UPDATE`.
 pub(crate) fn delete_by_primary_key_functions(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> TokenStream {
     let table_name = ch_args.table_name();
     let partition_keys_len = fields.partition_key_fields.len();
     let primary_key_stack = &fields.primary_key_fields;
+    let fun_name_prefix = match fields.soft_delete_column {
+        Some(_) => "hard_delete_by",
+        None => "delete_by",
+    };
     let mut generated = quote! {};
 
     for i in 0..primary_key_stack.len() {
@@ -46,7 +53,8 @@ pub(crate) fn delete_by_primary_key_functions(ch_args: &CharybdisMacroArgs, fiel
             table_name,
             current_fields.where_placeholders()
         );
-        let find_by_fun_name_str = format!("delete_by_{}", current_fields.names().join("_and_"));
+        let find_by_fun_name_str =
+            ch_args.method_name(&format!("{}_{}", fun_name_prefix, current_fields.names().join("_and_")));
         let delete_by_fun_name = syn::Ident::new(&find_by_fun_name_str, proc_macro2::Span::call_site());
         let arguments = current_fields.to_fn_args();
         let types_tp = arguments.types_tp();
@@ -1,9 +1,16 @@
 use proc_macro2::TokenStream;
 use quote::quote;
+use syn::parse_str;
 
-use charybdis_parser::fields::{CharybdisFields, Field};
+use charybdis_parser::fields::{CharybdisFields, CollectionIndex, Field};
 use charybdis_parser::traits::CharybdisMacroArgs;
 
+use crate::model::{first_row_query, soft_delete_filtered};
+use crate::traits::fields::{FieldsFindFirstFns, FieldsFindFn, FieldsFindFnNames, FieldsQuery, FieldsToArguments};
+use crate::traits::r#type::{CollectionTypes, TypeWithoutOptions};
+use crate::traits::string::ToIdent;
+use crate::traits::tuple::Tuple;
+
 pub(crate) fn find_all_function(
     struct_name: &syn::Ident,
     ch_args: &CharybdisMacroArgs,
@@ -11,7 +18,7 @@ pub(crate) fn find_all_function(
 ) -> TokenStream {
     let comma_sep_cols = fields.db_fields.comma_sep_cols();
     let table_name = ch_args.table_name();
-    let query_str = format!("SELECT {} FROM {}", comma_sep_cols, table_name);
+    let query_str = soft_delete_filtered(fields, format!("SELECT {} FROM {}", comma_sep_cols, table_name));
     quote! {
     pub fn find_all<'a>() -> charybdis::query::CharybdisQuery<'a, (), #struct_name, charybdis::query::ModelStream<Self>> {
             <#struct_name as charybdis::operations::Find>::find(&#query_str, ())
@@ -19,12 +26,8 @@ pub(crate) fn find_all_function(
     }
 }
 
-/// for up to 3 primary keys, generate find_by_primary_key functions
-use crate::traits::fields::{FieldsFindFirstFns, FieldsFindFn, FieldsQuery};
-
-const MAX_FIND_BY_FIELDS: usize = 3;
-
-/// for up to 3 primary keys, generate find_by_primary_key functions e.g.
+/// for every prefix of primary key that contains complete partition key, generate find_by_primary_key
+/// functions that take key values as separate typed arguments, e.g.
 /// ```rust
 /// use scylla::CachingSession;
 /// use charybdis::errors::CharybdisError;
@@ -87,10 +90,6 @@ pub(crate) fn find_by_primary_keys_functions(
     let mut generated = quote! {};
 
     for i in 0..primary_key_stack.len() {
-        if i == MAX_FIND_BY_FIELDS {
-            break;
-        }
-
         let current_fields = primary_key_stack.iter().take(i + 1).cloned().collect::<Vec<&Field>>();
 
         // we need complete partition key to query
@@ -104,23 +103,78 @@ pub(crate) fn find_by_primary_keys_functions(
             table_name,
             current_fields.where_placeholders()
         );
+        let query_str = soft_delete_filtered(fields, query_str);
 
         if current_fields.len() == primary_key_stack.len() {
             // for complete primary key we get single row
-            generated.extend(current_fields.find_one_fn(struct_name, &query_str));
+            generated.extend(current_fields.find_one_fn(struct_name, ch_args, &query_str));
         } else {
             // for partial primary key we get a stream
-            generated.extend(current_fields.find_fn(struct_name, &query_str));
+            generated.extend(current_fields.find_fn(struct_name, ch_args, &query_str));
         }
 
         // query one row
-        generated.extend(current_fields.find_first_fn(struct_name, &query_str));
-        generated.extend(current_fields.maybe_find_first_fn(struct_name, &query_str));
+        generated.extend(current_fields.find_first_fn(struct_name, ch_args, &query_str));
+        generated.extend(current_fields.maybe_find_first_fn(struct_name, ch_args, &query_str));
     }
 
     generated
 }
 
+/// Generates `find_by_<partition_key>_in` function that selects rows of multiple partitions with `IN` on the
+/// last partition key column, while preceding partition key columns are matched by equality, e.g.
+/// `Post::find_by_date_in(vec![today, yesterday])` or `Post::find_by_org_id_and_date_in(org_id, dates)`.
+pub(crate) fn find_by_partition_key_in_function(
+    struct_name: &syn::Ident,
+    ch_args: &CharybdisMacroArgs,
+    fields: &CharybdisFields,
+) -> TokenStream {
+    let partition_keys = &fields.partition_key_fields;
+    let (in_field, eq_fields) = match partition_keys.split_last() {
+        Some(split) => split,
+        None => return quote!(),
+    };
+
+    let mut where_clause = eq_fields.to_vec().where_placeholders();
+    if !where_clause.is_empty() {
+        where_clause.push_str(" AND ");
+    }
+    where_clause.push_str(&format!("{} IN ?", in_field.cql_name()));
+
+    let query_str = format!(
+        "SELECT {} FROM {} WHERE {}",
+        fields.db_fields.comma_sep_cols(),
+        ch_args.table_name(),
+        where_clause
+    );
+    let query_str = soft_delete_filtered(fields, query_str);
+
+    let find_by_fn_name = ch_args
+        .method_name(&format!("{}_in", partition_keys.find_by_fn_name()))
+        .to_ident();
+    let in_type = in_field.ty.type_without_options();
+    let mut arguments = eq_fields.to_vec().to_fn_args();
+    arguments.push(syn::parse_str(&format!("{}: Vec<{}>", in_field.name, in_type)).unwrap());
+    let types_tp = arguments.types_tp();
+    let values_tp = arguments.values_tp();
+
+    // partitions of single-column partition key can be queried separately by `per_partition`
+    let per_partition_statement = eq_fields.is_empty().then(|| {
+        quote! {
+            .per_partition_statement(<#struct_name as charybdis::model::BaseModel>::FIND_BY_PARTITION_KEY_QUERY)
+        }
+    });
+
+    quote! {
+        pub fn #find_by_fn_name<'a>(
+            #(#arguments),*
+        ) -> charybdis::query::CharybdisQuery<'a, #types_tp, Self, charybdis::query::ModelStream<Self>> {
+            <#struct_name as charybdis::operations::Find>::find(#query_str, #values_tp)
+                #per_partition_statement
+        }
+    }
+}
+
 pub(crate) fn find_by_local_secondary_index(
     struct_name: &syn::Ident,
     ch_args: &CharybdisMacroArgs,
@@ -143,9 +197,10 @@ pub(crate) fn find_by_local_secondary_index(
             table_name,
             current_fields.where_placeholders()
         );
-        let find_fn = current_fields.find_fn(struct_name, &query_str);
-        let find_first_fn = current_fields.find_first_fn(struct_name, &query_str);
-        let maybe_find_first_fn = current_fields.maybe_find_first_fn(struct_name, &query_str);
+        let query_str = soft_delete_filtered(fields, query_str);
+        let find_fn = current_fields.find_fn(struct_name, ch_args, &query_str);
+        let find_first_fn = current_fields.find_first_fn(struct_name, ch_args, &query_str);
+        let maybe_find_first_fn = current_fields.maybe_find_first_fn(struct_name, ch_args, &query_str);
 
         generated.extend(find_fn);
         generated.extend(find_first_fn);
@@ -167,10 +222,16 @@ pub(crate) fn find_by_global_secondary_index(
     let mut generated = quote! {};
 
     gsi_fields.iter().for_each(|gsi| {
-        let query_str = format!("SELECT {} FROM {} WHERE {} = ?", comma_sep_cols, table_name, gsi.name);
-        let find_fn = gsi.find_fn(struct_name, &query_str);
-        let find_first_fn = gsi.find_first_fn(struct_name, &query_str);
-        let maybe_find_first_fn = gsi.maybe_find_first_fn(struct_name, &query_str);
+        let query_str = format!(
+            "SELECT {} FROM {} WHERE {} = ?",
+            comma_sep_cols,
+            table_name,
+            gsi.cql_name()
+        );
+        let query_str = soft_delete_filtered(fields, query_str);
+        let find_fn = gsi.find_fn(struct_name, ch_args, &query_str);
+        let find_first_fn = gsi.find_first_fn(struct_name, ch_args, &query_str);
+        let maybe_find_first_fn = gsi.maybe_find_first_fn(struct_name, ch_args, &query_str);
 
         generated.extend(find_fn);
         generated.extend(find_first_fn);
@@ -179,3 +240,207 @@ pub(crate) fn find_by_global_secondary_index(
 
     generated
 }
+
+/// Generates `find_by_<field>_filtering`, `find_first_by_<field>_filtering` and
+/// `maybe_find_first_by_<field>_filtering` functions for fields marked with `#[charybdis(allow_filtering)]`.
+/// Finders on non-key columns that are not indexed are generated only on explicit opt-in, as they
+/// scan whole cluster.
+pub(crate) fn find_by_filtering_fields(
+    struct_name: &syn::Ident,
+    ch_args: &CharybdisMacroArgs,
+    fields: &CharybdisFields,
+) -> TokenStream {
+    let table_name = ch_args.table_name();
+    let comma_sep_cols = fields.db_fields.comma_sep_cols();
+
+    let mut generated = quote! {};
+
+    fields
+        .db_fields
+        .iter()
+        .filter(|field| field.allow_filtering)
+        .for_each(|field| {
+            let query_str = format!(
+                "SELECT {} FROM {} WHERE {} = ? ALLOW FILTERING",
+                comma_sep_cols,
+                table_name,
+                field.cql_name()
+            );
+            let query_str = soft_delete_filtered(fields, query_str);
+            let first_query_str = first_row_query(&query_str);
+
+            let find_by_fn_name = ch_args
+                .method_name(&format!("find_by_{}_filtering", field.name))
+                .to_ident();
+            let find_first_by_fn_name = ch_args
+                .method_name(&format!("find_first_by_{}_filtering", field.name))
+                .to_ident();
+            let maybe_find_first_by_fn_name = ch_args
+                .method_name(&format!("maybe_find_first_by_{}_filtering", field.name))
+                .to_ident();
+            let arguments = field.to_fn_args();
+            let types_tp = arguments.types_tp();
+            let values_tp = arguments.values_tp();
+
+            generated.extend(quote! {
+                pub fn #find_by_fn_name<'a>(
+                    #(#arguments),*
+                ) -> charybdis::query::CharybdisQuery<'a, #types_tp, Self, charybdis::query::ModelStream<Self>> {
+                    <#struct_name as charybdis::operations::Find>::find(#query_str, #values_tp)
+                }
+
+                pub fn #find_first_by_fn_name<'a>(
+                    #(#arguments),*
+                ) -> charybdis::query::CharybdisQuery<'a, #types_tp, Self, charybdis::query::ModelRow<Self>> {
+                    <#struct_name as charybdis::operations::Find>::find_first(#first_query_str, #values_tp)
+                }
+
+                pub fn #maybe_find_first_by_fn_name<'a>(
+                    #(#arguments),*
+                ) -> charybdis::query::CharybdisQuery<'a, #types_tp, Self, charybdis::query::OptionalModelRow<Self>> {
+                    <#struct_name as charybdis::operations::Find>::maybe_find_first(#first_query_str, #values_tp)
+                }
+            });
+        });
+
+    generated
+}
+
+/// Generates finders for fields with collection index:
+/// - `#[charybdis(index_values)]`: `find_by_<field>_contains(value)`, `WHERE <field> CONTAINS ?`
+/// - `#[charybdis(index_keys)]`: `find_by_<field>_contains_key(key)`, `WHERE <field> CONTAINS KEY ?`
+/// - `#[charybdis(index_entries)]`: `find_by_<field>_entry(key, value)`, `WHERE <field>[?] = ?`
+///
+/// along with their `find_first_by_` and `maybe_find_first_by_` variants.
+pub(crate) fn find_by_collection_index(
+    struct_name: &syn::Ident,
+    ch_args: &CharybdisMacroArgs,
+    fields: &CharybdisFields,
+) -> TokenStream {
+    let table_name = ch_args.table_name();
+    let comma_sep_cols = fields.db_fields.comma_sep_cols();
+
+    let mut generated = quote! {};
+
+    fields.db_fields.iter().for_each(|field| {
+        let collection_index = match field.collection_index {
+            Some(collection_index) => collection_index,
+            None => return,
+        };
+
+        let collection_types = field.ty.collection_types();
+        let key_type = collection_types.first().expect("collection type argument");
+        let value_type = collection_types.last().expect("collection type argument");
+
+        let (fn_suffix, condition, arguments) = match collection_index {
+            CollectionIndex::Values => (
+                "contains",
+                format!("{} CONTAINS ?", field.cql_name()),
+                vec![quote!(value: #value_type)],
+            ),
+            CollectionIndex::Keys => (
+                "contains_key",
+                format!("{} CONTAINS KEY ?", field.cql_name()),
+                vec![quote!(key: #key_type)],
+            ),
+            CollectionIndex::Entries => (
+                "entry",
+                format!("{}[?] = ?", field.cql_name()),
+                vec![quote!(key: #key_type), quote!(value: #value_type)],
+            ),
+        };
+
+        let arguments = arguments
+            .into_iter()
+            .map(|argument| syn::parse2::<syn::FnArg>(argument).unwrap())
+            .collect::<Vec<syn::FnArg>>();
+        let types_tp = arguments.types_tp();
+        let values_tp = arguments.values_tp();
+
+        let query_str = format!("SELECT {} FROM {} WHERE {}", comma_sep_cols, table_name, condition);
+        let query_str = soft_delete_filtered(fields, query_str);
+        let first_query_str = first_row_query(&query_str);
+
+        let find_by_fn_name = ch_args
+            .method_name(&format!("find_by_{}_{}", field.name, fn_suffix))
+            .to_ident();
+        let find_first_by_fn_name = ch_args
+            .method_name(&format!("find_first_by_{}_{}", field.name, fn_suffix))
+            .to_ident();
+        let maybe_find_first_by_fn_name = ch_args
+            .method_name(&format!("maybe_find_first_by_{}_{}", field.name, fn_suffix))
+            .to_ident();
+
+        generated.extend(quote! {
+            pub fn #find_by_fn_name<'a>(
+                #(#arguments),*
+            ) -> charybdis::query::CharybdisQuery<'a, #types_tp, Self, charybdis::query::ModelStream<Self>> {
+                <#struct_name as charybdis::operations::Find>::find(#query_str, #values_tp)
+            }
+
+            pub fn #find_first_by_fn_name<'a>(
+                #(#arguments),*
+            ) -> charybdis::query::CharybdisQuery<'a, #types_tp, Self, charybdis::query::ModelRow<Self>> {
+                <#struct_name as charybdis::operations::Find>::find_first(#first_query_str, #values_tp)
+            }
+
+            pub fn #maybe_find_first_by_fn_name<'a>(
+                #(#arguments),*
+            ) -> charybdis::query::CharybdisQuery<'a, #types_tp, Self, charybdis::query::OptionalModelRow<Self>> {
+                <#struct_name as charybdis::operations::Find>::maybe_find_first(#first_query_str, #values_tp)
+            }
+        });
+    });
+
+    generated
+}
+
+/// Generates finders on `base_model` of materialized view, e.g. `User::find_via_users_by_username(username)`, that
+/// query the view by its partition key and read rows as base model.
+pub(crate) fn find_via_view_functions(
+    struct_name: &syn::Ident,
+    ch_args: &CharybdisMacroArgs,
+    fields: &CharybdisFields,
+) -> TokenStream {
+    let base_model = match &ch_args.base_model {
+        Some(base_model) => parse_str::<TokenStream>(base_model)
+            .unwrap_or_else(|_| panic!("Invalid base_model path of view {}", struct_name)),
+        None => return quote!(),
+    };
+
+    let view_name = ch_args.table_name();
+    let arguments = fields.partition_key_fields.to_fn_args();
+    let types_tp = arguments.types_tp();
+    let values_tp = arguments.values_tp();
+    let query_str = fields.partition_key_fields.where_placeholders();
+    let first_query_str = format!("{} LIMIT 1", query_str);
+
+    let find_via_fn_name = format!("find_via_{}", view_name).to_ident();
+    let find_first_via_fn_name = format!("find_first_via_{}", view_name).to_ident();
+    let maybe_find_first_via_fn_name = format!("maybe_find_first_via_{}", view_name).to_ident();
+
+    quote! {
+        impl #base_model {
+            pub fn #find_via_fn_name<'a>(
+                #(#arguments),*
+            ) -> charybdis::query::CharybdisQuery<'a, #types_tp, #struct_name, charybdis::query::ModelStream<Self>> {
+                <Self as charybdis::operations::Find>::find_via::<#struct_name, _>(#query_str, #values_tp)
+            }
+
+            pub fn #find_first_via_fn_name<'a>(
+                #(#arguments),*
+            ) -> charybdis::query::CharybdisQuery<'a, #types_tp, #struct_name, charybdis::query::ModelRow<Self>> {
+                <Self as charybdis::operations::Find>::find_first_via::<#struct_name, _>(#first_query_str, #values_tp)
+            }
+
+            pub fn #maybe_find_first_via_fn_name<'a>(
+                #(#arguments),*
+            ) -> charybdis::query::CharybdisQuery<'a, #types_tp, #struct_name, charybdis::query::OptionalModelRow<Self>> {
+                <Self as charybdis::operations::Find>::maybe_find_first_via::<#struct_name, _>(
+                    #first_query_str,
+                    #values_tp,
+                )
+            }
+        }
+    }
+}
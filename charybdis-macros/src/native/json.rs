@@ -0,0 +1,157 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Fields, LitStr};
+
+use charybdis_parser::fields::CharybdisFields;
+use charybdis_parser::traits::CharybdisMacroArgs;
+
+struct JsonField<'a> {
+    ident: &'a syn::Ident,
+    key: String,
+    ignore: bool,
+}
+
+/// JSON key of the field respects `#[serde(rename = "...")]`, so generated methods can replace serde
+/// derived ones without changing the payload.
+fn json_key(ident: &syn::Ident, attrs: &[Attribute]) -> String {
+    let mut key = ident.to_string();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                if let Ok(value) = meta.value() {
+                    key = value.parse::<LitStr>()?.value();
+                }
+            } else if meta.input.peek(syn::Token![=]) {
+                // skip other `key = value` args
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+
+            Ok(())
+        });
+    }
+
+    key
+}
+
+fn cql_json_impl(struct_name: &syn::Ident, fields: &[JsonField]) -> TokenStream {
+    let type_name = struct_name.to_string();
+
+    let to_json_fields = fields.iter().filter(|field| !field.ignore).map(|field| {
+        let ident = field.ident;
+        let key = &field.key;
+
+        quote! {
+            object.insert(#key.to_string(), charybdis::serializers::CqlJson::to_json_value(&self.#ident));
+        }
+    });
+
+    let from_json_fields = fields.iter().map(|field| {
+        let ident = field.ident;
+        let key = &field.key;
+
+        if field.ignore {
+            return quote!(#ident: Default::default());
+        }
+
+        quote! {
+            #ident: charybdis::serializers::CqlJson::from_json_value(object.remove(#key).unwrap_or_default())
+                .map_err(|e| charybdis::serializers::json_field_error(#key, e))?
+        }
+    });
+
+    quote! {
+        impl charybdis::serializers::CqlJson for #struct_name {
+            fn to_json_value(&self) -> charybdis::serializers::JsonValue {
+                let mut object = charybdis::serializers::JsonMap::new();
+
+                #(#to_json_fields)*
+
+                charybdis::serializers::JsonValue::Object(object)
+            }
+
+            fn from_json_value(
+                value: charybdis::serializers::JsonValue,
+            ) -> Result<Self, charybdis::serializers::JsonError> {
+                let mut object = charybdis::serializers::json_object(value, #type_name)?;
+
+                Ok(Self {
+                    #(#from_json_fields,)*
+                })
+            }
+        }
+    }
+}
+
+///
+/// Generates `CqlJson` implementation for model declared with `cql_json`, together with `to_json` and `from_json`
+/// methods that handle CQL specific types, e.g. `blob` is serialized as base64 string. Fields marked with
+/// `#[charybdis(ignore)]` are skipped and set to default value on deserialization. Methods take precedence over
+/// serde based `ToJson` and `FromJson` traits.
+///
+pub(crate) fn model_json_impl(
+    struct_name: &syn::Ident,
+    ch_args: &CharybdisMacroArgs,
+    fields: &CharybdisFields,
+) -> TokenStream {
+    if !ch_args.cql_json.unwrap_or(false) {
+        return quote!();
+    }
+
+    let json_fields = fields
+        .all_fields
+        .iter()
+        .map(|field| JsonField {
+            ident: &field.ident,
+            key: json_key(&field.ident, field.attrs),
+            ignore: field.ignore,
+        })
+        .collect::<Vec<JsonField>>();
+
+    let cql_json_impl = cql_json_impl(struct_name, &json_fields);
+
+    quote! {
+        #cql_json_impl
+
+        impl #struct_name {
+            pub fn to_json(&self) -> Result<String, charybdis::errors::CharybdisError> {
+                charybdis::serializers::CqlJson::to_json_string(self)
+            }
+
+            pub fn from_json(json: &str) -> Result<Self, charybdis::errors::CharybdisError> {
+                <Self as charybdis::serializers::CqlJson>::from_json_str(json)
+            }
+        }
+    }
+}
+
+/// Generates `CqlJson` implementation for UDT declared with `cql_json`, so it can be used within model
+/// `to_json` and `from_json`.
+pub(crate) fn udt_json_impl(input: &DeriveInput, ch_args: &CharybdisMacroArgs) -> TokenStream {
+    if !ch_args.cql_json.unwrap_or(false) {
+        return quote!();
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => panic!("#[charybdis_udt_model] works only for structs with named fields"),
+        },
+        _ => panic!("#[charybdis_udt_model] works only on structs"),
+    };
+
+    let json_fields = fields
+        .iter()
+        .map(|field| {
+            let ident = field.ident.as_ref().unwrap();
+
+            JsonField {
+                ident,
+                key: json_key(ident, &field.attrs),
+                ignore: false,
+            }
+        })
+        .collect::<Vec<JsonField>>();
+
+    cql_json_impl(&input.ident, &json_fields)
+}
@@ -0,0 +1,281 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_str, DeriveInput};
+
+use charybdis_parser::fields::{CharybdisFields, Field};
+use charybdis_parser::traits::string::ToPascalCase;
+use charybdis_parser::traits::CharybdisMacroArgs;
+
+fn lookup_field<'a>(fields: &'a CharybdisFields, field_name: &str) -> &'a Field<'a> {
+    fields
+        .db_fields
+        .iter()
+        .find(|field| field.name == field_name)
+        .unwrap_or_else(|| panic!("Lookup table field {} not found in struct fields", field_name))
+}
+
+/// e.g. `UserByEmail` for `lookup_tables = [email]` of `User` model
+fn lookup_struct_name(struct_name: &syn::Ident, field_name: &str) -> syn::Ident {
+    format_ident!("{}By{}", struct_name, field_name.to_pascal_case())
+}
+
+///
+/// Generates lookup model for each field in `lookup_tables = [...]`. Lookup model is keyed by
+/// unique field and holds primary key of the base model:
+/// ```ignore
+/// #[charybdis_model(
+///     table_name = users_by_email,
+///     partition_keys = [email],
+///     clustering_keys = []
+/// )]
+/// pub struct UserByEmail {
+///     pub email: Text,
+///     pub id: Uuid,
+/// }
+/// ```
+///
+pub(crate) fn lookup_models(input: &DeriveInput, args: &CharybdisMacroArgs, fields: &CharybdisFields) -> TokenStream {
+    let vis = &input.vis;
+
+    let lookup_models = args.lookup_tables().iter().map(|field_name| {
+        let lookup_field = lookup_field(fields, field_name);
+        let lookup_struct_name = lookup_struct_name(&input.ident, field_name);
+        let table_name = parse_str::<TokenStream>(&args.lookup_table_name(field_name)).unwrap();
+
+        let field_ident = &lookup_field.ident;
+        let field_type = &lookup_field.ty;
+        let field_db_name = lookup_field.cql_name();
+        let primary_key_idents = fields.primary_key_fields.iter().map(|field| &field.ident);
+        let primary_key_types = fields.primary_key_fields.iter().map(|field| &field.ty);
+        let primary_key_db_names = fields.primary_key_fields.iter().map(|field| field.cql_name());
+
+        quote! {
+            #[charybdis::macros::charybdis_model(
+                table_name = #table_name,
+                partition_keys = [#field_ident],
+                clustering_keys = [],
+                exclude_partial_model = true
+            )]
+            #vis struct #lookup_struct_name {
+                #[charybdis(db_name = #field_db_name)]
+                pub #field_ident: #field_type,
+                #(
+                    #[charybdis(db_name = #primary_key_db_names)]
+                    pub #primary_key_idents: #primary_key_types,
+                )*
+            }
+        }
+    });
+
+    quote! {
+        #(#lookup_models)*
+    }
+}
+
+///
+/// Generates operations that keep base table and its lookup tables consistent:
+///
+/// - `insert_with_lookups` claims lookup rows with LWT before inserting model. If some lookup row is
+///   already claimed by another record, `UniqueConstraintError` is returned and already claimed rows are released.
+///   Rows already claimed by the same record are accepted, so failed insert can be retried.
+/// - `update_with_lookups` claims lookup rows for changed lookup fields and updates model and removes
+///   previous lookup rows within single logged batch.
+/// - `delete_with_lookups` deletes model and its lookup rows within single logged batch.
+///
+/// Versioned models are rejected, as their update is conditional, and conditional batch can't write lookup rows
+/// of other partitions.
+///
+pub(crate) fn lookup_table_methods(
+    struct_name: &syn::Ident,
+    args: &CharybdisMacroArgs,
+    fields: &CharybdisFields,
+) -> TokenStream {
+    if args.lookup_tables().is_empty() {
+        return quote!();
+    }
+
+    if let Some(version_field) = fields.version_field {
+        panic!(
+            "lookup_tables are not supported by model with version field {}, as update_with_lookups can't batch \
+            conditional update with lookup rows of other partitions",
+            version_field.name
+        );
+    }
+
+    let lookup_fields = args
+        .lookup_tables()
+        .iter()
+        .map(|field_name| lookup_field(fields, field_name))
+        .collect::<Vec<&Field>>();
+    let lookup_struct_names = args
+        .lookup_tables()
+        .iter()
+        .map(|field_name| lookup_struct_name(struct_name, field_name))
+        .collect::<Vec<syn::Ident>>();
+    let lookup_vars = args
+        .lookup_tables()
+        .iter()
+        .map(|field_name| format_ident!("{}_lookup", field_name))
+        .collect::<Vec<syn::Ident>>();
+    let previous_lookup_vars = args
+        .lookup_tables()
+        .iter()
+        .map(|field_name| format_ident!("previous_{}_lookup", field_name))
+        .collect::<Vec<syn::Ident>>();
+    let changed_vars = args
+        .lookup_tables()
+        .iter()
+        .map(|field_name| format_ident!("{}_changed", field_name))
+        .collect::<Vec<syn::Ident>>();
+
+    let primary_key_idents = fields
+        .primary_key_fields
+        .iter()
+        .map(|field| &field.ident)
+        .collect::<Vec<_>>();
+
+    let build_lookups = |model: TokenStream, vars: &Vec<syn::Ident>| {
+        let primary_key_values = primary_key_idents
+            .iter()
+            .map(|ident| quote!(#ident: #model.#ident.clone()))
+            .collect::<Vec<TokenStream>>();
+
+        let lookups = lookup_fields
+            .iter()
+            .zip(lookup_struct_names.iter())
+            .zip(vars.iter())
+            .map(|((lookup_field, lookup_struct_name), var)| {
+                let field_ident = &lookup_field.ident;
+
+                quote! {
+                    let #var = #lookup_struct_name {
+                        #field_ident: #model.#field_ident.clone(),
+                        #(#primary_key_values,)*
+                    };
+                }
+            });
+
+        quote! {
+            #(#lookups)*
+        }
+    };
+
+    let lookups = build_lookups(quote!(self), &lookup_vars);
+    let previous_lookups = build_lookups(quote!(previous), &previous_lookup_vars);
+
+    // lookup row claimed by the same record is accepted, so retried operation succeeds
+    let is_owner = quote! {
+        |stored| (#(&stored.#primary_key_idents,)*) == (#(&self.#primary_key_idents,)*)
+    };
+
+    // on conflict, release lookups claimed before the conflicting one
+    let claim_lookups = lookup_vars.iter().enumerate().map(|(i, var)| {
+        let claimed = &lookup_vars[..i];
+
+        quote! {
+            if let Err(mut e) = charybdis::lookup::claim_for(&#var, #is_owner, session).await {
+                #(e = charybdis::lookup::release_on_error(&#claimed, e, session).await;)*
+
+                return Err(e);
+            }
+        }
+    });
+
+    let claim_changed_lookups = lookup_vars
+        .iter()
+        .zip(changed_vars.iter())
+        .enumerate()
+        .map(|(i, (var, changed))| {
+            let claimed = &lookup_vars[..i];
+            let claimed_changed = &changed_vars[..i];
+
+            quote! {
+                if #changed {
+                    if let Err(mut e) = charybdis::lookup::claim_for(&#var, #is_owner, session).await {
+                        #(
+                            if #claimed_changed {
+                                e = charybdis::lookup::release_on_error(&#claimed, e, session).await;
+                            }
+                        )*
+
+                        return Err(e);
+                    }
+                }
+            }
+        });
+
+    let lookup_field_idents = lookup_fields.iter().map(|field| &field.ident).collect::<Vec<_>>();
+
+    quote! {
+        pub async fn insert_with_lookups(
+            &self,
+            session: &charybdis::scylla::CachingSession,
+        ) -> Result<(), charybdis::errors::CharybdisError> {
+            use charybdis::operations::Insert;
+
+            #lookups
+            #(#claim_lookups)*
+
+            if let Err(mut e) = self.insert().execute(session).await {
+                #(e = charybdis::lookup::release_on_error(&#lookup_vars, e, session).await;)*
+
+                return Err(e);
+            }
+
+            Ok(())
+        }
+
+        pub async fn update_with_lookups(
+            &self,
+            previous: &Self,
+            session: &charybdis::scylla::CachingSession,
+        ) -> Result<(), charybdis::errors::CharybdisError> {
+            use charybdis::operations::{Delete, Update};
+
+            #lookups
+            #previous_lookups
+            #(let #changed_vars = self.#lookup_field_idents != previous.#lookup_field_idents;)*
+            #(#claim_changed_lookups)*
+
+            let mut batch = charybdis::batch::CharybdisBatch::new();
+
+            batch.append(self.update());
+
+            #(
+                if #changed_vars {
+                    batch.append(#previous_lookup_vars.delete());
+                }
+            )*
+
+            if let Err(mut e) = batch.execute(session).await {
+                #(
+                    if #changed_vars {
+                        e = charybdis::lookup::release_on_error(&#lookup_vars, e, session).await;
+                    }
+                )*
+
+                return Err(e);
+            }
+
+            Ok(())
+        }
+
+        pub async fn delete_with_lookups(
+            &self,
+            session: &charybdis::scylla::CachingSession,
+        ) -> Result<(), charybdis::errors::CharybdisError> {
+            use charybdis::operations::Delete;
+
+            #lookups
+
+            let mut batch = charybdis::batch::CharybdisBatch::new();
+
+            batch.append(self.delete());
+            #(batch.append(#lookup_vars.delete());)*
+
+            batch.execute(session).await?;
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use charybdis_parser::fields::{CharybdisFields, CqlType, Field};
+use charybdis_parser::traits::CharybdisMacroArgs;
+
+use crate::model::soft_delete_filtered;
+use crate::traits::fields::{FieldsQuery, FieldsToArguments};
+use crate::traits::tuple::Tuple;
+
+/// `TTL()` and `WRITETIME()` are not supported for primary key, collection and counter columns.
+/// UDT columns support them only if frozen.
+fn metadata_fields<'a>(fields: &'a CharybdisFields) -> Vec<&'a Field<'a>> {
+    fields
+        .db_fields
+        .iter()
+        .filter(|field| !field.is_primary_key() && !field.is_collection() && !field.is_counter())
+        .filter(|field| field.outer_type != CqlType::Unknown || field.is_frozen())
+        .cloned()
+        .collect()
+}
+
+pub(crate) fn metadata_fields_impl(struct_name: &syn::Ident, fields: &CharybdisFields) -> TokenStream {
+    let names = metadata_fields(fields)
+        .iter()
+        .map(|field| field.name.clone())
+        .collect::<Vec<String>>();
+
+    quote! {
+        impl charybdis::metadata::MetadataFields for #struct_name {
+            const METADATA_FIELDS: &'static [&'static str] = &[#(#names),*];
+        }
+    }
+}
+
+/// Generates `select_with_metadata` function that finds model by primary key together with `TTL()` and
+/// `WRITETIME()` of its columns.
+pub(crate) fn select_with_metadata_function(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields) -> TokenStream {
+    let metadata_selectors = metadata_fields(fields)
+        .iter()
+        .map(|field| format!(", TTL({}), WRITETIME({})", field.cql_name(), field.cql_name()))
+        .collect::<String>();
+
+    let query_str = format!(
+        "SELECT {}{} FROM {} WHERE {}",
+        fields.db_fields.comma_sep_cols(),
+        metadata_selectors,
+        ch_args.table_name(),
+        fields.primary_key_fields.where_placeholders()
+    );
+    let query_str = soft_delete_filtered(fields, query_str);
+
+    let arguments = fields.primary_key_fields.to_fn_args();
+    let types_tp = arguments.types_tp();
+    let values_tp = arguments.values_tp();
+
+    quote! {
+        pub fn select_with_metadata<'a>(
+            #(#arguments),*
+        ) -> charybdis::query::CharybdisQuery<'a, #types_tp, Self, charybdis::query::ModelRowWithMetadata<Self>> {
+            charybdis::query::CharybdisQuery::new(#query_str, charybdis::query::QueryValue::Owned(#values_tp))
+        }
+    }
+}
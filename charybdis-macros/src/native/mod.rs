@@ -1,11 +1,29 @@
+pub(crate) use association::*;
+pub(crate) use changes::*;
 pub(crate) use collection::*;
 pub(crate) use counter::*;
 pub(crate) use delete::*;
 pub(crate) use find::*;
+pub(crate) use json::*;
+pub(crate) use lookup::*;
+pub(crate) use metadata::*;
+pub(crate) use prepare::*;
+pub(crate) use registry::*;
+pub(crate) use tenancy::*;
+pub(crate) use time_series::*;
 
+mod association;
+mod changes;
 mod collection;
 mod counter;
 mod delete;
 
 mod find;
+mod json;
+mod lookup;
+mod metadata;
+mod prepare;
+mod registry;
+mod tenancy;
+mod time_series;
 
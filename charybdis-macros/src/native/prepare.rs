@@ -0,0 +1,115 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_str;
+
+use charybdis_parser::fields::CharybdisFields;
+use charybdis_parser::traits::CharybdisMacroArgs;
+
+use crate::model::find_by_partition_key_query;
+
+/// Statement consts of model, that can be prepared without knowing the data. Counter models can't use
+/// `INSERT` and plain `UPDATE`, so these are left out.
+fn statement_consts(ch_args: &CharybdisMacroArgs, fields: &CharybdisFields, is_table: bool) -> Vec<TokenStream> {
+    let mut statements = vec![
+        quote!(<Self as charybdis::model::BaseModel>::FIND_BY_PRIMARY_KEY_QUERY),
+        quote!(<Self as charybdis::model::BaseModel>::FIND_BY_PARTITION_KEY_QUERY),
+        quote!(<Self as charybdis::model::BaseModel>::FIND_FIRST_BY_PARTITION_KEY_QUERY),
+        quote!(<Self as charybdis::model::BaseModel>::COUNT_BY_PARTITION_KEY_QUERY),
+        quote!(<Self as charybdis::model::BaseModel>::FIND_BY_TOKEN_RANGE_QUERY),
+        quote!(<Self as charybdis::model::BaseModel>::FIND_BY_PARTITION_KEY_LIMIT_QUERY),
+    ];
+
+    // ordered variants are optional consts, so their statements are inlined
+    if !fields.clustering_key_fields.is_empty() {
+        for order in ["ASC", "DESC"] {
+            for limit in [false, true] {
+                let query_str = find_by_partition_key_query(ch_args, fields, Some(order), limit);
+                statements.push(quote!(#query_str));
+            }
+        }
+    }
+
+    if !is_table {
+        return statements;
+    }
+
+    let is_counter_model = fields.db_fields.iter().any(|field| field.is_counter());
+
+    if !is_counter_model {
+        statements.push(quote!(<Self as charybdis::model::Model>::INSERT_QUERY));
+        statements.push(quote!(<Self as charybdis::model::Model>::INSERT_IF_NOT_EXIST_QUERY));
+
+        if !fields.non_primary_key_db_fields().is_empty() {
+            statements.push(quote!(<Self as charybdis::model::Model>::UPDATE_QUERY));
+        }
+    }
+
+    statements.push(quote!(<Self as charybdis::model::Model>::DELETE_QUERY));
+    statements.push(quote!(<Self as charybdis::model::Model>::HARD_DELETE_QUERY));
+    statements.push(quote!(<Self as charybdis::model::Model>::DELETE_BY_PARTITION_KEY_QUERY));
+
+    for field in &fields.db_fields {
+        let name = field.name.to_uppercase();
+        let mut const_names = vec![];
+
+        if field.is_collection() {
+            const_names.push(format!("PUSH_{}_QUERY", name));
+            const_names.push(format!("PUSH_{}_IF_EXISTS_QUERY", name));
+            const_names.push(format!("PULL_{}_QUERY", name));
+            const_names.push(format!("PULL_{}_IF_EXISTS_QUERY", name));
+        }
+
+        if field.is_map() {
+            const_names.push(format!("SET_{}_ENTRY_QUERY", name));
+            const_names.push(format!("REMOVE_{}_ENTRY_QUERY", name));
+        }
+
+        if field.is_list() {
+            const_names.push(format!("SET_{}_AT_QUERY", name));
+            const_names.push(format!("PREPEND_{}_QUERY", name));
+        }
+
+        statements.extend(
+            const_names
+                .into_iter()
+                .map(|const_name| parse_str::<TokenStream>(&ch_args.const_path(&const_name)).unwrap()),
+        );
+    }
+
+    statements
+}
+
+/// Generates `PREPARED_STATEMENTS` const and `prepare_all` function, that prepares them on session, so the
+/// first request of each statement doesn't pay for preparation:
+/// ```rust ignore
+/// User::prepare_all(&session).await?;
+/// ```
+pub(crate) fn prepare_all_function(
+    ch_args: &CharybdisMacroArgs,
+    fields: &CharybdisFields,
+    is_table: bool,
+) -> TokenStream {
+    let statements = statement_consts(ch_args, fields, is_table);
+
+    quote! {
+        pub const PREPARED_STATEMENTS: &'static [&'static str] = &[#(#statements),*];
+
+        pub async fn prepare_all(
+            session: &impl charybdis::session::CharybdisSession,
+        ) -> Result<(), charybdis::errors::CharybdisError> {
+            charybdis::registry::prepare_statements(session, Self::PREPARED_STATEMENTS).await
+        }
+    }
+}
+
+/// Submits `PREPARED_STATEMENTS` to the registry, used by `charybdis::prepare_registered_models`.
+pub(crate) fn register_statements(struct_name: &syn::Ident) -> TokenStream {
+    quote! {
+        charybdis::macros::inventory::submit! {
+            charybdis::registry::ModelStatements {
+                model: <#struct_name as charybdis::model::BaseModel>::DB_MODEL_NAME,
+                statements: #struct_name::PREPARED_STATEMENTS,
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields};
+
+use charybdis_parser::schema::code_schema::{model_schema_objects, ModelMacro};
+use charybdis_parser::traits::CharybdisMacroArgs;
+
+/// Submits schema of the model, and of its lookup tables, to `charybdis::registry`. Schema is extracted
+/// the same way migration tool extracts it from source files.
+pub(crate) fn register_model(input: &DeriveInput, args: &CharybdisMacroArgs, model_macro: ModelMacro) -> TokenStream {
+    // partial models share table with the base model, lookup models are registered by the base model
+    if args.exclude_partial_model.unwrap_or(false) {
+        return quote!();
+    }
+
+    let fields_named = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields_named) => fields_named,
+            _ => return quote!(),
+        },
+        _ => return quote!(),
+    };
+
+    let kind = match model_macro {
+        ModelMacro::Table | ModelMacro::CounterTable => quote!(charybdis::registry::ModelKind::Table),
+        ModelMacro::MaterializedView => quote!(charybdis::registry::ModelKind::MaterializedView),
+        ModelMacro::Udt => quote!(charybdis::registry::ModelKind::Udt),
+    };
+
+    let definitions = model_schema_objects(args, fields_named, &model_macro)
+        .into_iter()
+        .map(|schema_object| {
+            let table_name = &schema_object.table_name;
+            let type_name = &schema_object.type_name;
+            let base_table = &schema_object.base_table;
+            let partition_keys = &schema_object.partition_keys;
+            let clustering_keys = &schema_object.clustering_keys;
+            let clustering_order = schema_object.clustering_order.iter().map(|(key, order)| {
+                let order = order.to_string();
+                quote!((#key, #order))
+            });
+            let global_secondary_indexes = schema_object.global_secondary_indexes.iter().map(|(_, field)| field);
+            let local_secondary_indexes = schema_object.local_secondary_indexes.iter().map(|(_, field)| field);
+
+            let fields = schema_object.fields.iter().map(|(name, cql_type, is_static)| {
+                let renamed_from = match schema_object
+                    .renamed_fields
+                    .iter()
+                    .find(|(_, new_name)| new_name == name)
+                {
+                    Some((old_name, _)) => quote!(Some(#old_name)),
+                    None => quote!(None),
+                };
+
+                quote! {
+                    charybdis::registry::FieldDefinition {
+                        name: #name,
+                        cql_type: #cql_type,
+                        is_static: #is_static,
+                        renamed_from: #renamed_from,
+                    }
+                }
+            });
+
+            let custom_indexes = schema_object.custom_indexes.iter().map(|(_, custom_index)| {
+                let target = &custom_index.target;
+                let class_name = &custom_index.class_name;
+                let options = custom_index.options.iter().map(|(key, value)| quote!((#key, #value)));
+
+                quote! {
+                    charybdis::registry::CustomIndexDefinition {
+                        target: #target,
+                        class_name: #class_name,
+                        options: &[#(#options),*],
+                    }
+                }
+            });
+
+            let table_options = match &schema_object.table_options {
+                Some(table_options) => quote!(Some(#table_options)),
+                None => quote!(None),
+            };
+
+            let renamed_from = match &schema_object.renamed_from {
+                Some(renamed_from) => quote!(Some(#renamed_from)),
+                None => quote!(None),
+            };
+
+            quote! {
+                charybdis::macros::inventory::submit! {
+                    charybdis::registry::ModelDefinition {
+                        kind: #kind,
+                        table_name: #table_name,
+                        type_name: #type_name,
+                        base_table: #base_table,
+                        fields: &[#(#fields),*],
+                        partition_keys: &[#(#partition_keys),*],
+                        clustering_keys: &[#(#clustering_keys),*],
+                        clustering_order: &[#(#clustering_order),*],
+                        global_secondary_indexes: &[#(#global_secondary_indexes),*],
+                        local_secondary_indexes: &[#(#local_secondary_indexes),*],
+                        custom_indexes: &[#(#custom_indexes),*],
+                        table_options: #table_options,
+                        renamed_from: #renamed_from,
+                    }
+                }
+            }
+        });
+
+    quote! {
+        #(#definitions)*
+    }
+}
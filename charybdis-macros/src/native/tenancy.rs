@@ -0,0 +1,25 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use charybdis_parser::fields::CharybdisFields;
+
+/// `TenantScoped` of models that declare `tenant_key`
+pub(crate) fn tenant_scoped_impl(struct_name: &syn::Ident, fields: &CharybdisFields) -> TokenStream {
+    let tenant_field = match fields.tenant_field {
+        Some(tenant_field) => tenant_field,
+        None => return quote!(),
+    };
+
+    let tenant_ident = &tenant_field.ident;
+    let tenant_type = &tenant_field.ty;
+
+    quote! {
+        impl charybdis::tenancy::TenantScoped for #struct_name {
+            type TenantId = #tenant_type;
+
+            fn tenant_id(&self) -> &Self::TenantId {
+                &self.#tenant_ident
+            }
+        }
+    }
+}
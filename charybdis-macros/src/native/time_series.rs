@@ -0,0 +1,110 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use charybdis_parser::fields::{CharybdisFields, CqlType, Field};
+use charybdis_parser::schema::ClusteringOrder;
+use charybdis_parser::traits::CharybdisMacroArgs;
+
+use crate::model::soft_delete_filtered;
+use crate::traits::fields::{FieldsQuery, FieldsToArguments};
+use crate::traits::r#type::TypeWithoutOptions;
+use crate::traits::tuple::Tuple;
+
+/// For models whose last clustering key is `Timestamp` or `Timeuuid`, generates `find_in_range` and
+/// `find_latest` functions, that take rest of the primary key as arguments.
+/// ```rust ignore
+/// #[charybdis_model(
+///     table_name = readings,
+///     partition_keys = [sensor_id],
+///     clustering_keys = [recorded_at],
+///     clustering_order = [(recorded_at, desc)]
+/// )]
+/// pub struct Reading {
+///     pub sensor_id: Uuid,
+///     pub recorded_at: Timestamp,
+///     pub value: Double,
+/// }
+///
+/// // SELECT ... WHERE sensor_id = ? AND recorded_at >= ? AND recorded_at < ?
+/// let readings = Reading::find_in_range(sensor_id, from, to).execute(&session).await?;
+///
+/// // SELECT ... WHERE sensor_id = ? ORDER BY recorded_at DESC LIMIT ?
+/// let readings = Reading::find_latest(sensor_id, 10).execute(&session).await?;
+/// ```
+pub(crate) fn time_series_functions(
+    struct_name: &syn::Ident,
+    ch_args: &CharybdisMacroArgs,
+    fields: &CharybdisFields,
+) -> TokenStream {
+    let time_field = match fields.clustering_key_fields.last() {
+        Some(field) if matches!(field.outer_type, CqlType::Timestamp | CqlType::Timeuuid) => field,
+        _ => return quote!(),
+    };
+
+    let table_name = ch_args.table_name();
+    let comma_sep_cols = fields.db_fields.comma_sep_cols();
+    let key_fields = fields
+        .primary_key_fields
+        .iter()
+        .take(fields.primary_key_fields.len() - 1)
+        .cloned()
+        .collect::<Vec<&Field>>();
+    let key_where = key_fields.where_placeholders();
+
+    // ORDER BY has to follow declared clustering order or reverse all of it, so declared order is reversed
+    // unless time field is already descending
+    let is_reversed = ch_args.clustering_order_of(&time_field.name) == ClusteringOrder::Asc;
+    let order_by = fields
+        .clustering_key_fields
+        .iter()
+        .map(|field| {
+            let order = ch_args.clustering_order_of(&field.name);
+            let order = if is_reversed { order.reversed() } else { order };
+
+            format!("{} {}", field.cql_name(), order)
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let range_query_str = format!(
+        "SELECT {} FROM {} WHERE {} AND {} >= ? AND {} < ?",
+        comma_sep_cols,
+        table_name,
+        key_where,
+        time_field.cql_name(),
+        time_field.cql_name()
+    );
+    let range_query_str = soft_delete_filtered(fields, range_query_str);
+    let latest_query_str = format!(
+        "SELECT {} FROM {} WHERE {} ORDER BY {} LIMIT ?",
+        comma_sep_cols, table_name, key_where, order_by
+    );
+    let latest_query_str = soft_delete_filtered(fields, latest_query_str);
+
+    let time_type = time_field.ty.type_without_options();
+
+    let mut range_arguments = key_fields.to_fn_args();
+    range_arguments.push(syn::parse_quote!(from: #time_type));
+    range_arguments.push(syn::parse_quote!(to: #time_type));
+    let range_types_tp = range_arguments.types_tp();
+    let range_values_tp = range_arguments.values_tp();
+
+    let mut latest_arguments = key_fields.to_fn_args();
+    latest_arguments.push(syn::parse_quote!(limit: charybdis::types::Int));
+    let latest_types_tp = latest_arguments.types_tp();
+    let latest_values_tp = latest_arguments.values_tp();
+
+    quote! {
+        pub fn find_in_range<'a>(
+            #(#range_arguments),*
+        ) -> charybdis::query::CharybdisQuery<'a, #range_types_tp, Self, charybdis::query::ModelStream<Self>> {
+            <#struct_name as charybdis::operations::Find>::find(#range_query_str, #range_values_tp)
+        }
+
+        pub fn find_latest<'a>(
+            #(#latest_arguments),*
+        ) -> charybdis::query::CharybdisQuery<'a, #latest_types_tp, Self, charybdis::query::ModelStream<Self>> {
+            <#struct_name as charybdis::operations::Find>::find(#latest_query_str, #latest_values_tp)
+        }
+    }
+}
@@ -8,6 +8,23 @@ use charybdis_parser::traits::string::ToSnakeCase;
 
 use crate::traits::fields::FieldsQuery;
 
+/// Statement of `find_<model>!` macros, built from literal at compile time. Rows marked as deleted by models with
+/// `soft_delete` field are filtered out by condition that has to precede trailing clauses of the query, so their
+/// statement is built at runtime, once per query.
+fn find_query_expr(struct_name: &Ident, args: &CharybdisMacroArgs, fields: &CharybdisFields) -> TokenStream {
+    if fields.soft_delete_column.is_some() {
+        return quote!(charybdis::operations::soft_delete_find_query::<#struct_name>($query));
+    }
+
+    let query_str = format!(
+        "SELECT {} FROM {} WHERE ",
+        fields.db_fields.comma_sep_cols(),
+        args.table_name(),
+    );
+
+    quote!(concat!(#query_str, $query))
+}
+
 pub(crate) fn find_model_query_rule(
     struct_name: &Ident,
     args: &CharybdisMacroArgs,
@@ -16,17 +33,13 @@ pub(crate) fn find_model_query_rule(
     let macro_name_str: String = format!("find_{}_query", struct_name.to_string().to_snake_case());
     let macro_name: TokenStream = parse_str::<TokenStream>(&macro_name_str).unwrap();
 
-    let query_str = format!(
-        "SELECT {} FROM {} WHERE ",
-        fields.db_fields.comma_sep_cols(),
-        args.table_name()
-    );
+    let query = find_query_expr(struct_name, args, fields);
 
     let expanded = quote! {
         #[allow(unused_macros)]
         macro_rules! #macro_name {
             ($query: literal) => {
-                concat!(#query_str, $query)
+                #query
             }
         }
 
@@ -40,17 +53,13 @@ pub(crate) fn find_model_rule(struct_name: &Ident, args: &CharybdisMacroArgs, fi
     let macro_name_str: String = format!("find_{}", struct_name.to_string().to_snake_case());
     let macro_name: TokenStream = parse_str::<TokenStream>(&macro_name_str).unwrap();
 
-    let query_str = format!(
-        "SELECT {} FROM {} WHERE ",
-        fields.db_fields.comma_sep_cols(),
-        args.table_name()
-    );
+    let query = find_query_expr(struct_name, args, fields);
 
     let expanded = quote! {
         #[allow(unused_macros)]
         macro_rules! #macro_name {
             ($query: literal, $values: expr) => {
-               <#struct_name as charybdis::operations::Find>::find(concat!(#query_str, $query), $values)
+               <#struct_name as charybdis::operations::Find>::find(#query, $values)
             }
         }
 
@@ -68,17 +77,13 @@ pub(crate) fn find_first_model_rule(
     let macro_name_str: String = format!("find_first_{}", struct_name.to_string().to_snake_case());
     let macro_name: TokenStream = parse_str::<TokenStream>(&macro_name_str).unwrap();
 
-    let query_str = format!(
-        "SELECT {} FROM {} WHERE ",
-        fields.db_fields.comma_sep_cols(),
-        args.table_name()
-    );
+    let query = find_query_expr(struct_name, args, fields);
 
     let expanded = quote! {
         #[allow(unused_macros)]
         macro_rules! #macro_name {
             ($query: literal, $values: expr) => {
-                <#struct_name as charybdis::operations::Find>::find_first(concat!(#query_str, $query), $values)
+                <#struct_name as charybdis::operations::Find>::find_first(#query, $values)
             }
         }
 
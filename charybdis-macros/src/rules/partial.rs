@@ -143,6 +143,28 @@ pub(crate) fn partial_model_macro_generator(
     let global_secondary_indexes: Vec<syn::Ident> = fields.global_secondary_index_fields.to_idents();
     let local_secondary_indexes: Vec<syn::Ident> = fields.local_secondary_index_fields.to_idents();
 
+    // partial models soft delete rows of base table as well, and are scoped by its tenant
+    let soft_delete = args
+        .soft_delete
+        .as_ref()
+        .map(|soft_delete| quote!(soft_delete = #soft_delete,));
+    let tenant_key = args
+        .tenant_key
+        .as_ref()
+        .map(|tenant_key| quote!(tenant_key = #tenant_key,));
+    let cql_json = args.cql_json.map(|cql_json| quote!(cql_json = #cql_json,));
+
+    // partial models keep naming of base model, while its statement consts stay in their own impl, as
+    // `queries_module` of base model is already declared
+    let method_prefix = args
+        .method_prefix
+        .as_ref()
+        .map(|method_prefix| quote!(method_prefix = #method_prefix,));
+    let const_prefix = args
+        .const_prefix
+        .as_ref()
+        .map(|const_prefix| quote!(const_prefix = #const_prefix,));
+
     // attributes that are not charybdis_model
     let other_attrs = &input
         .attrs
@@ -165,11 +187,18 @@ pub(crate) fn partial_model_macro_generator(
                     clustering_keys=[ #(#clustering_keys),* ],
                     global_secondary_indexes=[ #(#global_secondary_indexes),* ],
                     local_secondary_indexes=[ #(#local_secondary_indexes),* ],
+                    #soft_delete
+                    #tenant_key
+                    #cql_json
+                    #method_prefix
+                    #const_prefix
                     exclude_partial_model=true
                 )]
                 #(#other_attrs)*
                 pub struct $struct_name {}
 
+                impl charybdis::model::PartialModel<#struct_name> for $struct_name {}
+
                 impl charybdis::model::AsNative<#struct_name> for $struct_name {
                     fn as_native(&self) -> #struct_name {
                         let mut new_model = <#struct_name as charybdis::operations::New>::new();
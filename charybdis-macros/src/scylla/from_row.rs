@@ -10,6 +10,31 @@ pub(crate) fn from_row(struct_name: &syn::Ident, fields: &CharybdisFields) -> Im
         let field_ident = &field.ident;
         let field_type = &field.ty;
 
+        if let Some(codec) = &field.codec {
+            let encoded_type = &codec.encoded_type;
+            let path = &codec.path;
+
+            return quote_spanned! {field.span =>
+                #field_ident: {
+                    let (col_ix, col_value) = vals_iter
+                        .next()
+                        .unwrap();
+
+                    let encoded = <#encoded_type as charybdis::scylla::FromCqlVal<::std::option::Option<charybdis::scylla::CqlValue>>>::from_cql(col_value)
+                        .map_err(|e| charybdis::scylla::FromRowError::BadCqlVal {
+                            err: e,
+                            column: col_ix,
+                        })?;
+
+                    #path::decode(encoded)
+                        .map_err(|_| charybdis::scylla::FromRowError::BadCqlVal {
+                            err: charybdis::scylla::FromCqlValError::BadVal,
+                            column: col_ix,
+                        })?
+                },
+            };
+        }
+
         quote_spanned! {field.span =>
             #field_ident: {
                 let (col_ix, col_value) = vals_iter
@@ -1,3 +1,5 @@
 pub(crate) use from_row::*;
+pub(crate) use serialize_row::*;
 
 mod from_row;
+mod serialize_row;
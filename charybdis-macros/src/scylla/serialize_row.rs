@@ -0,0 +1,86 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use charybdis_parser::fields::CharybdisFields;
+
+/// Types of fields as bound to queries: references to model fields, or encoded values of fields with codec.
+pub(crate) fn bound_field_types(fields: &CharybdisFields) -> Vec<TokenStream> {
+    fields
+        .db_fields
+        .iter()
+        .map(|field| match &field.codec {
+            Some(codec) => {
+                let encoded_type = &codec.encoded_type;
+                quote!(#encoded_type)
+            }
+            None => {
+                let ty = &field.ty;
+                quote!(&'a #ty)
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn bound_field_values(fields: &CharybdisFields) -> Vec<TokenStream> {
+    fields
+        .db_fields
+        .iter()
+        .map(|field| {
+            let ident = &field.ident;
+
+            match &field.codec {
+                Some(codec) => {
+                    let path = &codec.path;
+                    quote!(#path::encode(&self.#ident))
+                }
+                None => quote!(&self.#ident),
+            }
+        })
+        .collect()
+}
+
+/// `SerializeRow` is derived, unless model has fields with codec.
+pub(crate) fn serialize_row_derive(fields: &CharybdisFields) -> TokenStream {
+    if fields.has_codecs() {
+        return quote!();
+    }
+
+    quote!(#[derive(charybdis::macros::scylla::SerializeRow)])
+}
+
+/// Fields with codec are bound as encoded values, so row is serialized from local struct holding references to
+/// model fields and encoded values.
+pub(crate) fn serialize_row_impl(struct_name: &syn::Ident, fields: &CharybdisFields) -> TokenStream {
+    if !fields.has_codecs() {
+        return quote!();
+    }
+
+    let idents = fields.db_fields.iter().map(|field| &field.ident).collect::<Vec<_>>();
+    let types = bound_field_types(fields);
+    let values = bound_field_values(fields);
+
+    quote! {
+        impl charybdis::scylla::SerializeRow for #struct_name {
+            fn serialize(
+                &self,
+                ctx: &charybdis::scylla::RowSerializationContext<'_>,
+                writer: &mut charybdis::scylla::RowWriter,
+            ) -> Result<(), charybdis::scylla::SerializationError> {
+                #[derive(charybdis::macros::scylla::SerializeRow)]
+                struct EncodedValues<'a> {
+                    #(#idents: #types,)*
+                }
+
+                let values = EncodedValues {
+                    #(#idents: #values,)*
+                };
+
+                charybdis::scylla::SerializeRow::serialize(&values, ctx, writer)
+            }
+
+            fn is_empty(&self) -> bool {
+                false
+            }
+        }
+    }
+}
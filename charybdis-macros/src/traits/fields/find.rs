@@ -2,7 +2,9 @@ use proc_macro2::TokenStream;
 use quote::quote;
 
 use charybdis_parser::fields::Field;
+use charybdis_parser::traits::CharybdisMacroArgs;
 
+use crate::model::first_row_query;
 use crate::traits::fields::{FieldsNames, FieldsToArguments};
 use crate::traits::string::ToIdent;
 use crate::traits::tuple::Tuple;
@@ -42,8 +44,8 @@ impl FieldsFindFnNames for Vec<&Field<'_>> {
 }
 
 pub(crate) trait FieldsFindFn: FieldsFindFnNames + FieldsToArguments {
-    fn find_fn(&self, struct_name: &syn::Ident, query_str: &String) -> TokenStream {
-        let find_by_fn_name = self.find_by_fn_name().to_ident();
+    fn find_fn(&self, struct_name: &syn::Ident, ch_args: &CharybdisMacroArgs, query_str: &String) -> TokenStream {
+        let find_by_fn_name = ch_args.method_name(&self.find_by_fn_name()).to_ident();
         let arguments = self.to_fn_args();
         let types_tp = arguments.types_tp();
         let values_tp = arguments.values_tp();
@@ -60,8 +62,8 @@ pub(crate) trait FieldsFindFn: FieldsFindFnNames + FieldsToArguments {
     /// Generates a function that finds the first model that matches the query. Difference from
     /// `find_first` is that this function does not limit the number of results to 1 as it is only
     /// used when provided keys matches complete primary key.
-    fn find_one_fn(&self, struct_name: &syn::Ident, query_str: &String) -> TokenStream {
-        let find_by_fn_name = self.find_by_fn_name().to_ident();
+    fn find_one_fn(&self, struct_name: &syn::Ident, ch_args: &CharybdisMacroArgs, query_str: &String) -> TokenStream {
+        let find_by_fn_name = ch_args.method_name(&self.find_by_fn_name()).to_ident();
         let arguments = self.to_fn_args();
         let types_tp = arguments.types_tp();
         let values_tp = arguments.values_tp();
@@ -80,12 +82,12 @@ impl FieldsFindFn for Field<'_> {}
 impl FieldsFindFn for Vec<&Field<'_>> {}
 
 pub(crate) trait FieldsFindFirstFns: FieldsFindFnNames + FieldsToArguments {
-    fn find_first_fn(&self, struct_name: &syn::Ident, query_str: &String) -> TokenStream {
-        let find_first_by_fn_name = self.find_first_by_fn_name().to_ident();
+    fn find_first_fn(&self, struct_name: &syn::Ident, ch_args: &CharybdisMacroArgs, query_str: &String) -> TokenStream {
+        let find_first_by_fn_name = ch_args.method_name(&self.find_first_by_fn_name()).to_ident();
         let arguments = self.to_fn_args();
         let types_tp = arguments.types_tp();
         let values_tp = arguments.values_tp();
-        let query_str = format!("{} LIMIT 1", query_str);
+        let query_str = first_row_query(query_str);
 
         quote! {
             pub fn #find_first_by_fn_name<'a>(
@@ -96,12 +98,17 @@ pub(crate) trait FieldsFindFirstFns: FieldsFindFnNames + FieldsToArguments {
         }
     }
 
-    fn maybe_find_first_fn(&self, struct_name: &syn::Ident, query_str: &String) -> TokenStream {
-        let maybe_find_first_by_fn_name = self.maybe_find_first_by_fn_name().to_ident();
+    fn maybe_find_first_fn(
+        &self,
+        struct_name: &syn::Ident,
+        ch_args: &CharybdisMacroArgs,
+        query_str: &String,
+    ) -> TokenStream {
+        let maybe_find_first_by_fn_name = ch_args.method_name(&self.maybe_find_first_by_fn_name()).to_ident();
         let arguments = self.to_fn_args();
         let types_tp = arguments.types_tp();
         let values_tp = arguments.values_tp();
-        let query_str = format!("{} LIMIT 1", query_str);
+        let query_str = first_row_query(query_str);
 
         quote! {
             pub fn #maybe_find_first_by_fn_name<'a>(
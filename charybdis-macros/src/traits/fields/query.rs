@@ -1,10 +1,7 @@
 use charybdis_parser::fields::Field;
 
-use crate::traits::fields::FieldsNames;
-
 pub(crate) trait FieldsQuery {
     fn comma_sep_cols(&self) -> String;
-    fn insert_bind_markers(&self) -> String;
     fn set_bind_markers(&self) -> String;
     fn where_placeholders(&self) -> String;
     fn where_bind_markers(&self) -> String;
@@ -12,36 +9,29 @@ pub(crate) trait FieldsQuery {
 
 impl FieldsQuery for Vec<&Field<'_>> {
     fn comma_sep_cols(&self) -> String {
-        self.names().join(", ")
-    }
-
-    fn insert_bind_markers(&self) -> String {
-        let str_vec = self
-            .iter()
-            .map(|field| format!(":{}", field.name))
+        self.iter()
+            .map(|field| field.cql_name())
             .collect::<Vec<String>>()
-            .join(", ");
-
-        str_vec
+            .join(", ")
     }
 
     fn set_bind_markers(&self) -> String {
         self.iter()
-            .map(|field| format!("{} = :{}", field.name, field.name))
+            .map(|field| format!("{} = {}", field.cql_name(), field.bind_marker()))
             .collect::<Vec<String>>()
             .join(", ")
     }
 
     fn where_placeholders(&self) -> String {
         self.iter()
-            .map(|field| format!("{} = ?", field.name))
+            .map(|field| format!("{} = ?", field.cql_name()))
             .collect::<Vec<String>>()
             .join(" AND ")
     }
 
     fn where_bind_markers(&self) -> String {
         self.iter()
-            .map(|field| format!("{} = :{}", field.name, field.name))
+            .map(|field| format!("{} = {}", field.cql_name(), field.bind_marker()))
             .collect::<Vec<String>>()
             .join(" AND ")
     }
@@ -24,3 +24,31 @@ impl TypeWithoutOptions for Type {
         type_name
     }
 }
+
+pub(crate) trait CollectionTypes {
+    /// Generic arguments of collection type, e.g. `[K, V]` for `Option<Map<K, V>>`
+    fn collection_types(&self) -> Vec<Type>;
+}
+
+impl CollectionTypes for Type {
+    fn collection_types(&self) -> Vec<Type> {
+        let type_wo_options = syn::parse2::<Type>(self.type_without_options()).expect("valid type");
+
+        if let Type::Path(type_path) = type_wo_options {
+            if let Some(last_segment) = type_path.path.segments.last() {
+                if let PathArguments::AngleBracketed(angle_bracketed_args) = &last_segment.arguments {
+                    return angle_bracketed_args
+                        .args
+                        .iter()
+                        .filter_map(|arg| match arg {
+                            GenericArgument::Type(ty) => Some(ty.clone()),
+                            _ => None,
+                        })
+                        .collect();
+                }
+            }
+        }
+
+        panic!("Unable to parse collection type: {}", quote::quote! { #self });
+    }
+}
@@ -1,3 +1,4 @@
+use crate::keyspace::Replication;
 use clap::Parser;
 use std::env;
 use std::fs::read_dir;
@@ -43,8 +44,31 @@ pub struct Args {
     #[arg(long, default_value = None)]
     pub key: Option<String>,
 
+    /// Writes planned changes to html report at given path instead of running migration
+    #[arg(long, default_value = None)]
+    pub html_report: Option<String>,
+
+    /// Directory where applied statements and rollback scripts are written.
+    /// Applied statements are also recorded in `charybdis_ddl_log` table.
+    #[arg(long, default_value = None)]
+    pub replay_log_dir: Option<String>,
+
+    /// Keyspace replication, e.g. `3` for SimpleStrategy or `dc1:3,dc2:2` for NetworkTopologyStrategy.
+    /// Keyspace is created if it doesn't exist, or its replication is altered to match.
+    #[arg(long, default_value = None)]
+    pub replication: Option<Replication>,
+
+    /// Seconds to wait for schema agreement of all nodes before migration and after each DDL statement
+    #[arg(long, default_value_t = 60)]
+    pub schema_agreement_timeout: u64,
+
     #[arg(skip = get_project_root())]
     pub project_root: String,
+
+    /// Models are taken from `charybdis::registry` instead of source files. Available only through
+    /// [MigrationBuilder](crate::MigrationBuilder), as registry of CLI binary is empty.
+    #[arg(skip)]
+    pub use_registry: bool,
 }
 
 impl Default for Args {
@@ -60,7 +84,12 @@ impl Default for Args {
             ca: None,
             cert: None,
             key: None,
+            html_report: None,
+            replay_log_dir: None,
+            replication: None,
+            schema_agreement_timeout: 60,
             project_root: get_project_root(),
+            use_registry: false,
         }
     }
 }
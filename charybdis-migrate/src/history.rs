@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use scylla::Session;
+
+pub(crate) const HISTORY_TABLE: &str = "charybdis_migrations";
+
+/// Structured history of applied plan steps, stored in `charybdis_migrations` table of migrated keyspace.
+/// Each applied statement is recorded with its checksum, checksum of its rollback statement, time of
+/// application and host that applied it.
+///
+/// Step is considered applied if its checksum was recorded and it was not reverted afterwards, i.e. no
+/// later statement had it as rollback (e.g. re-adding a field after it was dropped is not skipped).
+/// Rows can be deleted to make migration re-apply statements.
+pub(crate) struct MigrationHistory {
+    run_id: i64,
+    keyspace: String,
+    host: String,
+    seq: AtomicI32,
+}
+
+impl MigrationHistory {
+    pub(crate) fn new(keyspace: &str) -> Self {
+        let run_id = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as i64)
+            .unwrap_or_default();
+
+        Self {
+            run_id,
+            keyspace: keyspace.to_string(),
+            host: applying_host(),
+            seq: AtomicI32::new(0),
+        }
+    }
+
+    pub(crate) async fn init(&self, session: &Session) {
+        let cql = format!(
+            "CREATE TABLE IF NOT EXISTS {}.{} (
+                run_id bigint,
+                seq int,
+                object_name text,
+                statement text,
+                checksum text,
+                rollback_checksum text,
+                applied_at timestamp,
+                applied_by text,
+                PRIMARY KEY ((run_id), seq)
+            )",
+            self.keyspace, HISTORY_TABLE
+        );
+
+        session
+            .query_unpaged(cql, ())
+            .await
+            .unwrap_or_else(|e| panic!("Failed to create {} table: {}", HISTORY_TABLE, e));
+    }
+
+    /// Checksums of statements that are currently applied, replayed from history in order of application.
+    pub(crate) async fn applied_checksums(&self, session: &Session) -> HashSet<String> {
+        let cql = format!(
+            "SELECT run_id, seq, checksum, rollback_checksum FROM {}.{}",
+            self.keyspace, HISTORY_TABLE
+        );
+
+        let mut entries = session
+            .query_unpaged(cql, ())
+            .await
+            .unwrap_or_else(|e| panic!("Failed to read {} table: {}", HISTORY_TABLE, e))
+            .rows_typed::<(i64, i32, String, String)>()
+            .unwrap_or_else(|e| panic!("Failed to read {} table: {}", HISTORY_TABLE, e))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_or_else(|e| panic!("Failed to parse {} rows: {}", HISTORY_TABLE, e));
+
+        replay_applied(entries)
+    }
+
+    pub(crate) async fn record(&self, session: &Session, object_name: &str, statement: &str, rollback: &str) {
+        let seq = self.seq.fetch_add(1, Ordering::SeqCst) + 1;
+
+        let cql = format!(
+            "INSERT INTO {}.{} \
+            (run_id, seq, object_name, statement, checksum, rollback_checksum, applied_at, applied_by) \
+            VALUES (?, ?, ?, ?, ?, ?, toTimestamp(now()), ?)",
+            self.keyspace, HISTORY_TABLE
+        );
+
+        let values = (
+            self.run_id,
+            seq,
+            object_name,
+            statement.trim(),
+            checksum(statement),
+            checksum(rollback),
+            &self.host,
+        );
+
+        session
+            .query_unpaged(cql, values)
+            .await
+            .unwrap_or_else(|e| panic!("Failed to record applied statement in {}: {}", HISTORY_TABLE, e));
+    }
+}
+
+/// Replays `(run_id, seq, checksum, rollback_checksum)` history entries in order of application. Statement
+/// stops being applied once a later statement has its checksum as rollback.
+fn replay_applied(mut entries: Vec<(i64, i32, String, String)>) -> HashSet<String> {
+    entries.sort_by_key(|(run_id, seq, _, _)| (*run_id, *seq));
+
+    let mut applied = HashSet::new();
+
+    for (_, _, checksum, rollback_checksum) in entries {
+        applied.remove(&rollback_checksum);
+        applied.insert(checksum);
+    }
+
+    applied
+}
+
+/// FNV-1a hash of statement with normalized whitespace, so formatting changes of generated statements
+/// don't change the checksum.
+pub(crate) fn checksum(statement: &str) -> String {
+    let normalized = statement.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+    let hash = normalized.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{:016x}", hash)
+}
+
+fn applying_host() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(run_id: i64, seq: i32, statement: &str, rollback: &str) -> (i64, i32, String, String) {
+        (run_id, seq, checksum(statement), checksum(rollback))
+    }
+
+    #[test]
+    fn checksum_ignores_whitespace() {
+        assert_eq!(
+            checksum("ALTER TABLE users ADD name text;"),
+            checksum("  ALTER TABLE users\n    ADD name   text;\n")
+        );
+        assert_ne!(
+            checksum("ALTER TABLE users ADD name text;"),
+            checksum("ALTER TABLE users ADD email text;")
+        );
+    }
+
+    #[test]
+    fn replay_applied_keeps_recorded_statements() {
+        let applied = replay_applied(vec![
+            entry(1, 1, "ALTER TABLE users ADD name text;", "ALTER TABLE users DROP name;"),
+            entry(1, 2, "ALTER TABLE users ADD age int;", "ALTER TABLE users DROP age;"),
+        ]);
+
+        assert!(applied.contains(&checksum("ALTER TABLE users ADD name text;")));
+        assert!(applied.contains(&checksum("ALTER TABLE users ADD age int;")));
+    }
+
+    #[test]
+    fn replay_applied_forgets_reverted_statements() {
+        let applied = replay_applied(vec![
+            entry(2, 1, "ALTER TABLE users DROP name;", "ALTER TABLE users ADD name text;"),
+            entry(1, 1, "ALTER TABLE users ADD name text;", "ALTER TABLE users DROP name;"),
+        ]);
+
+        assert!(!applied.contains(&checksum("ALTER TABLE users ADD name text;")));
+        assert!(applied.contains(&checksum("ALTER TABLE users DROP name;")));
+    }
+
+    #[test]
+    fn replay_applied_orders_by_run_and_seq() {
+        let applied = replay_applied(vec![
+            entry(1, 2, "ALTER TABLE users ADD name text;", "ALTER TABLE users DROP name;"),
+            entry(1, 1, "ALTER TABLE users DROP name;", "ALTER TABLE users ADD name text;"),
+        ]);
+
+        assert!(applied.contains(&checksum("ALTER TABLE users ADD name text;")));
+        assert!(!applied.contains(&checksum("ALTER TABLE users DROP name;")));
+    }
+}
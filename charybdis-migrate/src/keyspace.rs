@@ -0,0 +1,214 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::str::FromStr;
+
+use colored::Colorize;
+use scylla::Session;
+
+use charybdis_parser::log_info;
+
+use crate::replay::ReplayLog;
+
+/// Replication strategy of migrated keyspace. When provided, migration creates keyspace if it doesn't
+/// exist, or alters its replication if it differs from the declared one, so fresh environment can be
+/// bootstrapped from the codebase.
+/// ```rust ignore
+/// let migration = MigrationBuilder::new()
+///     .keyspace("app".to_string())
+///     .replication(Replication::network_topology([("dc1", 3), ("dc2", 2)]))
+///     .build(&session)
+///     .await;
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Replication {
+    SimpleStrategy { replication_factor: u32 },
+    NetworkTopologyStrategy { datacenters: BTreeMap<String, u32> },
+}
+
+impl Replication {
+    pub fn simple(replication_factor: u32) -> Self {
+        Replication::SimpleStrategy { replication_factor }
+    }
+
+    pub fn network_topology<Dc: Into<String>>(datacenters: impl IntoIterator<Item = (Dc, u32)>) -> Self {
+        Replication::NetworkTopologyStrategy {
+            datacenters: datacenters.into_iter().map(|(dc, rf)| (dc.into(), rf)).collect(),
+        }
+    }
+
+    /// Builds replication from `system_schema.keyspaces.replication` map.
+    fn from_system_schema(replication: &HashMap<String, String>) -> Option<Self> {
+        let class = replication.get("class")?;
+
+        if class.ends_with("SimpleStrategy") {
+            let replication_factor = replication.get("replication_factor")?.parse().ok()?;
+
+            return Some(Replication::simple(replication_factor));
+        }
+
+        if class.ends_with("NetworkTopologyStrategy") {
+            let datacenters = replication
+                .iter()
+                .filter(|(key, _)| key.as_str() != "class")
+                .map(|(dc, rf)| rf.parse().ok().map(|rf| (dc.clone(), rf)))
+                .collect::<Option<BTreeMap<String, u32>>>()?;
+
+            return Some(Replication::NetworkTopologyStrategy { datacenters });
+        }
+
+        None
+    }
+}
+
+impl Display for Replication {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Replication::SimpleStrategy { replication_factor } => {
+                write!(
+                    f,
+                    "{{'class': 'SimpleStrategy', 'replication_factor': {}}}",
+                    replication_factor
+                )
+            }
+            Replication::NetworkTopologyStrategy { datacenters } => {
+                let datacenters = datacenters
+                    .iter()
+                    .map(|(dc, rf)| format!(", '{}': {}", dc, rf))
+                    .collect::<String>();
+
+                write!(f, "{{'class': 'NetworkTopologyStrategy'{}}}", datacenters)
+            }
+        }
+    }
+}
+
+/// Parses `--replication` argument: `3` for `SimpleStrategy`, or `dc1:3,dc2:2` for
+/// `NetworkTopologyStrategy`.
+impl FromStr for Replication {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(replication_factor) = s.trim().parse::<u32>() {
+            return Ok(Replication::simple(replication_factor));
+        }
+
+        let datacenters = s
+            .split(',')
+            .map(|dc| {
+                let (name, rf) = dc
+                    .split_once(':')
+                    .ok_or_else(|| format!("Invalid datacenter replication '{}', expected <dc>:<rf>", dc))?;
+                let rf = rf
+                    .trim()
+                    .parse::<u32>()
+                    .map_err(|e| format!("Invalid replication factor for {}: {}", name, e))?;
+
+                Ok((name.trim().to_string(), rf))
+            })
+            .collect::<Result<BTreeMap<String, u32>, String>>()?;
+
+        Ok(Replication::NetworkTopologyStrategy { datacenters })
+    }
+}
+
+pub(crate) struct KeyspaceMigration<'a> {
+    session: &'a Session,
+    keyspace: &'a str,
+    replication: &'a Replication,
+}
+
+impl<'a> KeyspaceMigration<'a> {
+    pub(crate) fn new(session: &'a Session, keyspace: &'a str, replication: &'a Replication) -> Self {
+        Self {
+            session,
+            keyspace,
+            replication,
+        }
+    }
+
+    async fn current_replication(&self) -> Option<HashMap<String, String>> {
+        let cql = "SELECT replication FROM system_schema.keyspaces WHERE keyspace_name = ?";
+
+        self.session
+            .query_unpaged(cql, (self.keyspace,))
+            .await
+            .unwrap_or_else(|e| panic!("Failed to get replication of keyspace {}: {}", self.keyspace, e))
+            .rows
+            .and_then(|rows| rows.into_iter().next())
+            .map(|row| {
+                row.into_typed::<(HashMap<String, String>,)>()
+                    .unwrap_or_else(|e| panic!("Failed to parse replication of keyspace {}: {}", self.keyspace, e))
+                    .0
+            })
+    }
+
+    async fn execute(&self, cql: &str) {
+        log_info!("{} {}", "Running CQL:".on_bright_green().black(), cql.bright_purple());
+
+        self.session
+            .query_unpaged(cql, ())
+            .await
+            .unwrap_or_else(|e| panic!("{} {}\n", "CQL execution failed! ❌".bright_red(), e));
+
+        log_info!("{}\n", "CQL executed successfully! ✅".bright_green());
+    }
+
+    /// Creates keyspace or aligns its replication with declared one, and switches session to it.
+    /// Applied statements are recorded after keyspace exists, as replay log is stored within it.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "keyspace_migration", skip_all))]
+    pub(crate) async fn run(&self, replay_log: &ReplayLog) {
+        let applied = match self.current_replication().await {
+            None => {
+                log_info!(
+                    "\n{} {}!",
+                    "Detected first migration for keyspace:".bright_cyan(),
+                    self.keyspace.bright_yellow()
+                );
+
+                let cql = format!(
+                    "CREATE KEYSPACE IF NOT EXISTS {} WITH replication = {}",
+                    self.keyspace, self.replication
+                );
+                let rollback = format!("DROP KEYSPACE IF EXISTS {}", self.keyspace);
+
+                self.execute(&cql).await;
+
+                Some((cql, rollback))
+            }
+            Some(current) => match Replication::from_system_schema(&current) {
+                Some(current) if current == *self.replication => None,
+                current => {
+                    log_info!(
+                        "\n{} {}!",
+                        "Detected replication change for keyspace:".bright_cyan(),
+                        self.keyspace.bright_yellow()
+                    );
+
+                    let cql = format!(
+                        "ALTER KEYSPACE {} WITH replication = {}",
+                        self.keyspace, self.replication
+                    );
+                    let rollback = match current {
+                        Some(current) => format!("ALTER KEYSPACE {} WITH replication = {}", self.keyspace, current),
+                        None => format!("-- irreversible: unknown replication of keyspace {}", self.keyspace),
+                    };
+
+                    self.execute(&cql).await;
+
+                    Some((cql, rollback))
+                }
+            },
+        };
+
+        self.session
+            .use_keyspace(self.keyspace, false)
+            .await
+            .unwrap_or_else(|e| panic!("Failed to use keyspace {}: {}", self.keyspace, e));
+
+        replay_log.init(self.session).await;
+
+        if let Some((cql, rollback)) = applied {
+            replay_log.record(self.session, self.keyspace, &cql, &rollback).await;
+        }
+    }
+}
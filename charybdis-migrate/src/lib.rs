@@ -1,12 +1,20 @@
 use crate::args::Args;
+use crate::keyspace::Replication;
 use crate::migration::Migration;
 use charybdis_parser::schema::code_schema::CodeSchema;
 use charybdis_parser::schema::db_schema::DbSchema;
 use scylla::Session;
 
 pub mod args;
+pub(crate) mod history;
+pub mod keyspace;
+pub mod lint;
 pub mod migration;
 pub(crate) mod model;
+pub mod plan;
+pub(crate) mod registry;
+pub(crate) mod replay;
+pub(crate) mod report;
 pub mod session;
 
 pub struct MigrationBuilder {
@@ -28,7 +36,11 @@ impl MigrationBuilder {
         }
 
         let current_db_schema = DbSchema::new(&session, self.args.keyspace.clone()).await;
-        let current_code_schema = CodeSchema::new(&self.args.project_root);
+        let current_code_schema = if self.args.use_registry {
+            registry::code_schema_from_registry()
+        } else {
+            CodeSchema::new(&self.args.project_root)
+        };
 
         let migration = Migration::new(current_db_schema, current_code_schema, session, self.args);
 
@@ -45,6 +57,14 @@ impl MigrationBuilder {
         self
     }
 
+    /// Takes models from `charybdis::registry` instead of parsing source files under project root. Registry
+    /// contains only models linked into current binary, so it's meant for migrations run from the
+    /// application itself.
+    pub fn use_registry(mut self, use_registry: bool) -> Self {
+        self.args.use_registry = use_registry;
+        self
+    }
+
     pub fn drop_and_replace(mut self, drop_and_replace: bool) -> Self {
         self.args.drop_and_replace = drop_and_replace;
         self
@@ -54,6 +74,23 @@ impl MigrationBuilder {
         self.args.verbose = verbose;
         self
     }
+
+    /// Creates keyspace with given replication if it doesn't exist, or alters its replication.
+    pub fn replication(mut self, replication: Replication) -> Self {
+        self.args.replication = Some(replication);
+        self
+    }
+
+    /// Seconds to wait for schema agreement of all nodes before migration and after each DDL statement.
+    pub fn schema_agreement_timeout(mut self, seconds: u64) -> Self {
+        self.args.schema_agreement_timeout = seconds;
+        self
+    }
+
+    pub fn replay_log_dir(mut self, replay_log_dir: String) -> Self {
+        self.args.replay_log_dir = Some(replay_log_dir);
+        self
+    }
 }
 
 impl From<Args> for MigrationBuilder {
@@ -0,0 +1,269 @@
+use std::fmt;
+
+use charybdis_parser::schema::SchemaObject;
+
+use crate::model::ModelType;
+
+/// Tables with more secondary indexes are reported, as each index is written on every write of the table.
+const MAX_SECONDARY_INDEXES: usize = 3;
+
+const TIME_TYPES: [&str; 3] = ["timestamp", "timeuuid", "date"];
+const TEXT_TYPES: [&str; 3] = ["text", "varchar", "ascii"];
+const COLLECTION_TYPES: [&str; 3] = ["list<", "set<", "map<"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintKind {
+    /// Time series table without time bucket in partition key and without default TTL, so its partitions grow
+    /// as long as rows are written.
+    UnboundedPartition,
+    TooManySecondaryIndexes,
+    /// Non-frozen collection, that is read as a whole and can grow without bound.
+    LargeCollection,
+    /// Text partition key, that often has low cardinality or is unevenly distributed, e.g. country or status.
+    TextPartitionKey,
+}
+
+/// Anti-pattern found in schema of models. Warnings don't block migration, they are part of
+/// [MigrationPlan](crate::plan::MigrationPlan) so they can be reviewed with planned steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintWarning {
+    pub object_name: String,
+    pub kind: LintKind,
+    /// column the warning refers to, `None` for warnings about whole table
+    pub field_name: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.field_name {
+            Some(field_name) => write!(
+                f,
+                "[{:?}] {}.{}: {}",
+                self.kind, self.object_name, field_name, self.message
+            ),
+            None => write!(f, "[{:?}] {}: {}", self.kind, self.object_name, self.message),
+        }
+    }
+}
+
+/// e.g. `Frozen < List < Text > >` becomes `frozen<list<text>>`
+fn normalize_type(field_type: &str) -> String {
+    field_type
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn field_type(schema: &SchemaObject, field_name: &str) -> Option<String> {
+    schema
+        .types_by_name
+        .get(field_name)
+        .map(|field_type| normalize_type(field_type))
+}
+
+fn is_time_type(field_type: &str) -> bool {
+    TIME_TYPES.contains(&field_type)
+}
+
+fn has_default_ttl(schema: &SchemaObject) -> bool {
+    let table_options = match &schema.table_options {
+        Some(table_options) => normalize_type(table_options),
+        None => return false,
+    };
+
+    table_options
+        .split_once("default_time_to_live=")
+        .is_some_and(|(_, ttl)| {
+            let ttl = ttl.trim_start_matches('\'');
+            let digits = ttl.chars().take_while(|c| c.is_ascii_digit()).collect::<String>();
+
+            digits.parse::<u64>().is_ok_and(|ttl| ttl > 0)
+        })
+}
+
+/// Lints code schema of a model. UDTs are not linted, and materialized views are checked only for their
+/// partition keys, as their other properties follow base table.
+pub(crate) fn lint_model(model_type: ModelType, name: &str, schema: &SchemaObject) -> Vec<LintWarning> {
+    let mut warnings = vec![];
+
+    let warning = |kind: LintKind, field_name: Option<&str>, message: String| LintWarning {
+        object_name: name.to_string(),
+        kind,
+        field_name: field_name.map(str::to_string),
+        message,
+    };
+
+    if model_type == ModelType::Udt {
+        return warnings;
+    }
+
+    for partition_key in &schema.partition_keys {
+        if field_type(schema, partition_key).is_some_and(|field_type| TEXT_TYPES.contains(&field_type.as_str())) {
+            warnings.push(warning(
+                LintKind::TextPartitionKey,
+                Some(partition_key),
+                "text partition key can be unevenly distributed, prefer uuid or add more columns to partition key"
+                    .to_string(),
+            ));
+        }
+    }
+
+    if model_type != ModelType::Table {
+        return warnings;
+    }
+
+    let time_clustering_key = schema
+        .clustering_keys
+        .iter()
+        .find(|key| field_type(schema, key).is_some_and(|field_type| is_time_type(&field_type)));
+    let time_bucket = schema
+        .partition_keys
+        .iter()
+        .any(|key| field_type(schema, key).is_some_and(|field_type| is_time_type(&field_type)));
+
+    if let Some(time_clustering_key) = time_clustering_key {
+        if !time_bucket && !has_default_ttl(schema) {
+            warnings.push(warning(
+                LintKind::UnboundedPartition,
+                Some(time_clustering_key),
+                "time series partition is not bounded by time bucket in partition key nor by default_time_to_live"
+                    .to_string(),
+            ));
+        }
+    }
+
+    let secondary_indexes =
+        schema.global_secondary_indexes.len() + schema.local_secondary_indexes.len() + schema.custom_indexes.len();
+
+    if secondary_indexes > MAX_SECONDARY_INDEXES {
+        warnings.push(warning(
+            LintKind::TooManySecondaryIndexes,
+            None,
+            format!(
+                "{} secondary indexes, more than {}, are updated on each write; consider materialized views or \
+                lookup tables",
+                secondary_indexes, MAX_SECONDARY_INDEXES
+            ),
+        ));
+    }
+
+    for (field_name, field_type, _) in &schema.fields {
+        let field_type = normalize_type(field_type);
+
+        if COLLECTION_TYPES.iter().any(|prefix| field_type.starts_with(prefix)) {
+            warnings.push(warning(
+                LintKind::LargeCollection,
+                Some(field_name),
+                format!(
+                    "{} is read as a whole, keep it small or store its elements as clustered rows",
+                    field_type
+                ),
+            ));
+        }
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema(fields: &[(&str, &str)], partition_keys: &[&str], clustering_keys: &[&str]) -> SchemaObject {
+        let mut schema = SchemaObject::default();
+
+        for (field_name, field_type) in fields {
+            schema.push_field(field_name.to_string(), field_type.to_string(), false);
+        }
+
+        schema.partition_keys = partition_keys.iter().map(|key| key.to_string()).collect();
+        schema.clustering_keys = clustering_keys.iter().map(|key| key.to_string()).collect();
+
+        schema
+    }
+
+    fn kinds(warnings: &[LintWarning]) -> Vec<LintKind> {
+        warnings.iter().map(|warning| warning.kind).collect()
+    }
+
+    #[test]
+    fn reports_unbounded_time_series_partition() {
+        let events = schema(
+            &[("user_id", "uuid"), ("created_at", "timestamp")],
+            &["user_id"],
+            &["created_at"],
+        );
+
+        assert_eq!(
+            kinds(&lint_model(ModelType::Table, "events", &events)),
+            vec![LintKind::UnboundedPartition]
+        );
+    }
+
+    #[test]
+    fn accepts_time_series_bounded_by_bucket_or_ttl() {
+        let bucketed = schema(
+            &[("user_id", "uuid"), ("day", "date"), ("created_at", "timestamp")],
+            &["user_id", "day"],
+            &["created_at"],
+        );
+        let mut expiring = schema(
+            &[("user_id", "uuid"), ("created_at", "timestamp")],
+            &["user_id"],
+            &["created_at"],
+        );
+        expiring.table_options = Some("default_time_to_live = 86400".to_string());
+
+        assert!(lint_model(ModelType::Table, "events", &bucketed).is_empty());
+        assert!(lint_model(ModelType::Table, "events", &expiring).is_empty());
+    }
+
+    #[test]
+    fn ignores_zero_default_ttl() {
+        let mut events = schema(
+            &[("user_id", "uuid"), ("created_at", "timestamp")],
+            &["user_id"],
+            &["created_at"],
+        );
+        events.table_options = Some("default_time_to_live = 0".to_string());
+
+        assert!(!has_default_ttl(&events));
+    }
+
+    #[test]
+    fn reports_text_partition_key_and_non_frozen_collections() {
+        let users = schema(
+            &[
+                ("country", "text"),
+                ("tags", "Set < Text >"),
+                ("address", "frozen<address>"),
+            ],
+            &["country"],
+            &[],
+        );
+        let warnings = lint_model(ModelType::Table, "users", &users);
+
+        assert_eq!(
+            kinds(&warnings),
+            vec![LintKind::TextPartitionKey, LintKind::LargeCollection]
+        );
+        assert_eq!(warnings[1].field_name.as_deref(), Some("tags"));
+    }
+
+    #[test]
+    fn lints_only_partition_keys_of_views_and_nothing_of_udts() {
+        let by_country = schema(&[("country", "text"), ("tags", "set<text>")], &["country"], &[]);
+
+        assert_eq!(
+            kinds(&lint_model(
+                ModelType::MaterializedView,
+                "users_by_country",
+                &by_country
+            )),
+            vec![LintKind::TextPartitionKey]
+        );
+        assert!(lint_model(ModelType::Udt, "address", &by_country).is_empty());
+    }
+}
@@ -12,10 +12,26 @@ async fn main() {
         colored::control::set_override(true);
     }
 
+    // emit json events, so output can be collected by log aggregation
+    #[cfg(feature = "tracing")]
+    tracing_subscriber::fmt()
+        .json()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .init();
+
     let args = Args::parse();
+    let html_report = args.html_report.clone();
     let session: Session = initialize_session(&args).await;
     let migration = MigrationBuilder::from(args).build(&session).await;
 
+    if let Some(path) = html_report {
+        migration.write_html_report(&path);
+        return;
+    }
+
     migration.run().await;
     migration.write_schema_to_json().await;
 }
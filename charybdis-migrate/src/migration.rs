@@ -1,10 +1,20 @@
+use std::time::Duration;
+
 use colored::Colorize;
 use scylla::Session;
 
 use crate::args::Args;
+use crate::history::{checksum, MigrationHistory};
+use crate::keyspace::KeyspaceMigration;
+use crate::lint::lint_model;
 use crate::model::data::ModelData;
 use crate::model::{ModelMigration, ModelType};
+use crate::plan::{ApplyPolicy, DestructiveChangesError, MigrationPlan, PlanStep};
+use crate::replay::ReplayLog;
+use crate::report::HtmlReport;
 
+use charybdis_parser::identifier::{normalize_identifier, split_qualified_name};
+use charybdis_parser::log_info;
 use charybdis_parser::schema::code_schema::CodeSchema;
 use charybdis_parser::schema::db_schema::DbSchema;
 use charybdis_parser::schema::SchemaObject;
@@ -13,81 +23,308 @@ pub struct Migration<'a> {
     current_db_schema: DbSchema,
     current_code_schema: CodeSchema,
     session: &'a Session,
+    replay_log: ReplayLog,
+    history: MigrationHistory,
     args: Args,
 }
 
 impl<'a> Migration<'a> {
     pub fn new(current_db_schema: DbSchema, current_code_schema: CodeSchema, session: &'a Session, args: Args) -> Self {
+        let replay_log = ReplayLog::new(args.replay_log_dir.as_ref(), &args.keyspace);
+        let history = MigrationHistory::new(&args.keyspace);
+
         Migration {
             current_db_schema,
             current_code_schema,
             session,
+            replay_log,
+            history,
             args,
         }
     }
 
+    /// Applies all changes. Field type changes are applied only with `drop_and_replace` flag, otherwise
+    /// migration panics before any statement is executed.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "migration", skip_all))]
     pub async fn run(&self) {
-        self.run_udts().await;
-        self.run_tables().await;
-        self.run_materialized_views().await;
+        let plan = self.build_plan(self.args.drop_and_replace);
 
-        println!("\n{}", "Migration plan ran successfully!".bright_green());
+        self.apply(&plan, ApplyPolicy::AllowAll)
+            .await
+            .expect("AllowAll policy doesn't reject any step");
     }
 
-    pub async fn write_schema_to_json(&self) {
-        DbSchema::new(&self.session, self.args.keyspace.clone())
-            .await
-            .write_schema_to_json(&self.args.project_root);
+    /// Plans changes without applying them. Unlike `run`, field type changes are planned as destructive
+    /// drop and re-add of the field regardless of `drop_and_replace` flag, so they can be handled by
+    /// [ApplyPolicy].
+    /// ```rust ignore
+    /// let migration = MigrationBuilder::new().keyspace("app".to_string()).build(&session).await;
+    /// let plan = migration.plan();
+    ///
+    /// for step in plan.destructive_steps() {
+    ///     println!("{}", step);
+    /// }
+    ///
+    /// migration.apply(&plan, ApplyPolicy::FailOnDestructive).await?;
+    /// ```
+    pub fn plan(&self) -> MigrationPlan {
+        self.build_plan(true)
     }
 
-    async fn run_udts(&self) {
-        let empty_udt = SchemaObject::default();
+    /// Applies steps of given plan in order. With `FailOnDestructive` policy, error is returned
+    /// before any statement is executed if plan contains destructive step. Steps recorded as applied in
+    /// `charybdis_migrations` table are skipped.
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "apply_migration_plan", skip_all))]
+    pub async fn apply(&self, plan: &MigrationPlan, policy: ApplyPolicy) -> Result<(), DestructiveChangesError> {
+        if policy == ApplyPolicy::FailOnDestructive && plan.has_destructive_steps() {
+            return Err(DestructiveChangesError {
+                steps: plan.destructive_steps().cloned().collect(),
+            });
+        }
 
-        for (name, code_udt_schema) in self.current_code_schema.udts.iter() {
-            let model_data = ModelData::new(
-                name,
-                ModelType::Udt,
-                code_udt_schema,
-                self.current_db_schema.udts.get(name).unwrap_or(&empty_udt),
+        for warning in plan.warnings() {
+            log_info!(
+                "{} {}",
+                "Schema warning:".on_bright_yellow().black(),
+                warning.to_string().bright_yellow()
             );
+        }
+
+        // schema changes of previous runs or other clients can still be propagating
+        self.await_schema_agreement().await;
+
+        match &self.args.replication {
+            Some(replication) => {
+                KeyspaceMigration::new(self.session, &self.args.keyspace, replication)
+                    .run(&self.replay_log)
+                    .await
+            }
+            None => self.replay_log.init(self.session).await,
+        }
+
+        self.history.init(self.session).await;
+        self.await_schema_agreement().await;
+
+        let applied = self.history.applied_checksums(self.session).await;
+
+        for step in plan.steps() {
+            if applied.contains(&checksum(&strip_colors(&step.cql))) {
+                log_info!(
+                    "{} {}",
+                    "Skipping already applied CQL:".on_bright_yellow().black(),
+                    step.cql.trim().bright_purple()
+                );
+                continue;
+            }
 
-            let migration = ModelMigration::new(&model_data, &self.session, &self.args);
+            if step.is_destructive() && policy == ApplyPolicy::SkipDestructive {
+                log_info!(
+                    "{} {}",
+                    "Skipping destructive CQL:".on_bright_yellow().black(),
+                    step.cql.trim().bright_purple()
+                );
+                continue;
+            }
 
-            migration.run().await;
+            self.execute(step).await;
         }
+
+        self.replay_log.print_summary();
+
+        log_info!("\n{}", "Migration plan ran successfully!".bright_green());
+
+        Ok(())
     }
 
-    async fn run_tables(&self) {
-        let empty_table = SchemaObject::default();
+    /// Executes DDL statement and records it in replay log together with statement that reverts it.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "execute_ddl", skip_all, fields(cql = tracing::field::Empty))
+    )]
+    async fn execute(&self, step: &PlanStep) {
+        let print = !step.verbose_only || self.args.verbose;
 
-        for (name, code_table_schema) in self.current_code_schema.tables.iter() {
-            let model_data = ModelData::new(
-                name,
-                ModelType::Table,
-                code_table_schema,
-                self.current_db_schema.tables.get(name).unwrap_or(&empty_table),
+        if print {
+            log_info!(
+                "{} {}",
+                "Running CQL:".on_bright_green().black(),
+                step.cql.bright_purple()
             );
+        }
+
+        let cql = strip_colors(&step.cql);
 
-            let migration = ModelMigration::new(&model_data, &self.session, &self.args);
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("cql", cql.as_str());
 
-            migration.run().await;
+        let res = self.session.query_unpaged(cql.clone(), ()).await;
+
+        match res {
+            Ok(_) => {
+                if print {
+                    log_info!("{}\n", "CQL executed successfully! ✅".bright_green());
+                }
+            }
+            Err(e) => panic!("{} {}\n", "CQL execution failed! ❌".bright_red(), e),
         }
+
+        self.await_schema_agreement().await;
+
+        self.replay_log
+            .record(self.session, &step.object_name, &cql, &step.rollback)
+            .await;
+
+        self.history
+            .record(self.session, &step.object_name, &cql, &step.rollback)
+            .await;
+    }
+
+    /// Waits until all nodes agree on schema version, so next statement is not applied on top of schema
+    /// that some nodes haven't seen yet.
+    async fn await_schema_agreement(&self) {
+        let timeout = Duration::from_secs(self.args.schema_agreement_timeout);
+
+        charybdis::cluster::await_schema_agreement(self.session, timeout)
+            .await
+            .unwrap_or_else(|e| panic!("{} {}\n", "Schema agreement failed! ❌".bright_red(), e));
     }
 
-    async fn run_materialized_views(&self) {
-        let empty_mv = SchemaObject::default();
+    fn build_plan(&self, allow_type_change: bool) -> MigrationPlan {
+        let mut plan = MigrationPlan::default();
+        let empty = SchemaObject::default();
 
-        for (name, code_mv_schema) in self.current_code_schema.materialized_views.iter() {
-            let model_data = ModelData::new(
-                name,
+        let models = [
+            (
+                ModelType::Udt,
+                &self.current_code_schema.udts,
+                &self.current_db_schema.udts,
+            ),
+            (
+                ModelType::Table,
+                &self.current_code_schema.tables,
+                &self.current_db_schema.tables,
+            ),
+            (
                 ModelType::MaterializedView,
-                code_mv_schema,
-                self.current_db_schema.materialized_views.get(name).unwrap_or(&empty_mv),
-            );
+                &self.current_code_schema.materialized_views,
+                &self.current_db_schema.materialized_views,
+            ),
+        ];
+
+        for (model_type, code_schemas, db_schemas) in models {
+            for (name, code_schema) in code_schemas.iter() {
+                let name = match self.local_name(model_type, name) {
+                    Some(name) => name,
+                    None => continue,
+                };
+                let model_data =
+                    ModelData::new(&name, model_type, code_schema, db_schemas.get(&name).unwrap_or(&empty));
+
+                if let Some(renamed_from) = &code_schema.renamed_from {
+                    if !db_schemas.contains_key(&name) && db_schemas.contains_key(renamed_from) {
+                        Self::log_table_copy_guidance(renamed_from, &name);
+                    }
+                }
+
+                plan.extend(ModelMigration::new(&model_data).plan(allow_type_change));
+                plan.extend_warnings(lint_model(model_type, &name, code_schema));
+            }
+        }
+
+        plan
+    }
 
-            let migration = ModelMigration::new(&model_data, &self.session, &self.args);
+    /// Name of model within migrated keyspace. UDTs can be declared with type name qualified by keyspace, e.g.
+    /// `type_name = other_ks.address`, in which case they are migrated only with their own keyspace, and compared
+    /// with db schema by unqualified name, as `system_schema.types` stores them.
+    fn local_name(&self, model_type: ModelType, name: &str) -> Option<String> {
+        if model_type != ModelType::Udt {
+            return Some(name.to_string());
+        }
 
-            migration.run().await;
+        match split_qualified_name(name) {
+            (Some(keyspace), _) if keyspace != normalize_identifier(&self.args.keyspace) => None,
+            (_, type_name) => Some(type_name),
         }
     }
+
+    /// CQL can't rename tables, so renamed table is created as new one and old table is kept until its data
+    /// is copied by application.
+    fn log_table_copy_guidance(old_name: &str, new_name: &str) {
+        log_info!(
+            "\n{} {} {} {}\n{}",
+            "Renamed table".bright_cyan(),
+            old_name.bright_yellow(),
+            "->".bright_cyan(),
+            new_name.bright_yellow(),
+            format!(
+                "Tables can't be renamed in place. Table {} is created and {} is kept, copy data from {} to {} \
+                and drop {} once it's no longer used.",
+                new_name, old_name, old_name, new_name, old_name
+            )
+            .bright_magenta()
+        );
+    }
+
+    pub async fn write_schema_to_json(&self) {
+        DbSchema::new(&self.session, self.args.keyspace.clone())
+            .await
+            .write_schema_to_json(&self.args.project_root);
+    }
+
+    /// Renders planned changes as standalone html report without applying them.
+    pub fn write_html_report(&self, path: &str) {
+        let mut report = HtmlReport::new(&self.args.keyspace);
+        let empty = SchemaObject::default();
+
+        let models = [
+            (
+                ModelType::Udt,
+                &self.current_code_schema.udts,
+                &self.current_db_schema.udts,
+            ),
+            (
+                ModelType::Table,
+                &self.current_code_schema.tables,
+                &self.current_db_schema.tables,
+            ),
+            (
+                ModelType::MaterializedView,
+                &self.current_code_schema.materialized_views,
+                &self.current_db_schema.materialized_views,
+            ),
+        ];
+
+        for (model_type, code_schemas, db_schemas) in models {
+            let mut names = code_schemas.keys().collect::<Vec<&String>>();
+            names.sort();
+
+            for name in names {
+                let local_name = match self.local_name(model_type, name) {
+                    Some(local_name) => local_name,
+                    None => continue,
+                };
+                let model_data = ModelData::new(
+                    &local_name,
+                    model_type,
+                    &code_schemas[name],
+                    db_schemas.get(&local_name).unwrap_or(&empty),
+                );
+
+                report.push_model(&model_data);
+                report.push_warnings(lint_model(model_type, &local_name, &code_schemas[name]));
+            }
+        }
+
+        report.write(path);
+
+        log_info!("Html report written to {}", path.bright_cyan());
+    }
+}
+
+/// removes all colors from cql string
+fn strip_colors(cql: &str) -> String {
+    let stripped = strip_ansi_escapes::strip(cql.as_bytes());
+
+    String::from_utf8(stripped).unwrap()
 }
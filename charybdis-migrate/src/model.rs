@@ -1,11 +1,11 @@
 use std::fmt::Display;
 
-use crate::args::Args;
+use charybdis_parser::log_info;
 use colored::Colorize;
-use scylla::Session;
 
 use crate::model::data::ModelData;
 use crate::model::runner::ModelRunner;
+use crate::plan::PlanStep;
 
 pub(crate) mod data;
 mod runner;
@@ -31,22 +31,28 @@ impl Display for ModelType {
 enum MigrationStep {
     ChangeTableOptions,
     ChangeFieldTypes,
+    RenameFields,
     AddFields,
     AddGlobalIndexes,
     AddLocalIndexes,
+    RemoveCustomIndexes,
+    AddCustomIndexes,
     RemoveLocalIndexes,
     RemoveGlobalIndexes,
     RemoveFields,
 }
 
 impl MigrationStep {
-    fn array() -> [MigrationStep; 8] {
+    fn array() -> [MigrationStep; 11] {
         [
             MigrationStep::ChangeTableOptions,
             MigrationStep::ChangeFieldTypes,
+            MigrationStep::RenameFields,
             MigrationStep::AddFields,
             MigrationStep::AddGlobalIndexes,
             MigrationStep::AddLocalIndexes,
+            MigrationStep::RemoveCustomIndexes,
+            MigrationStep::AddCustomIndexes,
             MigrationStep::RemoveLocalIndexes,
             MigrationStep::RemoveGlobalIndexes,
             MigrationStep::RemoveFields,
@@ -57,77 +63,114 @@ impl MigrationStep {
 pub(crate) struct ModelMigration<'a> {
     data: &'a ModelData<'a>,
     runner: ModelRunner<'a>,
-    args: &'a Args,
 }
 
 impl<'a> ModelMigration<'a> {
-    pub(crate) fn new(data: &'a ModelData, session: &'a Session, args: &'a Args) -> Self {
-        let runner = ModelRunner::new(session, data, args);
+    pub(crate) fn new(data: &'a ModelData) -> Self {
+        let runner = ModelRunner::new(data);
 
-        Self { data, runner, args }
+        Self { data, runner }
     }
 
-    pub(crate) async fn run(&self) {
+    /// Plans steps of model migration. Field type change is planned as drop and re-add of the field if
+    /// `allow_type_change` is set, otherwise it panics.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "model_migration",
+            skip_all,
+            fields(name = %self.data.migration_object_name, model_type = %self.data.migration_object_type)
+        )
+    )]
+    pub(crate) fn plan(self, allow_type_change: bool) -> Vec<PlanStep> {
         if self.data.is_first_migration() {
-            self.handle_first_migration().await;
-            return;
+            self.handle_first_migration();
+        } else {
+            self.handle_changes(allow_type_change);
         }
 
+        self.runner.into_steps()
+    }
+
+    fn handle_changes(&self, allow_type_change: bool) {
         self.panic_on_partition_key_change();
         self.panic_on_clustering_key_change();
+        self.panic_on_clustering_order_change();
 
         let mut is_any_field_changed = false;
 
         for step in MigrationStep::array().iter() {
             match step {
-                MigrationStep::ChangeTableOptions => self.runner.run_table_options_change_migration().await,
+                MigrationStep::ChangeTableOptions => self.runner.run_table_options_change_migration(),
                 MigrationStep::ChangeFieldTypes => {
                     if self.data.has_changed_type_fields() {
                         is_any_field_changed = true;
-                        self.handle_fields_type_change().await;
+                        self.handle_fields_type_change(allow_type_change);
+                    }
+                }
+                MigrationStep::RenameFields => {
+                    if self.data.has_renamed_fields() {
+                        is_any_field_changed = true;
+                        self.handle_renamed_fields();
                     }
                 }
                 MigrationStep::AddFields => {
                     if self.data.has_new_fields() {
                         is_any_field_changed = true;
-                        self.handle_new_fields().await;
+                        self.handle_new_fields();
+                    }
+
+                    if self.data.has_copied_fields() {
+                        self.runner.log_field_copy_guidance();
                     }
                 }
                 MigrationStep::AddGlobalIndexes => {
                     if self.data.has_new_global_secondary_indexes() {
                         is_any_field_changed = true;
-                        self.runner.run_global_index_added_migration().await;
+                        self.runner.run_global_index_added_migration();
                     }
                 }
                 MigrationStep::AddLocalIndexes => {
                     if self.data.has_new_local_secondary_indexes() {
                         is_any_field_changed = true;
-                        self.runner.run_local_index_added_migration().await;
+                        self.runner.run_local_index_added_migration();
+                    }
+                }
+                MigrationStep::RemoveCustomIndexes => {
+                    if self.data.has_removed_custom_indexes() {
+                        is_any_field_changed = true;
+                        self.runner.run_custom_index_removed_migration();
+                    }
+                }
+                MigrationStep::AddCustomIndexes => {
+                    if self.data.has_new_custom_indexes() {
+                        is_any_field_changed = true;
+                        self.runner.run_custom_index_added_migration();
                     }
                 }
                 MigrationStep::RemoveGlobalIndexes => {
                     if self.data.has_removed_global_secondary_indexes() {
                         is_any_field_changed = true;
-                        self.runner.run_global_index_removed_migration().await;
+                        self.runner.run_global_index_removed_migration();
                     }
                 }
                 MigrationStep::RemoveLocalIndexes => {
                     if self.data.has_removed_local_secondary_indexes() {
                         is_any_field_changed = true;
-                        self.runner.run_local_index_removed_migration().await;
+                        self.runner.run_local_index_removed_migration();
                     }
                 }
                 MigrationStep::RemoveFields => {
                     if self.data.has_removed_fields() {
                         is_any_field_changed = true;
-                        self.handle_removed_fields().await;
+                        self.handle_removed_fields();
                     }
                 }
             }
         }
 
         if !is_any_field_changed {
-            println!(
+            log_info!(
                 "{} {} {}",
                 "No field changes in".green(),
                 self.data.migration_object_name.bright_yellow(),
@@ -136,40 +179,50 @@ impl<'a> ModelMigration<'a> {
         }
     }
 
-    async fn handle_first_migration(&self) {
-        self.runner.run_first_migration().await;
+    fn handle_first_migration(&self) {
+        self.runner.run_first_migration();
 
         if self.data.has_new_global_secondary_indexes() {
-            self.runner.run_global_index_added_migration().await;
+            self.runner.run_global_index_added_migration();
         }
 
         if self.data.has_new_local_secondary_indexes() {
-            self.runner.run_local_index_added_migration().await;
+            self.runner.run_local_index_added_migration();
+        }
+
+        if self.data.has_new_custom_indexes() {
+            self.runner.run_custom_index_added_migration();
         }
     }
 
-    async fn handle_fields_type_change(&self) {
-        if self.args.drop_and_replace {
+    fn handle_fields_type_change(&self, allow_type_change: bool) {
+        if allow_type_change {
             self.panic_on_mv_fields_change();
             self.panic_on_udt_fields_removal();
 
-            self.runner.run_field_type_changed_migration().await;
+            self.runner.run_field_type_changed_migration();
         } else {
             self.panic_on_field_type_change();
         }
     }
 
-    async fn handle_new_fields(&self) {
+    fn handle_new_fields(&self) {
         self.panic_on_mv_fields_change();
 
-        self.runner.run_field_added_migration().await;
+        self.runner.run_field_added_migration();
     }
 
-    async fn handle_removed_fields(&self) {
+    fn handle_renamed_fields(&self) {
+        self.panic_on_mv_fields_change();
+
+        self.runner.run_field_renamed_migration();
+    }
+
+    fn handle_removed_fields(&self) {
         self.panic_on_mv_fields_change();
         self.panic_on_udt_fields_removal();
 
-        self.runner.run_field_removed_migration().await;
+        self.runner.run_field_removed_migration();
     }
 
     fn panic_on_field_type_change(&self) {
@@ -206,6 +259,18 @@ impl<'a> ModelMigration<'a> {
         }
     }
 
+    fn panic_on_clustering_order_change(&self) {
+        if self.data.migration_object_type != ModelType::Udt && self.data.clustering_order_changed() {
+            panic!(
+                "\n\n{} {} {}\n{}\n\n",
+                "Illegal change in".bright_red(),
+                self.data.migration_object_name.bright_yellow(),
+                self.data.migration_object_type.to_string().bright_magenta(),
+                "Clustering order change is not allowed!".bright_red(),
+            );
+        }
+    }
+
     fn panic_on_udt_fields_removal(&self) {
         if self.data.migration_object_type == ModelType::Udt
             && (self.data.has_removed_fields() || self.data.has_changed_type_fields())
@@ -1,4 +1,5 @@
-use charybdis_parser::schema::{IndexName, SchemaObject};
+use charybdis_parser::schema::secondary_indexes::CustomIndex;
+use charybdis_parser::schema::{normalize_cql_type, IndexName, SchemaObject};
 
 use crate::model::ModelType;
 use crate::model::runner::INDEX_SUFFIX;
@@ -7,6 +8,8 @@ type FieldName = String;
 type FieldType = String;
 type OldFieldType = FieldType;
 type NewFieldType = FieldType;
+type OldFieldName = FieldName;
+type NewFieldName = FieldName;
 
 pub struct ModelData<'a> {
     pub(crate) migration_object_name: &'a String,
@@ -19,7 +22,14 @@ pub struct ModelData<'a> {
     pub(crate) new_local_secondary_indexes: Vec<FieldName>,
     pub(crate) removed_global_secondary_indexes: Vec<IndexName>,
     pub(crate) removed_local_secondary_indexes: Vec<IndexName>,
+    pub(crate) new_custom_indexes: Vec<CustomIndex>,
+    pub(crate) removed_custom_indexes: Vec<IndexName>,
     pub(crate) changed_field_types: Vec<(FieldName, OldFieldType, NewFieldType)>,
+    /// fields renamed in place with `ALTER ... RENAME`, i.e. primary key columns of tables and fields of UDTs
+    pub(crate) renamed_fields: Vec<(OldFieldName, NewFieldName)>,
+    /// renamed regular columns of tables, which can't be renamed in place. New column is added and old one
+    /// is kept, so data can be copied before old column is dropped.
+    pub(crate) copied_fields: Vec<(OldFieldName, NewFieldName)>,
 }
 
 impl<'a> ModelData<'a> {
@@ -40,21 +50,35 @@ impl<'a> ModelData<'a> {
             new_local_secondary_indexes: vec![],
             removed_global_secondary_indexes: vec![],
             removed_local_secondary_indexes: vec![],
+            new_custom_indexes: vec![],
+            removed_custom_indexes: vec![],
             changed_field_types: vec![],
+            renamed_fields: vec![],
+            copied_fields: vec![],
         };
 
+        data.fetch_renamed_fields();
         data.fetch_new_fields();
         data.fetch_removed_fields();
         data.fetch_new_global_secondary_indexes();
         data.fetch_removed_global_secondary_indexes();
         data.fetch_new_local_secondary_indexes();
         data.fetch_removed_local_secondary_indexes();
+        data.fetch_new_custom_indexes();
+        data.fetch_removed_custom_indexes();
         data.fetch_changed_field_types();
 
         data
     }
 
+    /// collection index targets are named by column and function, e.g. `keys(tags)` -> `<table>_tags_keys_idx`
     pub(crate) fn construct_index_name(&self, column_name: &String) -> String {
+        let column_name = column_name.replace('"', "");
+        let column_name = match column_name.split_once('(') {
+            Some((function, column)) => format!("{}_{}", column.replace(')', ""), function),
+            None => column_name,
+        };
+
         format!("{}_{}_{}", self.migration_object_name, column_name, INDEX_SUFFIX)
     }
 
@@ -78,6 +102,14 @@ impl<'a> ModelData<'a> {
         !self.removed_local_secondary_indexes.is_empty()
     }
 
+    pub(crate) fn has_new_custom_indexes(&self) -> bool {
+        !self.new_custom_indexes.is_empty()
+    }
+
+    pub(crate) fn has_removed_custom_indexes(&self) -> bool {
+        !self.removed_custom_indexes.is_empty()
+    }
+
     pub(crate) fn has_new_fields(&self) -> bool {
         !self.new_fields.is_empty()
     }
@@ -86,6 +118,14 @@ impl<'a> ModelData<'a> {
         !self.removed_fields.is_empty()
     }
 
+    pub(crate) fn has_renamed_fields(&self) -> bool {
+        !self.renamed_fields.is_empty()
+    }
+
+    pub(crate) fn has_copied_fields(&self) -> bool {
+        !self.copied_fields.is_empty()
+    }
+
     // Checks if any field of db schema has changed type in code schema.
     pub(crate) fn has_changed_type_fields(&self) -> bool {
         !self.changed_field_types.is_empty()
@@ -93,7 +133,7 @@ impl<'a> ModelData<'a> {
 
     pub(crate) fn partition_key_changed(&self) -> bool {
         let mut code_partition_keys = self.current_code_schema.partition_keys.clone();
-        let mut db_partition_keys = self.current_db_schema.partition_keys.clone();
+        let mut db_partition_keys = self.renamed_keys(&self.current_db_schema.partition_keys);
 
         code_partition_keys.sort();
         db_partition_keys.sort();
@@ -103,7 +143,7 @@ impl<'a> ModelData<'a> {
 
     pub(crate) fn clustering_key_changed(&self) -> bool {
         let mut code_clustering_keys = self.current_code_schema.clustering_keys.clone();
-        let mut db_clustering_keys = self.current_db_schema.clustering_keys.clone();
+        let mut db_clustering_keys = self.renamed_keys(&self.current_db_schema.clustering_keys);
 
         code_clustering_keys.sort();
         db_clustering_keys.sort();
@@ -111,8 +151,61 @@ impl<'a> ModelData<'a> {
         code_clustering_keys != db_clustering_keys
     }
 
+    // clustering order can't be altered, so any difference in order of existing keys is reported
+    pub(crate) fn clustering_order_changed(&self) -> bool {
+        let db_clustering_keys = &self.current_db_schema.clustering_keys;
+        let renamed_clustering_keys = self.renamed_keys(db_clustering_keys);
+
+        db_clustering_keys
+            .iter()
+            .zip(renamed_clustering_keys.iter())
+            .any(|(db_key, code_key)| {
+                self.current_db_schema.clustering_order_of(db_key)
+                    != self.current_code_schema.clustering_order_of(code_key)
+            })
+    }
+
+    // db keys as they will be named after renames are applied
+    fn renamed_keys(&self, db_keys: &[String]) -> Vec<String> {
+        db_keys
+            .iter()
+            .map(|key| {
+                self.renamed_fields
+                    .iter()
+                    .find(|(old_name, _)| old_name == key)
+                    .map_or(key.clone(), |(_, new_name)| new_name.clone())
+            })
+            .collect()
+    }
+
+    // Rename is applied only while old field exists in db and new one doesn't, so `renamed_from` can be
+    // left in code after migration. Old field of copied rename is kept as long as `renamed_from` is declared.
+    fn fetch_renamed_fields(&mut self) {
+        for (old_name, new_name) in self.current_code_schema.renamed_fields.iter() {
+            if !self.current_db_schema.contains_field(old_name) {
+                continue;
+            }
+
+            let is_key = self.current_code_schema.partition_keys.contains(new_name)
+                || self.current_code_schema.clustering_keys.contains(new_name);
+
+            // only primary key columns of tables can be renamed in place
+            if self.migration_object_type == ModelType::Udt || is_key {
+                if !self.current_db_schema.contains_field(new_name) {
+                    self.renamed_fields.push((old_name.clone(), new_name.clone()));
+                }
+            } else {
+                self.copied_fields.push((old_name.clone(), new_name.clone()));
+            }
+        }
+    }
+
     fn fetch_new_fields(&mut self) {
         for (field_name, field_type, _) in self.current_code_schema.fields.iter() {
+            if self.renamed_fields.iter().any(|(_, new_name)| new_name == field_name) {
+                continue;
+            }
+
             if !self.current_db_schema.contains_field(field_name) {
                 self.new_fields.push((field_name.clone(), field_type.clone()));
             }
@@ -121,7 +214,13 @@ impl<'a> ModelData<'a> {
 
     fn fetch_removed_fields(&mut self) {
         for (field_name, _, _) in self.current_db_schema.fields.iter() {
-            if !self.current_code_schema.contains_field(field_name) {
+            let is_renamed = self
+                .renamed_fields
+                .iter()
+                .chain(self.copied_fields.iter())
+                .any(|(old_name, _)| old_name == field_name);
+
+            if !is_renamed && !self.current_code_schema.contains_field(field_name) {
                 self.removed_fields.push(field_name.clone());
             }
         }
@@ -195,11 +294,46 @@ impl<'a> ModelData<'a> {
             });
     }
 
+    // custom index is considered changed if class or options differ, so it's dropped and recreated
+    fn fetch_new_custom_indexes(&mut self) {
+        let _ = &self
+            .current_code_schema
+            .custom_indexes
+            .iter()
+            .for_each(|(_index_name, custom_index)| {
+                if !self
+                    .current_db_schema
+                    .custom_indexes
+                    .iter()
+                    .any(|(_, db_custom_index)| db_custom_index == custom_index)
+                {
+                    self.new_custom_indexes.push(custom_index.clone());
+                }
+            });
+    }
+
+    fn fetch_removed_custom_indexes(&mut self) {
+        let _ = &self
+            .current_db_schema
+            .custom_indexes
+            .iter()
+            .for_each(|(index_name, db_custom_index)| {
+                if !self
+                    .current_code_schema
+                    .custom_indexes
+                    .iter()
+                    .any(|(_index_name, custom_index)| custom_index == db_custom_index)
+                {
+                    self.removed_custom_indexes.push(index_name.clone());
+                }
+            });
+    }
+
     fn fetch_changed_field_types(&mut self) {
         for (field_name, field_type, _) in self.current_code_schema.fields.iter() {
             if let Some(db_field_type) = self.current_db_schema.types_by_name.get(field_name) {
-                let code_field_type = field_type.to_lowercase().replace(' ', "");
-                let db_field_type = db_field_type.to_lowercase().replace(' ', "");
+                let code_field_type = normalize_cql_type(field_type);
+                let db_field_type = normalize_cql_type(db_field_type);
 
                 if code_field_type != db_field_type {
                     self.changed_field_types
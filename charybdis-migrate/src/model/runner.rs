@@ -1,44 +1,46 @@
-use crate::args::Args;
+use std::cell::RefCell;
+
 use crate::model::{ModelData, ModelType};
+use crate::plan::{PlanStep, StepKind};
+use charybdis_parser::identifier::quote_identifier;
+use charybdis_parser::log_info;
+use charybdis_parser::schema::SchemaObject;
 use colored::*;
 use regex::Regex;
-use scylla::Session;
 
 pub(crate) const INDEX_SUFFIX: &str = "idx";
 
 pub(crate) struct ModelRunner<'a> {
-    session: &'a Session,
     data: &'a ModelData<'a>,
-    args: &'a Args,
+    steps: RefCell<Vec<PlanStep>>,
 }
 
 impl<'a> ModelRunner<'a> {
-    pub fn new(session: &'a Session, data: &'a ModelData, args: &'a Args) -> Self {
-        Self { session, data, args }
-    }
-
-    async fn execute(&self, cql: &String, print: bool) {
-        if print {
-            println!("{} {}", "Running CQL:".on_bright_green().black(), cql.bright_purple());
+    pub fn new(data: &'a ModelData) -> Self {
+        Self {
+            data,
+            steps: RefCell::new(vec![]),
         }
+    }
 
-        // remove all colors from cql string
-        let stripped = strip_ansi_escapes::strip(cql.as_bytes());
-        let cql: String = String::from_utf8(stripped).unwrap();
-        let res = self.session.query_unpaged(cql.clone(), ()).await;
+    /// Planned steps in order they were added.
+    pub(crate) fn into_steps(self) -> Vec<PlanStep> {
+        self.steps.into_inner()
+    }
 
-        match res {
-            Ok(_) => {
-                if print {
-                    println!("{}\n", "CQL executed successfully! ✅".bright_green());
-                }
-            }
-            Err(e) => panic!("{} {}\n", "CQL execution failed! ❌".bright_red(), e),
-        }
+    /// Adds DDL statement to the plan together with `rollback` statement that reverts it.
+    fn step(&self, kind: StepKind, cql: String, rollback: String, print: bool) {
+        self.steps.borrow_mut().push(PlanStep {
+            object_name: self.data.migration_object_name.to_string(),
+            kind,
+            cql,
+            rollback,
+            verbose_only: !print,
+        });
     }
 
-    pub(crate) async fn run_first_migration(&self) {
-        println!(
+    pub(crate) fn run_first_migration(&self) {
+        log_info!(
             "\n{} {} {}!",
             "Detected first migration for:".bright_cyan(),
             self.data.migration_object_name.bright_yellow(),
@@ -52,75 +54,66 @@ impl<'a> ModelRunner<'a> {
                     self.data.migration_object_name,
                     self.data.current_code_schema.create_fields_clause()
                 );
+                let rollback = format!("DROP TYPE IF EXISTS {}", self.data.migration_object_name);
 
-                self.execute(&cql, true).await;
+                self.step(StepKind::CreateModel, cql, rollback, true);
             }
             ModelType::Table => {
-                let clustering_keys = self.data.current_code_schema.clustering_keys.join(", ");
+                let clustering_keys = cql_names(self.data.current_code_schema.clustering_keys.iter());
                 let clustering_keys_clause = if !clustering_keys.is_empty() {
                     format!(",{}", clustering_keys)
                 } else {
                     "".to_string()
                 };
 
-                let table_options = &self.data.current_code_schema.table_options;
-                let mut table_options_clause = String::new();
-
-                if let Some(table_options) = table_options {
-                    table_options_clause = format!("WITH {}", table_options);
-                }
+                let table_options_clause = table_options_clause(self.data.current_code_schema);
 
                 let cql = format!(
                     "CREATE TABLE IF NOT EXISTS {}\n(\n{}, \n    PRIMARY KEY (({}) {})\n) \n {}",
                     self.data.migration_object_name,
                     self.data.current_code_schema.create_fields_clause(),
-                    self.data.current_code_schema.partition_keys.join(", "),
+                    cql_names(self.data.current_code_schema.partition_keys.iter()),
                     clustering_keys_clause,
                     table_options_clause,
                 );
+                let rollback = format!("DROP TABLE IF EXISTS {}", self.data.migration_object_name);
 
-                self.execute(&cql, true).await;
+                self.step(StepKind::CreateModel, cql, rollback, true);
             }
             ModelType::MaterializedView => {
                 let mut primary_key = self.data.current_code_schema.partition_keys.clone();
                 primary_key.append(&mut self.data.current_code_schema.clustering_keys.clone());
 
-                let table_options = &self.data.current_code_schema.table_options;
-                let mut table_options_clause = String::new();
-
-                if let Some(table_options) = table_options {
-                    table_options_clause = format!("WITH {}", table_options);
-                }
+                let table_options_clause = table_options_clause(self.data.current_code_schema);
 
                 let materialized_view_where_clause = format!(
                     "WHERE {}",
                     primary_key
                         .iter()
-                        .map(|field| format!("{} IS NOT NULL", field))
+                        .map(|field| format!("{} IS NOT NULL", quote_identifier(field)))
                         .collect::<Vec<String>>()
                         .join(" AND ")
                 );
 
-                let mv_fields_without_types = self
-                    .data
-                    .current_code_schema
-                    .fields
-                    .clone()
-                    .into_iter()
-                    .map(|(field_name, _, _)| field_name)
-                    .collect::<Vec<String>>();
+                let mv_fields_without_types = cql_names(
+                    self.data
+                        .current_code_schema
+                        .fields
+                        .iter()
+                        .map(|(field_name, _, _)| field_name),
+                );
 
                 let materialized_view_select_clause = format!(
                     "SELECT {} \nFROM {}\n{}",
-                    mv_fields_without_types.join(", "),
+                    mv_fields_without_types,
                     self.data.current_code_schema.base_table.clone(),
                     materialized_view_where_clause
                 );
 
                 let primary_key_clause = format!(
                     "PRIMARY KEY (({}), {})\n",
-                    self.data.current_code_schema.partition_keys.join(", "),
-                    self.data.current_code_schema.clustering_keys.join(", ")
+                    cql_names(self.data.current_code_schema.partition_keys.iter()),
+                    cql_names(self.data.current_code_schema.clustering_keys.iter())
                 );
 
                 let cql = format!(
@@ -130,14 +123,15 @@ impl<'a> ModelRunner<'a> {
                     primary_key_clause,
                     table_options_clause
                 );
+                let rollback = format!("DROP MATERIALIZED VIEW IF EXISTS {}", self.data.migration_object_name);
 
-                self.execute(&cql, true).await;
+                self.step(StepKind::CreateModel, cql, rollback, true);
             }
         }
     }
 
-    pub(crate) async fn run_field_added_migration(&self) {
-        println!(
+    pub(crate) fn run_field_added_migration(&self) {
+        log_info!(
             "\n{} {} {}",
             "Detected new fields in".bright_cyan(),
             self.data.migration_object_name.bright_blue(),
@@ -145,18 +139,18 @@ impl<'a> ModelRunner<'a> {
         );
 
         if self.data.migration_object_type == ModelType::Table {
-            self.run_table_field_added_migration().await;
+            self.run_table_field_added_migration();
         } else {
-            self.run_udt_field_added_migration().await;
+            self.run_udt_field_added_migration();
         }
     }
 
-    async fn run_table_field_added_migration(&self) {
+    fn run_table_field_added_migration(&self) {
         let add_fields_clause = self
             .data
             .new_fields
             .iter()
-            .map(|(field_name, field_type)| format!("{} {}", field_name, field_type))
+            .map(|(field_name, field_type)| format!("{} {}", quote_identifier(field_name), field_type))
             .collect::<Vec<String>>()
             .join(", ");
 
@@ -164,41 +158,113 @@ impl<'a> ModelRunner<'a> {
             "ALTER {} {} ADD ({})",
             self.data.migration_object_type, self.data.migration_object_name, add_fields_clause,
         );
+        let rollback = format!(
+            "ALTER {} {} DROP ({})",
+            self.data.migration_object_type,
+            self.data.migration_object_name,
+            cql_names(self.data.new_fields.iter().map(|(field_name, _)| field_name)),
+        );
 
-        self.execute(&cql, true).await;
+        self.step(StepKind::AddFields, cql, rollback, true);
     }
 
-    async fn run_udt_field_added_migration(&self) {
+    fn run_udt_field_added_migration(&self) {
         for (field_name, field_type) in self.data.new_fields.iter() {
             let cql = format!(
                 "ALTER TYPE {} ADD {} {}",
-                self.data.migration_object_name, field_name, field_type
+                self.data.migration_object_name,
+                quote_identifier(field_name),
+                field_type
+            );
+            let rollback = format!(
+                "-- irreversible: field {} can't be removed from type {}",
+                field_name, self.data.migration_object_name
             );
 
-            self.execute(&cql, true).await;
+            self.step(StepKind::AddFields, cql, rollback, true);
         }
     }
 
-    pub(crate) async fn run_field_removed_migration(&self) {
-        println!(
+    pub(crate) fn run_field_renamed_migration(&self) {
+        log_info!(
+            "\n{} {} {}",
+            "Detected renamed fields in".bright_cyan(),
+            self.data.migration_object_name.bright_yellow(),
+            self.data.migration_object_type.to_string().bright_yellow()
+        );
+
+        let object_type = match self.data.migration_object_type {
+            ModelType::Udt => "TYPE",
+            _ => "TABLE",
+        };
+
+        for (old_name, new_name) in self.data.renamed_fields.iter() {
+            let cql = format!(
+                "ALTER {} {} RENAME {} TO {}",
+                object_type,
+                self.data.migration_object_name,
+                quote_identifier(old_name),
+                quote_identifier(new_name)
+            );
+            let rollback = format!(
+                "ALTER {} {} RENAME {} TO {}",
+                object_type,
+                self.data.migration_object_name,
+                quote_identifier(new_name),
+                quote_identifier(old_name)
+            );
+
+            self.step(StepKind::RenameFields, cql, rollback, true);
+        }
+    }
+
+    /// Regular columns can't be renamed in place, so new column is added with other new fields and old one
+    /// is kept until data is copied by application.
+    pub(crate) fn log_field_copy_guidance(&self) {
+        for (old_name, new_name) in self.data.copied_fields.iter() {
+            log_info!(
+                "\n{} {}.{} {} {}\n{}",
+                "Renamed field".bright_cyan(),
+                self.data.migration_object_name.bright_yellow(),
+                old_name.bright_yellow(),
+                "->".bright_cyan(),
+                new_name.bright_yellow(),
+                format!(
+                    "Only primary key columns can be renamed in place. Column {} is kept while `renamed_from` is \
+                    declared, copy data from {} to {} and remove `renamed_from` to drop {} in next migration.",
+                    old_name, old_name, new_name, old_name
+                )
+                .bright_magenta()
+            );
+        }
+    }
+
+    pub(crate) fn run_field_removed_migration(&self) {
+        log_info!(
             "\n{} {} {}",
             "Detected removed fields in".bright_cyan(),
             self.data.migration_object_name.bright_yellow(),
             self.data.migration_object_type.to_string().bright_yellow()
         );
 
-        let removed_fields = self.data.removed_fields.join(", ");
+        let removed_fields = cql_names(self.data.removed_fields.iter());
 
         let cql = format!(
             "ALTER {} {} DROP ({})",
             self.data.migration_object_type, self.data.migration_object_name, removed_fields,
         );
+        let rollback = format!(
+            "-- data of dropped columns can't be restored\nALTER {} {} ADD ({});",
+            self.data.migration_object_type,
+            self.data.migration_object_name,
+            self.db_fields_clause(self.data.removed_fields.iter()),
+        );
 
-        self.execute(&cql, true).await;
+        self.step(StepKind::DropFields, cql, rollback, true);
     }
 
-    pub(crate) async fn run_field_type_changed_migration(&self) {
-        println!(
+    pub(crate) fn run_field_type_changed_migration(&self) {
+        log_info!(
             "{}",
             "Field Type Change Migration (Drop and replace):"
                 .on_bright_green()
@@ -206,26 +272,36 @@ impl<'a> ModelRunner<'a> {
         );
 
         // remove fields with changed types
-        let changed_fields = self
-            .data
-            .changed_field_types
-            .iter()
-            .map(|(field_name, _, _)| field_name.clone())
-            .collect::<Vec<String>>()
-            .join(", ");
+        let changed_fields = cql_names(
+            self.data
+                .changed_field_types
+                .iter()
+                .map(|(field_name, _, _)| field_name),
+        );
 
         let cql = format!(
             "ALTER {} {} DROP ({})",
             self.data.migration_object_type, self.data.migration_object_name, changed_fields,
         );
+        let rollback = format!(
+            "-- data of dropped columns can't be restored\nALTER {} {} ADD ({});",
+            self.data.migration_object_type,
+            self.data.migration_object_name,
+            self.db_fields_clause(
+                self.data
+                    .changed_field_types
+                    .iter()
+                    .map(|(field_name, _, _)| field_name)
+            ),
+        );
 
-        self.execute(&cql, true).await;
+        self.step(StepKind::ChangeFieldTypes, cql, rollback, true);
 
         let add_fields_clause = self
             .data
             .changed_field_types
             .iter()
-            .map(|(field_name, _, field_type)| format!("{} {}", field_name, field_type))
+            .map(|(field_name, _, field_type)| format!("{} {}", quote_identifier(field_name), field_type))
             .collect::<Vec<String>>()
             .join(", ");
 
@@ -233,12 +309,16 @@ impl<'a> ModelRunner<'a> {
             "ALTER {} {} ADD ({})",
             self.data.migration_object_type, self.data.migration_object_name, add_fields_clause,
         );
+        let rollback = format!(
+            "ALTER {} {} DROP ({})",
+            self.data.migration_object_type, self.data.migration_object_name, changed_fields,
+        );
 
-        self.execute(&cql, true).await;
+        self.step(StepKind::ChangeFieldTypes, cql, rollback, true);
     }
 
-    pub(crate) async fn run_global_index_added_migration(&self) {
-        println!(
+    pub(crate) fn run_global_index_added_migration(&self) {
+        log_info!(
             "\n{} {} {}",
             "Detected new indexes in ".bright_cyan(),
             self.data.migration_object_name.bright_yellow(),
@@ -252,13 +332,14 @@ impl<'a> ModelRunner<'a> {
                 "CREATE INDEX IF NOT EXISTS {} ON {} ({})",
                 index_name, self.data.migration_object_name, column_name,
             );
+            let rollback = format!("DROP INDEX IF EXISTS {}", index_name);
 
-            self.execute(&cql, true).await;
+            self.step(StepKind::AddIndex, cql, rollback, true);
         }
     }
 
-    pub(crate) async fn run_global_index_removed_migration(&self) {
-        println!(
+    pub(crate) fn run_global_index_removed_migration(&self) {
+        log_info!(
             "\n{} {} {}",
             "Detected removed indexes for ".bright_cyan(),
             self.data.migration_object_name.bright_yellow(),
@@ -267,13 +348,26 @@ impl<'a> ModelRunner<'a> {
 
         for index in &self.data.removed_global_secondary_indexes {
             let cql = format!("DROP INDEX {}", index,);
-
-            self.execute(&cql, true).await;
+            let rollback = self
+                .data
+                .current_db_schema
+                .global_secondary_indexes
+                .iter()
+                .find(|(index_name, _)| index_name == index)
+                .map(|(_, target)| {
+                    format!(
+                        "CREATE INDEX IF NOT EXISTS {} ON {} ({})",
+                        index, self.data.migration_object_name, target
+                    )
+                })
+                .unwrap_or_else(|| format!("-- definition of index {} not found", index));
+
+            self.step(StepKind::DropIndex, cql, rollback, true);
         }
     }
 
-    pub(crate) async fn run_local_index_added_migration(&self) {
-        println!(
+    pub(crate) fn run_local_index_added_migration(&self) {
+        log_info!(
             "\n{} {} {}",
             "Detected new local indexes in ".bright_cyan(),
             self.data.migration_object_name.bright_yellow(),
@@ -288,18 +382,19 @@ impl<'a> ModelRunner<'a> {
             idx_name.push_str(local_secondary_index);
 
             let index_name: String = self.data.construct_index_name(&idx_name);
-            let pks = partition_keys.join(", ");
+            let pks = cql_names(partition_keys.iter());
             let cql = format!(
                 "CREATE INDEX IF NOT EXISTS {} ON {} (({}), {})",
                 index_name, self.data.migration_object_name, pks, local_secondary_index,
             );
+            let rollback = format!("DROP INDEX IF EXISTS {}", index_name);
 
-            self.execute(&cql, true).await;
+            self.step(StepKind::AddIndex, cql, rollback, true);
         }
     }
 
-    pub(crate) async fn run_local_index_removed_migration(&self) {
-        println!(
+    pub(crate) fn run_local_index_removed_migration(&self) {
+        log_info!(
             "\n{} {} {}",
             "Detected removed local indexes for ".bright_cyan(),
             self.data.migration_object_name.bright_yellow(),
@@ -308,12 +403,87 @@ impl<'a> ModelRunner<'a> {
 
         for index in &self.data.removed_local_secondary_indexes {
             let cql = format!("DROP INDEX {}", index,);
+            let rollback = self
+                .data
+                .current_db_schema
+                .local_secondary_indexes
+                .iter()
+                .find(|(index_name, _)| index_name == index)
+                .map(|(_, target)| {
+                    format!(
+                        "CREATE INDEX IF NOT EXISTS {} ON {} (({}), {})",
+                        index,
+                        self.data.migration_object_name,
+                        cql_names(self.data.current_db_schema.partition_keys.iter()),
+                        target
+                    )
+                })
+                .unwrap_or_else(|| format!("-- definition of index {} not found", index));
+
+            self.step(StepKind::DropIndex, cql, rollback, true);
+        }
+    }
+
+    pub(crate) fn run_custom_index_added_migration(&self) {
+        log_info!(
+            "\n{} {} {}",
+            "Detected new custom indexes in ".bright_cyan(),
+            self.data.migration_object_name.bright_yellow(),
+            self.data.migration_object_type.to_string().bright_yellow()
+        );
+
+        for custom_index in &self.data.new_custom_indexes {
+            let index_name: String = self
+                .data
+                .construct_index_name(&format!("{}_custom", custom_index.target));
 
-            self.execute(&cql, true).await;
+            let cql = format!(
+                "CREATE CUSTOM INDEX IF NOT EXISTS {} ON {} ({}) USING '{}'{}",
+                index_name,
+                self.data.migration_object_name,
+                custom_index.target,
+                custom_index.class_name,
+                custom_index.options_clause(),
+            );
+            let rollback = format!("DROP INDEX IF EXISTS {}", index_name);
+
+            self.step(StepKind::AddIndex, cql, rollback, true);
         }
     }
 
-    pub(crate) async fn run_table_options_change_migration(&self) {
+    pub(crate) fn run_custom_index_removed_migration(&self) {
+        log_info!(
+            "\n{} {} {}",
+            "Detected removed custom indexes for ".bright_cyan(),
+            self.data.migration_object_name.bright_yellow(),
+            self.data.migration_object_type.to_string().bright_yellow()
+        );
+
+        for index in &self.data.removed_custom_indexes {
+            let cql = format!("DROP INDEX {}", index,);
+            let rollback = self
+                .data
+                .current_db_schema
+                .custom_indexes
+                .iter()
+                .find(|(index_name, _)| index_name == index)
+                .map(|(_, custom_index)| {
+                    format!(
+                        "CREATE CUSTOM INDEX IF NOT EXISTS {} ON {} ({}) USING '{}'{}",
+                        index,
+                        self.data.migration_object_name,
+                        custom_index.target,
+                        custom_index.class_name,
+                        custom_index.options_clause(),
+                    )
+                })
+                .unwrap_or_else(|| format!("-- definition of index {} not found", index));
+
+            self.step(StepKind::DropIndex, cql, rollback, true);
+        }
+    }
+
+    pub(crate) fn run_table_options_change_migration(&self) {
         if self.data.migration_object_type == ModelType::Table
             || self.data.migration_object_type == ModelType::MaterializedView
         {
@@ -323,11 +493,34 @@ impl<'a> ModelRunner<'a> {
                     self.data.migration_object_name, alter_table_options
                 );
 
-                self.execute(&cql, self.args.verbose).await;
+                let rollback = format!(
+                    "-- previous table options of {} are not tracked",
+                    self.data.migration_object_name
+                );
+
+                self.step(StepKind::ChangeTableOptions, cql, rollback, false);
             }
         }
     }
 
+    /// e.g. `title text, tags set<text>` with types of given fields in db schema
+    fn db_fields_clause<'b>(&self, field_names: impl Iterator<Item = &'b String>) -> String {
+        field_names
+            .map(|field_name| {
+                let field_type = self
+                    .data
+                    .current_db_schema
+                    .types_by_name
+                    .get(field_name)
+                    .map(|field_type| field_type.as_str())
+                    .unwrap_or_default();
+
+                format!("{} {}", quote_identifier(field_name), field_type)
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
     fn extract_alter_table_options(&self) -> Option<String> {
         // strip clustering order and compact storage options from table options
         // because they are not supported by ALTER TABLE
@@ -349,3 +542,28 @@ impl<'a> ModelRunner<'a> {
         }
     }
 }
+
+/// `WITH` clause of declared clustering order and table options, empty if neither is declared.
+fn table_options_clause(schema: &SchemaObject) -> String {
+    let options = [
+        schema.clustering_order_clause(),
+        schema.table_options.clone().unwrap_or_default(),
+    ]
+    .into_iter()
+    .filter(|option| !option.trim().is_empty())
+    .collect::<Vec<String>>();
+
+    if options.is_empty() {
+        return String::new();
+    }
+
+    format!("WITH {}", options.join(" AND "))
+}
+
+/// e.g. `id, "CamelCase"` for given column names
+fn cql_names<'b>(names: impl Iterator<Item = &'b String>) -> String {
+    names
+        .map(|name| quote_identifier(name))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
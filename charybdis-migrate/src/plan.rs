@@ -0,0 +1,135 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::lint::LintWarning;
+
+/// Whether applying the step can lose data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Safety {
+    Safe,
+    Destructive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepKind {
+    CreateModel,
+    ChangeTableOptions,
+    AddFields,
+    /// Drop and re-add of fields with changed type. Both statements are destructive, as re-add
+    /// can't be applied without preceding drop.
+    ChangeFieldTypes,
+    DropFields,
+    /// `ALTER ... RENAME` of primary key columns of tables and fields of UDTs
+    RenameFields,
+    AddIndex,
+    DropIndex,
+}
+
+impl StepKind {
+    pub fn safety(&self) -> Safety {
+        match self {
+            StepKind::ChangeFieldTypes | StepKind::DropFields | StepKind::DropIndex => Safety::Destructive,
+            _ => Safety::Safe,
+        }
+    }
+}
+
+/// Single DDL statement of migration plan, together with best-effort statement that reverts it.
+#[derive(Debug, Clone)]
+pub struct PlanStep {
+    pub object_name: String,
+    pub kind: StepKind,
+    pub cql: String,
+    pub rollback: String,
+    /// statement is logged only in verbose mode
+    pub(crate) verbose_only: bool,
+}
+
+impl PlanStep {
+    pub fn safety(&self) -> Safety {
+        self.kind.safety()
+    }
+
+    pub fn is_destructive(&self) -> bool {
+        self.safety() == Safety::Destructive
+    }
+}
+
+impl fmt::Display for PlanStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:?}] {}: {}", self.safety(), self.object_name, self.cql.trim())
+    }
+}
+
+/// Statements that bring database schema to the schema of models, in order they are applied.
+/// It's built by [Migration::plan](crate::migration::Migration::plan) without touching the database,
+/// so it can be inspected before [Migration::apply](crate::migration::Migration::apply).
+#[derive(Debug, Clone, Default)]
+pub struct MigrationPlan {
+    steps: Vec<PlanStep>,
+    warnings: Vec<LintWarning>,
+}
+
+impl MigrationPlan {
+    pub fn steps(&self) -> &[PlanStep] {
+        &self.steps
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn destructive_steps(&self) -> impl Iterator<Item = &PlanStep> {
+        self.steps.iter().filter(|step| step.is_destructive())
+    }
+
+    pub fn has_destructive_steps(&self) -> bool {
+        self.destructive_steps().next().is_some()
+    }
+
+    /// Anti-patterns found in schema of models, e.g. unbounded partitions. They are logged before plan is
+    /// applied, but don't block it.
+    pub fn warnings(&self) -> &[LintWarning] {
+        &self.warnings
+    }
+
+    pub(crate) fn extend(&mut self, steps: Vec<PlanStep>) {
+        self.steps.extend(steps);
+    }
+
+    pub(crate) fn extend_warnings(&mut self, warnings: Vec<LintWarning>) {
+        self.warnings.extend(warnings);
+    }
+}
+
+/// Decides what happens with destructive steps of applied plan.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ApplyPolicy {
+    /// Nothing is applied if plan contains destructive step.
+    #[default]
+    FailOnDestructive,
+    /// Safe steps are applied, destructive steps are logged and skipped.
+    SkipDestructive,
+    AllowAll,
+}
+
+/// Returned by [Migration::apply](crate::migration::Migration::apply) with `FailOnDestructive` policy,
+/// before any statement is executed.
+#[derive(Debug)]
+pub struct DestructiveChangesError {
+    pub steps: Vec<PlanStep>,
+}
+
+impl fmt::Display for DestructiveChangesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Migration plan contains {} destructive steps:", self.steps.len())?;
+
+        for step in &self.steps {
+            writeln!(f, "{}", step)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for DestructiveChangesError {}
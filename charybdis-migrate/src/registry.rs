@@ -0,0 +1,85 @@
+use charybdis::registry::{ModelDefinition, ModelKind};
+use charybdis_parser::schema::code_schema::{CodeSchema, ModelMacro};
+use charybdis_parser::schema::secondary_indexes::CustomIndex;
+use charybdis_parser::schema::{ClusteringOrder, SchemaObject};
+
+/// Builds code schema from models registered by charybdis macros, instead of parsing source files.
+/// Only models linked into current binary are included.
+pub(crate) fn code_schema_from_registry() -> CodeSchema {
+    let mut code_schema = CodeSchema::default();
+
+    for model in charybdis::registry::models() {
+        let model_macro = match model.kind {
+            ModelKind::Table => ModelMacro::Table,
+            ModelKind::MaterializedView => ModelMacro::MaterializedView,
+            ModelKind::Udt => ModelMacro::Udt,
+        };
+
+        code_schema.insert(model_macro, schema_object(model));
+    }
+
+    code_schema
+}
+
+fn schema_object(model: &ModelDefinition) -> SchemaObject {
+    let mut schema_object = SchemaObject {
+        type_name: model.type_name.to_string(),
+        table_name: model.table_name.to_string(),
+        base_table: model.base_table.to_string(),
+        partition_keys: to_strings(model.partition_keys),
+        clustering_keys: to_strings(model.clustering_keys),
+        clustering_order: model
+            .clustering_order
+            .iter()
+            .filter_map(|(key, order)| Some((key.to_string(), ClusteringOrder::parse(order)?)))
+            .collect(),
+        static_columns: model
+            .fields
+            .iter()
+            .filter(|field| field.is_static)
+            .map(|field| field.name.to_string())
+            .collect(),
+        global_secondary_indexes: indexes(model.global_secondary_indexes),
+        local_secondary_indexes: indexes(model.local_secondary_indexes),
+        custom_indexes: model
+            .custom_indexes
+            .iter()
+            .map(|custom_index| {
+                let custom_index = CustomIndex {
+                    target: custom_index.target.to_string(),
+                    class_name: custom_index.class_name.to_string(),
+                    options: custom_index
+                        .options
+                        .iter()
+                        .map(|(key, value)| (key.to_string(), value.to_string()))
+                        .collect(),
+                };
+
+                ("".to_string(), custom_index)
+            })
+            .collect(),
+        table_options: model.table_options.map(str::to_string),
+        renamed_fields: model
+            .fields
+            .iter()
+            .filter_map(|field| Some((field.renamed_from?.to_string(), field.name.to_string())))
+            .collect(),
+        renamed_from: model.renamed_from.map(str::to_string),
+        ..Default::default()
+    };
+
+    for field in model.fields {
+        schema_object.push_field(field.name.to_string(), field.cql_type.to_string(), field.is_static);
+    }
+
+    schema_object
+}
+
+fn to_strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+// index names are not known in code, same as in parsed code schema
+fn indexes(fields: &[&str]) -> Vec<(String, String)> {
+    fields.iter().map(|field| ("".to_string(), field.to_string())).collect()
+}
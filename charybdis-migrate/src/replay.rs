@@ -0,0 +1,156 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use colored::Colorize;
+use scylla::Session;
+
+use charybdis_parser::log_info;
+
+pub(crate) const DDL_LOG_TABLE: &str = "charybdis_ddl_log";
+
+struct ReplayEntry {
+    object_name: String,
+    statement: String,
+    rollback: String,
+}
+
+/// Log of DDL statements applied within single migration run. Each statement is stored with
+/// best-effort inverse statement, so applied migration can be reverted manually. Statements that
+/// can't be reverted (e.g. data of dropped columns) are represented by comment in rollback script.
+///
+/// After each applied statement, log is persisted to:
+/// - `<dir>/<run_id>_applied.cql` with statements in order they were applied
+/// - `<dir>/<run_id>_rollback.cql` with inverse statements in reverse order
+/// - `charybdis_ddl_log` table in migrated keyspace
+///
+/// where `run_id` is unix timestamp of migration start.
+pub(crate) struct ReplayLog {
+    run_id: i64,
+    dir: Option<PathBuf>,
+    keyspace: String,
+    entries: Mutex<Vec<ReplayEntry>>,
+}
+
+impl ReplayLog {
+    pub(crate) fn new(dir: Option<&String>, keyspace: &str) -> Self {
+        let run_id = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or_default();
+
+        Self {
+            run_id,
+            dir: dir.map(PathBuf::from),
+            keyspace: keyspace.to_string(),
+            entries: Mutex::new(vec![]),
+        }
+    }
+
+    pub(crate) async fn init(&self, session: &Session) {
+        let Some(dir) = &self.dir else {
+            return;
+        };
+
+        fs::create_dir_all(dir)
+            .unwrap_or_else(|e| panic!("Failed to create replay log directory {}: {}", dir.display(), e));
+
+        let cql = format!(
+            "CREATE TABLE IF NOT EXISTS {}.{} (
+                run_id bigint,
+                seq int,
+                object_name text,
+                statement text,
+                rollback text,
+                applied_at timestamp,
+                PRIMARY KEY ((run_id), seq)
+            )",
+            self.keyspace, DDL_LOG_TABLE
+        );
+
+        session
+            .query_unpaged(cql, ())
+            .await
+            .unwrap_or_else(|e| panic!("Failed to create {} table: {}", DDL_LOG_TABLE, e));
+    }
+
+    pub(crate) async fn record(&self, session: &Session, object_name: &str, statement: &str, rollback: &str) {
+        let Some(dir) = &self.dir else {
+            return;
+        };
+
+        let seq = {
+            let mut entries = self.entries.lock().unwrap();
+
+            entries.push(ReplayEntry {
+                object_name: object_name.to_string(),
+                statement: statement.trim().to_string(),
+                rollback: rollback.trim().to_string(),
+            });
+
+            self.write_scripts(dir, &entries);
+
+            entries.len() as i32
+        };
+
+        let cql = format!(
+            "INSERT INTO {}.{} (run_id, seq, object_name, statement, rollback, applied_at) \
+            VALUES (?, ?, ?, ?, ?, toTimestamp(now()))",
+            self.keyspace, DDL_LOG_TABLE
+        );
+
+        session
+            .query_unpaged(cql, (self.run_id, seq, object_name, statement.trim(), rollback.trim()))
+            .await
+            .unwrap_or_else(|e| panic!("Failed to record applied statement in {}: {}", DDL_LOG_TABLE, e));
+    }
+
+    fn write_scripts(&self, dir: &Path, entries: &[ReplayEntry]) {
+        let applied = entries
+            .iter()
+            .map(|entry| format!("-- {}\n{};\n", entry.object_name, entry.statement))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let rollback = entries
+            .iter()
+            .rev()
+            .map(|entry| {
+                if entry.rollback.starts_with("--") {
+                    format!("-- {}\n{}\n", entry.object_name, entry.rollback)
+                } else {
+                    format!("-- {}\n{};\n", entry.object_name, entry.rollback)
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let applied_path = dir.join(format!("{}_applied.cql", self.run_id));
+        let rollback_path = dir.join(format!("{}_rollback.cql", self.run_id));
+
+        fs::write(&applied_path, applied)
+            .unwrap_or_else(|e| panic!("Failed to write {}: {}", applied_path.display(), e));
+        fs::write(&rollback_path, rollback)
+            .unwrap_or_else(|e| panic!("Failed to write {}: {}", rollback_path.display(), e));
+    }
+
+    pub(crate) fn print_summary(&self) {
+        let Some(dir) = &self.dir else {
+            return;
+        };
+
+        let entries = self.entries.lock().unwrap();
+
+        if entries.is_empty() {
+            return;
+        }
+
+        log_info!(
+            "{} {} {}",
+            "Replay log written to".bright_cyan(),
+            dir.join(format!("{}_applied.cql", self.run_id)).display(),
+            dir.join(format!("{}_rollback.cql", self.run_id)).display()
+        );
+    }
+}
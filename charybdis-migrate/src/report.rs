@@ -0,0 +1,302 @@
+use std::fs;
+
+use charybdis_parser::schema::SchemaObject;
+
+use crate::lint::LintWarning;
+use crate::model::data::ModelData;
+
+const STYLE: &str = r#"
+body { font-family: sans-serif; margin: 2rem; color: #222; }
+h1 { margin-bottom: 0.25rem; }
+section { border: 1px solid #ddd; border-radius: 6px; padding: 1rem; margin: 1rem 0; }
+section.destructive { border-color: #d33; }
+table { border-collapse: collapse; margin: 0.5rem 0; }
+th, td { border: 1px solid #ddd; padding: 0.25rem 0.75rem; text-align: left; font-family: monospace; }
+th { background: #f5f5f5; }
+tr.added { background: #e6ffed; }
+tr.removed, tr.type-changed { background: #ffeef0; }
+li.added { color: #22863a; }
+li.removed, li.blocked { color: #d33; }
+.badge { font-size: 0.75rem; padding: 0.1rem 0.4rem; border-radius: 4px; background: #eee; margin-left: 0.5rem; }
+.badge.destructive { background: #d33; color: #fff; }
+.badge.new { background: #22863a; color: #fff; }
+section.warnings { border-color: #e3b341; }
+li.warning { color: #9a6700; }
+"#;
+
+/// Standalone HTML report of planned schema changes, grouped by migration object.
+/// Destructive changes (dropped columns or indexes, drop and replace of columns and key changes) are highlighted.
+pub(crate) struct HtmlReport {
+    keyspace: String,
+    sections: Vec<String>,
+    destructive_changes: usize,
+    warnings: Vec<LintWarning>,
+}
+
+impl HtmlReport {
+    pub(crate) fn new(keyspace: &str) -> Self {
+        Self {
+            keyspace: keyspace.to_string(),
+            sections: vec![],
+            destructive_changes: 0,
+            warnings: vec![],
+        }
+    }
+
+    /// Warnings are listed for all models, including models without changes.
+    pub(crate) fn push_warnings(&mut self, warnings: Vec<LintWarning>) {
+        self.warnings.extend(warnings);
+    }
+
+    fn warnings_section(&self) -> String {
+        if self.warnings.is_empty() {
+            return String::new();
+        }
+
+        let items = self
+            .warnings
+            .iter()
+            .map(|warning| format!(r#"<li class="warning">{}</li>"#, escape(&warning.to_string())))
+            .collect::<String>();
+
+        format!(
+            r#"<section class="warnings"><h2>Schema warnings</h2><ul>{}</ul></section>"#,
+            items
+        )
+    }
+
+    pub(crate) fn push_model(&mut self, data: &ModelData) {
+        let is_first_migration = data.is_first_migration();
+        let key_changed = !is_first_migration
+            && (data.partition_key_changed() || data.clustering_key_changed() || data.clustering_order_changed());
+        let destructive_changes = data.removed_fields.len()
+            + data.changed_field_types.len()
+            + data.removed_global_secondary_indexes.len()
+            + data.removed_local_secondary_indexes.len()
+            + data.removed_custom_indexes.len()
+            + key_changed as usize;
+
+        let has_changes = destructive_changes > 0
+            || data.has_new_fields()
+            || data.has_renamed_fields()
+            || data.has_new_global_secondary_indexes()
+            || data.has_new_local_secondary_indexes()
+            || data.has_new_custom_indexes();
+
+        if !has_changes {
+            return;
+        }
+
+        self.destructive_changes += destructive_changes;
+
+        let mut badges = String::new();
+
+        if is_first_migration {
+            badges.push_str(r#"<span class="badge new">new</span>"#);
+        }
+
+        if destructive_changes > 0 {
+            badges.push_str(&format!(
+                r#"<span class="badge destructive">{} destructive</span>"#,
+                destructive_changes
+            ));
+        }
+
+        let mut section = format!(
+            r#"<section class="{}"><h2>{} {}{}</h2>"#,
+            if destructive_changes > 0 { "destructive" } else { "" },
+            escape(&data.migration_object_type.to_string()),
+            escape(data.migration_object_name),
+            badges
+        );
+
+        section.push_str(&Self::columns_table(data));
+        section.push_str(&Self::changes_list(data, key_changed));
+        section.push_str("</section>");
+
+        self.sections.push(section);
+    }
+
+    fn columns_table(data: &ModelData) -> String {
+        let mut rows = String::new();
+
+        for (field_name, field_type, _) in data.current_code_schema.fields.iter() {
+            let renamed_from = data
+                .renamed_fields
+                .iter()
+                .chain(data.copied_fields.iter())
+                .find(|(_, new_name)| new_name == field_name)
+                .map(|(old_name, _)| old_name);
+            let before = data
+                .current_db_schema
+                .types_by_name
+                .get(renamed_from.unwrap_or(field_name));
+            let (class, change) = if let Some(old_name) = renamed_from {
+                ("added", format!("renamed from {}", escape(old_name)))
+            } else if before.is_none() {
+                ("added", "added".to_string())
+            } else if data.changed_field_types.iter().any(|(name, _, _)| name == field_name) {
+                ("type-changed", "type changed (drop and replace)".to_string())
+            } else {
+                ("", String::new())
+            };
+
+            rows.push_str(&Self::column_row(
+                class,
+                field_name,
+                before.map(|t| t.as_str()).unwrap_or(""),
+                field_type,
+                &change,
+            ));
+        }
+
+        for field_name in data.removed_fields.iter() {
+            let before = data.current_db_schema.types_by_name.get(field_name);
+
+            rows.push_str(&Self::column_row(
+                "removed",
+                field_name,
+                before.map(|t| t.as_str()).unwrap_or(""),
+                "",
+                "removed",
+            ));
+        }
+
+        format!(
+            "<table><tr><th>Column</th><th>Before</th><th>After</th><th>Change</th></tr>{}</table>",
+            rows
+        )
+    }
+
+    fn column_row(class: &str, name: &str, before: &str, after: &str, change: &str) -> String {
+        format!(
+            r#"<tr class="{}"><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>"#,
+            class,
+            escape(name),
+            escape(before),
+            escape(after),
+            change
+        )
+    }
+
+    fn changes_list(data: &ModelData, key_changed: bool) -> String {
+        let mut items = vec![];
+
+        if key_changed {
+            items.push((
+                "blocked",
+                format!(
+                    "primary key changed from ({}) ({}) to ({}) ({}); migration will not be applied",
+                    data.current_db_schema.partition_keys.join(", "),
+                    clustering_keys_with_order(data.current_db_schema),
+                    data.current_code_schema.partition_keys.join(", "),
+                    clustering_keys_with_order(data.current_code_schema),
+                ),
+            ));
+        }
+
+        for (old_name, new_name) in data.renamed_fields.iter() {
+            items.push(("added", format!("rename {} to {}", old_name, new_name)));
+        }
+
+        for (old_name, new_name) in data.copied_fields.iter() {
+            items.push((
+                "added",
+                format!(
+                    "keep {} until its data is copied to {} and `renamed_from` is removed",
+                    old_name, new_name
+                ),
+            ));
+        }
+
+        for idx in data.new_global_secondary_indexes.iter() {
+            items.push(("added", format!("create global secondary index on {}", idx)));
+        }
+
+        for idx in data.new_local_secondary_indexes.iter() {
+            items.push(("added", format!("create local secondary index on {}", idx)));
+        }
+
+        for idx in data.new_custom_indexes.iter() {
+            items.push((
+                "added",
+                format!("create custom index on {} using {}", idx.target, idx.class_name),
+            ));
+        }
+
+        for idx in data
+            .removed_global_secondary_indexes
+            .iter()
+            .chain(data.removed_local_secondary_indexes.iter())
+            .chain(data.removed_custom_indexes.iter())
+        {
+            items.push(("removed", format!("drop index {}", idx)));
+        }
+
+        if items.is_empty() {
+            return String::new();
+        }
+
+        let items = items
+            .into_iter()
+            .map(|(class, item)| format!(r#"<li class="{}">{}</li>"#, class, escape(&item)))
+            .collect::<String>();
+
+        format!("<ul>{}</ul>", items)
+    }
+
+    pub(crate) fn render(&self) -> String {
+        let body = if self.sections.is_empty() {
+            "<p>No schema changes detected.</p>".to_string()
+        } else {
+            self.sections.join("\n")
+        };
+
+        format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Schema changes: {keyspace}</title>
+<style>{style}</style>
+</head>
+<body>
+<h1>Schema changes: {keyspace}</h1>
+<p>{objects} changed objects, {destructive} destructive changes, {warnings} schema warnings</p>
+{warnings_section}
+{body}
+</body>
+</html>
+"#,
+            keyspace = escape(&self.keyspace),
+            style = STYLE,
+            objects = self.sections.len(),
+            destructive = self.destructive_changes,
+            warnings = self.warnings.len(),
+            warnings_section = self.warnings_section(),
+            body = body
+        )
+    }
+
+    pub(crate) fn write(&self, path: &str) {
+        fs::write(path, self.render()).unwrap_or_else(|e| panic!("Failed to write html report to {}: {}", path, e));
+    }
+}
+
+/// e.g. `created_at DESC, id ASC`
+fn clustering_keys_with_order(schema: &SchemaObject) -> String {
+    schema
+        .clustering_keys
+        .iter()
+        .map(|key| format!("{} {}", key, schema.clustering_order_of(key)))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -1,15 +1,20 @@
 use std::time::Duration;
 
 use crate::args::Args;
+use charybdis_parser::log_error;
 use openssl::ssl::{SslContextBuilder, SslMethod, SslVerifyMode};
 use scylla::{Session, SessionBuilder};
 
 pub async fn initialize_session(args: &Args) -> Session {
     let mut builder = SessionBuilder::new()
         .known_node(&args.host)
-        .use_keyspace(&args.keyspace, false)
         .connection_timeout(Duration::from_secs(args.timeout));
 
+    // keyspace might not exist yet, migration switches to it after it's created
+    if args.replication.is_none() {
+        builder = builder.use_keyspace(&args.keyspace, false);
+    }
+
     if let (Some(user), Some(password)) = (&args.user, &args.password) {
         builder = builder.user(user, password);
     }
@@ -17,7 +22,7 @@ pub async fn initialize_session(args: &Args) -> Session {
     if let Some(ca) = &args.ca {
         let mut context_builder = SslContextBuilder::new(SslMethod::tls())
             .map_err(|e| {
-                eprintln!("Failed to create SSL context: {}", e);
+                log_error!("Failed to create SSL context: {}", e);
                 std::process::exit(1);
             })
             .unwrap();
@@ -25,7 +30,7 @@ pub async fn initialize_session(args: &Args) -> Session {
         context_builder
             .set_ca_file(ca)
             .map_err(|e| {
-                eprintln!("Failed to set CA file: {}", e);
+                log_error!("Failed to set CA file: {}", e);
                 std::process::exit(1);
             })
             .unwrap();
@@ -36,7 +41,7 @@ pub async fn initialize_session(args: &Args) -> Session {
             context_builder
                 .set_certificate_file(key, openssl::ssl::SslFiletype::PEM)
                 .map_err(|e| {
-                    eprintln!("Failed to set certificate file: {}", e);
+                    log_error!("Failed to set certificate file: {}", e);
                     std::process::exit(1);
                 })
                 .unwrap();
@@ -48,7 +53,7 @@ pub async fn initialize_session(args: &Args) -> Session {
             context_builder
                 .set_private_key_file(key, openssl::ssl::SslFiletype::PEM)
                 .map_err(|e| {
-                    eprintln!("Failed to set private key file: {}", e);
+                    log_error!("Failed to set private key file: {}", e);
                     std::process::exit(1);
                 })
                 .unwrap();
@@ -61,7 +66,7 @@ pub async fn initialize_session(args: &Args) -> Session {
         .build()
         .await
         .map_err(|e| {
-            eprintln!("Failed to create session: {}", e);
+            log_error!("Failed to create session: {}", e);
             std::process::exit(1);
         })
         .unwrap()
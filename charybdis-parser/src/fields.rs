@@ -1,9 +1,11 @@
 use std::collections::{HashMap, HashSet};
 
 use darling::FromAttributes;
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Data, DeriveInput, Fields, FieldsNamed, GenericArgument, PathArguments, Type};
+use syn::{Data, DeriveInput, Fields, FieldsNamed, GenericArgument, Meta, MetaNameValue, PathArguments, Token, Type};
 
+use crate::identifier::{normalize_identifier, quote_identifier};
 use crate::traits::CharybdisMacroArgs;
 
 #[derive(Clone, PartialEq, strum_macros::Display, strum_macros::EnumString)]
@@ -13,21 +15,33 @@ pub enum CqlType {
     Blob,
     Boolean,
     Counter,
+    #[strum(to_string = "Date", serialize = "NaiveDate", serialize = "CqlDate")]
     Date,
+    #[strum(to_string = "Decimal", serialize = "BigDecimal", serialize = "CqlDecimal")]
     Decimal,
     Double,
+    #[strum(to_string = "Duration", serialize = "CqlDuration")]
     Duration,
     Float,
+    #[strum(to_string = "Inet", serialize = "IpAddr")]
     Inet,
     Int,
     SmallInt,
     Text,
+    #[strum(to_string = "Time", serialize = "NaiveTime", serialize = "CqlTime")]
     Time,
+    #[strum(
+        to_string = "Timestamp",
+        serialize = "DateTime",
+        serialize = "OffsetDateTime",
+        serialize = "CqlTimestamp"
+    )]
     Timestamp,
     Timeuuid,
     TinyInt,
     Uuid,
     Varchar,
+    #[strum(to_string = "Varint", serialize = "CqlVarint")]
     Varint,
     Map,
     List,
@@ -39,15 +53,237 @@ pub enum CqlType {
     Unknown,
 }
 
+/// Strategy used to populate unset key fields on insert.
+#[derive(Clone)]
+pub enum IdStrategy {
+    UuidV7,
+    Snowflake,
+    /// path to user defined generator function `fn() -> T`
+    Custom(syn::Path),
+}
+
+impl IdStrategy {
+    fn parse(strategy: &str, field_name: &str) -> Self {
+        match strategy.trim() {
+            "uuid_v7" => IdStrategy::UuidV7,
+            "snowflake" => IdStrategy::Snowflake,
+            custom if custom.starts_with("custom(") && custom.ends_with(')') => {
+                let path = &custom["custom(".len()..custom.len() - 1];
+                let path = syn::parse_str::<syn::Path>(path.trim())
+                    .unwrap_or_else(|_| panic!("Invalid id_strategy generator path for field {}", field_name));
+
+                IdStrategy::Custom(path)
+            }
+            _ => panic!(
+                "Unknown id_strategy {} for field {}. Use \"uuid_v7\", \"snowflake\" or \"custom(path)\"",
+                strategy, field_name
+            ),
+        }
+    }
+}
+
+/// Encode/decode pair applied to field value when it's bound to queries and read from rows, set by
+/// `#[charybdis(with = "path::to::codec", encoded_as = "Blob")]`.
+#[derive(Clone)]
+pub struct FieldCodec {
+    /// module with `encode(&T) -> E` and `decode(E) -> Result<T, _>` functions
+    pub path: syn::Path,
+    /// type of value stored in database
+    pub encoded_type: syn::TypePath,
+}
+
+impl FieldCodec {
+    fn parse(with: &str, encoded_as: Option<&str>, field_name: &str) -> Self {
+        let path = syn::parse_str::<syn::Path>(with.trim())
+            .unwrap_or_else(|_| panic!("Invalid codec path for field {}", field_name));
+
+        let encoded_as = encoded_as.unwrap_or_else(|| {
+            panic!(
+                "Field {} with codec requires encoded_as type, e.g. #[charybdis(with = \"{}\", encoded_as = \"Blob\")]",
+                field_name, with
+            )
+        });
+
+        let encoded_type = syn::parse_str::<syn::TypePath>(encoded_as.trim())
+            .unwrap_or_else(|_| panic!("Invalid encoded_as type for field {}", field_name));
+
+        FieldCodec { path, encoded_type }
+    }
+}
+
+/// Check run by generated `validate` method, declared with `#[charybdis(validate = "...")]`.
+#[derive(Clone)]
+pub enum Validator {
+    Email,
+    NotEmpty,
+    Length {
+        min: Option<syn::Expr>,
+        max: Option<syn::Expr>,
+    },
+    Range {
+        min: Option<syn::Expr>,
+        max: Option<syn::Expr>,
+    },
+    /// path to user defined function `fn(&T) -> Result<(), String>`
+    Custom(syn::Path),
+}
+
+impl Validator {
+    /// Parses comma separated validators, e.g. `"not_empty, length(max = 64)"`.
+    fn parse_all(validators: &str, field_name: &str) -> Vec<Self> {
+        let metas = syn::parse::Parser::parse_str(Punctuated::<Meta, Token![,]>::parse_terminated, validators)
+            .unwrap_or_else(|e| panic!("Invalid validate attribute for field {}: {}", field_name, e));
+
+        metas.iter().map(|meta| Validator::parse(meta, field_name)).collect()
+    }
+
+    fn parse(meta: &Meta, field_name: &str) -> Self {
+        let name = meta
+            .path()
+            .get_ident()
+            .map(|ident| ident.to_string())
+            .unwrap_or_default();
+
+        match (name.as_str(), meta) {
+            ("email", Meta::Path(_)) => Validator::Email,
+            ("not_empty", Meta::Path(_)) => Validator::NotEmpty,
+            ("length", Meta::List(_)) => {
+                let (min, max) = Validator::bounds(meta, field_name);
+
+                Validator::Length { min, max }
+            }
+            ("range", Meta::List(_)) => {
+                let (min, max) = Validator::bounds(meta, field_name);
+
+                Validator::Range { min, max }
+            }
+            ("custom", Meta::List(list)) => {
+                let path = list
+                    .parse_args::<syn::Path>()
+                    .unwrap_or_else(|_| panic!("Invalid custom validator path for field {}", field_name));
+
+                Validator::Custom(path)
+            }
+            _ => panic!(
+                "Unknown validator {} for field {}. Use \"email\", \"not_empty\", \"length(min = .., max = ..)\", \
+                \"range(min = .., max = ..)\" or \"custom(path)\"",
+                name, field_name
+            ),
+        }
+    }
+
+    fn bounds(meta: &Meta, field_name: &str) -> (Option<syn::Expr>, Option<syn::Expr>) {
+        let args = meta
+            .require_list()
+            .and_then(|list| list.parse_args_with(Punctuated::<MetaNameValue, Token![,]>::parse_terminated))
+            .unwrap_or_else(|e| panic!("Invalid validator bounds for field {}: {}", field_name, e));
+
+        let mut min = None;
+        let mut max = None;
+
+        for arg in args {
+            match arg.path.get_ident().map(|ident| ident.to_string()).as_deref() {
+                Some("min") => min = Some(arg.value),
+                Some("max") => max = Some(arg.value),
+                _ => panic!("Validator bounds of field {} can be only min and max", field_name),
+            }
+        }
+
+        if min.is_none() && max.is_none() {
+            panic!("Validator of field {} requires min or max bound", field_name);
+        }
+
+        (min, max)
+    }
+}
+
+/// Secondary index on contents of collection field, queried with `CONTAINS`, `CONTAINS KEY` or `field[key] = ?`.
+#[derive(Clone, Copy, PartialEq)]
+pub enum CollectionIndex {
+    Values,
+    Keys,
+    Entries,
+}
+
+impl CollectionIndex {
+    /// Index target as used in `CREATE INDEX` and reported by `system_schema.indexes`, e.g. `values(tags)`.
+    pub fn target(&self, field_name: &str) -> String {
+        match self {
+            CollectionIndex::Values => format!("values({})", field_name),
+            CollectionIndex::Keys => format!("keys({})", field_name),
+            CollectionIndex::Entries => format!("entries({})", field_name),
+        }
+    }
+}
+
 #[derive(FromAttributes, Clone)]
 #[darling(attributes(charybdis))]
 pub struct FieldAttributes {
     #[darling(default)]
     pub ignore: Option<bool>,
+
+    #[darling(default)]
+    pub id_strategy: Option<String>,
+
+    #[darling(default)]
+    pub version: Option<bool>,
+
+    #[darling(default)]
+    pub allow_filtering: Option<bool>,
+
+    #[darling(default)]
+    pub index_values: Option<bool>,
+
+    #[darling(default)]
+    pub index_keys: Option<bool>,
+
+    #[darling(default)]
+    pub index_entries: Option<bool>,
+
+    #[darling(default)]
+    pub renamed_from: Option<String>,
+
+    #[darling(default)]
+    pub db_name: Option<String>,
+
+    #[darling(default)]
+    pub validate: Option<String>,
+
+    #[darling(default)]
+    pub with: Option<String>,
+
+    #[darling(default)]
+    pub encoded_as: Option<String>,
+}
+
+impl FieldAttributes {
+    fn collection_index(&self, field_name: &str) -> Option<CollectionIndex> {
+        let indexes = [
+            (self.index_values, CollectionIndex::Values),
+            (self.index_keys, CollectionIndex::Keys),
+            (self.index_entries, CollectionIndex::Entries),
+        ]
+        .into_iter()
+        .filter(|(enabled, _)| enabled.unwrap_or(false))
+        .map(|(_, index)| index)
+        .collect::<Vec<CollectionIndex>>();
+
+        if indexes.len() > 1 {
+            panic!(
+                "Field {} can have only one of index_values, index_keys or index_entries",
+                field_name
+            );
+        }
+
+        indexes.first().copied()
+    }
 }
 
 pub struct Field<'a> {
     pub name: String,
+    /// Name of column as stored in database. It's lowercased field name, unless overridden with
+    /// `#[charybdis(db_name = "\"CamelCase\"")]`
+    pub db_name: String,
     pub ident: syn::Ident,
     pub ty: Type,
     pub ty_path: syn::TypePath,
@@ -55,6 +291,20 @@ pub struct Field<'a> {
     pub span: proc_macro2::Span,
     pub attrs: &'a Vec<syn::Attribute>,
     pub ignore: bool,
+    pub id_strategy: Option<IdStrategy>,
+    pub is_version: bool,
+    /// Opt-in for `find_by_<field>_filtering` finders, that query non-key column with `ALLOW FILTERING`
+    pub allow_filtering: bool,
+    /// Index on collection contents, set by `#[charybdis(index_values)]`, `index_keys` or `index_entries`
+    pub collection_index: Option<CollectionIndex>,
+    /// Previous name of column, set by `#[charybdis(renamed_from = "old_name")]`, so migration renames
+    /// the column instead of dropping it
+    pub renamed_from: Option<String>,
+    /// Checks run by generated `validate` method, set by `#[charybdis(validate = "email, length(max = 255)")]`
+    pub validators: Vec<Validator>,
+    /// Codec of field value, set by `#[charybdis(with = "...", encoded_as = "...")]`. Column type and
+    /// `outer_type` are derived from encoded type.
+    pub codec: Option<FieldCodec>,
     pub is_partition_key: bool,
     pub is_clustering_key: bool,
     pub is_static_column: bool,
@@ -107,20 +357,76 @@ impl<'a> Field<'a> {
         FieldAttributes::from_attributes(&field.attrs)
             .map(|char_attrs| {
                 let ignore = char_attrs.ignore.unwrap_or(false);
+                let is_version = char_attrs.version.unwrap_or(false);
+                let allow_filtering = char_attrs.allow_filtering.unwrap_or(false);
                 let ident = field.ident.clone().unwrap();
+                let collection_index = char_attrs.collection_index(&ident.to_string());
+                let id_strategy = char_attrs
+                    .id_strategy
+                    .map(|strategy| IdStrategy::parse(&strategy, &ident.to_string()));
+                let validators = char_attrs
+                    .validate
+                    .as_deref()
+                    .map(|validators| Validator::parse_all(validators, &ident.to_string()))
+                    .unwrap_or_default();
+
+                if ignore && !validators.is_empty() {
+                    panic!("Ignored field {} can't be validated", ident);
+                }
+
+                if char_attrs.encoded_as.is_some() && char_attrs.with.is_none() {
+                    panic!(
+                        "Field {} has encoded_as, but no codec. Set it with `with` attribute",
+                        ident
+                    );
+                }
+
+                let codec = char_attrs
+                    .with
+                    .as_deref()
+                    .map(|with| FieldCodec::parse(with, char_attrs.encoded_as.as_deref(), &ident.to_string()));
+
+                if ignore && codec.is_some() {
+                    panic!("Ignored field {} can't have codec", ident);
+                }
+
+                let ty_path = match &field.ty {
+                    Type::Path(type_path) => type_path.clone(),
+                    _ => panic!("Only type path is supported!"),
+                };
+                let db_ty = codec
+                    .as_ref()
+                    .map_or(field.ty.clone(), |codec| Type::Path(codec.encoded_type.clone()));
+
+                if let Some(renamed_from) = &char_attrs.renamed_from {
+                    if ignore {
+                        panic!("Ignored field {} can't be renamed", ident);
+                    }
+
+                    if renamed_from == &ident.to_string() {
+                        panic!("Field {} can't be renamed from itself", ident);
+                    }
+                }
+
+                let db_name = normalize_identifier(char_attrs.db_name.as_deref().unwrap_or(&ident.to_string()));
 
                 Field {
                     name: ident.to_string(),
+                    db_name,
                     ident: ident.clone(),
                     ty: field.ty.clone(),
-                    ty_path: match &field.ty {
-                        Type::Path(type_path) => type_path.clone(),
-                        _ => panic!("Only type path is supported!"),
-                    },
-                    outer_type: Field::outer_type(&field.ty, ignore),
+                    ty_path,
+                    outer_type: Field::outer_type(&db_ty, ignore),
                     span: field.span(),
                     attrs: &field.attrs,
                     ignore,
+                    id_strategy,
+                    is_version,
+                    allow_filtering,
+                    collection_index,
+                    renamed_from: char_attrs.renamed_from,
+                    validators,
+                    codec,
                     is_partition_key,
                     is_clustering_key,
                     is_static_column,
@@ -129,6 +435,22 @@ impl<'a> Field<'a> {
             .unwrap()
     }
 
+    /// Type of value stored in database, i.e. encoded type of field with codec.
+    pub fn db_ty_path(&self) -> &syn::TypePath {
+        self.codec.as_ref().map_or(&self.ty_path, |codec| &codec.encoded_type)
+    }
+
+    /// Column name as written in CQL statements, quoted if needed.
+    pub fn cql_name(&self) -> String {
+        quote_identifier(&self.db_name)
+    }
+
+    /// Named bind marker of field, e.g. `:user_id`. Markers are named by struct fields, as values are
+    /// serialized by field names.
+    pub fn bind_marker(&self) -> String {
+        format!(":{}", quote_identifier(&self.name))
+    }
+
     pub fn is_primary_key(&self) -> bool {
         self.is_partition_key || self.is_clustering_key
     }
@@ -157,6 +479,18 @@ impl<'a> Field<'a> {
         self.outer_type == CqlType::Tuple
     }
 
+    pub fn is_option(&self) -> bool {
+        self.ty_path
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "Option")
+    }
+
+    pub fn is_version_type(&self) -> bool {
+        !self.is_option() && matches!(self.outer_type, CqlType::Int | CqlType::BigInt | CqlType::Timeuuid)
+    }
+
     pub fn is_frozen(&self) -> bool {
         self.ty_path
             .path
@@ -164,6 +498,15 @@ impl<'a> Field<'a> {
             .iter()
             .any(|segment| segment.ident == "Frozen")
     }
+
+    /// Field holding changes of model with `track_changes`, e.g. `changes: Changes`.
+    pub fn is_changes_type(&self) -> bool {
+        self.ty_path
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "Changes")
+    }
 }
 
 #[derive(Default)]
@@ -175,6 +518,12 @@ pub struct CharybdisFields<'a> {
     pub db_fields: Vec<&'a Field<'a>>,
     pub global_secondary_index_fields: Vec<&'a Field<'a>>,
     pub local_secondary_index_fields: Vec<&'a Field<'a>>,
+    pub version_field: Option<&'a Field<'a>>,
+    pub soft_delete_field: Option<&'a Field<'a>>,
+    /// column of `soft_delete` field, set also for partial models that don't select it
+    pub soft_delete_column: Option<String>,
+    pub tenant_field: Option<&'a Field<'a>>,
+    pub changes_field: Option<&'a Field<'a>>,
 }
 
 impl CharybdisFields<'_> {
@@ -189,6 +538,46 @@ impl CharybdisFields<'_> {
     pub fn non_db_fields(&self) -> Vec<&Field> {
         self.all_fields.iter().filter(|field| field.ignore).collect()
     }
+
+    /// Models with codec fields implement `SerializeRow` instead of deriving it.
+    pub fn has_codecs(&self) -> bool {
+        self.db_fields.iter().any(|field| field.codec.is_some())
+    }
+
+    /// Counter tables can't mix counter and regular columns, and rows of counter tables are created by
+    /// updates only, so they can't have lookup tables or soft delete, as both are maintained by inserts.
+    pub fn panic_on_counter_model_misuse(&self, args: &CharybdisMacroArgs) {
+        let counter_fields = self.non_primary_key_db_fields();
+
+        if counter_fields.is_empty() {
+            panic!("Counter model must have at least one counter field besides the primary key");
+        }
+
+        for field in counter_fields {
+            if !field.is_counter() {
+                panic!(
+                    "Field {} must be Counter, as counter model can't have regular columns besides the primary key",
+                    field.name
+                );
+            }
+        }
+
+        if !args.lookup_tables().is_empty() {
+            panic!("Lookup tables are not supported on counter model");
+        }
+
+        if args.soft_delete.is_some() {
+            panic!("Soft delete is not supported on counter model");
+        }
+
+        if args.tenant_key.is_some() {
+            panic!("Tenant key is not supported on counter model, as it's checked by callbacks");
+        }
+
+        if args.track_changes.unwrap_or(false) {
+            panic!("Change tracking is not supported on counter model, as counters are changed by increments");
+        }
+    }
 }
 
 impl<'a> CharybdisFields<'a> {
@@ -211,6 +600,80 @@ impl<'a> CharybdisFields<'a> {
                 panic!("Field {} cannot be both partition and clustering key", field_name);
             }
 
+            if ch_field.id_strategy.is_some() && !ch_field.is_primary_key() {
+                panic!(
+                    "Field {} has id_strategy, but it is not part of the primary key",
+                    field_name
+                );
+            }
+
+            if matches!(ch_field.id_strategy, Some(IdStrategy::UuidV7)) && ch_field.outer_type == CqlType::Timeuuid {
+                panic!(
+                    "Field {} has id_strategy uuid_v7, but it is Timeuuid. Version 7 uuid is not valid timeuuid, \
+                    use Uuid field instead",
+                    field_name
+                );
+            }
+
+            if ch_field.is_version {
+                if ch_field.is_primary_key() || ch_field.ignore {
+                    panic!(
+                        "Version field {} cannot be part of the primary key or ignored",
+                        field_name
+                    );
+                }
+
+                if !ch_field.is_version_type() {
+                    panic!("Version field {} must be of type Int, BigInt or Timeuuid", field_name);
+                }
+
+                if me.all_fields.iter().any(|field| field.is_version) {
+                    panic!("Only one version field is allowed. Found another one: {}", field_name);
+                }
+            }
+
+            if ch_field.allow_filtering && (ch_field.is_primary_key() || ch_field.ignore) {
+                panic!(
+                    "Field {} has allow_filtering, but it is part of the primary key or ignored. \
+                    Primary key fields are queried by generated find_by_<key> functions.",
+                    field_name
+                );
+            }
+
+            if let Some(collection_index) = ch_field.collection_index {
+                if !ch_field.is_collection() || ch_field.is_frozen() || ch_field.ignore {
+                    panic!(
+                        "Field {} has collection index, but it is not non-frozen List, Set or Map",
+                        field_name
+                    );
+                }
+
+                if collection_index != CollectionIndex::Values && !ch_field.is_map() {
+                    panic!(
+                        "Field {} has index_keys or index_entries, but it is not Map",
+                        field_name
+                    );
+                }
+            }
+
+            if ch_field.codec.is_some() {
+                if ch_field.is_primary_key() || ch_field.is_version || ch_field.allow_filtering {
+                    panic!(
+                        "Field {} with codec can't be part of the primary key, version field or filtered, \
+                        as it's bound to generated queries without encoding",
+                        field_name
+                    );
+                }
+
+                if ch_field.is_collection() || ch_field.is_counter() {
+                    panic!(
+                        "Field {} with codec can't be encoded as collection or counter, as it's bound to \
+                        generated collection and counter queries without encoding",
+                        field_name
+                    );
+                }
+            }
+
             if is_static_column && (is_partition_key || is_clustering_key) {
                 panic!(
                     "Field {} cannot be both static column and partition or clustering key",
@@ -257,6 +720,7 @@ impl<'a> CharybdisFields<'a> {
         let mut pk_struct_fields = HashSet::new();
         let mut ck_struct_fields = HashSet::new();
         let mut static_struct_fields = HashSet::new();
+        let mut custom_index_struct_fields = HashSet::new();
 
         // populate fields
         for ch_field in self.all_fields.iter() {
@@ -264,6 +728,10 @@ impl<'a> CharybdisFields<'a> {
                 self.db_fields.push(ch_field);
             }
 
+            if ch_field.is_version {
+                self.version_field = Some(ch_field);
+            }
+
             if ch_field.is_static_column {
                 static_struct_fields.insert(ch_field.name.clone());
             }
@@ -276,6 +744,36 @@ impl<'a> CharybdisFields<'a> {
                 self.local_secondary_index_fields.push(ch_field);
             }
 
+            if ch_field.codec.is_some()
+                && (args.global_secondary_indexes().contains(&ch_field.name)
+                    || args.local_secondary_indexes().contains(&ch_field.name)
+                    || args.lookup_tables().contains(&ch_field.name))
+            {
+                panic!(
+                    "Field {} with codec can't be indexed or used as lookup table key, as it's queried without encoding",
+                    ch_field.name
+                );
+            }
+
+            if ch_field.allow_filtering
+                && (args.global_secondary_indexes().contains(&ch_field.name)
+                    || args.local_secondary_indexes().contains(&ch_field.name))
+            {
+                panic!(
+                    "Field {} has allow_filtering, but it is indexed. Use generated find_by_{} instead.",
+                    ch_field.name, ch_field.name
+                );
+            }
+
+            if args.custom_indexes().iter().any(|idx| idx.target == ch_field.name) {
+                custom_index_struct_fields.insert(ch_field.name.clone());
+            }
+
+            // duration values have no total order, so they can't be compared as keys
+            if ch_field.is_primary_key() && ch_field.outer_type == CqlType::Duration {
+                panic!("Duration field {} can't be part of the primary key", ch_field.name);
+            }
+
             if ch_field.is_partition_key {
                 let partition_key_index = *partition_key_indexes_by_name
                     .get(&ch_field.name)
@@ -316,12 +814,127 @@ impl<'a> CharybdisFields<'a> {
             }
         }
 
+        for (key, _) in args.clustering_order() {
+            if !ck_struct_fields.contains(key) {
+                panic!(
+                    "Clustering order is declared for {}, but it is not a clustering key",
+                    key
+                );
+            }
+        }
+
+        let table_options_order = args.table_options.as_ref().map_or(false, |table_options| {
+            table_options.to_uppercase().contains("CLUSTERING ORDER BY")
+        });
+
+        if !args.clustering_order().is_empty() && table_options_order {
+            panic!("Clustering order can't be declared both in clustering_order and table_options");
+        }
+
         for key in args.static_columns() {
             if !static_struct_fields.contains(key) {
                 panic!("Static column {} not found in struct fields", key);
             }
         }
 
+        for lookup_field_name in args.lookup_tables() {
+            let lookup_field = self
+                .db_fields
+                .iter()
+                .find(|field| &field.name == lookup_field_name)
+                .unwrap_or_else(|| panic!("Lookup table field {} not found in struct fields", lookup_field_name));
+
+            if lookup_field.is_primary_key() || lookup_field.is_collection() || lookup_field.is_option() {
+                panic!(
+                    "Lookup table field {} must be required non-collection field that is not part of the primary key",
+                    lookup_field_name
+                );
+            }
+        }
+
+        // finders are generated only for key, indexed and allow_filtering fields, so secondary index has to refer
+        // to column of the model
+        for index_field_name in args
+            .global_secondary_indexes()
+            .iter()
+            .chain(args.local_secondary_indexes())
+        {
+            let indexed = self.db_fields.iter().any(|field| &field.name == index_field_name);
+
+            // partial model keeps indexes of base model, even if it doesn't select indexed field
+            if !indexed && !args.exclude_partial_model.unwrap_or(false) {
+                panic!(
+                    "Secondary index field {} not found in struct fields or ignored. Finders are generated only for \
+                    key, indexed and allow_filtering fields",
+                    index_field_name
+                );
+            }
+        }
+
+        for custom_index in args.custom_indexes() {
+            if !custom_index_struct_fields.contains(&custom_index.target) {
+                panic!("Custom index target {} not found in struct fields", custom_index.target);
+            }
+        }
+
+        if let Some(soft_delete) = &args.soft_delete {
+            match self.db_fields.iter().find(|field| &field.name == soft_delete).copied() {
+                Some(field) => {
+                    if field.is_primary_key() || field.is_option() || field.outer_type != CqlType::Timestamp {
+                        panic!(
+                            "Soft delete field {} must be Timestamp that is not part of the primary key",
+                            soft_delete
+                        );
+                    }
+
+                    self.soft_delete_field = Some(field);
+                    self.soft_delete_column = Some(field.cql_name());
+                }
+                // partial model that doesn't select soft delete field still filters rows by its column
+                None if args.exclude_partial_model.unwrap_or(false) => {
+                    self.soft_delete_column = Some(quote_identifier(soft_delete));
+                }
+                None => panic!("Soft delete field {} not found in struct fields", soft_delete),
+            }
+        }
+
+        if let Some(tenant_key) = &args.tenant_key {
+            match self.db_fields.iter().find(|field| &field.name == tenant_key).copied() {
+                Some(field) => {
+                    if field.is_clustering_key || field.is_option() || field.is_collection() {
+                        panic!(
+                            "Tenant key field {} must be required non-collection field that is not clustering key",
+                            tenant_key
+                        );
+                    }
+
+                    self.tenant_field = Some(field);
+                }
+                // partial model that doesn't select tenant key field
+                None if args.exclude_partial_model.unwrap_or(false) => {}
+                None => panic!("Tenant key field {} not found in struct fields", tenant_key),
+            }
+        }
+
+        if args.track_changes.unwrap_or(false) {
+            let changes_field = self
+                .all_fields
+                .iter()
+                .find(|field| field.is_changes_type())
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Model with track_changes must have field of type Changes, e.g. \
+                        `#[charybdis(ignore)] changes: charybdis::changes::Changes`"
+                    )
+                });
+
+            if !changes_field.ignore {
+                panic!("Changes field {} must be ignored", changes_field.name);
+            }
+
+            self.changes_field = Some(changes_field);
+        }
+
         // populate primary key fields
         self.partition_key_fields = partition_key_fields.into_iter().flatten().collect();
         self.clustering_key_fields = clustering_key_fields.into_iter().flatten().collect();
@@ -371,6 +984,27 @@ impl<'a> CharybdisFields<'a> {
         }
     }
 
+    /// UDT values are serialized and deserialized by derived driver traits, so UDT fields can't override
+    /// `db_name`, as fields are matched by name, nor declare codec.
+    pub fn panic_on_udt_field_attributes(input: &DeriveInput) {
+        if let Data::Struct(data_struct) = &input.data {
+            for field in data_struct.fields.iter() {
+                let char_attrs = FieldAttributes::from_attributes(&field.attrs).unwrap();
+
+                if char_attrs.db_name.is_some() {
+                    panic!(
+                        "db_name is not supported on UDT field {}, as UDT fields are matched by name",
+                        field.ident.as_ref().unwrap()
+                    );
+                }
+
+                if char_attrs.with.is_some() {
+                    panic!("Codec is not supported on UDT field {}", field.ident.as_ref().unwrap());
+                }
+            }
+        }
+    }
+
     pub fn strip_charybdis_attributes(input: &mut DeriveInput) {
         if let Data::Struct(data_struct) = &mut input.data {
             if let Fields::Named(fields_named) = &mut data_struct.fields {
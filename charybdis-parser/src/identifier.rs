@@ -0,0 +1,172 @@
+/// Keywords that can be used as identifiers only when quoted.
+const RESERVED_KEYWORDS: [&str; 56] = [
+    "add",
+    "allow",
+    "alter",
+    "and",
+    "apply",
+    "asc",
+    "authorize",
+    "batch",
+    "begin",
+    "by",
+    "columnfamily",
+    "create",
+    "delete",
+    "desc",
+    "describe",
+    "drop",
+    "entries",
+    "execute",
+    "from",
+    "full",
+    "grant",
+    "if",
+    "in",
+    "index",
+    "infinity",
+    "insert",
+    "into",
+    "is",
+    "keyspace",
+    "limit",
+    "materialized",
+    "modify",
+    "nan",
+    "norecursive",
+    "not",
+    "null",
+    "of",
+    "on",
+    "or",
+    "order",
+    "primary",
+    "rename",
+    "revoke",
+    "schema",
+    "select",
+    "set",
+    "table",
+    "to",
+    "token",
+    "truncate",
+    "unlogged",
+    "update",
+    "use",
+    "using",
+    "where",
+    "with",
+];
+
+pub fn is_reserved_keyword(name: &str) -> bool {
+    RESERVED_KEYWORDS
+        .iter()
+        .any(|keyword| keyword.eq_ignore_ascii_case(name))
+}
+
+/// Name of identifier as stored in database. Quoted identifiers are case-sensitive, unquoted ones are
+/// lowercased, e.g. `"CamelCase"` -> `CamelCase` and `userId` -> `userid`.
+pub fn normalize_identifier(identifier: &str) -> String {
+    let identifier = identifier.trim();
+
+    if identifier.len() >= 2 && identifier.starts_with('"') && identifier.ends_with('"') {
+        return identifier[1..identifier.len() - 1].replace("\"\"", "\"");
+    }
+
+    identifier.to_lowercase()
+}
+
+/// Identifier of name stored in database as written in CQL statement. Name is quoted if it's reserved keyword
+/// or if it would not survive case folding, e.g. `CamelCase` -> `"CamelCase"` and `order` -> `"order"`.
+pub fn quote_identifier(name: &str) -> String {
+    let is_plain = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+    if is_plain && !is_reserved_keyword(name) {
+        return name.to_string();
+    }
+
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Splits name qualified by keyspace, e.g. `other_ks.address` -> `(Some("other_ks"), "address")`. Both parts are
+/// normalized like identifiers, and dots within quoted parts don't split them.
+pub fn split_qualified_name(name: &str) -> (Option<String>, String) {
+    let mut quoted = false;
+
+    for (position, c) in name.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '.' if !quoted => {
+                return (
+                    Some(normalize_identifier(&name[..position])),
+                    normalize_identifier(&name[position + 1..]),
+                )
+            }
+            _ => {}
+        }
+    }
+
+    (None, normalize_identifier(name))
+}
+
+/// Canonical form of index target, so targets of code and db schema are compared regardless of quoting,
+/// e.g. `values("Tags")` or `"CamelCase"`.
+pub fn normalize_index_target(target: &str) -> String {
+    let target = target.trim();
+
+    match target.split_once('(') {
+        Some((function, column)) if column.ends_with(')') => format!(
+            "{}({})",
+            function.trim().to_lowercase(),
+            quote_identifier(&normalize_identifier(&column[..column.len() - 1]))
+        ),
+        _ => quote_identifier(&normalize_identifier(target)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_quoted_and_unquoted_identifiers() {
+        assert_eq!(normalize_identifier("userId"), "userid");
+        assert_eq!(normalize_identifier("\"CamelCase\""), "CamelCase");
+        assert_eq!(normalize_identifier("\"say \"\"hi\"\"\""), "say \"hi\"");
+    }
+
+    #[test]
+    fn quotes_identifiers_that_would_not_survive_case_folding() {
+        assert_eq!(quote_identifier("user_id"), "user_id");
+        assert_eq!(quote_identifier("CamelCase"), "\"CamelCase\"");
+        assert_eq!(quote_identifier("order"), "\"order\"");
+        assert_eq!(quote_identifier("1st"), "\"1st\"");
+        assert_eq!(quote_identifier("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn splits_qualified_names_outside_of_quotes() {
+        assert_eq!(
+            split_qualified_name("other_ks.Address"),
+            (Some("other_ks".to_string()), "address".to_string())
+        );
+        assert_eq!(
+            split_qualified_name("\"home.address\""),
+            (None, "home.address".to_string())
+        );
+        assert_eq!(
+            split_qualified_name("\"My.Ks\".\"Address\""),
+            (Some("My.Ks".to_string()), "Address".to_string())
+        );
+    }
+
+    #[test]
+    fn normalizes_index_targets() {
+        assert_eq!(normalize_index_target("VALUES(\"Tags\")"), "values(\"Tags\")");
+        assert_eq!(normalize_index_target(" keys(tags) "), "keys(tags)");
+        assert_eq!(normalize_index_target("\"CamelCase\""), "\"CamelCase\"");
+    }
+}
@@ -1,4 +1,7 @@
 pub mod errors;
 pub mod fields;
+pub mod identifier;
+pub mod logging;
 pub mod schema;
+pub mod statement;
 pub mod traits;
@@ -0,0 +1,94 @@
+//! Output of schema introspection and migrations.
+//!
+//! With `tracing` feature enabled, messages are emitted as `tracing` events, so they can be collected by
+//! subscriber of the application. Otherwise, they are printed to stdout/stderr. Colors are meant for terminal
+//! output, so events are emitted as plain text, without changing global `colored` override of the application.
+
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+pub use tracing as __tracing;
+
+#[cfg(feature = "tracing")]
+#[macro_export]
+macro_rules! log_info {
+    ($($arg:tt)+) => {
+        $crate::logging::__tracing::info!("{}", $crate::logging::__message(format_args!($($arg)+), false))
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+#[macro_export]
+macro_rules! log_info {
+    ($($arg:tt)+) => {
+        println!($($arg)+)
+    };
+}
+
+#[cfg(feature = "tracing")]
+#[macro_export]
+macro_rules! log_error {
+    ($($arg:tt)+) => {
+        $crate::logging::__tracing::error!("{}", $crate::logging::__message(format_args!($($arg)+), false))
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+#[macro_export]
+macro_rules! log_error {
+    ($($arg:tt)+) => {
+        eprintln!($($arg)+)
+    };
+}
+
+/// Formats message of logging macros, ANSI escape sequences of `colored` strings are removed unless `colored`.
+#[doc(hidden)]
+pub fn __message(args: std::fmt::Arguments<'_>, colored: bool) -> String {
+    let message = args.to_string();
+
+    if colored {
+        return message;
+    }
+
+    let mut plain = String::with_capacity(message.len());
+    let mut chars = message.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            plain.push(c);
+            continue;
+        }
+
+        // control sequence, e.g. `\u{1b}[1;32m`, ends with byte in range `@`..=`~`
+        if chars.next() == Some('[') {
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+
+    plain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removes_colors_of_plain_message() {
+        let message = __message(
+            format_args!("{} {}", "\u{1b}[1;32mRunning CQL:\u{1b}[0m", "users"),
+            false,
+        );
+
+        assert_eq!(message, "Running CQL: users");
+    }
+
+    #[test]
+    fn keeps_colors_of_colored_message() {
+        let message = __message(format_args!("\u{1b}[31m{}\u{1b}[0m", "failed"), true);
+
+        assert_eq!(message, "\u{1b}[31mfailed\u{1b}[0m");
+    }
+}
@@ -1,8 +1,12 @@
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 
+use crate::identifier::{normalize_identifier, quote_identifier};
+use crate::schema::secondary_indexes::CustomIndex;
+
 pub mod code_schema;
 pub mod db_schema;
 pub mod secondary_indexes;
@@ -13,6 +17,41 @@ pub type IsStatic = bool;
 pub type IndexName = String;
 pub type IdxField = String;
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ClusteringOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl ClusteringOrder {
+    /// Parses `asc` or `desc` in any case, as declared in macro or stored in `system_schema.columns`.
+    pub fn parse(order: &str) -> Option<Self> {
+        match order.trim().to_lowercase().as_str() {
+            "asc" => Some(ClusteringOrder::Asc),
+            "desc" => Some(ClusteringOrder::Desc),
+            _ => None,
+        }
+    }
+
+    pub fn reversed(&self) -> Self {
+        match self {
+            ClusteringOrder::Asc => ClusteringOrder::Desc,
+            ClusteringOrder::Desc => ClusteringOrder::Asc,
+        }
+    }
+}
+
+impl fmt::Display for ClusteringOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClusteringOrder::Asc => write!(f, "ASC"),
+            ClusteringOrder::Desc => write!(f, "DESC"),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct SchemaObject {
     pub fields: Vec<(FieldName, FieldType, IsStatic)>,
@@ -26,11 +65,20 @@ pub struct SchemaObject {
     pub static_columns: Vec<String>,
     pub global_secondary_indexes: Vec<(IndexName, IdxField)>,
     pub local_secondary_indexes: Vec<(IndexName, IdxField)>,
+    pub custom_indexes: Vec<(IndexName, CustomIndex)>,
     pub table_options: Option<String>,
+    /// order of clustering keys declared with `clustering_order`, keys that are not listed are ascending
+    #[serde(default)]
+    pub clustering_order: Vec<(FieldName, ClusteringOrder)>,
+    /// `(old_name, new_name)` of fields declared with `renamed_from`
+    #[serde(default)]
+    pub renamed_fields: Vec<(FieldName, FieldName)>,
+    #[serde(default)]
+    pub renamed_from: Option<String>,
 }
 
 impl SchemaObject {
-    pub(crate) fn push_field(&mut self, field_name: String, field_type: String, is_static: bool) {
+    pub fn push_field(&mut self, field_name: String, field_type: String, is_static: bool) {
         self.fields.push((field_name.clone(), field_type.clone(), is_static));
         self.field_names.insert(field_name.clone());
         self.types_by_name.insert(field_name, field_type);
@@ -39,6 +87,39 @@ impl SchemaObject {
     pub fn contains_field(&self, field_name: &str) -> bool {
         self.field_names.contains(field_name)
     }
+
+    /// Order of clustering key, declared with `clustering_order` or with `CLUSTERING ORDER BY` of table
+    /// options.
+    pub fn clustering_order_of(&self, clustering_key: &str) -> ClusteringOrder {
+        let table_options_order = self
+            .table_options
+            .as_deref()
+            .map(clustering_order_from_table_options)
+            .unwrap_or_default();
+
+        self.clustering_order
+            .iter()
+            .chain(table_options_order.iter())
+            .find(|(key, _)| key == clustering_key)
+            .map_or(ClusteringOrder::Asc, |(_, order)| *order)
+    }
+
+    /// `CLUSTERING ORDER BY (...)` clause of declared `clustering_order`, listing all clustering keys, as
+    /// order of each key has to be given. Empty if order is not declared.
+    pub fn clustering_order_clause(&self) -> String {
+        if self.clustering_order.is_empty() {
+            return String::new();
+        }
+
+        let orders = self
+            .clustering_keys
+            .iter()
+            .map(|key| format!("{} {}", quote_identifier(key), self.clustering_order_of(key)))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        format!("CLUSTERING ORDER BY ({})", orders)
+    }
 }
 
 impl SchemaObject {
@@ -55,7 +136,11 @@ impl SchemaObject {
             static_columns: Vec::new(),
             global_secondary_indexes: Vec::new(),
             local_secondary_indexes: Vec::new(),
+            custom_indexes: Vec::new(),
             table_options: None,
+            clustering_order: Vec::new(),
+            renamed_fields: Vec::new(),
+            renamed_from: None,
         }
     }
 
@@ -65,7 +150,7 @@ impl SchemaObject {
         for (field_name, field_type, is_static) in self.fields.iter() {
             cql_fields.push_str(&format!(
                 "    {} {}{},\n",
-                field_name.bright_cyan().bold(),
+                quote_identifier(field_name).bright_cyan().bold(),
                 field_type.bright_yellow(),
                 if *is_static { " static" } else { "" }
             ));
@@ -77,5 +162,204 @@ impl SchemaObject {
     }
 }
 
+/// Rust type names that are accepted for CQL native types, besides charybdis type aliases. `time::Date` and
+/// `time::Time` already match CQL type names once their path is stripped.
+const RUST_TYPE_ALIASES: [(&str, &str); 12] = [
+    ("BigDecimal", "Decimal"),
+    ("CqlDate", "Date"),
+    ("CqlDecimal", "Decimal"),
+    ("CqlDuration", "Duration"),
+    ("CqlTime", "Time"),
+    ("CqlTimestamp", "Timestamp"),
+    ("CqlVarint", "Varint"),
+    ("DateTime", "Timestamp"),
+    ("IpAddr", "Inet"),
+    ("NaiveDate", "Date"),
+    ("NaiveTime", "Time"),
+    ("OffsetDateTime", "Timestamp"),
+];
+
+/// CQL type of Rust field type, e.g. `chrono::DateTime<chrono::Utc>` becomes `Timestamp` and
+/// `Map<Text, BigDecimal>` becomes `Map<Text, Decimal>`.
+pub fn rust_type_to_cql(field_type: &str) -> String {
+    let field_type = strip_type_paths(field_type);
+    let field_type = strip_date_time_arguments(&field_type);
+
+    replace_rust_type_aliases(&field_type)
+}
+
+/// Strips module paths of type names, e.g. `chrono :: DateTime < chrono :: Utc >` becomes `DateTime < Utc >`.
+fn strip_type_paths(field_type: &str) -> String {
+    let mut stripped = String::with_capacity(field_type.len());
+
+    for (i, segment) in field_type.split("::").enumerate() {
+        if i == 0 {
+            stripped.push_str(segment);
+            continue;
+        }
+
+        // identifier preceding `::` is module
+        let module_start = stripped
+            .trim_end()
+            .trim_end_matches(|c: char| c.is_alphanumeric() || c == '_')
+            .len();
+
+        stripped.truncate(module_start);
+        stripped.push_str(segment.trim_start());
+    }
+
+    stripped
+}
+
+/// `DateTime<Tz>` is stored as `timestamp` regardless of its time zone, so time zone argument is dropped.
+fn strip_date_time_arguments(field_type: &str) -> String {
+    let mut stripped = String::with_capacity(field_type.len());
+    let mut rest = field_type;
+
+    while let Some(pos) = rest.find("DateTime") {
+        let (date_time, after) = rest.split_at(pos + "DateTime".len());
+        stripped.push_str(date_time);
+
+        let time_zone = after
+            .trim_start()
+            .strip_prefix('<')
+            .and_then(|args| args.split_once('>'));
+
+        rest = match time_zone {
+            Some((_, after_time_zone)) => after_time_zone,
+            None => after,
+        };
+    }
+
+    stripped.push_str(rest);
+
+    stripped
+}
+
+/// Replaces Rust type names with CQL type names, e.g. `Map < Text , BigDecimal >` becomes
+/// `Map < Text , Decimal >`.
+pub fn replace_rust_type_aliases(field_type: &str) -> String {
+    map_type_names(field_type, |type_name| {
+        RUST_TYPE_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == type_name)
+            .map(|(_, cql_type)| *cql_type)
+    })
+}
+
+/// Normalizes type of code or db schema for comparison: `Map < Text , Varchar >` and `map<text, text>` are
+/// the same type, and so are `frozen<app.address>` and `frozen<address>`.
+pub fn normalize_cql_type(field_type: &str) -> String {
+    let field_type = replace_rust_type_aliases(field_type).to_lowercase().replace(' ', "");
+    let field_type = strip_keyspace_qualifiers(&field_type);
+
+    // varchar is stored as text
+    map_type_names(&field_type, |type_name| (type_name == "varchar").then_some("text"))
+}
+
+/// Strips keyspace of qualified UDT names, e.g. `frozen<app.address>` becomes `frozen<address>`. Columns can refer
+/// only to UDTs of their own keyspace, and `system_schema` stores their types unqualified. Dots within quoted names
+/// don't separate keyspace, like in [split_qualified_name](crate::identifier::split_qualified_name).
+pub fn strip_keyspace_qualifiers(field_type: &str) -> String {
+    let mut stripped = String::with_capacity(field_type.len());
+    let mut type_name = String::new();
+    let mut quoted = false;
+
+    for c in field_type.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                type_name.push(c);
+            }
+            c if quoted => type_name.push(c),
+            '.' => type_name.clear(),
+            c if c.is_alphanumeric() || c == '_' => type_name.push(c),
+            c => {
+                stripped.push_str(&type_name);
+                stripped.push(c);
+                type_name.clear();
+            }
+        }
+    }
+
+    stripped.push_str(&type_name);
+
+    stripped
+}
+
+fn map_type_names(field_type: &str, map: impl Fn(&str) -> Option<&'static str>) -> String {
+    let mut mapped = String::with_capacity(field_type.len());
+    let mut type_name = String::new();
+
+    for c in field_type.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            type_name.push(c);
+            continue;
+        }
+
+        mapped.push_str(map(&type_name).unwrap_or(&type_name));
+        mapped.push(c);
+        type_name.clear();
+    }
+
+    mapped.push_str(map(&type_name).unwrap_or(&type_name));
+
+    mapped
+}
+
+/// Clustering order declared in table options, e.g. `CLUSTERING ORDER BY (created_at DESC, id ASC)`.
+pub(crate) fn clustering_order_from_table_options(table_options: &str) -> Vec<(FieldName, ClusteringOrder)> {
+    let uppercase = table_options.to_ascii_uppercase();
+
+    let clause = match uppercase.find("CLUSTERING ORDER BY") {
+        Some(position) => &table_options[position + "CLUSTERING ORDER BY".len()..],
+        None => return vec![],
+    };
+
+    let orders = clause
+        .trim_start()
+        .strip_prefix('(')
+        .and_then(|orders| orders.split_once(')'));
+    let (orders, _) = match orders {
+        Some(orders) => orders,
+        None => return vec![],
+    };
+
+    orders
+        .split(',')
+        .filter_map(|key_order| {
+            let (key, order) = key_order.trim().rsplit_once(char::is_whitespace)?;
+
+            Some((normalize_identifier(key), ClusteringOrder::parse(order)?))
+        })
+        .collect()
+}
+
 pub type ModelName = String;
 pub type SchemaObjects = HashMap<ModelName, SchemaObject>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_keyspace_of_qualified_types() {
+        assert_eq!(
+            strip_keyspace_qualifiers("map<text, frozen<app.address>>"),
+            "map<text, frozen<address>>"
+        );
+        assert_eq!(strip_keyspace_qualifiers("\"App\".\"Address\""), "\"Address\"");
+    }
+
+    #[test]
+    fn keeps_dots_of_quoted_types() {
+        assert_eq!(
+            strip_keyspace_qualifiers("frozen<\"home.address\">"),
+            "frozen<\"home.address\">"
+        );
+        assert_eq!(
+            strip_keyspace_qualifiers("list<frozen<app.\"home.address\">>"),
+            "list<frozen<\"home.address\">>"
+        );
+    }
+}
@@ -5,21 +5,33 @@ use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use walkdir::{DirEntry, WalkDir};
 
+use crate::log_error;
 use crate::schema::{SchemaObject, SchemaObjects};
 
 mod parser;
 
-#[derive(Eq, PartialEq)]
-pub(crate) enum ModelMacro {
+pub use parser::model_schema_objects;
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum ModelMacro {
     Table,
+    /// Table holding only counter columns besides primary key
+    CounterTable,
     Udt,
     MaterializedView,
 }
 
+impl ModelMacro {
+    pub fn is_table(&self) -> bool {
+        matches!(self, ModelMacro::Table | ModelMacro::CounterTable)
+    }
+}
+
 impl Display for ModelMacro {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ModelMacro::Table => write!(f, "charybdis_model"),
+            ModelMacro::CounterTable => write!(f, "charybdis_counter_model"),
             ModelMacro::MaterializedView => write!(f, "charybdis_view_model"),
             ModelMacro::Udt => write!(f, "charybdis_udt_model"),
         }
@@ -34,6 +46,10 @@ pub struct CodeSchema {
 }
 
 impl CodeSchema {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "code_schema", skip_all, fields(project_root = %project_root))
+    )]
     pub fn new(project_root: &String) -> CodeSchema {
         let mut current_code_schema = CodeSchema {
             tables: SchemaObjects::new(),
@@ -61,7 +77,7 @@ impl CodeSchema {
                 let file_content: String = parser::parse_file_as_string(entry.path());
                 let ast: syn::File = syn::parse_file(&file_content)
                     .map_err(|e| {
-                        println!(
+                        log_error!(
                             "{}\n",
                             format!("Error parsing file: {}", file_content).bright_red().bold()
                         );
@@ -80,9 +96,7 @@ impl CodeSchema {
         let schema_objects: Vec<SchemaObject> = parser::parse_charybdis_model_def(ast, ModelMacro::MaterializedView);
 
         schema_objects.into_iter().for_each(|schema_object| {
-            let table_name = schema_object.table_name.clone();
-
-            self.materialized_views.insert(table_name, schema_object);
+            self.insert(ModelMacro::MaterializedView, schema_object);
         });
     }
 
@@ -90,19 +104,33 @@ impl CodeSchema {
         let schema_objects: Vec<SchemaObject> = parser::parse_charybdis_model_def(ast, ModelMacro::Udt);
 
         schema_objects.into_iter().for_each(|schema_object| {
-            let type_name = schema_object.type_name.to_lowercase().clone();
-
-            self.udts.insert(type_name, schema_object);
+            self.insert(ModelMacro::Udt, schema_object);
         });
     }
 
     pub fn populate_tables(&mut self, ast: &syn::File) {
-        let schema_object: Vec<SchemaObject> = parser::parse_charybdis_model_def(ast, ModelMacro::Table);
+        for model_macro in [ModelMacro::Table, ModelMacro::CounterTable] {
+            let schema_objects: Vec<SchemaObject> = parser::parse_charybdis_model_def(ast, model_macro);
 
-        schema_object.into_iter().for_each(|schema_object| {
-            let table_name = schema_object.table_name.clone();
+            schema_objects.into_iter().for_each(|schema_object| {
+                self.insert(model_macro, schema_object);
+            });
+        }
+    }
 
-            self.tables.insert(table_name, schema_object);
-        });
+    /// Tables and materialized views are keyed by table name, UDTs by lowercase type name.
+    pub fn insert(&mut self, model_macro: ModelMacro, schema_object: SchemaObject) {
+        match model_macro {
+            ModelMacro::Table | ModelMacro::CounterTable => {
+                self.tables.insert(schema_object.table_name.clone(), schema_object);
+            }
+            ModelMacro::MaterializedView => {
+                self.materialized_views
+                    .insert(schema_object.table_name.clone(), schema_object);
+            }
+            ModelMacro::Udt => {
+                self.udts.insert(schema_object.type_name.to_lowercase(), schema_object);
+            }
+        }
     }
 }
@@ -1,12 +1,14 @@
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
-use syn::{Fields, GenericArgument, Item, ItemStruct, PathArguments};
+use syn::{Fields, FieldsNamed, GenericArgument, Item, PathArguments};
 
 use crate::fields::CharybdisFields;
+use crate::identifier::{normalize_identifier, normalize_index_target, quote_identifier};
 use crate::schema::code_schema::ModelMacro;
-use crate::schema::SchemaObject;
+use crate::schema::{rust_type_to_cql, SchemaObject};
 use crate::traits::CharybdisMacroArgs;
 
 pub(crate) fn parse_file_as_string(path: &Path) -> String {
@@ -25,90 +27,209 @@ pub(crate) fn parse_charybdis_model_def(ast: &syn::File, model_macro: ModelMacro
 
     for item in &ast.items {
         if let Item::Struct(item_struct) = item {
-            // If the struct doesn't have the required macro, continue to the next item.
-            if !item_struct
-                .attrs
-                .iter()
-                .any(|attr| attr.path().is_ident(model_macro.to_string().as_str()))
-            {
-                continue;
-            }
+            for attr in &item_struct.attrs {
+                // If the struct doesn't have the required macro, continue to the next item.
+                if !attr.path().is_ident(model_macro.to_string().as_str()) {
+                    continue;
+                }
+
+                let args: CharybdisMacroArgs = attr.parse_args().unwrap();
 
-            schema_objects.push(extract_schema_object(item_struct, &model_macro));
+                if let Fields::Named(fields_named) = &item_struct.fields {
+                    schema_objects.extend(model_schema_objects(&args, fields_named, &model_macro));
+                }
+            }
         }
     }
 
     schema_objects
 }
 
-fn extract_schema_object(item_struct: &ItemStruct, model_macro: &ModelMacro) -> SchemaObject {
+/// Schema objects of single model definition. Table models also produce schema objects of their
+/// lookup tables.
+pub fn model_schema_objects(
+    args: &CharybdisMacroArgs,
+    fields_named: &FieldsNamed,
+    model_macro: &ModelMacro,
+) -> Vec<SchemaObject> {
+    let schema_object = extract_schema_object(args, fields_named, model_macro);
+    let mut schema_objects = Vec::new();
+
+    if model_macro == &ModelMacro::Table {
+        schema_objects.extend(lookup_schema_objects(args, fields_named, &schema_object));
+    }
+
+    schema_objects.push(schema_object);
+
+    schema_objects
+}
+
+fn extract_schema_object(
+    args: &CharybdisMacroArgs,
+    fields_named: &FieldsNamed,
+    model_macro: &ModelMacro,
+) -> SchemaObject {
     let mut schema_object: SchemaObject = SchemaObject::new();
 
-    for attr in &item_struct.attrs {
-        if attr.path().is_ident(model_macro.to_string().as_str()) {
-            let args: CharybdisMacroArgs = attr.parse_args().unwrap();
+    if let Some(table_name) = &args.table_name {
+        schema_object.table_name = table_name.clone();
+    } else if model_macro.is_table() {
+        panic!("Table name is required in {} macro", model_macro);
+    }
 
-            if let Some(table_name) = args.table_name {
-                schema_object.table_name = table_name;
-            } else if model_macro == &ModelMacro::Table {
-                panic!("Table name is required in charybdis_model macro");
-            }
+    if let Some(base_table) = &args.base_table {
+        schema_object.base_table = base_table.clone();
+    } else if model_macro == &ModelMacro::MaterializedView {
+        panic!("Base table is required in charybdis_view_model macro");
+    }
 
-            if let Some(base_table) = args.base_table {
-                schema_object.base_table = base_table;
-            } else if model_macro == &ModelMacro::MaterializedView {
-                panic!("Base table is required in charybdis_view_model macro");
-            }
+    if let Some(type_name) = &args.type_name {
+        schema_object.type_name = type_name.clone();
+    } else if model_macro == &ModelMacro::Udt {
+        panic!("Type name is required in charybdis_udt_model macro");
+    }
 
-            if let Some(type_name) = args.type_name {
-                schema_object.type_name = type_name;
-            } else if model_macro == &ModelMacro::Udt {
-                panic!("Type name is required in charybdis_udt_model macro");
-            }
+    // macro args reference columns by struct field names
+    let db_fields = CharybdisFields::db_fields(fields_named);
+    let db_names = db_names(fields_named);
+    let db_name = |name: &String| {
+        db_names
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| normalize_identifier(name))
+    };
+
+    if let Some(partition_keys) = &args.partition_keys {
+        schema_object.partition_keys = partition_keys.iter().map(db_name).collect();
+    } else if model_macro.is_table() {
+        panic!("Partition keys are required in {} macro", model_macro);
+    }
 
-            if let Some(partition_keys) = args.partition_keys {
-                schema_object.partition_keys = partition_keys;
-            } else if model_macro == &ModelMacro::Table {
-                panic!("Partition keys are required in charybdis_model macro");
-            }
+    schema_object.clustering_keys = args.clustering_keys.iter().flatten().map(db_name).collect();
+    schema_object.clustering_order = args
+        .clustering_order()
+        .iter()
+        .map(|(key, order)| (db_name(key), *order))
+        .collect();
+    schema_object.static_columns = args.static_columns().iter().map(db_name).collect();
+
+    if let Some(gsi) = &args.global_secondary_indexes {
+        gsi.iter().for_each(|global_idx| {
+            schema_object
+                .global_secondary_indexes
+                .push(("".to_string(), index_target(global_idx, &db_names)));
+        });
+    }
 
-            schema_object.clustering_keys = args.clustering_keys.unwrap_or_default();
-            schema_object.static_columns = args.static_columns.unwrap_or_default();
+    if let Some(lsi) = &args.local_secondary_indexes {
+        lsi.iter().for_each(|local_idx| {
+            schema_object
+                .local_secondary_indexes
+                .push(("".to_string(), index_target(local_idx, &db_names)));
+        });
+    }
 
-            if let Some(gsi) = args.global_secondary_indexes {
-                gsi.iter().for_each(|global_idx| {
-                    schema_object
-                        .global_secondary_indexes
-                        .push(("".to_string(), global_idx.to_string()));
-                });
-            }
+    if let Some(custom_indexes) = &args.custom_indexes {
+        custom_indexes.iter().for_each(|custom_idx| {
+            let mut custom_idx = custom_idx.clone();
+            custom_idx.target = index_target(&custom_idx.target, &db_names);
 
-            if let Some(lsi) = args.local_secondary_indexes {
-                lsi.iter().for_each(|local_idx| {
-                    schema_object
-                        .local_secondary_indexes
-                        .push(("".to_string(), local_idx.clone()));
-                });
-            }
+            schema_object.custom_indexes.push(("".to_string(), custom_idx));
+        });
+    }
+
+    schema_object.table_options = args.table_options.clone();
+    schema_object.renamed_from = args.renamed_from.clone();
 
-            schema_object.table_options = args.table_options;
+    // parse struct fields
+    for field in db_fields {
+        let field_name = field.db_name.clone();
+        let field_type = type_with_arguments(field.db_ty_path());
+        let is_static = args.static_columns().contains(&field.name);
+
+        if let Some(collection_index) = field.collection_index {
+            schema_object.global_secondary_indexes.push((
+                "".to_string(),
+                normalize_index_target(&collection_index.target(&field.cql_name())),
+            ));
         }
+
+        if let Some(renamed_from) = field.renamed_from {
+            schema_object
+                .renamed_fields
+                .push((normalize_identifier(&renamed_from), field_name.clone()));
+        }
+
+        schema_object.push_field(field_name, field_type, is_static);
     }
 
-    // parse struct fields
-    if let Fields::Named(fields_named) = &item_struct.fields {
-        let db_fields = CharybdisFields::db_fields(fields_named);
+    schema_object
+}
 
-        for field in db_fields {
-            let field_name = field.ident.to_string();
-            let field_type = type_with_arguments(&field.ty_path);
-            let is_static = schema_object.static_columns.contains(&field_name);
+/// Column names of struct fields, keyed by field name.
+fn db_names(fields_named: &FieldsNamed) -> HashMap<String, String> {
+    CharybdisFields::db_fields(fields_named)
+        .into_iter()
+        .map(|field| (field.name, field.db_name))
+        .collect()
+}
 
-            schema_object.push_field(field_name, field_type, is_static);
+/// Index target of macro args with struct field replaced by its column, e.g. `keys(tags)`.
+fn index_target(target: &str, db_names: &HashMap<String, String>) -> String {
+    let column_target = |column: &str| {
+        db_names
+            .get(column.trim())
+            .map_or(column.to_string(), |db_name| quote_identifier(db_name))
+    };
+
+    let target = match target.trim().split_once('(') {
+        Some((function, column)) if column.ends_with(')') => {
+            format!("{}({})", function, column_target(&column[..column.len() - 1]))
         }
+        _ => column_target(target),
+    };
+
+    normalize_index_target(&target)
+}
+
+/// Lookup tables are generated by `charybdis_model` macro, so there is no struct to parse. They are keyed by
+/// lookup field and hold primary key of the base table.
+fn lookup_schema_objects(
+    args: &CharybdisMacroArgs,
+    fields_named: &FieldsNamed,
+    base: &SchemaObject,
+) -> Vec<SchemaObject> {
+    let mut schema_objects = Vec::new();
+    let db_names = db_names(fields_named);
+
+    for lookup_field in args.lookup_tables() {
+        let mut schema_object = SchemaObject::new();
+
+        schema_object.table_name = args.lookup_table_name(lookup_field);
+
+        let lookup_field = db_names
+            .get(lookup_field)
+            .unwrap_or_else(|| panic!("Lookup table field {} not found in struct fields", lookup_field));
+
+        schema_object.partition_keys = vec![lookup_field.clone()];
+
+        let fields = std::iter::once(lookup_field)
+            .chain(base.partition_keys.iter())
+            .chain(base.clustering_keys.iter());
+
+        for field_name in fields {
+            let field_type = base
+                .types_by_name
+                .get(field_name)
+                .unwrap_or_else(|| panic!("Lookup table field {} not found in struct fields", field_name));
+
+            schema_object.push_field(field_name.clone(), field_type.clone(), false);
+        }
+
+        schema_objects.push(schema_object);
     }
 
-    schema_object
+    schema_objects
 }
 
 fn type_with_arguments(type_path: &syn::TypePath) -> String {
@@ -125,10 +246,5 @@ fn type_with_arguments(type_path: &syn::TypePath) -> String {
         }
     }
 
-    // strip if full path is provided
-    if type_name.contains("::") {
-        type_name = type_name.split("::").last().unwrap().to_string();
-    }
-
-    type_name
+    rust_type_to_cql(&type_name)
 }
@@ -6,8 +6,9 @@ use serde::{Deserialize, Serialize};
 use serde_json::to_string_pretty;
 
 use crate::errors::DbSchemaParserError;
+use crate::log_error;
 use crate::schema::secondary_indexes::{IndexTarget, SecondaryIndex};
-use crate::schema::{SchemaObject, SchemaObjects};
+use crate::schema::{ClusteringOrder, SchemaObject, SchemaObjects};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DbSchema {
@@ -23,6 +24,10 @@ pub struct DbSchema {
  * It is used to compare the current state to the desired state of the database schema.
  */
 impl DbSchema {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "db_schema", skip_all, fields(keyspace = %keyspace_name))
+    )]
     pub async fn new(session: &Session, keyspace_name: String) -> DbSchema {
         let mut current_schema = DbSchema {
             tables: HashMap::new(),
@@ -35,7 +40,7 @@ impl DbSchema {
             .get_tables_from_system_schema(session)
             .await
             .map_err(|e| {
-                println!(
+                log_error!(
                     "{}\n",
                     format!("Error getting tables from system_schema: {}", e)
                         .bright_red()
@@ -49,7 +54,7 @@ impl DbSchema {
             .get_udts_from_system_schema(session)
             .await
             .map_err(|e| {
-                println!(
+                log_error!(
                     "{}\n",
                     format!("Error getting udts from system_schema: {}", e)
                         .bright_red()
@@ -63,7 +68,7 @@ impl DbSchema {
             .get_mvs_from_system_schema(session)
             .await
             .map_err(|e| {
-                println!(
+                log_error!(
                     "{}\n",
                     format!("Error getting materialized views from system_schema: {}", e)
                         .bright_red()
@@ -76,6 +81,7 @@ impl DbSchema {
         current_schema
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     async fn get_tables_from_system_schema(&mut self, session: &Session) -> Result<(), DbSchemaParserError> {
         // get tables as a HashMap of column_name => column_type
         // Parse row as a single column containing an int value
@@ -99,6 +105,7 @@ impl DbSchema {
         Ok(())
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(table = %table_name)))]
     async fn populate_table_columns(
         &mut self,
         table_name: &String,
@@ -130,6 +137,7 @@ impl DbSchema {
         Ok(())
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(table = %table_name)))]
     async fn populate_table_partition_keys(
         &mut self,
         table_name: &String,
@@ -162,38 +170,24 @@ impl DbSchema {
         Ok(())
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(table = %table_name)))]
     async fn populate_table_clustering_keys(
         &mut self,
         table_name: &String,
         session: &Session,
     ) -> Result<(), DbSchemaParserError> {
-        // get partition keys for provided table
-        let cql = r#"
-            SELECT column_name
-            FROM system_schema.columns
-            WHERE keyspace_name = ?
-            AND table_name = ?
-            AND kind = 'clustering'
-            ALLOW FILTERING"#;
+        let clustering_keys = self.clustering_keys(table_name, session).await?;
+        let table_schema = self.tables.get_mut(table_name).unwrap();
 
-        if let Some(rows) = session
-            .query_unpaged(cql, (&self.keyspace_name, &table_name))
-            .await?
-            .rows
-        {
-            for row in rows {
-                let str_value: (String,) = row.into_typed::<(String,)>()?;
-                self.tables
-                    .get_mut(table_name)
-                    .unwrap()
-                    .clustering_keys
-                    .push(str_value.0);
-            }
+        for (column_name, clustering_order) in clustering_keys {
+            table_schema.clustering_keys.push(column_name.clone());
+            table_schema.clustering_order.push((column_name, clustering_order));
         }
 
         Ok(())
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(table = %table_name)))]
     async fn populate_table_secondary_indexes(
         &mut self,
         table_name: &String,
@@ -226,6 +220,9 @@ impl DbSchema {
                     IndexTarget::LocalSecondaryIndex(target) => {
                         table_schema.local_secondary_indexes.push((index_name, target));
                     }
+                    IndexTarget::CustomIndex(custom_index) => {
+                        table_schema.custom_indexes.push((index_name, custom_index));
+                    }
                 }
             }
         }
@@ -233,6 +230,7 @@ impl DbSchema {
         Ok(())
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     async fn get_udts_from_system_schema(&mut self, session: &Session) -> Result<(), DbSchemaParserError> {
         // get tables as a HashMap of column_name => column_type
         // Parse row as a single column containing an int value
@@ -260,6 +258,7 @@ impl DbSchema {
         Ok(())
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     async fn get_mvs_from_system_schema(&mut self, session: &Session) -> Result<(), DbSchemaParserError> {
         // get tables as a HashMap of column_name => column_type
         let cql = r#"
@@ -279,6 +278,7 @@ impl DbSchema {
         Ok(())
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(view = %view_name)))]
     async fn populate_mv_columns(&mut self, view_name: &String, session: &Session) -> Result<(), DbSchemaParserError> {
         // get columns and types for views
         let cql = r#"
@@ -304,6 +304,7 @@ impl DbSchema {
         Ok(())
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(view = %view_name)))]
     async fn populate_mv_partition_key(
         &mut self,
         view_name: &String,
@@ -335,35 +336,58 @@ impl DbSchema {
         Ok(())
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(view = %view_name)))]
     async fn populate_mv_clustering_keys(
         &mut self,
         view_name: &String,
         session: &Session,
     ) -> Result<(), DbSchemaParserError> {
+        let clustering_keys = self.clustering_keys(view_name, session).await?;
+        let view_schema = self.materialized_views.get_mut(view_name).unwrap();
+
+        for (column_name, clustering_order) in clustering_keys {
+            view_schema.clustering_keys.push(column_name.clone());
+            view_schema.clustering_order.push((column_name, clustering_order));
+        }
+
+        Ok(())
+    }
+
+    /// Clustering keys of table or view with their order, sorted by position in primary key.
+    async fn clustering_keys(
+        &self,
+        table_name: &String,
+        session: &Session,
+    ) -> Result<Vec<(String, ClusteringOrder)>, DbSchemaParserError> {
         let cql = r#"
-            SELECT column_name
+            SELECT column_name, position, clustering_order
             FROM system_schema.columns
             WHERE keyspace_name = ?
             AND table_name = ?
             AND kind = 'clustering'
             ALLOW FILTERING"#;
 
+        let mut clustering_keys = vec![];
+
         if let Some(rows) = session
-            .query_unpaged(cql, (&self.keyspace_name, &view_name))
+            .query_unpaged(cql, (&self.keyspace_name, &table_name))
             .await?
             .rows
         {
             for row in rows {
-                let str_value: (String,) = row.into_typed::<(String,)>()?;
-                self.materialized_views
-                    .get_mut(view_name)
-                    .unwrap()
-                    .clustering_keys
-                    .push(str_value.0);
+                let (column_name, position, clustering_order) = row.into_typed::<(String, i32, String)>()?;
+                let clustering_order = ClusteringOrder::parse(&clustering_order).unwrap_or_default();
+
+                clustering_keys.push((position, column_name, clustering_order));
             }
         }
 
-        Ok(())
+        clustering_keys.sort_by_key(|(position, _, _)| *position);
+
+        Ok(clustering_keys
+            .into_iter()
+            .map(|(_, column_name, clustering_order)| (column_name, clustering_order))
+            .collect())
     }
 
     pub fn get_current_schema_as_json(&self) -> String {
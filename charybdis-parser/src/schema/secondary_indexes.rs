@@ -1,22 +1,60 @@
+use std::collections::BTreeMap;
+
 use scylla::cql_to_rust::{FromCqlVal, FromCqlValError};
 use scylla::frame::response::result::CqlValue;
 use serde::{Deserialize, Serialize};
 
+use crate::identifier::{normalize_index_target, quote_identifier};
+
+const TARGET_OPTION: &str = "target";
+const CLASS_NAME_OPTION: &str = "class_name";
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct LocalIndexStructure {
     pub pk: Vec<String>,
     pub ck: Vec<String>,
 }
 
+/// Custom secondary index, e.g. SASI:
+/// `CREATE CUSTOM INDEX ON posts (title) USING 'org.apache.cassandra.index.sasi.SASIIndex'
+///  WITH OPTIONS = {'mode': 'CONTAINS'}`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct CustomIndex {
+    pub target: String,
+    pub class_name: String,
+    pub options: BTreeMap<String, String>,
+}
+
+impl CustomIndex {
+    /// returns `WITH OPTIONS = {...}` clause or empty string if there are no options
+    pub fn options_clause(&self) -> String {
+        if self.options.is_empty() {
+            return String::new();
+        }
+
+        let options = self
+            .options
+            .iter()
+            .map(|(key, value)| format!("'{}': '{}'", key, value))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        format!(" WITH OPTIONS = {{{}}}", options)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(untagged)]
 pub enum IndexTarget {
     GlobalSecondaryIndex(String),
     LocalSecondaryIndex(String),
+    CustomIndex(CustomIndex),
 }
 
-// cql returns {'target': '{"pk":["node_id"],"ck":["id"]}'} for a local secondary index
-// and {'target': 'node_id'} for a global secondary index
+// cql returns {'target': '{"pk":["node_id"],"ck":["id"]}'} for a local secondary index,
+// {'target': 'node_id'} for a global secondary index
+// and {'class_name': 'org.apache.cassandra.index.sasi.SASIIndex', 'mode': 'CONTAINS', 'target': 'title'}
+// for a custom index
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct SecondaryIndex {
     pub target: IndexTarget,
@@ -26,20 +64,39 @@ impl FromCqlVal<CqlValue> for SecondaryIndex {
     fn from_cql(value: CqlValue) -> Result<Self, FromCqlValError> {
         match value {
             CqlValue::Map(map) => {
-                let target_value = map[0].1.clone();
-                let target_val_string = target_value.into_string().unwrap();
+                let mut options = BTreeMap::new();
+
+                for (key, value) in map {
+                    let key = key.into_string().ok_or(FromCqlValError::BadCqlType)?;
+                    let value = value.into_string().ok_or(FromCqlValError::BadCqlType)?;
+
+                    options.insert(key, value);
+                }
+
+                let target_val_string = options.remove(TARGET_OPTION).ok_or(FromCqlValError::BadVal)?;
+
+                if let Some(class_name) = options.remove(CLASS_NAME_OPTION) {
+                    return Ok(SecondaryIndex {
+                        target: IndexTarget::CustomIndex(CustomIndex {
+                            target: normalize_index_target(&target_val_string),
+                            class_name,
+                            options,
+                        }),
+                    });
+                }
 
                 if target_val_string.starts_with('{') {
                     let parsed: LocalIndexStructure = serde_json::from_str(&target_val_string).unwrap();
                     let idx = parsed.ck.first().unwrap();
 
+                    // local index structure holds column names as stored, not as written in CQL
                     return Ok(SecondaryIndex {
-                        target: IndexTarget::LocalSecondaryIndex(idx.to_string()),
+                        target: IndexTarget::LocalSecondaryIndex(quote_identifier(idx)),
                     });
                 }
 
                 Ok(SecondaryIndex {
-                    target: IndexTarget::GlobalSecondaryIndex(target_val_string),
+                    target: IndexTarget::GlobalSecondaryIndex(normalize_index_target(&target_val_string)),
                 })
             }
             _ => Err(FromCqlValError::BadVal),
@@ -0,0 +1,163 @@
+/// Clauses that follow `WHERE` conditions. Conditions are inserted before the first of them.
+const TRAILING_CLAUSES: [&str; 6] = [
+    " GROUP BY ",
+    " ORDER BY ",
+    " PER PARTITION LIMIT ",
+    " LIMIT ",
+    " ALLOW FILTERING",
+    " IF ",
+];
+
+/// Position of the first occurrence of `keyword` in CQL statement, compared case-insensitively and skipping string
+/// literals and quoted identifiers, e.g. `LIMIT` of `title = ' LIMIT '` or of `"LIMIT"` column is not found.
+/// Keyword is expected to be uppercase, like `" WHERE "` or `"TOKEN("`.
+pub fn find_keyword(statement: &str, keyword: &str) -> Option<usize> {
+    let mut quote = None;
+
+    for (position, c) in statement.char_indices() {
+        match (quote, c) {
+            // doubled quotes escape themselves, so they close and reopen quoted part
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, _) if starts_with_keyword(&statement[position..], keyword) => return Some(position),
+            (None, _) => {}
+        }
+    }
+
+    None
+}
+
+/// True if `keyword` occurs in CQL statement outside of string literals and quoted identifiers, see
+/// [find_keyword].
+pub fn contains_keyword(statement: &str, keyword: &str) -> bool {
+    find_keyword(statement, keyword).is_some()
+}
+
+fn starts_with_keyword(rest: &str, keyword: &str) -> bool {
+    rest.len() >= keyword.len() && rest.as_bytes()[..keyword.len()].eq_ignore_ascii_case(keyword.as_bytes())
+}
+
+/// Splits statement before the first of its clauses that follow `WHERE` conditions, see [find_keyword].
+pub fn split_trailing_clauses(statement: &str) -> (&str, &str) {
+    let position = TRAILING_CLAUSES
+        .iter()
+        .filter_map(|clause| find_keyword(statement, clause))
+        .min()
+        .unwrap_or(statement.len());
+
+    statement.split_at(position)
+}
+
+/// Appends conditions to WHERE clause of statement, or adds WHERE clause if statement has none.
+pub fn insert_conditions(statement: &str, conditions_cql: &str) -> String {
+    let (conditions, trailing) = split_trailing_clauses(statement);
+
+    let keyword = if contains_keyword(conditions, " WHERE ") {
+        "AND"
+    } else {
+        "WHERE"
+    };
+
+    format!("{} {} {}{}", conditions.trim_end(), keyword, conditions_cql, trailing)
+}
+
+/// Filters out rows marked as deleted by model with `soft_delete` field, e.g.
+/// `SELECT ... FROM posts WHERE category_id = ? AND deleted_at = 0 LIMIT ? ALLOW FILTERING`. Live rows keep the
+/// field at Unix epoch, as CQL can't filter rows by `null` column. Used by generated statements and by statements
+/// built at runtime.
+pub fn soft_delete_filtered(statement: &str, soft_delete_column: &str) -> String {
+    let mut filtered = insert_conditions(statement, &format!("{} = 0", soft_delete_column));
+
+    if !filtered.to_ascii_uppercase().ends_with(" ALLOW FILTERING") {
+        filtered.push_str(" ALLOW FILTERING");
+    }
+
+    filtered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_keywords_regardless_of_case() {
+        assert_eq!(find_keyword("SELECT id FROM posts WHERE id = ?", " WHERE "), Some(20));
+        assert_eq!(find_keyword("select id from posts where id = ?", " WHERE "), Some(20));
+        assert_eq!(find_keyword("SELECT id FROM posts", " WHERE "), None);
+    }
+
+    #[test]
+    fn skips_string_literals_and_quoted_identifiers() {
+        assert_eq!(
+            find_keyword("SELECT id FROM posts WHERE title = ' LIMIT ' LIMIT 1", " LIMIT "),
+            Some(44)
+        );
+        assert!(!contains_keyword(
+            r#"SELECT id FROM posts WHERE " LIMIT " = ?"#,
+            " LIMIT "
+        ));
+        assert!(!contains_keyword(
+            "SELECT id FROM posts WHERE title = 'token(id)'",
+            "TOKEN("
+        ));
+        assert!(contains_keyword("SELECT id FROM posts WHERE token(id) > ?", "TOKEN("));
+    }
+
+    #[test]
+    fn handles_escaped_quotes() {
+        assert!(!contains_keyword(
+            "SELECT id FROM posts WHERE title = 'it''s LIMIT 1'",
+            " LIMIT "
+        ));
+        assert!(contains_keyword(r#"SELECT "a""b" FROM posts WHERE id = ?"#, " WHERE "));
+    }
+
+    #[test]
+    fn trailing_clauses_are_not_matched_inside_literals_and_quoted_identifiers() {
+        let statement = r#"SELECT id FROM posts WHERE title = ' LIMIT 1' AND "if" = ' IF ' LIMIT 10"#;
+
+        assert_eq!(
+            split_trailing_clauses(statement),
+            (
+                r#"SELECT id FROM posts WHERE title = ' LIMIT 1' AND "if" = ' IF '"#,
+                " LIMIT 10"
+            )
+        );
+        assert_eq!(
+            insert_conditions("SELECT id FROM posts WHERE title = ' LIMIT '", "author_id = ?"),
+            "SELECT id FROM posts WHERE title = ' LIMIT ' AND author_id = ?"
+        );
+        assert_eq!(
+            insert_conditions("SELECT \" where \" FROM posts", "author_id = ?"),
+            "SELECT \" where \" FROM posts WHERE author_id = ?"
+        );
+    }
+
+    #[test]
+    fn soft_delete_filter_is_inserted_before_trailing_clauses() {
+        assert_eq!(
+            soft_delete_filtered("SELECT id FROM posts WHERE category_id = ? LIMIT ?", "deleted_at"),
+            "SELECT id FROM posts WHERE category_id = ? AND deleted_at = 0 LIMIT ? ALLOW FILTERING"
+        );
+        assert_eq!(
+            soft_delete_filtered("SELECT id FROM posts", "deleted_at"),
+            "SELECT id FROM posts WHERE deleted_at = 0 ALLOW FILTERING"
+        );
+        assert_eq!(
+            soft_delete_filtered("SELECT id FROM posts WHERE title = ' limit 1'", "deleted_at"),
+            "SELECT id FROM posts WHERE title = ' limit 1' AND deleted_at = 0 ALLOW FILTERING"
+        );
+    }
+
+    #[test]
+    fn soft_delete_filter_matches_clauses_regardless_of_case() {
+        assert_eq!(
+            soft_delete_filtered(
+                "select id from posts where category_id = ? order by id desc limit ? allow filtering",
+                "deleted_at"
+            ),
+            "select id from posts where category_id = ? AND deleted_at = 0 order by id desc limit ? allow filtering"
+        );
+    }
+}
@@ -4,7 +4,9 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::parse::{Parse, ParseStream};
 
-use crate::traits::array::ToStringCollection;
+use crate::schema::secondary_indexes::CustomIndex;
+use crate::schema::{clustering_order_from_table_options, ClusteringOrder};
+use crate::traits::array::{ToAssociations, ToClusteringOrder, ToCustomIndexes, ToStringCollection};
 use crate::traits::hash::hash_expr_lit_to_hash;
 
 mod array;
@@ -12,22 +14,61 @@ mod hash;
 pub mod string;
 
 static EMPTY_VEC: Vec<String> = Vec::new();
+static EMPTY_CUSTOM_INDEXES: Vec<CustomIndex> = Vec::new();
+static EMPTY_ASSOCIATIONS: Vec<Association> = Vec::new();
+static EMPTY_CLUSTERING_ORDER: Vec<(String, ClusteringOrder)> = Vec::new();
+
+/// Association declared in `belongs_to` or `has_many`, e.g. `(author, User, [author_id])`.
+/// Fields of declaring model are matched in order with primary key (`belongs_to`) or partition
+/// key (`has_many`) of associated model.
+#[derive(Debug, Clone)]
+pub struct Association {
+    pub name: String,
+    /// path of associated model, e.g. `User` or `crate::models::User`
+    pub model: String,
+    pub fields: Vec<String>,
+}
 
 #[derive(Debug, Default, Clone)]
 pub struct CharybdisMacroArgs {
     pub table_name: Option<String>,
     pub type_name: Option<String>,
     pub base_table: Option<String>,
+    /// path of model of `base_table`, e.g. `User`, that gets `find_via_<view>` finders of materialized view
+    pub base_model: Option<String>,
     pub partition_keys: Option<Vec<String>>,
     pub clustering_keys: Option<Vec<String>>,
+    /// e.g. `[(created_at, desc)]`, clustering keys that are not listed are ascending
+    pub clustering_order: Option<Vec<(String, ClusteringOrder)>>,
     pub static_columns: Option<Vec<String>>,
     pub global_secondary_indexes: Option<Vec<String>>,
     pub local_secondary_indexes: Option<Vec<String>>,
+    pub custom_indexes: Option<Vec<CustomIndex>>,
+    pub lookup_tables: Option<Vec<String>>,
     pub exclude_partial_model: Option<bool>,
     pub fields_names: Option<Vec<String>>,
     pub field_types_hash: Option<HashMap<String, TokenStream>>,
     pub field_attributes_hash: Option<HashMap<String, TokenStream>>,
     pub table_options: Option<String>,
+    /// previous name of table, so migration creates new table without dropping data of old one
+    pub renamed_from: Option<String>,
+    pub soft_delete: Option<String>,
+    /// field that rows are scoped by, checked against tenant of callback extension
+    pub tenant_key: Option<String>,
+    pub belongs_to: Option<Vec<Association>>,
+    pub has_many: Option<Vec<Association>>,
+    /// generate setters that mark changed fields, so `save` updates only changed columns
+    pub track_changes: Option<bool>,
+    /// generate `DiffModel`, so model can be written by `save_if_changed`
+    pub diff_model: Option<bool>,
+    /// generate `CqlJson` with `to_json` and `from_json` methods, all fields have to implement `CqlJson`
+    pub cql_json: Option<bool>,
+    /// prefix of generated functions and methods named after fields, e.g. `db_` for `db_find_by_id`
+    pub method_prefix: Option<String>,
+    /// prefix of generated statement consts named after fields, e.g. `DB_` for `DB_PUSH_TAGS_QUERY`
+    pub const_prefix: Option<String>,
+    /// module that statement consts named after fields are generated in, instead of model impl
+    pub queries_module: Option<String>,
 }
 
 impl CharybdisMacroArgs {
@@ -43,6 +84,26 @@ impl CharybdisMacroArgs {
         self.clustering_keys.as_ref().expect("clustering_keys is required")
     }
 
+    pub fn clustering_order(&self) -> &Vec<(String, ClusteringOrder)> {
+        self.clustering_order.as_ref().map_or(&EMPTY_CLUSTERING_ORDER, |x| x)
+    }
+
+    /// Order of clustering key declared with `clustering_order` or with `CLUSTERING ORDER BY` of table options,
+    /// ascending if it's not declared.
+    pub fn clustering_order_of(&self, field_name: &str) -> ClusteringOrder {
+        let table_options_order = self
+            .table_options
+            .as_deref()
+            .map(clustering_order_from_table_options)
+            .unwrap_or_default();
+
+        self.clustering_order()
+            .iter()
+            .chain(table_options_order.iter())
+            .find(|(key, _)| key == field_name)
+            .map_or(ClusteringOrder::Asc, |(_, order)| *order)
+    }
+
     pub fn static_columns(&self) -> &Vec<String> {
         self.static_columns.as_ref().map_or(&EMPTY_VEC, |x| x)
     }
@@ -55,6 +116,48 @@ impl CharybdisMacroArgs {
         self.local_secondary_indexes.as_ref().map_or(&EMPTY_VEC, |x| x)
     }
 
+    pub fn custom_indexes(&self) -> &Vec<CustomIndex> {
+        self.custom_indexes.as_ref().map_or(&EMPTY_CUSTOM_INDEXES, |x| x)
+    }
+
+    pub fn belongs_to(&self) -> &Vec<Association> {
+        self.belongs_to.as_ref().map_or(&EMPTY_ASSOCIATIONS, |x| x)
+    }
+
+    pub fn has_many(&self) -> &Vec<Association> {
+        self.has_many.as_ref().map_or(&EMPTY_ASSOCIATIONS, |x| x)
+    }
+
+    pub fn lookup_tables(&self) -> &Vec<String> {
+        self.lookup_tables.as_ref().map_or(&EMPTY_VEC, |x| x)
+    }
+
+    /// e.g. `users_by_email` for `lookup_tables = [email]` of `users` table
+    pub fn lookup_table_name(&self, field_name: &str) -> String {
+        format!("{}_by_{}", self.table_name(), field_name)
+    }
+
+    /// Name of generated function or method named after fields, e.g. `find_by_id` or `push_tags`, with
+    /// `method_prefix` prepended.
+    pub fn method_name(&self, name: &str) -> String {
+        format!("{}{}", self.method_prefix.as_deref().unwrap_or_default(), name)
+    }
+
+    /// Name of generated statement const named after fields, e.g. `PUSH_TAGS_QUERY`, with `const_prefix`
+    /// prepended.
+    pub fn const_name(&self, name: &str) -> String {
+        format!("{}{}", self.const_prefix.as_deref().unwrap_or_default(), name)
+    }
+
+    /// Path of statement const named after fields as referenced from generated code, e.g. `Self::PUSH_TAGS_QUERY`
+    /// or `user_queries::PUSH_TAGS_QUERY` if consts are generated in `queries_module`.
+    pub fn const_path(&self, name: &str) -> String {
+        match &self.queries_module {
+            Some(queries_module) => format!("{}::{}", queries_module, self.const_name(name)),
+            None => format!("Self::{}", self.const_name(name)),
+        }
+    }
+
     pub fn primary_key(&self) -> Vec<&String> {
         self.partition_keys().iter().chain(self.clustering_keys()).collect()
     }
@@ -65,25 +168,59 @@ impl Parse for CharybdisMacroArgs {
         let mut type_name = None;
         let mut table_name = None;
         let mut base_table = None;
+        let mut base_model = None;
         let mut partition_keys = None;
         let mut clustering_keys = None;
+        let mut clustering_order = None;
         let mut static_columns = None;
         let mut global_secondary_indexes = None;
         let mut local_secondary_indexes = None;
+        let mut custom_indexes = None;
+        let mut lookup_tables = None;
         let mut fields_names = None;
         let mut field_types_hash = None;
         let mut field_attributes_hash = None;
         let mut table_options = None;
+        let mut renamed_from = None;
         let mut exclude_partial_model = None;
+        let mut soft_delete = None;
+        let mut tenant_key = None;
+        let mut belongs_to = None;
+        let mut has_many = None;
+        let mut track_changes = None;
+        let mut diff_model = None;
+        let mut cql_json = None;
+        let mut method_prefix = None;
+        let mut const_prefix = None;
+        let mut queries_module = None;
 
         while !input.is_empty() {
             let key: syn::Ident = input.parse()?;
+
+            // flag without value, e.g. `track_changes`
+            if (key == "track_changes" || key == "diff_model" || key == "cql_json") && !input.peek(syn::Token![=]) {
+                if key == "track_changes" {
+                    track_changes = Some(true);
+                } else if key == "diff_model" {
+                    diff_model = Some(true);
+                } else {
+                    cql_json = Some(true);
+                }
+
+                if !input.is_empty() {
+                    input.parse::<syn::Token![,]>()?;
+                }
+
+                continue;
+            }
+
             input.parse::<syn::Token![=]>()?;
 
             match key.to_string().as_str() {
                 "type_name" => {
+                    // type name can be qualified by keyspace, e.g. `other_ks.address`
                     let value: syn::Expr = input.parse()?;
-                    type_name = Option::from(value.to_token_stream().to_string());
+                    type_name = Option::from(value.to_token_stream().to_string().replace(' ', ""));
                 }
                 "table_name" => {
                     let value: syn::Expr = input.parse()?;
@@ -93,6 +230,10 @@ impl Parse for CharybdisMacroArgs {
                     let value: syn::Expr = input.parse()?;
                     base_table = Option::from(value.to_token_stream().to_string());
                 }
+                "base_model" => {
+                    let value: syn::Expr = input.parse()?;
+                    base_model = Option::from(value.to_token_stream().to_string());
+                }
                 "partition_keys" => {
                     let array: syn::ExprArray = input.parse()?;
                     let parsed = array.to_vec();
@@ -105,6 +246,12 @@ impl Parse for CharybdisMacroArgs {
 
                     clustering_keys = Some(parsed)
                 }
+                "clustering_order" => {
+                    let array: syn::ExprArray = input.parse()?;
+                    let parsed = array.to_clustering_order();
+
+                    clustering_order = Some(parsed)
+                }
                 "static_columns" => {
                     let array: syn::ExprArray = input.parse()?;
                     let parsed = array.to_vec();
@@ -123,6 +270,18 @@ impl Parse for CharybdisMacroArgs {
 
                     local_secondary_indexes = Some(parsed)
                 }
+                "custom_indexes" => {
+                    let array: syn::ExprArray = input.parse()?;
+                    let parsed = array.to_custom_indexes();
+
+                    custom_indexes = Some(parsed)
+                }
+                "lookup_tables" => {
+                    let array: syn::ExprArray = input.parse()?;
+                    let parsed = array.to_vec();
+
+                    lookup_tables = Some(parsed)
+                }
                 "exclude_partial_model" => {
                     let value: syn::LitBool = input.parse()?;
                     exclude_partial_model = Option::from(value.value());
@@ -150,6 +309,66 @@ impl Parse for CharybdisMacroArgs {
                     let value: syn::LitStr = input.parse()?;
                     table_options = Option::from(value.value());
                 }
+                "renamed_from" => {
+                    let value: syn::Expr = input.parse()?;
+                    renamed_from = Option::from(value.to_token_stream().to_string());
+                }
+                "soft_delete" => {
+                    let value: syn::LitStr = input.parse()?;
+                    soft_delete = Option::from(value.value());
+                }
+                "tenant_key" => {
+                    let value: syn::LitStr = input.parse()?;
+                    tenant_key = Option::from(value.value());
+                }
+                "belongs_to" => {
+                    let array: syn::ExprArray = input.parse()?;
+                    let parsed = array.to_associations();
+
+                    belongs_to = Some(parsed)
+                }
+                "has_many" => {
+                    let array: syn::ExprArray = input.parse()?;
+                    let parsed = array.to_associations();
+
+                    has_many = Some(parsed)
+                }
+                "track_changes" => {
+                    let value: syn::LitBool = input.parse()?;
+                    track_changes = Option::from(value.value());
+                }
+                "diff_model" => {
+                    let value: syn::LitBool = input.parse()?;
+                    diff_model = Option::from(value.value());
+                }
+                "cql_json" => {
+                    let value: syn::LitBool = input.parse()?;
+                    cql_json = Option::from(value.value());
+                }
+                "method_prefix" => {
+                    let value: syn::LitStr = input.parse()?;
+                    let value = value.value();
+
+                    if value != value.to_ascii_lowercase() {
+                        panic!("method_prefix must be snake case, e.g. \"db_\", got: {}", value);
+                    }
+
+                    method_prefix = Some(value);
+                }
+                "const_prefix" => {
+                    let value: syn::LitStr = input.parse()?;
+                    let value = value.value();
+
+                    if value != value.to_ascii_uppercase() {
+                        panic!("const_prefix must be upper snake case, e.g. \"DB_\", got: {}", value);
+                    }
+
+                    const_prefix = Some(value);
+                }
+                "queries_module" => {
+                    let value: syn::Expr = input.parse()?;
+                    queries_module = Option::from(value.to_token_stream().to_string());
+                }
                 _ => {}
             }
 
@@ -162,16 +381,31 @@ impl Parse for CharybdisMacroArgs {
             type_name,
             table_name,
             base_table,
+            base_model,
             partition_keys,
             clustering_keys,
+            clustering_order,
             static_columns,
             global_secondary_indexes,
             local_secondary_indexes,
+            custom_indexes,
+            lookup_tables,
             fields_names,
             field_types_hash,
             field_attributes_hash,
             table_options,
+            renamed_from,
             exclude_partial_model,
+            soft_delete,
+            tenant_key,
+            belongs_to,
+            has_many,
+            track_changes,
+            diff_model,
+            cql_json,
+            method_prefix,
+            const_prefix,
+            queries_module,
         })
     }
 }
@@ -1,6 +1,12 @@
+use std::collections::BTreeMap;
+
 use quote::ToTokens;
 use syn::ExprArray;
 
+use crate::schema::secondary_indexes::CustomIndex;
+use crate::schema::ClusteringOrder;
+use crate::traits::Association;
+
 pub(crate) trait ToStringCollection {
     fn to_vec(self) -> Vec<String>;
 }
@@ -13,3 +19,128 @@ impl ToStringCollection for ExprArray {
             .collect()
     }
 }
+
+pub(crate) trait ToCustomIndexes {
+    fn to_custom_indexes(self) -> Vec<CustomIndex>;
+}
+
+/// Parses `[(field, "index_class", [("option", "value"), ...]), ...]` into custom indexes.
+/// Options are optional, so `[(field, "index_class")]` is also valid.
+impl ToCustomIndexes for ExprArray {
+    fn to_custom_indexes(self) -> Vec<CustomIndex> {
+        self.elems
+            .into_iter()
+            .map(|elem| {
+                let tuple = match elem {
+                    syn::Expr::Tuple(tuple) => tuple,
+                    _ => panic!(
+                        "custom index must be declared as tuple: (field, \"index_class\", [(\"option\", \"value\")])"
+                    ),
+                };
+                let mut elems = tuple.elems.into_iter();
+
+                let target = elems
+                    .next()
+                    .expect("custom index target is required")
+                    .to_token_stream()
+                    .to_string();
+                let class_name = lit_str_value(elems.next().expect("custom index class is required"));
+                let mut options = BTreeMap::new();
+
+                if let Some(syn::Expr::Array(options_array)) = elems.next() {
+                    for option in options_array.elems {
+                        match option {
+                            syn::Expr::Tuple(option) if option.elems.len() == 2 => {
+                                let mut option = option.elems.into_iter();
+                                let key = lit_str_value(option.next().unwrap());
+                                let value = lit_str_value(option.next().unwrap());
+
+                                options.insert(key, value);
+                            }
+                            _ => panic!("custom index option must be declared as tuple: (\"option\", \"value\")"),
+                        }
+                    }
+                }
+
+                CustomIndex {
+                    target,
+                    class_name,
+                    options,
+                }
+            })
+            .collect()
+    }
+}
+
+pub(crate) trait ToAssociations {
+    fn to_associations(self) -> Vec<Association>;
+}
+
+/// Parses `[(name, Model, [field, ...]), ...]` into associations.
+impl ToAssociations for ExprArray {
+    fn to_associations(self) -> Vec<Association> {
+        self.elems
+            .into_iter()
+            .map(|elem| {
+                let tuple = match elem {
+                    syn::Expr::Tuple(tuple) if tuple.elems.len() == 3 => tuple,
+                    _ => panic!("association must be declared as tuple: (name, Model, [field, ...])"),
+                };
+                let mut elems = tuple.elems.into_iter();
+
+                let name = elems.next().unwrap().to_token_stream().to_string();
+                let model = match elems.next().unwrap() {
+                    syn::Expr::Path(path) => path.to_token_stream().to_string(),
+                    expr => panic!("expected associated model path, got: {}", expr.to_token_stream()),
+                };
+                let fields = match elems.next().unwrap() {
+                    syn::Expr::Array(fields) if !fields.elems.is_empty() => fields.to_vec(),
+                    expr => panic!(
+                        "expected non-empty array of fields of association {}, got: {}",
+                        name,
+                        expr.to_token_stream()
+                    ),
+                };
+
+                Association { name, model, fields }
+            })
+            .collect()
+    }
+}
+
+pub(crate) trait ToClusteringOrder {
+    fn to_clustering_order(self) -> Vec<(String, ClusteringOrder)>;
+}
+
+/// Parses `[(field, desc), ...]` into order of clustering keys.
+impl ToClusteringOrder for ExprArray {
+    fn to_clustering_order(self) -> Vec<(String, ClusteringOrder)> {
+        self.elems
+            .into_iter()
+            .map(|elem| {
+                let tuple = match elem {
+                    syn::Expr::Tuple(tuple) if tuple.elems.len() == 2 => tuple,
+                    _ => panic!("clustering order must be declared as tuple: (field, asc) or (field, desc)"),
+                };
+                let mut elems = tuple.elems.into_iter();
+
+                let field = elems.next().unwrap().to_token_stream().to_string();
+                let order = elems.next().unwrap().to_token_stream().to_string();
+                let order = ClusteringOrder::parse(&order)
+                    .unwrap_or_else(|| panic!("clustering order of {} must be asc or desc, got: {}", field, order));
+
+                (field, order)
+            })
+            .collect()
+    }
+}
+
+fn lit_str_value(expr: syn::Expr) -> String {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit_str),
+            ..
+        }) => lit_str.value(),
+        _ => panic!("expected string literal, got: {}", expr.to_token_stream()),
+    }
+}
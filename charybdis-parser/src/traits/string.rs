@@ -1,3 +1,22 @@
+pub trait ToPascalCase {
+    fn to_pascal_case(&self) -> String;
+}
+
+impl ToPascalCase for str {
+    fn to_pascal_case(&self) -> String {
+        self.split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+
+                match chars.next() {
+                    Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            })
+            .collect()
+    }
+}
+
 pub trait ToSnakeCase {
     fn to_snake_case(&self) -> String;
 }
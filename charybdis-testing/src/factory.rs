@@ -0,0 +1,89 @@
+use charybdis::errors::CharybdisError;
+use charybdis::model::Model;
+use charybdis::operations::Insert;
+use charybdis::session::CharybdisSession;
+
+type Override<M> = Box<dyn Fn(&mut M, usize)>;
+
+/// Entry point for building test models, available on every model implementing `Default`.
+/// ```rust ignore
+/// let users = User::factory()
+///     .with(|user| user.country = "US".to_string())
+///     .sequence(|user, i| user.username = format!("user_{}", i))
+///     .create_many(10, &session)
+///     .await?;
+/// ```
+pub trait Factory: Model + Default {
+    fn factory() -> ModelFactory<Self> {
+        ModelFactory::new()
+    }
+}
+
+impl<M: Model + Default> Factory for M {}
+
+/// Builds models from `Default` with overrides applied in order they were added. Ids of fields with
+/// `id_strategy` are generated for each model, so created models don't overwrite each other.
+pub struct ModelFactory<M: Model + Default> {
+    overrides: Vec<Override<M>>,
+}
+
+impl<M: Model + Default> ModelFactory<M> {
+    pub fn new() -> Self {
+        Self { overrides: vec![] }
+    }
+
+    pub fn with(mut self, f: impl Fn(&mut M) + 'static) -> Self {
+        self.overrides.push(Box::new(move |model, _| f(model)));
+        self
+    }
+
+    /// Override that receives index of built model, starting at 0.
+    pub fn sequence(mut self, f: impl Fn(&mut M, usize) + 'static) -> Self {
+        self.overrides.push(Box::new(f));
+        self
+    }
+
+    fn build_nth(&self, index: usize) -> M {
+        let mut model = M::default();
+
+        for f in &self.overrides {
+            f(&mut model, index);
+        }
+
+        model.generate_ids();
+
+        model
+    }
+
+    pub fn build(&self) -> M {
+        self.build_nth(0)
+    }
+
+    pub fn build_many(&self, count: usize) -> Vec<M> {
+        (0..count).map(|index| self.build_nth(index)).collect()
+    }
+
+    pub async fn create(&self, session: &impl CharybdisSession) -> Result<M, CharybdisError> {
+        let model = self.build();
+
+        model.insert().execute(session).await?;
+
+        Ok(model)
+    }
+
+    pub async fn create_many(&self, count: usize, session: &impl CharybdisSession) -> Result<Vec<M>, CharybdisError> {
+        let models = self.build_many(count);
+
+        for model in &models {
+            model.insert().execute(session).await?;
+        }
+
+        Ok(models)
+    }
+}
+
+impl<M: Model + Default> Default for ModelFactory<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,82 @@
+use std::env;
+
+use charybdis::scylla::CachingSession;
+use migrate::keyspace::Replication;
+use migrate::MigrationBuilder;
+use scylla::SessionBuilder;
+use uuid::Uuid;
+
+const DEFAULT_SCYLLA_URI: &str = "127.0.0.1:9042";
+const STATEMENT_CACHE_SIZE: usize = 1000;
+
+/// Connects to Scylla at `SCYLLA_URI` (defaults to `127.0.0.1:9042`, e.g. container started with
+/// `charybdis-testing/docker-compose.yml`).
+pub async fn test_session() -> CachingSession {
+    let uri = env::var("SCYLLA_URI").unwrap_or_else(|_| DEFAULT_SCYLLA_URI.to_string());
+
+    let session = SessionBuilder::new()
+        .known_node(&uri)
+        .build()
+        .await
+        .unwrap_or_else(|e| panic!("Failed to connect to test Scylla at {}: {}", uri, e));
+
+    CachingSession::from(session, STATEMENT_CACHE_SIZE)
+}
+
+/// Keyspace with unique name, that exists only for the duration of a test.
+/// Models of the project are migrated into it, and session is switched to it, so model operations
+/// run against the fresh schema.
+/// ```rust ignore
+/// #[tokio::test]
+/// async fn test_insert() {
+///     let session = test_session().await;
+///     let keyspace = TestKeyspace::ephemeral(&session).await;
+///
+///     let user = User::factory().create(&session).await.unwrap();
+///
+///     keyspace.teardown().await;
+/// }
+/// ```
+pub struct TestKeyspace<'a> {
+    session: &'a CachingSession,
+    name: String,
+}
+
+impl<'a> TestKeyspace<'a> {
+    /// Creates keyspace with `SimpleStrategy` and replication factor 1, and runs migration for models
+    /// found in the project root.
+    pub async fn ephemeral(session: &'a CachingSession) -> TestKeyspace<'a> {
+        Self::ephemeral_with(session, MigrationBuilder::new()).await
+    }
+
+    /// Same as `ephemeral`, but allows configuring migration, e.g. to migrate models of another
+    /// `project_root`. Keyspace and replication of given builder are overridden.
+    pub async fn ephemeral_with(session: &'a CachingSession, builder: MigrationBuilder) -> TestKeyspace<'a> {
+        let name = format!("charybdis_test_{}", Uuid::new_v4().simple());
+
+        builder
+            .keyspace(name.clone())
+            .replication(Replication::simple(1))
+            .build(session.get_session())
+            .await
+            .run()
+            .await;
+
+        TestKeyspace { session, name }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Drops the keyspace. It has to be awaited at the end of the test, as async drop is not possible.
+    pub async fn teardown(self) {
+        let cql = format!("DROP KEYSPACE IF EXISTS {}", self.name);
+
+        self.session
+            .get_session()
+            .query_unpaged(cql, ())
+            .await
+            .unwrap_or_else(|e| panic!("Failed to drop test keyspace {}: {}", self.name, e));
+    }
+}
@@ -0,0 +1,7 @@
+pub use factory::{Factory, ModelFactory};
+pub use keyspace::{test_session, TestKeyspace};
+pub use mock::MockSession;
+
+mod factory;
+mod keyspace;
+mod mock;
@@ -0,0 +1,120 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use charybdis::scylla::{Batch, Query, QueryError, QueryResult, Row};
+use charybdis::session::{CharybdisSession, RowStream};
+use futures::StreamExt;
+use scylla::batch::BatchStatement;
+use scylla::serialize::batch::BatchValues;
+use scylla::serialize::row::SerializeRow;
+use scylla::statement::{PagingState, PagingStateResponse};
+
+/// In-memory session for unit tests. It records executed statements and returns canned rows,
+/// without connecting to the cluster. Bound values are not inspected.
+/// ```rust ignore
+/// let session = MockSession::new();
+///
+/// session.returning(
+///     User::FIND_BY_PRIMARY_KEY_QUERY,
+///     vec![Row { columns: vec![Some(CqlValue::Uuid(id)), Some(CqlValue::Text("alice".to_string()))] }],
+/// );
+///
+/// let user = User::find_by_id(id).execute(&session).await?;
+///
+/// assert_eq!(session.executed_queries(), vec![User::FIND_BY_PRIMARY_KEY_QUERY]);
+/// ```
+#[derive(Default)]
+pub struct MockSession {
+    executed: Mutex<Vec<String>>,
+    responses: Mutex<HashMap<String, VecDeque<Vec<Row>>>>,
+}
+
+impl MockSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues rows returned by the next execution of given query. Responses of the same query are
+    /// returned in order they were queued. Queries without queued response return no rows.
+    pub fn returning(&self, query: &str, rows: Vec<Row>) -> &Self {
+        self.responses
+            .lock()
+            .unwrap()
+            .entry(query.to_string())
+            .or_default()
+            .push_back(rows);
+
+        self
+    }
+
+    /// Statements in order they were executed. Statements of batch are recorded one by one.
+    pub fn executed_queries(&self) -> Vec<String> {
+        self.executed.lock().unwrap().clone()
+    }
+
+    /// Number of executions of given query.
+    pub fn executions(&self, query: &str) -> usize {
+        self.executed.lock().unwrap().iter().filter(|q| *q == query).count()
+    }
+
+    pub fn clear(&self) {
+        self.executed.lock().unwrap().clear();
+        self.responses.lock().unwrap().clear();
+    }
+
+    fn execute(&self, query: &str) -> Vec<Row> {
+        self.executed.lock().unwrap().push(query.to_string());
+
+        self.responses
+            .lock()
+            .unwrap()
+            .get_mut(query)
+            .and_then(VecDeque::pop_front)
+            .unwrap_or_default()
+    }
+}
+
+fn query_result(rows: Vec<Row>) -> QueryResult {
+    QueryResult {
+        rows: Some(rows),
+        ..Default::default()
+    }
+}
+
+impl CharybdisSession for MockSession {
+    async fn execute_unpaged(
+        &self,
+        statement: Query,
+        _values: impl SerializeRow + Send,
+    ) -> Result<QueryResult, QueryError> {
+        Ok(query_result(self.execute(&statement.contents)))
+    }
+
+    async fn execute_iter(&self, statement: Query, _values: impl SerializeRow + Send) -> Result<RowStream, QueryError> {
+        let rows = self.execute(&statement.contents);
+
+        Ok(futures::stream::iter(rows.into_iter().map(Ok)).boxed())
+    }
+
+    async fn execute_single_page(
+        &self,
+        statement: Query,
+        _values: impl SerializeRow + Send,
+        _paging_state: PagingState,
+    ) -> Result<(QueryResult, PagingStateResponse), QueryError> {
+        let rows = self.execute(&statement.contents);
+
+        Ok((query_result(rows), PagingStateResponse::NoMorePages))
+    }
+
+    async fn batch(&self, batch: &Batch, _values: impl BatchValues + Send) -> Result<QueryResult, QueryError> {
+        for statement in &batch.statements {
+            match statement {
+                BatchStatement::Query(query) => self.execute(&query.contents),
+                BatchStatement::PreparedStatement(prepared) => self.execute(prepared.get_statement()),
+            };
+        }
+
+        Ok(QueryResult::default())
+    }
+}
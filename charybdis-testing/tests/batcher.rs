@@ -0,0 +1,60 @@
+use charybdis::batch::ModelBatch;
+use charybdis::batcher::PartitionBatcherOptions;
+use charybdis::macros::charybdis_model;
+use charybdis::model::Model;
+use charybdis::types::{Text, Uuid};
+use charybdis_testing::MockSession;
+
+#[charybdis_model(
+    table_name = posts,
+    partition_keys = [category_id],
+    clustering_keys = [id]
+)]
+#[derive(Clone)]
+pub struct Post {
+    pub category_id: Uuid,
+    pub id: Uuid,
+    pub title: Text,
+}
+
+fn post(category_id: Uuid, title: &str) -> Post {
+    Post {
+        category_id,
+        id: Uuid::new_v4(),
+        title: title.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_later_write_of_same_row_replaces_pending_write() {
+    let session = MockSession::new();
+    let mut batcher = Post::partition_batcher(&session, PartitionBatcherOptions::new());
+    let mut inserted = post(Uuid::new_v4(), "first");
+
+    batcher.insert(inserted.clone()).await.unwrap();
+    inserted.title = "second".to_string();
+    batcher.update(inserted).await.unwrap();
+
+    assert_eq!(batcher.pending(), 1);
+
+    batcher.flush().await.unwrap();
+
+    assert_eq!(session.executed_queries(), vec![Post::INSERT_QUERY]);
+    assert_eq!(batcher.metrics().writes, 1);
+    assert_eq!(batcher.metrics().replaced_writes, 1);
+}
+
+#[tokio::test]
+async fn test_writes_of_distinct_rows_are_batched() {
+    let session = MockSession::new();
+    let mut batcher = Post::partition_batcher(&session, PartitionBatcherOptions::new());
+    let category_id = Uuid::new_v4();
+
+    batcher.update(post(category_id, "first")).await.unwrap();
+    batcher.update(post(category_id, "second")).await.unwrap();
+    batcher.flush().await.unwrap();
+
+    assert_eq!(session.executions(Post::UPDATE_QUERY), 2);
+    assert_eq!(batcher.metrics().batches, 1);
+    assert_eq!(batcher.metrics().replaced_writes, 0);
+}
@@ -0,0 +1,62 @@
+use charybdis::errors::CharybdisError;
+use charybdis::lookup;
+use charybdis::macros::charybdis_model;
+use charybdis::model::{BaseModel, Model};
+use charybdis::scylla::{CqlValue, Row};
+use charybdis::types::{Text, Uuid};
+use charybdis_testing::MockSession;
+
+#[charybdis_model(
+    table_name = users_by_email,
+    partition_keys = [email],
+    clustering_keys = []
+)]
+pub struct UserByEmail {
+    pub email: Text,
+    pub id: Uuid,
+}
+
+fn claimed_lookup(session: &MockSession, email: &str, id: Uuid) {
+    session.returning(
+        UserByEmail::INSERT_IF_NOT_EXIST_QUERY,
+        vec![Row {
+            columns: vec![Some(CqlValue::Boolean(false))],
+        }],
+    );
+    session.returning(
+        UserByEmail::FIND_BY_PRIMARY_KEY_QUERY,
+        vec![Row {
+            columns: vec![Some(CqlValue::Text(email.to_string())), Some(CqlValue::Uuid(id))],
+        }],
+    );
+}
+
+#[tokio::test]
+async fn test_claim_accepts_row_claimed_by_same_record() {
+    let session = MockSession::new();
+    let lookup = UserByEmail {
+        email: "alice@example.com".to_string(),
+        id: Uuid::new_v4(),
+    };
+
+    claimed_lookup(&session, &lookup.email, lookup.id);
+
+    let res = lookup::claim_for(&lookup, |stored| stored.id == lookup.id, &session).await;
+
+    assert!(res.is_ok());
+}
+
+#[tokio::test]
+async fn test_claim_rejects_row_claimed_by_other_record() {
+    let session = MockSession::new();
+    let lookup = UserByEmail {
+        email: "alice@example.com".to_string(),
+        id: Uuid::new_v4(),
+    };
+
+    claimed_lookup(&session, &lookup.email, Uuid::new_v4());
+
+    let res = lookup::claim_for(&lookup, |stored| stored.id == lookup.id, &session).await;
+
+    assert!(matches!(res, Err(CharybdisError::UniqueConstraintError(table)) if table == UserByEmail::DB_MODEL_NAME));
+}
@@ -0,0 +1,100 @@
+use charybdis::errors::CharybdisError;
+use charybdis::macros::charybdis_model;
+use charybdis::model::BaseModel;
+use charybdis::operations::Find;
+use charybdis::query::ClusteringOrder;
+use charybdis::types::{Text, Uuid};
+use charybdis_testing::MockSession;
+
+#[charybdis_model(
+    table_name = posts,
+    partition_keys = [category_id],
+    clustering_keys = [id]
+)]
+pub struct Post {
+    pub category_id: Uuid,
+    pub id: Uuid,
+    pub title: Text,
+}
+
+#[charybdis_model(
+    table_name = categories,
+    partition_keys = [id],
+    clustering_keys = []
+)]
+pub struct Category {
+    pub id: Uuid,
+    pub name: Text,
+}
+
+#[tokio::test]
+async fn test_limit_clamped_binds_limit_of_partition_finder() {
+    let session = MockSession::new();
+
+    Post::find_by_partition_key_value((Uuid::new_v4(),))
+        .limit_clamped(1000, 100)
+        .execute(&session)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        session.executed_queries(),
+        vec![Post::FIND_BY_PARTITION_KEY_LIMIT_QUERY]
+    );
+}
+
+#[tokio::test]
+async fn test_limit_clamped_of_other_query_fails_on_execution() {
+    let session = MockSession::new();
+
+    let res = Post::find_by_primary_key_value((Uuid::new_v4(), Uuid::new_v4()))
+        .limit_clamped(10, 100)
+        .execute(&session)
+        .await;
+
+    assert!(matches!(res, Err(CharybdisError::QueryBuilderError(_))));
+    assert!(session.executed_queries().is_empty());
+}
+
+#[tokio::test]
+async fn test_order_and_limit_switch_to_partition_finder_variant() {
+    let session = MockSession::new();
+
+    Post::find_by_partition_key_value((Uuid::new_v4(),))
+        .order_by_clustering(ClusteringOrder::Desc)
+        .limit(10)
+        .execute(&session)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        session.executed_queries(),
+        vec![Post::FIND_BY_PARTITION_KEY_DESC_LIMIT_QUERY.unwrap()]
+    );
+}
+
+#[tokio::test]
+async fn test_limit_of_other_query_fails_on_execution() {
+    let session = MockSession::new();
+
+    let res = Post::find_by_primary_key_value((Uuid::new_v4(), Uuid::new_v4()))
+        .limit(10)
+        .execute(&session)
+        .await;
+
+    assert!(matches!(res, Err(CharybdisError::QueryBuilderError(_))));
+    assert!(session.executed_queries().is_empty());
+}
+
+#[tokio::test]
+async fn test_order_by_clustering_without_clustering_keys_fails_on_execution() {
+    let session = MockSession::new();
+
+    let res = Category::find_by_partition_key_value((Uuid::new_v4(),))
+        .order_by_clustering(ClusteringOrder::Asc)
+        .execute(&session)
+        .await;
+
+    assert!(matches!(res, Err(CharybdisError::QueryBuilderError(_))));
+    assert!(session.executed_queries().is_empty());
+}
@@ -0,0 +1,46 @@
+use charybdis::macros::charybdis_model;
+use charybdis::model::Model;
+use charybdis::operations::Insert;
+use charybdis::types::{Text, Timestamp, Uuid};
+use charybdis_testing::MockSession;
+
+#[charybdis_model(
+    table_name = posts,
+    partition_keys = [category_id],
+    clustering_keys = [id],
+    soft_delete = "deleted_at"
+)]
+pub struct Post {
+    pub category_id: Uuid,
+    pub id: Uuid,
+    pub title: Text,
+    pub deleted_at: Timestamp,
+}
+
+#[tokio::test]
+async fn test_insert_sets_soft_delete_column_to_epoch() {
+    let session = MockSession::new();
+    let post = Post {
+        category_id: Uuid::new_v4(),
+        id: Uuid::new_v4(),
+        title: "first".to_string(),
+        deleted_at: Timestamp::default(),
+    };
+
+    post.insert().execute(&session).await.unwrap();
+
+    assert_eq!(
+        Post::INSERT_QUERY,
+        "INSERT INTO posts (category_id, id, title, deleted_at) VALUES (:category_id, :id, :title, 0)"
+    );
+    assert_eq!(session.executed_queries(), vec![Post::INSERT_QUERY]);
+}
+
+#[test]
+fn test_find_macro_filters_before_trailing_clauses() {
+    assert_eq!(
+        find_post_query!("category_id = ? LIMIT ?"),
+        "SELECT category_id, id, title, deleted_at FROM posts WHERE category_id = ? AND deleted_at = 0 LIMIT ? \
+         ALLOW FILTERING"
+    );
+}
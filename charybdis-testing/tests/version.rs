@@ -0,0 +1,82 @@
+use charybdis::id;
+use charybdis::macros::charybdis_model;
+use charybdis::model::{Model, Version};
+use charybdis::operations::Update;
+use charybdis::types::{Int, Text, Timeuuid, Uuid};
+use charybdis_testing::MockSession;
+
+#[charybdis_model(
+    table_name = posts,
+    partition_keys = [id],
+    clustering_keys = []
+)]
+pub struct Post {
+    pub id: Uuid,
+    pub title: Text,
+    #[charybdis(version)]
+    pub version: Timeuuid,
+}
+
+#[charybdis_model(
+    table_name = drafts,
+    partition_keys = [id],
+    clustering_keys = []
+)]
+pub struct Draft {
+    pub id: Uuid,
+    pub title: Text,
+    #[charybdis(version)]
+    pub version: Int,
+}
+
+#[tokio::test]
+async fn test_consecutive_timeuuid_updates() {
+    let session = MockSession::new();
+    let initial_version = id::timeuuid();
+    let mut post = Post {
+        id: Uuid::new_v4(),
+        title: "first".to_string(),
+        version: initial_version,
+    };
+
+    let update = post.update();
+    let first_version = update.next_version().unwrap();
+    update.execute(&session).await.unwrap();
+    post.increment_version(first_version);
+
+    assert_eq!(Version::Timeuuid(post.version), first_version);
+    assert_ne!(post.version, initial_version);
+
+    post.title = "second".to_string();
+
+    let update = post.update();
+    let second_version = update.next_version().unwrap();
+    update.execute(&session).await.unwrap();
+    post.increment_version(second_version);
+
+    assert_eq!(Version::Timeuuid(post.version), second_version);
+    assert_ne!(second_version, first_version);
+    assert_eq!(session.executions(Post::UPDATE_QUERY), 2);
+    assert!(!Post::UPDATE_QUERY.contains("now()"));
+}
+
+#[tokio::test]
+async fn test_consecutive_int_updates() {
+    let session = MockSession::new();
+    let mut draft = Draft {
+        id: Uuid::new_v4(),
+        title: "first".to_string(),
+        version: 0,
+    };
+
+    for expected in 1..=2 {
+        let update = draft.update();
+        let next_version = update.next_version().unwrap();
+        update.execute(&session).await.unwrap();
+        draft.increment_version(next_version);
+
+        assert_eq!(draft.version, expected);
+    }
+
+    assert_eq!(session.executions(Draft::UPDATE_QUERY), 2);
+}
@@ -0,0 +1,134 @@
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::hash::Hash;
+
+use charybdis_parser::statement::soft_delete_filtered;
+use futures::{StreamExt, TryFutureExt, TryStreamExt};
+use scylla::serialize::value::SerializeValue;
+
+use crate::errors::CharybdisError;
+use crate::model::BaseModel;
+use crate::operations::Find;
+use crate::query::intern;
+use crate::session::CharybdisSession;
+
+/// Max number of lookups in flight of generated `load_<association>_for` functions.
+pub const MAX_CONCURRENT_LOADS: usize = 32;
+
+/// Max number of keys bound to single `IN` statement of generated `load_<association>_for` functions, so statements
+/// stay below default `max_partition_key_restrictions_per_query` of Scylla.
+pub const MAX_IN_KEYS: usize = 100;
+
+/// Loads association once per distinct key of models concurrently, and pairs models with loaded associations in
+/// input order. Models sharing a key get clones of the same result. Used by `load_<association>_for` functions
+/// generated for `belongs_to` and `has_many` declarations with multiple fields.
+pub async fn load_for<'a, M, K, T, Key, F, Fut>(
+    models: &'a [M],
+    key: Key,
+    load: F,
+) -> Result<Vec<(&'a M, T)>, CharybdisError>
+where
+    K: Eq + Hash,
+    T: Clone,
+    Key: Fn(&M) -> K,
+    F: Fn(&'a M) -> Fut,
+    Fut: Future<Output = Result<T, CharybdisError>>,
+{
+    let mut distinct = HashMap::new();
+    for model in models {
+        distinct.entry(key(model)).or_insert(model);
+    }
+
+    let loaded: HashMap<K, T> = futures::stream::iter(distinct)
+        .map(|(key, model)| load(model).map_ok(move |loaded| (key, loaded)))
+        .buffer_unordered(MAX_CONCURRENT_LOADS)
+        .try_collect()
+        .await?;
+
+    Ok(models
+        .iter()
+        .map(|model| (model, loaded[&key(model)].clone()))
+        .collect())
+}
+
+/// Loads `belongs_to` association of single field with `IN` statements by primary key of associated model, at
+/// most [MAX_IN_KEYS] distinct keys per statement, and pairs models with loaded rows in input order.
+pub async fn load_belonging_for<'a, M, A, K, Key>(
+    models: &'a [M],
+    key: Key,
+    session: &impl CharybdisSession,
+) -> Result<Vec<(&'a M, Option<A>)>, CharybdisError>
+where
+    A: BaseModel<PrimaryKey = (K,)> + Clone,
+    K: Eq + Hash + Clone + SerializeValue + Send + Sync,
+    Key: Fn(&M) -> K,
+{
+    let rows = load_in::<A, K>(A::PRIMARY_KEY_FIELDS_CLAUSE, distinct_keys(models, &key), session).await?;
+    let loaded: HashMap<K, A> = rows.into_iter().map(|row| (row.primary_key_values().0, row)).collect();
+
+    Ok(models
+        .iter()
+        .map(|model| (model, loaded.get(&key(model)).cloned()))
+        .collect())
+}
+
+/// Loads `has_many` association of single field with `IN` statements by partition key of associated model, at
+/// most [MAX_IN_KEYS] distinct keys per statement, and pairs models with loaded rows in input order. Rows of each
+/// partition keep their clustering order.
+pub async fn load_many_for<'a, M, A, K, Key>(
+    models: &'a [M],
+    key: Key,
+    session: &impl CharybdisSession,
+) -> Result<Vec<(&'a M, Vec<A>)>, CharybdisError>
+where
+    A: BaseModel<PartitionKey = (K,)> + Clone,
+    K: Eq + Hash + Clone + SerializeValue + Send + Sync,
+    Key: Fn(&M) -> K,
+{
+    let rows = load_in::<A, K>(A::PARTITION_KEY_FIELDS_CLAUSE, distinct_keys(models, &key), session).await?;
+
+    let mut loaded: HashMap<K, Vec<A>> = HashMap::new();
+    for row in rows {
+        loaded.entry(row.partition_key_values().0).or_default().push(row);
+    }
+
+    Ok(models
+        .iter()
+        .map(|model| (model, loaded.get(&key(model)).cloned().unwrap_or_default()))
+        .collect())
+}
+
+fn distinct_keys<M, K: Eq + Hash + Clone>(models: &[M], key: impl Fn(&M) -> K) -> Vec<K> {
+    let mut seen = HashSet::new();
+
+    models.iter().map(key).filter(|key| seen.insert(key.clone())).collect()
+}
+
+async fn load_in<A, K>(column: &str, keys: Vec<K>, session: &impl CharybdisSession) -> Result<Vec<A>, CharybdisError>
+where
+    A: BaseModel,
+    K: Clone + SerializeValue + Send + Sync,
+{
+    let query = format!(
+        "SELECT {} FROM {} WHERE {} IN ?",
+        A::SELECT_FIELDS_CLAUSE,
+        A::DB_MODEL_NAME,
+        column
+    );
+    let query = match A::SOFT_DELETE_COLUMN {
+        Some(soft_delete_column) => intern(soft_delete_filtered(&query, soft_delete_column)),
+        None => intern(query),
+    };
+
+    let chunks: Vec<Vec<A>> = futures::stream::iter(keys.chunks(MAX_IN_KEYS))
+        .map(|chunk| {
+            A::find(query, (chunk.to_vec(),))
+                .execute(session)
+                .and_then(|rows| rows.try_collect::<Vec<A>>())
+        })
+        .buffer_unordered(MAX_CONCURRENT_LOADS)
+        .try_collect()
+        .await?;
+
+    Ok(chunks.into_iter().flatten().collect())
+}
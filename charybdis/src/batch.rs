@@ -1,40 +1,60 @@
+use std::hash::Hash;
 use std::sync::Arc;
 
 use scylla::_macro_internal::{RowSerializationContext, RowWriter, SerializationError};
-use scylla::batch::{Batch, BatchType};
+use scylla::batch::BatchType;
 use scylla::history::HistoryListener;
 use scylla::serialize::row::SerializeRow;
-use scylla::{CachingSession, QueryResult};
 
+use crate::batcher::{PartitionBatcher, PartitionBatcherOptions};
+use crate::driver::{Batch, QueryResult};
 use crate::errors::CharybdisError;
-use crate::model::Model;
+use crate::hooks::{self, QueryInfo};
+use crate::model::{BaseModel, Model, NotSoftDeleted};
 use crate::options::{Consistency, ExecutionProfileHandle, RetryPolicy, SerialConsistency};
 use crate::query::{CharybdisQuery, QueryExecutor, QueryValue};
+use crate::session::CharybdisSession;
+
+/// With `otel` feature, attempts of batch are recorded in its span.
+fn new_batch(batch_type: BatchType) -> Batch {
+    #[allow(unused_mut)]
+    let mut batch = Batch::new(batch_type);
+
+    #[cfg(feature = "otel")]
+    batch.set_history_listener(crate::otel::SpanHistoryListener::new(None));
+
+    batch
+}
 
 pub struct CharybdisModelBatch<'a, Val: SerializeRow, M: Model> {
     inner: Batch,
     values: Vec<QueryValue<'a, Val, M>>,
+    unscoped: bool,
 }
 
 impl<'a, Val: SerializeRow, M: Model> CharybdisModelBatch<'a, Val, M> {
     pub fn new() -> Self {
         Self {
-            inner: Batch::default(),
+            inner: new_batch(BatchType::Logged),
             values: Vec::new(),
+            unscoped: false,
         }
     }
 
     pub fn unlogged() -> Self {
         Self {
-            inner: Batch::new(BatchType::Unlogged),
+            inner: new_batch(BatchType::Unlogged),
             values: Vec::new(),
+            unscoped: false,
         }
     }
 
-    pub(crate) fn from_batch(batch: &Batch) -> Self {
+    /// Empty batch with options of given one.
+    pub(crate) fn from_batch<V: SerializeRow>(batch: &CharybdisModelBatch<'_, V, M>) -> Self {
         Self {
-            inner: batch.clone(),
+            inner: batch.inner.clone(),
             values: Vec::new(),
+            unscoped: batch.unscoped,
         }
     }
 
@@ -73,12 +93,20 @@ impl<'a, Val: SerializeRow, M: Model> CharybdisModelBatch<'a, Val, M> {
     }
 
     pub fn history_listener(mut self, history_listener: Arc<dyn HistoryListener>) -> Self {
+        #[cfg(feature = "otel")]
+        let history_listener = crate::otel::SpanHistoryListener::new(Some(history_listener));
+
         self.inner.set_history_listener(history_listener);
         self
     }
 
     pub fn remove_history_listener(mut self) -> Self {
         self.inner.remove_history_listener();
+
+        #[cfg(feature = "otel")]
+        self.inner
+            .set_history_listener(crate::otel::SpanHistoryListener::new(None));
+
         self
     }
 
@@ -87,16 +115,24 @@ impl<'a, Val: SerializeRow, M: Model> CharybdisModelBatch<'a, Val, M> {
         self
     }
 
+    /// Batches of models with `tenant_key` are rejected, as their models are not authorized by callbacks, unless
+    /// batch is explicitly unscoped, e.g. in background jobs. Use [UnitOfWork](crate::unit_of_work::UnitOfWork)
+    /// to batch callback queries.
+    pub fn unscoped(mut self) -> Self {
+        self.unscoped = true;
+        self
+    }
+
     pub async fn chunked_insert(
         self,
-        db_session: &CachingSession,
+        db_session: &impl CharybdisSession,
         iter: &[M],
         chunk_size: usize,
     ) -> Result<(), CharybdisError> {
         let chunks = iter.chunks(chunk_size);
 
         for chunk in chunks {
-            let mut batch: CharybdisModelBatch<M, M> = CharybdisModelBatch::from_batch(&self.inner);
+            let mut batch: CharybdisModelBatch<M, M> = CharybdisModelBatch::from_batch(&self);
 
             batch.append_inserts(chunk);
 
@@ -108,14 +144,14 @@ impl<'a, Val: SerializeRow, M: Model> CharybdisModelBatch<'a, Val, M> {
 
     pub async fn chunked_insert_if_not_exist(
         self,
-        db_session: &CachingSession,
+        db_session: &impl CharybdisSession,
         iter: &[M],
         chunk_size: usize,
     ) -> Result<(), CharybdisError> {
         let chunks = iter.chunks(chunk_size);
 
         for chunk in chunks {
-            let mut batch: CharybdisModelBatch<M, M> = CharybdisModelBatch::from_batch(&self.inner);
+            let mut batch: CharybdisModelBatch<M, M> = CharybdisModelBatch::from_batch(&self);
 
             batch.append_inserts_if_not_exist(chunk);
 
@@ -127,14 +163,14 @@ impl<'a, Val: SerializeRow, M: Model> CharybdisModelBatch<'a, Val, M> {
 
     pub async fn chunked_update(
         self,
-        db_session: &CachingSession,
+        db_session: &impl CharybdisSession,
         iter: &[M],
         chunk_size: usize,
     ) -> Result<(), CharybdisError> {
         let chunks = iter.chunks(chunk_size);
 
         for chunk in chunks {
-            let mut batch: CharybdisModelBatch<M, M> = CharybdisModelBatch::from_batch(&self.inner);
+            let mut batch: CharybdisModelBatch<M, M> = CharybdisModelBatch::from_batch(&self);
 
             batch.append_updates(chunk);
 
@@ -146,14 +182,14 @@ impl<'a, Val: SerializeRow, M: Model> CharybdisModelBatch<'a, Val, M> {
 
     pub async fn chunked_delete(
         self,
-        db_session: &CachingSession,
+        db_session: &impl CharybdisSession,
         iter: &[M],
         chunk_size: usize,
     ) -> Result<(), CharybdisError> {
         let chunks = iter.chunks(chunk_size);
 
         for chunk in chunks {
-            let mut batch: CharybdisModelBatch<M, M> = CharybdisModelBatch::from_batch(&self.inner);
+            let mut batch: CharybdisModelBatch<M, M> = CharybdisModelBatch::from_batch(&self);
 
             for model in chunk {
                 batch.append_delete(model);
@@ -167,14 +203,14 @@ impl<'a, Val: SerializeRow, M: Model> CharybdisModelBatch<'a, Val, M> {
 
     pub async fn chunked_delete_by_partition_key(
         self,
-        db_session: &CachingSession,
+        db_session: &impl CharybdisSession,
         iter: &[M],
         chunk_size: usize,
     ) -> Result<(), CharybdisError> {
         let chunks = iter.chunks(chunk_size);
 
         for chunk in chunks {
-            let mut batch: CharybdisModelBatch<M, M> = CharybdisModelBatch::from_batch(&self.inner);
+            let mut batch: CharybdisModelBatch<M, M> = CharybdisModelBatch::from_batch(&self);
 
             batch.append_deletes_by_partition_key(chunk);
 
@@ -186,14 +222,17 @@ impl<'a, Val: SerializeRow, M: Model> CharybdisModelBatch<'a, Val, M> {
 
     pub async fn chunked_statements(
         self,
-        db_session: &CachingSession,
+        db_session: &impl CharybdisSession,
         statement: &str,
         mut values: Vec<Val>,
         chunk_size: usize,
-    ) -> Result<(), CharybdisError> {
+    ) -> Result<(), CharybdisError>
+    where
+        Val: Sync,
+    {
         while !values.is_empty() {
             let chunk: Vec<Val> = values.drain(..std::cmp::min(chunk_size, values.len())).collect();
-            let mut batch: CharybdisModelBatch<Val, M> = CharybdisModelBatch::from_batch(&self.inner);
+            let mut batch: CharybdisModelBatch<Val, M> = CharybdisModelBatch::from_batch(&self);
 
             batch.append_statements(statement, chunk)?;
 
@@ -213,7 +252,7 @@ impl<'a, Val: SerializeRow, M: Model> CharybdisModelBatch<'a, Val, M> {
 
     pub fn append_insert(&mut self, model: &'a M) -> &mut Self {
         self.append_query_to_batch(M::INSERT_QUERY);
-        self.values.push(QueryValue::Model(model));
+        self.values.push(model.insert_values());
         self
     }
 
@@ -226,7 +265,7 @@ impl<'a, Val: SerializeRow, M: Model> CharybdisModelBatch<'a, Val, M> {
 
     pub fn append_insert_if_not_exist(&mut self, model: &'a M) -> &mut Self {
         self.append_query_to_batch(M::INSERT_IF_NOT_EXIST_QUERY);
-        self.values.push(QueryValue::Model(model));
+        self.values.push(model.insert_values());
         self
     }
 
@@ -239,7 +278,7 @@ impl<'a, Val: SerializeRow, M: Model> CharybdisModelBatch<'a, Val, M> {
 
     pub fn append_update(&mut self, model: &'a M) -> &mut Self {
         self.append_query_to_batch(M::UPDATE_QUERY);
-        self.values.push(QueryValue::Model(model));
+        self.values.push(model.update_values());
         self
     }
 
@@ -250,6 +289,8 @@ impl<'a, Val: SerializeRow, M: Model> CharybdisModelBatch<'a, Val, M> {
         self
     }
 
+    /// Soft delete is conditional, so batch with deletes of models with `soft_delete` field must target
+    /// single partition.
     pub fn append_delete(&mut self, model: &M) -> &mut Self {
         self.append_query_to_batch(M::DELETE_QUERY);
         self.values.push(QueryValue::PrimaryKey(model.primary_key_values()));
@@ -263,7 +304,16 @@ impl<'a, Val: SerializeRow, M: Model> CharybdisModelBatch<'a, Val, M> {
         self
     }
 
+    /// Refused at compile time for models with `soft_delete` field, see
+    /// [Delete::delete_by_partition_key](crate::operations::Delete::delete_by_partition_key).
     pub fn append_delete_by_partition_key(&mut self, model: &'a M) -> &mut Self {
+        #[allow(clippy::let_unit_value)]
+        let () = NotSoftDeleted::<M>::ASSERT;
+
+        self.append_hard_delete_by_partition_key(model)
+    }
+
+    pub fn append_hard_delete_by_partition_key(&mut self, model: &'a M) -> &mut Self {
         self.append_query_to_batch(M::DELETE_BY_PARTITION_KEY_QUERY);
         self.values.push(QueryValue::PartitionKey(model.partition_key_values()));
         self
@@ -282,13 +332,26 @@ impl<'a, Val: SerializeRow, M: Model> CharybdisModelBatch<'a, Val, M> {
         self
     }
 
-    pub async fn execute(&self, db_session: &CachingSession) -> Result<QueryResult, CharybdisError> {
-        let result = db_session
-            .batch(&self.inner, &self.values)
-            .await
-            .map_err(|e| CharybdisError::BatchError(M::DB_MODEL_NAME, e))?;
+    pub async fn execute(&self, db_session: &impl CharybdisSession) -> Result<QueryResult, CharybdisError>
+    where
+        Val: Sync,
+    {
+        if let Some(tenant_column) = M::TENANT_COLUMN.filter(|_| !self.unscoped) {
+            return Err(CharybdisError::TenantError(format!(
+                "{} is scoped by {}, but batch is not unscoped",
+                M::DB_MODEL_NAME,
+                tenant_column
+            )));
+        }
+
+        let execution = async {
+            db_session
+                .batch(&self.inner, &self.values)
+                .await
+                .map_err(|e| CharybdisError::BatchError(M::DB_MODEL_NAME, e))
+        };
 
-        Ok(result)
+        hooks::observe(|| QueryInfo::batch(&self.inner, Some(M::DB_MODEL_NAME)), execution).await
     }
 }
 
@@ -338,6 +401,18 @@ pub trait ModelBatch<'a>: Model {
     fn unlogged_statement_batch<Val: SerializeRow>() -> CharybdisModelBatch<'a, Val, Self> {
         CharybdisModelBatch::unlogged()
     }
+
+    /// Groups writes by partition key and writes each partition by its own unlogged batch, see [PartitionBatcher].
+    fn partition_batcher<S: CharybdisSession>(
+        session: &'a S,
+        options: PartitionBatcherOptions,
+    ) -> PartitionBatcher<'a, Self, S>
+    where
+        Self::PartitionKey: Hash + Eq,
+        Self::PrimaryKey: PartialEq,
+    {
+        PartitionBatcher::new(session, options)
+    }
 }
 
 impl<M: Model> ModelBatch<'_> for M {}
@@ -370,14 +445,23 @@ pub struct CharybdisBatch<'a> {
 impl<'a> CharybdisBatch<'a> {
     pub fn new() -> Self {
         Self {
-            inner: Batch::default(),
+            inner: new_batch(BatchType::Logged),
             values: Vec::new(),
         }
     }
 
     pub fn unlogged() -> Self {
         Self {
-            inner: Batch::new(BatchType::Unlogged),
+            inner: new_batch(BatchType::Unlogged),
+            values: Vec::new(),
+        }
+    }
+
+    /// Batch of counter updates, e.g. `increment_<field>` queries. Counter updates can't be mixed with
+    /// other statements in a batch.
+    pub fn counter() -> Self {
+        Self {
+            inner: new_batch(BatchType::Counter),
             values: Vec::new(),
         }
     }
@@ -385,7 +469,7 @@ impl<'a> CharybdisBatch<'a> {
     pub fn append<Val, M, RtQe>(&mut self, query: CharybdisQuery<'a, Val, M, RtQe>) -> &mut Self
     where
         Val: SerializeRow + Sync + Send,
-        M: Model + Sync + Send,
+        M: BaseModel + Sync + Send,
         RtQe: QueryExecutor,
     {
         self.inner.append_statement(query.query_string);
@@ -395,13 +479,20 @@ impl<'a> CharybdisBatch<'a> {
         self
     }
 
-    pub async fn execute(&self, db_session: &CachingSession) -> Result<QueryResult, CharybdisError> {
-        let result = db_session
-            .batch(&self.inner, &self.values)
-            .await
-            .map_err(|e| CharybdisError::BatchError("QueryBatchError", e))?;
+    pub(crate) fn append_values(&mut self, statement: &str, values: impl SerializeRow + Sync + Send + 'a) {
+        self.inner.append_statement(statement);
+        self.values.push(SerializeRowBox::new(values));
+    }
+
+    pub async fn execute(&self, db_session: &impl CharybdisSession) -> Result<QueryResult, CharybdisError> {
+        let execution = async {
+            db_session
+                .batch(&self.inner, &self.values)
+                .await
+                .map_err(|e| CharybdisError::BatchError("QueryBatchError", e))
+        };
 
-        Ok(result)
+        hooks::observe(|| QueryInfo::batch(&self.inner, None), execution).await
     }
 }
 
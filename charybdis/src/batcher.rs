@@ -0,0 +1,346 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use futures::{stream, StreamExt};
+
+use crate::batch::CharybdisModelBatch;
+use crate::errors::CharybdisError;
+use crate::model::Model;
+use crate::session::CharybdisSession;
+
+#[derive(Debug, Clone, Copy)]
+pub struct PartitionBatcherOptions {
+    max_batch_size: usize,
+    max_delay: Duration,
+    max_in_flight: usize,
+    unscoped: bool,
+}
+
+impl Default for PartitionBatcherOptions {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 50,
+            max_delay: Duration::from_millis(100),
+            max_in_flight: 16,
+            unscoped: false,
+        }
+    }
+}
+
+impl PartitionBatcherOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of pending writes of single partition at which they are flushed. It should keep batches below
+    /// `batch_size_warn_threshold_in_kb` of the cluster, so for large rows it should be lowered.
+    pub fn max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size.max(1);
+        self
+    }
+
+    /// Time after first pending write of partition at which partition is flushed, even if it didn't reach
+    /// `max_batch_size`.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Maximum number of batches executed concurrently when multiple partitions are flushed at once.
+    pub fn max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = max_in_flight.max(1);
+        self
+    }
+
+    /// Writes models with `tenant_key` without limiting them to tenant, see
+    /// [TenantScoped](crate::tenancy::TenantScoped). Batches of such models fail unless batcher is explicitly
+    /// unscoped.
+    pub fn unscoped(mut self) -> Self {
+        self.unscoped = true;
+        self
+    }
+}
+
+/// Counters of batches executed by [PartitionBatcher], as returned by [PartitionBatcher::metrics].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlushMetrics {
+    /// successfully executed batches
+    pub batches: u64,
+    /// writes of successfully executed batches
+    pub writes: u64,
+    pub failed_batches: u64,
+    /// writes of failed batches, that are not retried by batcher
+    pub failed_writes: u64,
+    /// batches flushed as partition reached `max_batch_size`
+    pub size_flushes: u64,
+    /// batches flushed as partition exceeded `max_delay`
+    pub time_flushes: u64,
+    /// batches flushed by [PartitionBatcher::flush]
+    pub manual_flushes: u64,
+    /// pending writes replaced by later write of the same row
+    pub replaced_writes: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FlushReason {
+    Size,
+    Time,
+    Manual,
+}
+
+enum PendingWrite<M> {
+    Insert(M),
+    Update(M),
+}
+
+impl<M: Model> PendingWrite<M> {
+    fn model(&self) -> &M {
+        match self {
+            PendingWrite::Insert(model) | PendingWrite::Update(model) => model,
+        }
+    }
+
+    fn into_model(self) -> M {
+        match self {
+            PendingWrite::Insert(model) | PendingWrite::Update(model) => model,
+        }
+    }
+
+    /// Replaces pending write of the same row by later `write`. Statements of batch share its timestamp, so
+    /// conflicting writes of the same row wouldn't be applied in order. Row that is inserted by either write is
+    /// still inserted, so its row marker is written.
+    fn replace(&mut self, write: PendingWrite<M>) {
+        let insert = matches!(self, PendingWrite::Insert(_)) || matches!(write, PendingWrite::Insert(_));
+        let model = write.into_model();
+
+        *self = if insert {
+            PendingWrite::Insert(model)
+        } else {
+            PendingWrite::Update(model)
+        };
+    }
+}
+
+struct PendingPartition<M> {
+    writes: Vec<PendingWrite<M>>,
+    first_write: Instant,
+}
+
+/// Groups inserts and updates by partition key, and writes each partition by its own unlogged batch. Single
+/// partition batch is applied by replicas of that partition as single mutation, so it's cheaper than separate
+/// writes, while batch spanning many partitions puts load of all of them on its coordinator.
+/// ```rust ignore
+/// let options = PartitionBatcherOptions::new().max_batch_size(20).max_delay(Duration::from_millis(50));
+/// let mut batcher = Post::partition_batcher(&session, options);
+///
+/// for post in posts {
+///     batcher.insert(post).await?;
+/// }
+///
+/// batcher.flush().await?;
+///
+/// println!("{:?}", batcher.metrics());
+/// ```
+/// Partition is flushed once it reaches `max_batch_size` writes, or once `max_delay` passes since its first
+/// pending write. Delay is checked on each write and by [PartitionBatcher::flush_expired], that can be called
+/// periodically if writes are sparse. Pending writes are not written when batcher is dropped, so it has to be
+/// flushed.
+///
+/// Later write of row that is already pending replaces pending write, so only the last state of each row is
+/// written, see [FlushMetrics::replaced_writes].
+///
+/// Writes of failed batch are dropped and first error is returned, other partitions flushed at the same time are
+/// still written.
+pub struct PartitionBatcher<'a, M: Model, S: CharybdisSession> {
+    session: &'a S,
+    options: PartitionBatcherOptions,
+    partitions: HashMap<M::PartitionKey, PendingPartition<M>>,
+    next_expiry: Option<Instant>,
+    metrics: FlushMetrics,
+}
+
+impl<'a, M, S> PartitionBatcher<'a, M, S>
+where
+    M: Model,
+    M::PartitionKey: Hash + Eq,
+    M::PrimaryKey: PartialEq,
+    S: CharybdisSession,
+{
+    pub fn new(session: &'a S, options: PartitionBatcherOptions) -> Self {
+        Self {
+            session,
+            options,
+            partitions: HashMap::new(),
+            next_expiry: None,
+            metrics: FlushMetrics::default(),
+        }
+    }
+
+    /// Generates ids for unset `id_strategy` fields and appends `INSERT_QUERY` of model to batch of its partition.
+    pub async fn insert(&mut self, mut model: M) -> Result<(), CharybdisError> {
+        model.generate_ids();
+
+        self.push(PendingWrite::Insert(model)).await
+    }
+
+    /// Appends `UPDATE_QUERY` of model to batch of its partition.
+    pub async fn update(&mut self, model: M) -> Result<(), CharybdisError> {
+        self.push(PendingWrite::Update(model)).await
+    }
+
+    /// Flushes partitions whose first pending write is older than `max_delay`.
+    pub async fn flush_expired(&mut self) -> Result<(), CharybdisError> {
+        let now = Instant::now();
+
+        if !self.next_expiry.is_some_and(|next_expiry| next_expiry <= now) {
+            return Ok(());
+        }
+
+        let max_delay = self.options.max_delay;
+        let mut next_expiry: Option<Instant> = None;
+        let mut expired = vec![];
+
+        self.partitions.retain(|_, partition| {
+            let expiry = partition.first_write + max_delay;
+
+            if expiry <= now {
+                expired.push(std::mem::take(&mut partition.writes));
+                return false;
+            }
+
+            next_expiry = Some(next_expiry.map_or(expiry, |next_expiry| next_expiry.min(expiry)));
+            true
+        });
+
+        self.next_expiry = next_expiry;
+
+        self.execute(expired, FlushReason::Time).await
+    }
+
+    /// Flushes all pending writes.
+    pub async fn flush(&mut self) -> Result<(), CharybdisError> {
+        let partitions = self.partitions.drain().map(|(_, partition)| partition.writes).collect();
+
+        self.next_expiry = None;
+
+        self.execute(partitions, FlushReason::Manual).await
+    }
+
+    /// Number of writes that are not flushed yet.
+    pub fn pending(&self) -> usize {
+        self.partitions.values().map(|partition| partition.writes.len()).sum()
+    }
+
+    pub fn metrics(&self) -> FlushMetrics {
+        self.metrics
+    }
+
+    async fn push(&mut self, write: PendingWrite<M>) -> Result<(), CharybdisError> {
+        let max_batch_size = self.options.max_batch_size;
+
+        let full_partition = match self.partitions.entry(write.model().partition_key_values()) {
+            Entry::Occupied(mut entry) => {
+                let writes = &mut entry.get_mut().writes;
+                let primary_key = write.model().primary_key_values();
+
+                match writes
+                    .iter_mut()
+                    .find(|pending| pending.model().primary_key_values() == primary_key)
+                {
+                    Some(pending) => {
+                        pending.replace(write);
+                        self.metrics.replaced_writes += 1;
+                    }
+                    None => writes.push(write),
+                }
+
+                if entry.get().writes.len() >= max_batch_size {
+                    Some(entry.remove().writes)
+                } else {
+                    None
+                }
+            }
+            Entry::Vacant(_) if max_batch_size == 1 => Some(vec![write]),
+            Entry::Vacant(entry) => {
+                let now = Instant::now();
+                let mut writes = Vec::with_capacity(max_batch_size);
+                writes.push(write);
+
+                entry.insert(PendingPartition {
+                    writes,
+                    first_write: now,
+                });
+
+                let expiry = now + self.options.max_delay;
+                self.next_expiry = Some(self.next_expiry.map_or(expiry, |next_expiry| next_expiry.min(expiry)));
+
+                None
+            }
+        };
+
+        if let Some(writes) = full_partition {
+            self.execute(vec![writes], FlushReason::Size).await?;
+        }
+
+        self.flush_expired().await
+    }
+
+    async fn execute(
+        &mut self,
+        partitions: Vec<Vec<PendingWrite<M>>>,
+        reason: FlushReason,
+    ) -> Result<(), CharybdisError> {
+        let session = self.session;
+        let unscoped = self.options.unscoped;
+        let results = stream::iter(partitions)
+            .map(|writes| async move {
+                let count = writes.len() as u64;
+                let mut batch = CharybdisModelBatch::<M, M>::unlogged();
+
+                if unscoped {
+                    batch = batch.unscoped();
+                }
+
+                for write in &writes {
+                    match write {
+                        PendingWrite::Insert(model) => batch.append_insert(model),
+                        PendingWrite::Update(model) => batch.append_update(model),
+                    };
+                }
+
+                (count, batch.execute(session).await.map(|_| ()))
+            })
+            .buffer_unordered(self.options.max_in_flight)
+            .collect::<Vec<(u64, Result<(), CharybdisError>)>>()
+            .await;
+
+        let mut first_error = None;
+
+        for (count, result) in results {
+            match result {
+                Ok(()) => {
+                    self.metrics.batches += 1;
+                    self.metrics.writes += count;
+
+                    match reason {
+                        FlushReason::Size => self.metrics.size_flushes += 1,
+                        FlushReason::Time => self.metrics.time_flushes += 1,
+                        FlushReason::Manual => self.metrics.manual_flushes += 1,
+                    }
+                }
+                Err(e) => {
+                    self.metrics.failed_batches += 1;
+                    self.metrics.failed_writes += count;
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,6 +1,8 @@
+use std::future::Future;
+
 use scylla::serialize::row::SerializeRow;
-use scylla::CachingSession;
 
+use crate::driver::CachingSession;
 use crate::errors::CharybdisError;
 use crate::model::Model;
 use crate::query::QueryValue;
@@ -12,56 +14,73 @@ use crate::query::QueryValue;
 /// sending messages to kafka, etc.
 /// In case one doesn't need extension it can be set to `Option<()>` and then
 /// it can be set to `None` when calling the operation.
+/// Callbacks can be implemented by `async fn`, as long as their futures are `Send`, so callback queries and
+/// [UnitOfWork](crate::unit_of_work::UnitOfWork) can be executed on multi-threaded runtime.
 pub trait Callbacks: Model {
-    type Extension;
+    type Extension: Send + Sync;
     type Error: From<CharybdisError>;
 
-    async fn before_insert(
+    /// Row-level access policy, checked by callback operations after `before_<action>` callbacks, so values set
+    /// by callbacks are checked as well. Models that declare `tenant_key` are rejected unless it's implemented,
+    /// usually by [TenantScoped::authorize_tenant](crate::tenancy::TenantScoped::authorize_tenant).
+    fn authorize(&self, _extension: &Self::Extension) -> Result<(), Self::Error> {
+        match Self::TENANT_COLUMN {
+            Some(tenant_column) => Err(CharybdisError::TenantError(format!(
+                "{} is scoped by {}, but its callbacks don't implement authorize",
+                Self::DB_MODEL_NAME,
+                tenant_column
+            ))
+            .into()),
+            None => Ok(()),
+        }
+    }
+
+    fn before_insert(
         &mut self,
         _session: &CachingSession,
         _extension: &Self::Extension,
-    ) -> Result<(), Self::Error> {
-        Ok(())
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
     }
 
-    async fn after_insert(
+    fn after_insert(
         &mut self,
         _session: &CachingSession,
         _extension: &Self::Extension,
-    ) -> Result<(), Self::Error> {
-        Ok(())
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
     }
 
-    async fn before_update(
+    fn before_update(
         &mut self,
         _session: &CachingSession,
         _extension: &Self::Extension,
-    ) -> Result<(), Self::Error> {
-        Ok(())
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
     }
 
-    async fn after_update(
+    fn after_update(
         &mut self,
         _session: &CachingSession,
         _extension: &Self::Extension,
-    ) -> Result<(), Self::Error> {
-        Ok(())
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
     }
 
-    async fn before_delete(
+    fn before_delete(
         &mut self,
         _session: &CachingSession,
         _extension: &Self::Extension,
-    ) -> Result<(), Self::Error> {
-        Ok(())
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
     }
 
-    async fn after_delete(
+    fn after_delete(
         &mut self,
         _session: &CachingSession,
         _extension: &Self::Extension,
-    ) -> Result<(), Self::Error> {
-        Ok(())
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async { Ok(()) }
     }
 }
 
@@ -72,22 +91,36 @@ pub trait Callbacks: Model {
 pub struct InsertAction<M: Callbacks>(M);
 pub struct UpdateAction<M: Callbacks>(M);
 pub struct DeleteAction<M: Callbacks>(M);
+pub struct CollectionUpdateAction<M: Callbacks>(M);
 
 pub trait CallbackAction<M: Callbacks> {
     fn query_value<Val: SerializeRow>(model: &M) -> QueryValue<Val, M>;
 
-    async fn before_execute(model: &mut M, session: &CachingSession, extension: &M::Extension) -> Result<(), M::Error>;
-
-    async fn after_execute(model: &mut M, session: &CachingSession, extension: &M::Extension) -> Result<(), M::Error>;
+    fn before_execute(
+        model: &mut M,
+        session: &CachingSession,
+        extension: &M::Extension,
+    ) -> impl Future<Output = Result<(), M::Error>> + Send;
+
+    fn after_execute(
+        model: &mut M,
+        session: &CachingSession,
+        extension: &M::Extension,
+    ) -> impl Future<Output = Result<(), M::Error>> + Send;
 }
 
 impl<M: Callbacks> CallbackAction<M> for InsertAction<M> {
     fn query_value<Val: SerializeRow>(model: &M) -> QueryValue<Val, M> {
-        QueryValue::Model(model)
+        model.insert_values()
     }
 
     async fn before_execute(model: &mut M, session: &CachingSession, extension: &M::Extension) -> Result<(), M::Error> {
-        model.before_insert(session, extension).await
+        model.generate_ids();
+        model.before_insert(session, extension).await?;
+        model.authorize(extension)?;
+        model.validate().map_err(CharybdisError::ValidationError)?;
+
+        Ok(())
     }
 
     async fn after_execute(model: &mut M, session: &CachingSession, extension: &M::Extension) -> Result<(), M::Error> {
@@ -97,11 +130,15 @@ impl<M: Callbacks> CallbackAction<M> for InsertAction<M> {
 
 impl<M: Callbacks> CallbackAction<M> for UpdateAction<M> {
     fn query_value<Val: SerializeRow>(model: &M) -> QueryValue<Val, M> {
-        QueryValue::Model(model)
+        model.update_values()
     }
 
     async fn before_execute(model: &mut M, session: &CachingSession, extension: &M::Extension) -> Result<(), M::Error> {
-        model.before_update(session, extension).await
+        model.before_update(session, extension).await?;
+        model.authorize(extension)?;
+        model.validate().map_err(CharybdisError::ValidationError)?;
+
+        Ok(())
     }
 
     async fn after_execute(model: &mut M, session: &CachingSession, extension: &M::Extension) -> Result<(), M::Error> {
@@ -115,10 +152,28 @@ impl<M: Callbacks> CallbackAction<M> for DeleteAction<M> {
     }
 
     async fn before_execute(model: &mut M, session: &CachingSession, extension: &M::Extension) -> Result<(), M::Error> {
-        model.before_delete(session, extension).await
+        model.before_delete(session, extension).await?;
+        model.authorize(extension)
     }
 
     async fn after_execute(model: &mut M, session: &CachingSession, extension: &M::Extension) -> Result<(), M::Error> {
         model.after_delete(session, extension).await
     }
 }
+
+/// Collection operations (`push_<field>_cb`, `pull_<field>_cb`) run update callbacks, while their values are
+/// bound by generated method, as they are not derived from the model.
+impl<M: Callbacks> CallbackAction<M> for CollectionUpdateAction<M> {
+    fn query_value<Val: SerializeRow>(_model: &M) -> QueryValue<Val, M> {
+        QueryValue::Empty
+    }
+
+    async fn before_execute(model: &mut M, session: &CachingSession, extension: &M::Extension) -> Result<(), M::Error> {
+        model.before_update(session, extension).await?;
+        model.authorize(extension)
+    }
+
+    async fn after_execute(model: &mut M, session: &CachingSession, extension: &M::Extension) -> Result<(), M::Error> {
+        model.after_update(session, extension).await
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::BTreeSet;
+
+use crate::model::Model;
+use crate::query::{NamedValues, QueryValue};
+
+/// Fields changed since model was read or saved. Fields are marked by generated `set_<field>` setters of
+/// model declared with `track_changes`, or by [Changes::mark] if field is assigned directly.
+/// ```rust ignore
+/// #[charybdis_model(table_name = users, partition_keys = [id], clustering_keys = [], track_changes)]
+/// #[derive(Default)]
+/// pub struct User {
+///     pub id: Uuid,
+///     pub email: Text,
+///     pub bio: Option<Text>,
+///     #[charybdis(ignore)]
+///     #[serde(skip)]
+///     pub changes: Changes,
+/// }
+///
+/// let mut user = User::find_by_id(id).execute(&session).await?;
+///
+/// user.set_bio(Some("...".to_string()));
+///
+/// // UPDATE users SET bio = :bio WHERE id = :id
+/// user.save(&session).await?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Changes {
+    fields: BTreeSet<&'static str>,
+}
+
+impl Changes {
+    pub fn mark(&mut self, field: &'static str) {
+        self.fields.insert(field);
+    }
+
+    pub fn is_changed(&self, field: &str) -> bool {
+        self.fields.contains(field)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Names of changed struct fields.
+    pub fn fields(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.fields.iter().copied()
+    }
+
+    pub fn clear(&mut self) {
+        self.fields.clear();
+    }
+}
+
+/// Model declared with `track_changes`. It's saved by [Save](crate::operations::Save), that updates only
+/// changed columns, so columns updated concurrently by other writers are not overwritten.
+pub trait TrackedModel: Model {
+    fn changes(&self) -> &Changes;
+
+    fn changes_mut(&mut self) -> &mut Changes;
+
+    /// `UPDATE` of changed columns and its values, bound by name along with primary key values. `None` if no
+    /// field was changed. Versioned models are updated with the same condition as `UPDATE_QUERY`, and values carry
+    /// the next version, see [QueryValue::next_version].
+    fn changes_update_statement(&self) -> Option<(String, QueryValue<'_, Self, Self>)>;
+}
+
+/// Model that can be compared with its stored row. It's saved by [SaveIfChanged](crate::operations::SaveIfChanged),
+/// that updates only columns that differ from the row. It's generated for models declared with `diff_model`.
+pub trait DiffModel: Model {
+    /// Non-key fields whose values differ from `other`. Values are compared by `PartialEq`, so sets and maps are
+    /// equal regardless of their iteration order. Version field is not compared.
+    fn diff(&self, other: &Self) -> Changes;
+
+    /// `UPDATE` of `changes` fields with values of this model. Versioned models are conditioned on version of
+    /// `current` row. If `lwt` is set, update is also conditioned on values of changed fields in `current` row.
+    fn diff_update_statement<'a>(
+        &'a self,
+        changes: &Changes,
+        current: &'a Self,
+        lwt: bool,
+    ) -> (String, NamedValues<'a>);
+}
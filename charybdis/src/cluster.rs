@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::driver::{self, QueryError, Session};
+use crate::errors::{CharybdisError, ErrorContext};
+
+const LOCAL_NODE_QUERY: &str =
+    "SELECT host_id, rpc_address, data_center, rack, release_version, schema_version FROM system.local";
+const PEERS_QUERY: &str =
+    "SELECT host_id, rpc_address, data_center, rack, release_version, schema_version FROM system.peers";
+const KEYSPACES_QUERY: &str = "SELECT keyspace_name, replication, durable_writes FROM system_schema.keyspaces";
+const SCHEMA_AGREEMENT_QUERY: &str = "schema agreement";
+
+const SCHEMA_AGREEMENT_INTERVAL: Duration = Duration::from_millis(200);
+
+type NodeRow = (
+    Option<Uuid>,
+    Option<IpAddr>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<Uuid>,
+);
+
+/// State of node as seen by the driver. Nodes that are listed in system tables, but not yet discovered by
+/// the driver, are `Unknown`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeState {
+    Up,
+    Down,
+    Unknown,
+}
+
+#[derive(Debug, Clone)]
+pub struct NodeHealth {
+    pub host_id: Uuid,
+    pub address: Option<IpAddr>,
+    pub datacenter: Option<String>,
+    pub rack: Option<String>,
+    pub release_version: Option<String>,
+    pub schema_version: Option<Uuid>,
+    pub state: NodeState,
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyspaceReplication {
+    pub name: String,
+    /// replication options, including `class` of replication strategy, e.g. `{"class": "...", "dc1": "3"}`
+    pub replication: HashMap<String, String>,
+    pub durable_writes: bool,
+}
+
+impl KeyspaceReplication {
+    pub fn strategy(&self) -> Option<&str> {
+        self.replication.get("class").map(String::as_str)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ClusterHealth {
+    pub nodes: Vec<NodeHealth>,
+    /// schema version all reachable nodes agree on, `None` if schema is not in agreement
+    pub schema_version: Option<Uuid>,
+    pub keyspaces: Vec<KeyspaceReplication>,
+}
+
+impl ClusterHealth {
+    pub fn is_schema_in_agreement(&self) -> bool {
+        self.schema_version.is_some()
+    }
+
+    pub fn down_nodes(&self) -> impl Iterator<Item = &NodeHealth> {
+        self.nodes.iter().filter(|node| node.state != NodeState::Up)
+    }
+
+    pub fn keyspace(&self, name: &str) -> Option<&KeyspaceReplication> {
+        self.keyspaces.iter().find(|keyspace| keyspace.name == name)
+    }
+}
+
+/// Node states, schema agreement and replication of keyspaces. Node details are read from `system.local` and
+/// `system.peers` and combined with node states tracked by the driver.
+/// ```rust ignore
+/// let health = charybdis::cluster::health(session.get_session()).await?;
+///
+/// for node in health.down_nodes() {
+///     println!("{} in {:?} is {:?}", node.host_id, node.datacenter, node.state);
+/// }
+/// ```
+pub async fn health(session: &Session) -> Result<ClusterHealth, CharybdisError> {
+    let mut nodes = node_rows(session, LOCAL_NODE_QUERY).await?;
+    nodes.extend(node_rows(session, PEERS_QUERY).await?);
+
+    let known_nodes = driver::known_nodes(session);
+
+    let mut nodes_health: Vec<NodeHealth> = vec![];
+
+    for (host_id, address, datacenter, rack, release_version, schema_version) in nodes {
+        let host_id = match host_id {
+            Some(host_id) => host_id,
+            None => continue,
+        };
+
+        // local node and peers can be read from different coordinators
+        if nodes_health.iter().any(|node| node.host_id == host_id) {
+            continue;
+        }
+
+        let state = match known_nodes.iter().find(|(node_id, _)| *node_id == host_id) {
+            Some((_, true)) => NodeState::Down,
+            Some(_) => NodeState::Up,
+            None => NodeState::Unknown,
+        };
+
+        nodes_health.push(NodeHealth {
+            host_id,
+            address,
+            datacenter,
+            rack,
+            release_version,
+            schema_version,
+            state,
+        });
+    }
+
+    let schema_version = driver::check_schema_agreement(session)
+        .await
+        .map_err(|e| CharybdisError::QueryError(ErrorContext::new(SCHEMA_AGREEMENT_QUERY), e))?;
+
+    Ok(ClusterHealth {
+        nodes: nodes_health,
+        schema_version,
+        keyspaces: keyspaces(session).await?,
+    })
+}
+
+/// Waits until all reachable nodes agree on schema version, e.g. after DDL statements. Unlike
+/// `Session::await_schema_agreement`, timeout is given per call.
+pub async fn await_schema_agreement(session: &Session, timeout: Duration) -> Result<Uuid, CharybdisError> {
+    let agreement = async {
+        loop {
+            if let Some(schema_version) = driver::check_schema_agreement(session).await? {
+                return Ok::<Uuid, QueryError>(schema_version);
+            }
+
+            tokio::time::sleep(SCHEMA_AGREEMENT_INTERVAL).await;
+        }
+    };
+
+    tokio::time::timeout(timeout, agreement)
+        .await
+        .map_err(|_| CharybdisError::SchemaAgreementTimeoutError(timeout))?
+        .map_err(|e| CharybdisError::QueryError(ErrorContext::new(SCHEMA_AGREEMENT_QUERY), e))
+}
+
+async fn node_rows(session: &Session, query: &'static str) -> Result<Vec<NodeRow>, CharybdisError> {
+    driver::query_unpaged(session, query, ())
+        .await
+        .map_err(|e| CharybdisError::QueryError(ErrorContext::new(query), e))?
+        .rows_typed::<NodeRow>()
+        .map_err(|e| CharybdisError::RowsExpectedError(ErrorContext::new(query), e))?
+        .collect::<Result<Vec<NodeRow>, _>>()
+        .map_err(|e| CharybdisError::FromRowError(ErrorContext::new(query), e))
+}
+
+async fn keyspaces(session: &Session) -> Result<Vec<KeyspaceReplication>, CharybdisError> {
+    let rows = driver::query_unpaged(session, KEYSPACES_QUERY, ())
+        .await
+        .map_err(|e| CharybdisError::QueryError(ErrorContext::new(KEYSPACES_QUERY), e))?
+        .rows_typed::<(String, HashMap<String, String>, Option<bool>)>()
+        .map_err(|e| CharybdisError::RowsExpectedError(ErrorContext::new(KEYSPACES_QUERY), e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| CharybdisError::FromRowError(ErrorContext::new(KEYSPACES_QUERY), e))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(name, replication, durable_writes)| KeyspaceReplication {
+            name,
+            replication,
+            durable_writes: durable_writes.unwrap_or(true),
+        })
+        .collect())
+}
@@ -0,0 +1,267 @@
+//! Internal seam between charybdis and the scylla driver.
+//!
+//! Session construction and query execution go through this module instead of calling driver
+//! directly, so driver specific API is confined to a single place. Driver types that appear in
+//! public API of charybdis (sessions, statements, results and query errors) are aliased here, and
+//! the rest of the crate names them only through these aliases. Each supported driver series is
+//! enabled by its own feature (`scylla-0-14` by default), which allows adding next major series
+//! behind a new feature, without forcing all downstream services to upgrade in lockstep. Public
+//! re-exports in `charybdis::scylla` and `charybdis::options` resolve to the selected driver.
+
+#[cfg(not(feature = "scylla-0-14"))]
+compile_error!("charybdis requires driver feature to be enabled, e.g. `scylla-0-14`");
+
+#[cfg(feature = "scylla-0-14")]
+mod scylla_0_14 {
+    use futures::StreamExt;
+    use scylla::batch::BatchStatement;
+    use scylla::execution_profile::{ExecutionProfile, ExecutionProfileHandle};
+    use scylla::serialize::batch::BatchValues;
+    use scylla::serialize::row::SerializeRow;
+    use scylla::statement::{PagingState, PagingStateResponse};
+    use scylla::transport::iterator::RowIterator;
+    use scylla::SessionBuilder;
+    use uuid::Uuid;
+
+    use crate::errors::{CharybdisError, ErrorContext};
+    use crate::session::{CharybdisSession, CharybdisSessionConfig, RowStream};
+    use crate::statements;
+
+    // Driver types used by public API of charybdis, re-exported by `charybdis::scylla`.
+    pub type CachingSession = scylla::CachingSession;
+    pub type Session = scylla::Session;
+    pub type Query = scylla::query::Query;
+    pub type Batch = scylla::batch::Batch;
+    pub type PreparedStatement = scylla::prepared_statement::PreparedStatement;
+    pub type QueryResult = scylla::QueryResult;
+    pub type QueryError = scylla::transport::errors::QueryError;
+
+    // Pinned statements are executed by underlying session, so they don't go through evictable cache.
+
+    pub(crate) async fn execute_unpaged(
+        session: &CachingSession,
+        statement: impl Into<Query>,
+        values: impl SerializeRow,
+    ) -> Result<QueryResult, QueryError> {
+        let mut statement = statement.into();
+        let tracked = statements::tracked(&statement.contents);
+
+        let res = match statements::pinned(&mut statement) {
+            Some(prepared) => session.get_session().execute_unpaged(&prepared, values).await,
+            None => session.execute_unpaged(statement, values).await,
+        };
+
+        if let Some(tracked) = tracked {
+            tracked.record_execution(res.is_ok());
+        }
+
+        res
+    }
+
+    pub(crate) async fn execute_iter(
+        session: &CachingSession,
+        statement: impl Into<Query>,
+        values: impl SerializeRow,
+    ) -> Result<RowIterator, QueryError> {
+        let mut statement = statement.into();
+        let tracked = statements::tracked(&statement.contents);
+
+        let res = match statements::pinned(&mut statement) {
+            Some(prepared) => session.get_session().execute_iter(prepared, values).await,
+            None => session.execute_iter(statement, values).await,
+        };
+
+        if let Some(tracked) = tracked {
+            tracked.record_execution(res.is_ok());
+        }
+
+        res
+    }
+
+    pub(crate) async fn execute_single_page(
+        session: &CachingSession,
+        statement: impl Into<Query>,
+        values: impl SerializeRow,
+        paging_state: PagingState,
+    ) -> Result<(QueryResult, PagingStateResponse), QueryError> {
+        let mut statement = statement.into();
+        let tracked = statements::tracked(&statement.contents);
+
+        let res = match statements::pinned(&mut statement) {
+            Some(prepared) => {
+                session
+                    .get_session()
+                    .execute_single_page(&prepared, values, paging_state)
+                    .await
+            }
+            None => session.execute_single_page(statement, values, paging_state).await,
+        };
+
+        if let Some(tracked) = tracked {
+            tracked.record_execution(res.is_ok());
+        }
+
+        res
+    }
+
+    pub(crate) async fn batch(
+        session: &CachingSession,
+        batch: &Batch,
+        values: impl BatchValues,
+    ) -> Result<QueryResult, QueryError> {
+        let queries = || {
+            batch.statements.iter().filter_map(|statement| match statement {
+                BatchStatement::Query(query) => Some(query.contents.as_str()),
+                _ => None,
+            })
+        };
+
+        let tracked = queries().filter_map(statements::tracked).collect::<Vec<_>>();
+
+        // batch is cloned only if some of its statements have to be replaced by pinned ones
+        let res = if statements::any_pinned(queries()) {
+            let mut batch = batch.clone();
+
+            for statement in batch.statements.iter_mut() {
+                if let BatchStatement::Query(query) = statement {
+                    if let Some(prepared) = statements::pinned(query) {
+                        *statement = BatchStatement::PreparedStatement(prepared);
+                    }
+                }
+            }
+
+            session.batch(&batch, values).await
+        } else {
+            session.batch(batch, values).await
+        };
+
+        for tracked in &tracked {
+            tracked.record_execution(res.is_ok());
+        }
+
+        res
+    }
+
+    pub(crate) async fn connect(config: CharybdisSessionConfig) -> Result<CachingSession, CharybdisError> {
+        let profile = ExecutionProfile::builder().consistency(config.consistency).build();
+
+        let mut builder = SessionBuilder::new()
+            .known_nodes(&config.known_nodes)
+            .compression(config.compression)
+            .connection_timeout(config.connection_timeout)
+            .default_execution_profile_handle(profile.into_handle());
+
+        if let Some((username, password)) = config.credentials {
+            builder = builder.user(username, password);
+        }
+
+        let session = builder.build().await.map_err(CharybdisError::NewSessionError)?;
+
+        if let Some(keyspace) = &config.keyspace {
+            if !session.get_cluster_data().get_keyspace_info().contains_key(keyspace) {
+                return Err(CharybdisError::KeyspaceNotFoundError(keyspace.clone()));
+            }
+
+            session
+                .use_keyspace(keyspace, false)
+                .await
+                .map_err(|e| CharybdisError::QueryError(ErrorContext::new("USE KEYSPACE"), e))?;
+        }
+
+        Ok(CachingSession::from(session, config.cache_size))
+    }
+
+    pub(crate) async fn prepare(session: &CachingSession, statement: impl Into<Query>) -> Result<(), QueryError> {
+        let statement = statement.into();
+
+        session.add_prepared_statement(&statement).await?;
+        statements::record_prepare(&statement.contents);
+
+        Ok(())
+    }
+
+    // Pinned statements and cluster metadata bypass statement cache, so they use underlying session.
+
+    pub(crate) fn base_session(session: &CachingSession) -> &Session {
+        session.get_session()
+    }
+
+    pub(crate) fn cache_capacity(session: &CachingSession) -> usize {
+        session.get_max_capacity()
+    }
+
+    pub(crate) fn default_profile_handle(session: &CachingSession) -> &ExecutionProfileHandle {
+        session.get_session().get_default_execution_profile_handle()
+    }
+
+    pub(crate) async fn prepare_uncached(
+        session: &CachingSession,
+        statement: &str,
+    ) -> Result<PreparedStatement, QueryError> {
+        session.get_session().prepare(Query::new(statement)).await
+    }
+
+    pub(crate) async fn query_unpaged(
+        session: &Session,
+        query: &'static str,
+        values: impl SerializeRow,
+    ) -> Result<QueryResult, QueryError> {
+        session.query_unpaged(query, values).await
+    }
+
+    pub(crate) async fn check_schema_agreement(session: &Session) -> Result<Option<Uuid>, QueryError> {
+        session.check_schema_agreement().await
+    }
+
+    /// Host ids of nodes discovered by the driver, and whether they are down.
+    pub(crate) fn known_nodes(session: &Session) -> Vec<(Uuid, bool)> {
+        session
+            .get_cluster_data()
+            .get_nodes_info()
+            .iter()
+            .map(|node| (node.host_id, node.is_down()))
+            .collect()
+    }
+
+    impl CharybdisSession for CachingSession {
+        async fn execute_unpaged(
+            &self,
+            statement: Query,
+            values: impl SerializeRow + Send,
+        ) -> Result<QueryResult, QueryError> {
+            execute_unpaged(self, statement, values).await
+        }
+
+        async fn execute_iter(
+            &self,
+            statement: Query,
+            values: impl SerializeRow + Send,
+        ) -> Result<RowStream, QueryError> {
+            execute_iter(self, statement, values).await.map(StreamExt::boxed)
+        }
+
+        async fn execute_single_page(
+            &self,
+            statement: Query,
+            values: impl SerializeRow + Send,
+            paging_state: PagingState,
+        ) -> Result<(QueryResult, PagingStateResponse), QueryError> {
+            execute_single_page(self, statement, values, paging_state).await
+        }
+
+        async fn batch(&self, batch: &Batch, values: impl BatchValues + Send) -> Result<QueryResult, QueryError> {
+            self::batch(self, batch, values).await
+        }
+
+        async fn prepare(&self, statement: Query) -> Result<(), QueryError> {
+            self::prepare(self, statement).await
+        }
+
+        fn default_profile_handle(&self) -> Option<&ExecutionProfileHandle> {
+            Some(self::default_profile_handle(self))
+        }
+    }
+}
+
+#[cfg(feature = "scylla-0-14")]
+pub use scylla_0_14::*;
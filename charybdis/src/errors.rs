@@ -4,63 +4,186 @@ use std::fmt;
 use colored::Colorize;
 use scylla::cql_to_rust::FromRowError;
 use scylla::frame::value::SerializeValuesError;
-use scylla::transport::errors::QueryError;
+use scylla::transport::errors::NewSessionError;
 use scylla::transport::iterator::NextRowError;
 use scylla::transport::query_result::{
     FirstRowTypedError, MaybeFirstRowTypedError, RowsExpectedError, SingleRowTypedError,
 };
 
+use crate::driver::QueryError;
+use crate::hooks::OperationKind;
+use crate::model::BaseModel;
+use crate::validation::ValidationErrors;
+
+/// Statement that failed and what it was executed for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorContext {
+    pub query: &'static str,
+    /// table of the model, `None` for statements that are not executed for a model
+    pub table: Option<&'static str>,
+    pub operation: OperationKind,
+    /// column that failed to convert, set for deserialization errors of model rows
+    pub column: Option<&'static str>,
+}
+
+impl ErrorContext {
+    pub(crate) fn new(query: &'static str) -> Self {
+        ErrorContext {
+            query,
+            table: None,
+            operation: OperationKind::from_query(query),
+            column: None,
+        }
+    }
+
+    pub(crate) fn model<M: BaseModel>(query: &'static str) -> Self {
+        ErrorContext {
+            table: Some(M::DB_MODEL_NAME),
+            ..ErrorContext::new(query)
+        }
+    }
+
+    /// Resolves index of column that failed to convert to its name. Columns of model rows are selected in
+    /// order of `SELECT_FIELDS_CLAUSE`.
+    pub(crate) fn column_of<M: BaseModel>(mut self, e: &FromRowError) -> Self {
+        if let FromRowError::BadCqlVal { column, .. } = e {
+            self.column = M::SELECT_FIELDS_CLAUSE.split(',').nth(*column).map(str::trim);
+        }
+
+        self
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Query: {}", self.query.bright_purple())?;
+
+        if let Some(table) = self.table {
+            write!(f, "\nTable: {}", table.bright_purple())?;
+        }
+
+        write!(f, "\nOperation: {:?}", self.operation)?;
+
+        if let Some(column) = self.column {
+            write!(f, "\nColumn: {}", column.bright_purple())?;
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub enum CharybdisError {
     // scylla
-    QueryError(&'static str, QueryError),
+    QueryError(ErrorContext, QueryError),
     BatchError(&'static str, QueryError),
-    RowsExpectedError(&'static str, RowsExpectedError),
-    SingleRowTypedError(&'static str, SingleRowTypedError),
-    SerializeValuesError(&'static str, SerializeValuesError),
-    FirstRowTypedError(&'static str, FirstRowTypedError),
-    MaybeFirstRowTypedError(&'static str, MaybeFirstRowTypedError),
-    FromRowError(&'static str, FromRowError),
-    NextRowError(&'static str, NextRowError),
-    NotFoundError(&'static str),
+    RowsExpectedError(ErrorContext, RowsExpectedError),
+    SingleRowTypedError(ErrorContext, SingleRowTypedError),
+    SerializeValuesError(ErrorContext, SerializeValuesError),
+    FirstRowTypedError(ErrorContext, FirstRowTypedError),
+    MaybeFirstRowTypedError(ErrorContext, MaybeFirstRowTypedError),
+    FromRowError(ErrorContext, FromRowError),
+    NextRowError(ErrorContext, NextRowError),
+    NewSessionError(NewSessionError),
+    NotFoundError(ErrorContext),
+    StaleObjectError(ErrorContext),
+    UniqueConstraintError(&'static str),
+    KeyspaceNotFoundError(String),
+    ExtensionNotRegisteredError(&'static str),
+    MaxPagesExceededError(ErrorContext, usize),
+    SchemaAgreementTimeoutError(std::time::Duration),
+    ValidationError(ValidationErrors),
     JsonError(serde_json::Error),
+    FixtureError(String),
+    TenantError(String),
+    /// Builder method can't be applied to the query, e.g. `limit_clamped` of query that is not partition finder.
+    QueryBuilderError(String),
+    /// Lookup row of given table was not released after operation failed, with error of the operation followed by
+    /// error of the release.
+    LookupReleaseError(&'static str, Box<CharybdisError>, Box<CharybdisError>),
 }
 
 impl fmt::Display for CharybdisError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             // scylla errors
-            CharybdisError::QueryError(query, e) => write!(f, "Query: {}\nQueryError: {}", query.bright_purple(), e),
+            CharybdisError::QueryError(context, e) => write!(f, "{}\nQueryError: {}", context, e),
             CharybdisError::BatchError(query, e) => write!(f, "Model: {}\nBatchError: {}", query.bright_purple(), e),
-            CharybdisError::RowsExpectedError(query, e) => {
-                write!(f, "Query: {}\nRowsExpectedError: {:?}", query.bright_purple(), e)
-            }
-            CharybdisError::SingleRowTypedError(query, e) => write!(
+            CharybdisError::RowsExpectedError(context, e) => write!(f, "{}\nRowsExpectedError: {:?}", context, e),
+            CharybdisError::SingleRowTypedError(context, e) => write!(
                 f,
-                "Query: {}\nSingleRowTypedError: {:?}. Did you forget to provide complete primary key?",
-                query.bright_purple(),
-                e
+                "{}\nSingleRowTypedError: {:?}. Did you forget to provide complete primary key?",
+                context, e
             ),
-            CharybdisError::FirstRowTypedError(query, e) => {
-                write!(f, "Query: {}\nFirstRowTypedError: {:?}", query.bright_purple(), e)
+            CharybdisError::FirstRowTypedError(context, e) => write!(f, "{}\nFirstRowTypedError: {:?}", context, e),
+            CharybdisError::MaybeFirstRowTypedError(context, e) => {
+                write!(f, "{}\nMaybeFirstRowTypedError: {:?}", context, e)
             }
-            CharybdisError::MaybeFirstRowTypedError(query, e) => {
-                write!(f, "Query: {}\nMaybeFirstRowTypedError: {:?}", query.bright_purple(), e)
+            CharybdisError::FromRowError(context, e) => write!(f, "{}\nFromRowError: {:?}", context, e),
+
+            CharybdisError::SerializeValuesError(context, e) => write!(f, "{}\nSerializeValuesError: {:?}", context, e),
+            CharybdisError::NotFoundError(context) => write!(f, "Records not found.\n{}", context),
+            CharybdisError::StaleObjectError(context) => {
+                write!(
+                    f,
+                    "Stale object. Record was modified or removed concurrently.\n{}",
+                    context
+                )
             }
-            CharybdisError::FromRowError(query, e) => {
-                write!(f, "Query: {}\nFromRowError: {:?}", query.bright_purple(), e)
+            CharybdisError::UniqueConstraintError(lookup_table) => {
+                write!(
+                    f,
+                    "Unique constraint violated. Lookup row is already claimed in: {}",
+                    lookup_table.bright_purple()
+                )
             }
-
-            CharybdisError::SerializeValuesError(query, e) => {
-                write!(f, "Query: {}\nSerializeValuesError: {:?}", query.bright_purple(), e)
+            CharybdisError::NextRowError(context, e) => write!(f, "{}\nNextRowError: {:?}", context, e),
+            CharybdisError::NewSessionError(e) => write!(f, "NewSessionError: {}", e),
+            CharybdisError::KeyspaceNotFoundError(keyspace) => {
+                write!(f, "Keyspace not found: {}", keyspace.bright_purple())
             }
-            CharybdisError::NotFoundError(query) => {
-                write!(f, "Records not found for query: {}", query.bright_purple())
+            CharybdisError::ExtensionNotRegisteredError(extension) => {
+                write!(f, "Callback extension not registered: {}", extension.bright_purple())
             }
-            CharybdisError::NextRowError(query, e) => {
-                write!(f, "Query: {}\nNextRowError: {:?}", query.bright_purple(), e)
+            CharybdisError::MaxPagesExceededError(context, max_pages) => {
+                write!(f, "{}\nMaxPagesExceededError: more than {} pages", context, max_pages)
             }
+            CharybdisError::SchemaAgreementTimeoutError(timeout) => {
+                write!(f, "Schema agreement not reached within {:?}", timeout)
+            }
+            CharybdisError::ValidationError(errors) => write!(f, "ValidationError: {}", errors),
             CharybdisError::JsonError(e) => write!(f, "JsonError: {:?}", e),
+            CharybdisError::FixtureError(message) => write!(f, "FixtureError: {}", message),
+            CharybdisError::TenantError(message) => write!(f, "TenantError: {}", message),
+            CharybdisError::QueryBuilderError(message) => write!(f, "QueryBuilderError: {}", message),
+            CharybdisError::LookupReleaseError(lookup_table, e, release_error) => write!(
+                f,
+                "{}\nLookupReleaseError: lookup row of {} not released: {}",
+                e,
+                lookup_table.bright_purple(),
+                release_error
+            ),
+        }
+    }
+}
+
+impl CharybdisError {
+    /// Statement, table and operation of failed query, `None` for errors that are not raised by query.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            CharybdisError::QueryError(context, _)
+            | CharybdisError::RowsExpectedError(context, _)
+            | CharybdisError::SingleRowTypedError(context, _)
+            | CharybdisError::SerializeValuesError(context, _)
+            | CharybdisError::FirstRowTypedError(context, _)
+            | CharybdisError::MaybeFirstRowTypedError(context, _)
+            | CharybdisError::FromRowError(context, _)
+            | CharybdisError::NextRowError(context, _)
+            | CharybdisError::NotFoundError(context)
+            | CharybdisError::StaleObjectError(context)
+            | CharybdisError::MaxPagesExceededError(context, _) => Some(context),
+            CharybdisError::LookupReleaseError(_, e, _) => e.context(),
+            _ => None,
         }
     }
 }
@@ -76,7 +199,10 @@ impl Error for CharybdisError {
             CharybdisError::FromRowError(_, e) => Some(e),
             CharybdisError::NextRowError(_, e) => Some(e),
             CharybdisError::SerializeValuesError(_, e) => Some(e),
+            CharybdisError::NewSessionError(e) => Some(e),
+            CharybdisError::ValidationError(errors) => Some(errors),
             CharybdisError::JsonError(e) => Some(e),
+            CharybdisError::LookupReleaseError(_, _, release_error) => Some(release_error.as_ref()),
             _ => None,
         }
     }
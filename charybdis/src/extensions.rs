@@ -0,0 +1,63 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+type ExtensionMap = HashMap<TypeId, Arc<dyn Any + Send + Sync>>;
+
+static EXTENSIONS: OnceLock<RwLock<ExtensionMap>> = OnceLock::new();
+
+fn extensions() -> &'static RwLock<ExtensionMap> {
+    EXTENSIONS.get_or_init(Default::default)
+}
+
+/// Global typed registry of callback extensions, so callback queries can be executed without passing
+/// `Callbacks::Extension` at every call site.
+/// ```rust ignore
+/// charybdis::extensions::Extensions::insert(AppCtx { search: ElasticClient::new() });
+///
+/// // resolves `AppCtx` registered above
+/// post.insert_cb_registered().execute(&session).await?;
+/// ```
+/// Values are keyed by type, so there is at most one registered value per extension type.
+pub struct Extensions;
+
+impl Extensions {
+    /// Registers value, replacing previously registered value of the same type.
+    pub fn insert<T: Send + Sync + 'static>(value: T) {
+        Self::insert_arc(Arc::new(value));
+    }
+
+    /// Same as `insert`, for values that are already shared with the rest of the application.
+    pub fn insert_arc<T: Send + Sync + 'static>(value: Arc<T>) {
+        extensions()
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(TypeId::of::<T>(), value);
+    }
+
+    pub fn get<T: Send + Sync + 'static>() -> Option<Arc<T>> {
+        let value = extensions()
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&TypeId::of::<T>())
+            .cloned()?;
+
+        value.downcast::<T>().ok()
+    }
+
+    pub fn remove<T: Send + Sync + 'static>() -> Option<Arc<T>> {
+        let value = extensions()
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&TypeId::of::<T>())?;
+
+        value.downcast::<T>().ok()
+    }
+
+    pub fn contains<T: Send + Sync + 'static>() -> bool {
+        extensions()
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains_key(&TypeId::of::<T>())
+    }
+}
@@ -0,0 +1,279 @@
+use std::path::Path;
+
+use futures::future::BoxFuture;
+use serde_json::Value;
+
+use crate::batch::CharybdisModelBatch;
+use crate::errors::CharybdisError;
+use crate::model::Model;
+use crate::serializers::CqlJson;
+use crate::session::CharybdisSession;
+
+const DEFAULT_CHUNK_SIZE: usize = 100;
+
+type Loader<S> = for<'a> fn(Vec<Value>, &'a S, usize, bool) -> BoxFuture<'a, Result<(), CharybdisError>>;
+
+struct FixtureModel<S> {
+    table: &'static str,
+    load: Loader<S>,
+}
+
+/// Seed data keyed by table name of the model, with rows in CQL JSON representation, see
+/// [CqlJson](crate::serializers::CqlJson):
+/// ```json
+/// {
+///     "users": [{ "id": "8e4fc0a4-...", "username": "admin" }],
+///     "posts": [{ "id": "b1b5bd41-...", "author_id": "8e4fc0a4-...", "title": "Hello" }]
+/// }
+/// ```
+/// Rows are parsed like by generated `from_json` and inserted in unlogged batches, like by
+/// [Insert::insert_many](crate::operations::Insert::insert_many). Models are loaded in order they were added, unless
+/// dependencies between them are declared:
+/// ```rust ignore
+/// Fixtures::new()
+///     .model::<Post>()
+///     .model::<User>()
+///     .depends_on::<Post, User>()
+///     .load_file(&session, "fixtures/dev.json")
+///     .await?;
+/// ```
+pub struct Fixtures<S: CharybdisSession> {
+    models: Vec<FixtureModel<S>>,
+    dependencies: Vec<(&'static str, &'static str)>,
+    chunk_size: usize,
+    unscoped: bool,
+}
+
+fn load_model<M, S>(
+    rows: Vec<Value>,
+    session: &S,
+    chunk_size: usize,
+    unscoped: bool,
+) -> BoxFuture<'_, Result<(), CharybdisError>>
+where
+    M: Model + CqlJson + 'static,
+    S: CharybdisSession,
+{
+    Box::pin(async move {
+        let mut models = rows
+            .into_iter()
+            .map(|row| M::from_json_value(row).map_err(CharybdisError::JsonError))
+            .collect::<Result<Vec<M>, CharybdisError>>()?;
+
+        models.iter_mut().for_each(Model::generate_ids);
+
+        let mut batch = CharybdisModelBatch::<M, M>::unlogged();
+
+        if unscoped {
+            batch = batch.unscoped();
+        }
+
+        batch.chunked_insert(session, &models, chunk_size).await
+    })
+}
+
+impl<S: CharybdisSession> Fixtures<S> {
+    pub fn new() -> Self {
+        Self {
+            models: vec![],
+            dependencies: vec![],
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            unscoped: false,
+        }
+    }
+
+    pub fn model<M: Model + CqlJson + 'static>(mut self) -> Self {
+        if self.models.iter().any(|model| model.table == M::DB_MODEL_NAME) {
+            return self;
+        }
+
+        self.models.push(FixtureModel {
+            table: M::DB_MODEL_NAME,
+            load: load_model::<M, S>,
+        });
+
+        self
+    }
+
+    /// Rows of `M` are inserted after rows of `D`, e.g. rows referencing `D` by its key.
+    pub fn depends_on<M: Model, D: Model>(mut self) -> Self {
+        self.dependencies.push((M::DB_MODEL_NAME, D::DB_MODEL_NAME));
+        self
+    }
+
+    /// Number of inserts in single unlogged batch.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Loads rows of models with `tenant_key` for all tenants, see [TenantScoped](crate::tenancy::TenantScoped).
+    /// Rows of such models are rejected unless fixtures are explicitly unscoped.
+    pub fn unscoped(mut self) -> Self {
+        self.unscoped = true;
+        self
+    }
+
+    pub async fn load_json(&self, session: &S, json: &str) -> Result<(), CharybdisError> {
+        let data = serde_json::from_str(json).map_err(CharybdisError::JsonError)?;
+
+        self.load(session, data).await
+    }
+
+    /// TOML tables are arrays of tables named after models, e.g. `[[users]]`. Timestamps should be given as strings,
+    /// as TOML datetimes are not part of CQL JSON representation.
+    #[cfg(feature = "toml")]
+    pub async fn load_toml(&self, session: &S, toml: &str) -> Result<(), CharybdisError> {
+        let data = toml::from_str(toml).map_err(|e| CharybdisError::FixtureError(e.to_string()))?;
+
+        self.load(session, data).await
+    }
+
+    /// Loads `.json` file, or `.toml` file with `toml` feature enabled.
+    pub async fn load_file(&self, session: &S, path: impl AsRef<Path>) -> Result<(), CharybdisError> {
+        let path = path.as_ref();
+        let contents = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| CharybdisError::FixtureError(format!("{}: {}", path.display(), e)))?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => self.load_json(session, &contents).await,
+            #[cfg(feature = "toml")]
+            Some("toml") => self.load_toml(session, &contents).await,
+            _ => Err(CharybdisError::FixtureError(format!(
+                "unsupported fixture file: {}",
+                path.display()
+            ))),
+        }
+    }
+
+    async fn load(&self, session: &S, data: Value) -> Result<(), CharybdisError> {
+        let mut data = match data {
+            Value::Object(data) => data,
+            other => {
+                return Err(CharybdisError::FixtureError(format!(
+                    "expected object keyed by table names, got {}",
+                    other
+                )))
+            }
+        };
+
+        if let Some(table) = data
+            .keys()
+            .find(|table| !self.models.iter().any(|model| model.table == table.as_str()))
+        {
+            return Err(CharybdisError::FixtureError(format!(
+                "model of table {} is not added",
+                table
+            )));
+        }
+
+        for model in self.ordered_models()? {
+            let rows = match data.remove(model.table) {
+                Some(Value::Array(rows)) => rows,
+                Some(other) => {
+                    return Err(CharybdisError::FixtureError(format!(
+                        "expected array of rows for {}, got {}",
+                        model.table, other
+                    )))
+                }
+                None => continue,
+            };
+
+            (model.load)(rows, session, self.chunk_size, self.unscoped).await?;
+        }
+
+        Ok(())
+    }
+
+    fn ordered_models(&self) -> Result<Vec<&FixtureModel<S>>, CharybdisError> {
+        let tables = self.models.iter().map(|model| model.table).collect::<Vec<_>>();
+        let order = ordered_tables(&tables, &self.dependencies)?;
+
+        Ok(order.into_iter().map(|i| &self.models[i]).collect())
+    }
+}
+
+/// Indexes of tables ordered so each table comes after tables it depends on, otherwise in order they were added.
+fn ordered_tables(tables: &[&str], dependencies: &[(&str, &str)]) -> Result<Vec<usize>, CharybdisError> {
+    if let Some((dependent, dependency)) = dependencies.iter().find(|(_, dependency)| !tables.contains(dependency)) {
+        return Err(CharybdisError::FixtureError(format!(
+            "model of table {} that {} depends on is not added",
+            dependency, dependent
+        )));
+    }
+
+    let mut ordered: Vec<usize> = vec![];
+
+    while ordered.len() < tables.len() {
+        let next = (0..tables.len()).find(|&i| {
+            !ordered.contains(&i)
+                && dependencies.iter().all(|(dependent, dependency)| {
+                    *dependent != tables[i] || ordered.iter().any(|&added| tables[added] == *dependency)
+                })
+        });
+
+        match next {
+            Some(i) => ordered.push(i),
+            None => {
+                let remaining = (0..tables.len())
+                    .filter(|i| !ordered.contains(i))
+                    .map(|i| tables[i])
+                    .collect::<Vec<_>>();
+
+                return Err(CharybdisError::FixtureError(format!(
+                    "circular fixture dependencies between {}",
+                    remaining.join(", ")
+                )));
+            }
+        }
+    }
+
+    Ok(ordered)
+}
+
+impl<S: CharybdisSession> Default for Fixtures<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_tables_after_their_dependencies() {
+        let tables = ["posts", "comments", "users"];
+        let dependencies = [("comments", "posts"), ("posts", "users")];
+
+        assert_eq!(ordered_tables(&tables, &dependencies).unwrap(), vec![2, 0, 1]);
+        assert_eq!(ordered_tables(&tables, &[]).unwrap(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn rejects_circular_dependencies() {
+        let tables = ["users", "posts", "comments"];
+        let dependencies = [("posts", "comments"), ("comments", "posts")];
+
+        match ordered_tables(&tables, &dependencies) {
+            Err(CharybdisError::FixtureError(message)) => {
+                assert_eq!(message, "circular fixture dependencies between posts, comments")
+            }
+            other => panic!("expected circular dependencies error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_missing_dependencies() {
+        let tables = ["posts"];
+        let dependencies = [("posts", "users")];
+
+        match ordered_tables(&tables, &dependencies) {
+            Err(CharybdisError::FixtureError(message)) => {
+                assert_eq!(message, "model of table users that posts depends on is not added")
+            }
+            other => panic!("expected missing dependency error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use charybdis_parser::statement::{insert_conditions, split_trailing_clauses};
+use scylla::frame::response::result::{ColumnSpec, PreparedMetadata};
+use scylla::serialize::row::{RowSerializationContext, SerializeRow};
+use scylla::serialize::{RowWriter, SerializationError};
+
+use crate::errors::CharybdisError;
+
+/// Additional `WHERE` conditions with their own bound values, appended to generated query by
+/// [CharybdisQuery::and_where](crate::query::CharybdisQuery::and_where):
+/// ```rust ignore
+/// // SELECT ... FROM posts WHERE category_id = ? AND created_at > ?
+/// let posts = Post::find_by_category_id(category_id)
+///     .and_where(QueryFragment::new("created_at > ?", (since,)))
+///     .execute(&session)
+///     .await?;
+/// ```
+/// Conditions on non-key columns still require secondary index or `ALLOW FILTERING`.
+pub struct QueryFragment<'a> {
+    cql: String,
+    values: Box<dyn SerializeRow + Send + Sync + 'a>,
+    bind_markers: usize,
+}
+
+impl<'a> QueryFragment<'a> {
+    pub fn new(cql: impl Into<String>, values: impl SerializeRow + Send + Sync + 'a) -> Self {
+        let cql = cql.into();
+        let bind_markers = count_bind_markers(&cql);
+
+        Self {
+            cql,
+            values: Box::new(values),
+            bind_markers,
+        }
+    }
+
+    /// Inserts conditions into WHERE clause of given statement, before `ORDER BY`, `LIMIT`,
+    /// `ALLOW FILTERING` and `IF` clauses. Statement whose trailing clauses contain bind markers is rejected,
+    /// as their values are bound after values of the fragment.
+    pub(crate) fn apply(&self, statement: &str) -> Result<String, CharybdisError> {
+        let (_, trailing) = split_trailing_clauses(statement);
+
+        if count_bind_markers(trailing) > 0 {
+            return Err(CharybdisError::QueryBuilderError(format!(
+                "query fragment can't be applied to statement with bound values after WHERE clause: {}",
+                statement
+            )));
+        }
+
+        Ok(insert_conditions(statement, &self.cql))
+    }
+
+    /// Serializes values of the query followed by values of the fragment. Each of them is given only
+    /// its own bind markers, as tuples check number of columns.
+    pub(crate) fn serialize_after(
+        &self,
+        query_values: &impl SerializeRow,
+        ctx: &RowSerializationContext<'_>,
+        writer: &mut RowWriter,
+    ) -> Result<(), SerializationError> {
+        let columns = ctx.columns();
+        let (query_columns, fragment_columns) = columns.split_at(columns.len().saturating_sub(self.bind_markers));
+
+        let query_metadata = prepared_metadata(query_columns);
+        query_values.serialize(&RowSerializationContext::from_prepared(&query_metadata), writer)?;
+
+        let fragment_metadata = prepared_metadata(fragment_columns);
+        self.values
+            .serialize(&RowSerializationContext::from_prepared(&fragment_metadata), writer)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Removes condition added by [soft_delete_filtered](charybdis_parser::statement::soft_delete_filtered), so rows
+/// marked as deleted are selected as well. `ALLOW FILTERING` is kept, as it may be required by other conditions.
+pub(crate) fn without_soft_delete_filter(statement: &str, soft_delete_column: &str) -> String {
+    let condition = format!("{} = 0", soft_delete_column);
+
+    // condition is either the last one, the only one, or precedes conditions of hand-written statement
+    let first_of_many = format!(" WHERE {} AND ", condition);
+    let following = format!(" AND {}", condition);
+    let only = format!(" WHERE {}", condition);
+
+    if statement.contains(&first_of_many) {
+        statement.replacen(&first_of_many, " WHERE ", 1)
+    } else if statement.contains(&following) {
+        statement.replacen(&following, "", 1)
+    } else {
+        statement.replacen(&only, "", 1)
+    }
+}
+
+/// Limits statement to the first row, before `ALLOW FILTERING` that has to be the last clause.
+pub(crate) fn first_row_statement(statement: &str) -> String {
+    let position = match statement.to_ascii_uppercase().strip_suffix(" ALLOW FILTERING") {
+        Some(conditions) => conditions.len(),
+        None => statement.len(),
+    };
+    let (conditions, trailing) = statement.split_at(position);
+
+    format!("{} LIMIT 1{}", conditions, trailing)
+}
+
+pub(crate) fn prepared_metadata(columns: &[ColumnSpec]) -> PreparedMetadata {
+    PreparedMetadata {
+        flags: 0,
+        col_count: columns.len(),
+        pk_indexes: vec![],
+        col_specs: columns.to_vec(),
+    }
+}
+
+/// Counts `?` and named `:name` bind markers outside of string literals.
+fn count_bind_markers(cql: &str) -> usize {
+    let mut in_literal = false;
+    let mut chars = cql.chars().peekable();
+    let mut count = 0;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => in_literal = !in_literal,
+            '?' if !in_literal => count += 1,
+            ':' if !in_literal && chars.peek().is_some_and(|next| is_name_start(*next)) => count += 1,
+            _ => {}
+        }
+    }
+
+    count
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || c == '"'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use charybdis_parser::statement::soft_delete_filtered;
+
+    #[test]
+    fn soft_delete_filter_is_inserted_before_trailing_clauses() {
+        let filtered = soft_delete_filtered("SELECT id FROM posts WHERE category_id = ? LIMIT ?", "deleted_at");
+
+        assert_eq!(
+            filtered,
+            "SELECT id FROM posts WHERE category_id = ? AND deleted_at = 0 LIMIT ? ALLOW FILTERING"
+        );
+        assert_eq!(
+            without_soft_delete_filter(&filtered, "deleted_at"),
+            "SELECT id FROM posts WHERE category_id = ? LIMIT ? ALLOW FILTERING"
+        );
+    }
+
+    #[test]
+    fn soft_delete_filter_is_removed_from_any_position() {
+        let only = soft_delete_filtered("SELECT id FROM posts", "deleted_at");
+        let first = "SELECT id FROM posts WHERE deleted_at = 0 AND title = ? ALLOW FILTERING";
+
+        assert_eq!(only, "SELECT id FROM posts WHERE deleted_at = 0 ALLOW FILTERING");
+        assert_eq!(
+            without_soft_delete_filter(&only, "deleted_at"),
+            "SELECT id FROM posts ALLOW FILTERING"
+        );
+        assert_eq!(
+            without_soft_delete_filter(first, "deleted_at"),
+            "SELECT id FROM posts WHERE title = ? ALLOW FILTERING"
+        );
+    }
+
+    #[test]
+    fn fragment_is_rejected_by_statement_with_bind_markers_after_where() {
+        let fragment = QueryFragment::new("created_at > ?", (1,));
+
+        assert!(matches!(
+            fragment.apply("SELECT id FROM posts WHERE category_id = ? LIMIT ?"),
+            Err(CharybdisError::QueryBuilderError(_))
+        ));
+        assert_eq!(
+            fragment
+                .apply("SELECT id FROM posts WHERE category_id = ? LIMIT 10")
+                .unwrap(),
+            "SELECT id FROM posts WHERE category_id = ? AND created_at > ? LIMIT 10"
+        );
+    }
+
+    #[test]
+    fn bind_markers_are_counted_outside_of_literals() {
+        assert_eq!(count_bind_markers("created_at > ? AND title = '?'"), 1);
+        assert_eq!(count_bind_markers("created_at > :since AND author_id = :author_id"), 2);
+        assert_eq!(count_bind_markers("tags CONTAINS 'a:b' AND meta = {'k': 1}"), 0);
+    }
+
+    #[test]
+    fn first_row_limit_precedes_allow_filtering() {
+        assert_eq!(
+            first_row_statement("SELECT id FROM posts"),
+            "SELECT id FROM posts LIMIT 1"
+        );
+        assert_eq!(
+            first_row_statement("SELECT id FROM posts WHERE deleted_at = 0 ALLOW FILTERING"),
+            "SELECT id FROM posts WHERE deleted_at = 0 LIMIT 1 ALLOW FILTERING"
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use std::borrow::Cow;
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use scylla::batch::{Batch, BatchStatement};
+use scylla::serialize::row::SerializeRow;
+
+use crate::errors::CharybdisError;
+use crate::options::Consistency;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    Select,
+    Insert,
+    Update,
+    Delete,
+    Batch,
+    Other,
+}
+
+impl OperationKind {
+    /// Kind of statement by its leading keyword.
+    pub fn from_query(query: &str) -> Self {
+        let keyword = query.split_whitespace().next().unwrap_or_default();
+
+        match keyword.to_ascii_uppercase().as_str() {
+            "SELECT" => OperationKind::Select,
+            "INSERT" => OperationKind::Insert,
+            "UPDATE" => OperationKind::Update,
+            "DELETE" => OperationKind::Delete,
+            "BEGIN" => OperationKind::Batch,
+            _ => OperationKind::Other,
+        }
+    }
+
+    /// Leading keyword of statement, e.g. `SELECT`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            OperationKind::Select => "SELECT",
+            OperationKind::Insert => "INSERT",
+            OperationKind::Update => "UPDATE",
+            OperationKind::Delete => "DELETE",
+            OperationKind::Batch => "BATCH",
+            OperationKind::Other => "OTHER",
+        }
+    }
+
+    pub fn is_mutation(&self) -> bool {
+        matches!(
+            self,
+            OperationKind::Insert | OperationKind::Update | OperationKind::Delete | OperationKind::Batch
+        )
+    }
+}
+
+/// Description of executed statement, as passed to [QueryHook].
+pub struct QueryInfo<'a> {
+    /// CQL as sent to the database, including appended `LIMIT` or `WHERE` conditions. Statements of batch
+    /// are separated by `;`.
+    pub query: Cow<'a, str>,
+    /// Table of the model, `None` for batch of arbitrary queries.
+    pub table: Option<&'static str>,
+    pub operation: OperationKind,
+    /// Consistency set on statement, `None` if default consistency of execution profile is used.
+    pub consistency: Option<Consistency>,
+    /// Bound values, `None` for batches. They can be serialized against column specs of prepared statement.
+    pub values: Option<&'a dyn SerializeRow>,
+}
+
+impl<'a> QueryInfo<'a> {
+    pub(crate) fn batch(batch: &Batch, table: Option<&'static str>) -> Self {
+        let statements = batch
+            .statements
+            .iter()
+            .map(|statement| match statement {
+                BatchStatement::Query(query) => query.contents.as_str(),
+                BatchStatement::PreparedStatement(prepared) => prepared.get_statement(),
+            })
+            .collect::<Vec<&str>>();
+
+        QueryInfo {
+            query: Cow::Owned(statements.join("; ")),
+            table,
+            operation: OperationKind::Batch,
+            consistency: batch.get_consistency(),
+            values: None,
+        }
+    }
+}
+
+/// Receives each statement executed by `CharybdisQuery` or batch, e.g. to write audit log of all mutations:
+/// ```rust ignore
+/// struct AuditHook;
+///
+/// impl QueryHook for AuditHook {
+///     fn after_execute(&self, query: &QueryInfo, elapsed: Duration, error: Option<&CharybdisError>) {
+///         if query.operation.is_mutation() {
+///             audit_log::write(query.table, &query.query, elapsed, error.is_none());
+///         }
+///     }
+/// }
+///
+/// charybdis::hooks::set_query_hook(AuditHook);
+/// ```
+/// Retried queries are reported once, with `elapsed` covering all attempts.
+pub trait QueryHook: Send + Sync {
+    fn before_execute(&self, _query: &QueryInfo) {}
+
+    fn after_execute(&self, _query: &QueryInfo, _elapsed: Duration, _error: Option<&CharybdisError>) {}
+}
+
+static QUERY_HOOK: OnceLock<Box<dyn QueryHook>> = OnceLock::new();
+
+/// Sets global query hook. Returns `false` if hook was already set.
+pub fn set_query_hook(hook: impl QueryHook + 'static) -> bool {
+    QUERY_HOOK.set(Box::new(hook)).is_ok()
+}
+
+/// Reports execution to query hook, if it's set. Info is built separately before and after execution, as it
+/// borrows values that are not necessarily `Sync`, so it can't be held across await. With `otel` feature,
+/// execution is also wrapped in span.
+pub(crate) async fn observe<'a, T>(
+    query_info: impl Fn() -> QueryInfo<'a>,
+    execution: impl Future<Output = Result<T, CharybdisError>>,
+) -> Result<T, CharybdisError> {
+    #[cfg(feature = "otel")]
+    let execution = crate::otel::in_span(&query_info(), execution);
+
+    let hook = match QUERY_HOOK.get() {
+        Some(hook) => hook,
+        None => return execution.await,
+    };
+
+    hook.before_execute(&query_info());
+
+    let start = Instant::now();
+    let res = execution.await;
+
+    hook.after_execute(&query_info(), start.elapsed(), res.as_ref().err());
+
+    res
+}
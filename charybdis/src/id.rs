@@ -0,0 +1,204 @@
+use std::sync::atomic::{AtomicU16, AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::types::{BigInt, Timeuuid, Uuid};
+
+/// Id generators used by `#[charybdis(id_strategy = "...")]` key fields.
+/// Generated `Model::generate_ids` populates only fields that are still unset, so ids assigned
+/// manually are never overwritten. It's called by inserts that own or mutably borrow the model:
+/// ```rust ignore
+/// #[charybdis_model(
+///     table_name = posts,
+///     partition_keys = [id],
+///     clustering_keys = []
+/// )]
+/// pub struct Post {
+///     #[charybdis(id_strategy = "uuid_v7")]
+///     pub id: Uuid,
+///     pub title: Text,
+/// }
+/// ```
+pub trait IdValue {
+    fn is_unset(&self) -> bool;
+}
+
+impl IdValue for Uuid {
+    fn is_unset(&self) -> bool {
+        self.is_nil()
+    }
+}
+
+impl IdValue for Timeuuid {
+    fn is_unset(&self) -> bool {
+        self.as_ref().is_nil()
+    }
+}
+
+impl IdValue for BigInt {
+    fn is_unset(&self) -> bool {
+        *self == 0
+    }
+}
+
+impl IdValue for String {
+    fn is_unset(&self) -> bool {
+        self.is_empty()
+    }
+}
+
+impl<T> IdValue for Option<T> {
+    fn is_unset(&self) -> bool {
+        self.is_none()
+    }
+}
+
+/// time ordered uuid (RFC 9562 version 7), suitable for `uuid` columns
+pub fn uuid_v7() -> Uuid {
+    Uuid::now_v7()
+}
+
+static TIMEUUID_NODE_ID: OnceLock<[u8; 6]> = OnceLock::new();
+
+/// time based uuid (RFC 9562 version 1) with random node id of the process, suitable for `timeuuid` columns
+pub fn timeuuid() -> Timeuuid {
+    let node_id = TIMEUUID_NODE_ID.get_or_init(|| {
+        let mut node_id = [0; 6];
+        node_id.copy_from_slice(&Uuid::new_v4().as_bytes()[..6]);
+
+        // multicast bit marks random node id, so it doesn't collide with MAC address of other process
+        node_id[0] |= 0x01;
+
+        node_id
+    });
+
+    Timeuuid::now_v1(node_id)
+}
+
+// 2024-01-01T00:00:00Z
+const SNOWFLAKE_EPOCH_MS: u64 = 1_704_067_200_000;
+const SNOWFLAKE_NODE_ID_BITS: u64 = 10;
+const SNOWFLAKE_SEQUENCE_BITS: u64 = 12;
+const SNOWFLAKE_NODE_ID_MASK: u64 = (1 << SNOWFLAKE_NODE_ID_BITS) - 1;
+const SNOWFLAKE_SEQUENCE_MASK: u64 = (1 << SNOWFLAKE_SEQUENCE_BITS) - 1;
+
+static SNOWFLAKE_NODE_ID: AtomicU16 = AtomicU16::new(0);
+
+// last used timestamp shifted by sequence bits | last used sequence
+static SNOWFLAKE_STATE: AtomicU64 = AtomicU64::new(0);
+
+/// Sets node id used by [snowflake] generator. Only lower 10 bits are used, so each process
+/// generating ids for the same table should use distinct value in range `0..1024`.
+pub fn set_snowflake_node_id(node_id: u16) {
+    SNOWFLAKE_NODE_ID.store(node_id, Ordering::Relaxed);
+}
+
+/// 64 bit time ordered id, suitable for `bigint` columns:
+/// 41 bits of milliseconds since 2024-01-01 | 10 bits of node id | 12 bits of sequence.
+/// If sequence is exhausted within single millisecond, or clock moves backwards,
+/// generator keeps issuing ids from last used timestamp so ids stay unique and monotonic.
+pub fn snowflake() -> BigInt {
+    let node_id = SNOWFLAKE_NODE_ID.load(Ordering::Relaxed) as u64 & SNOWFLAKE_NODE_ID_MASK;
+
+    loop {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default()
+            .saturating_sub(SNOWFLAKE_EPOCH_MS);
+
+        let last = SNOWFLAKE_STATE.load(Ordering::Acquire);
+        let next = next_snowflake_state(last, now);
+
+        if SNOWFLAKE_STATE
+            .compare_exchange_weak(last, next, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            return snowflake_id(next, node_id);
+        }
+    }
+}
+
+/// State following `last` state at `now` milliseconds since snowflake epoch.
+fn next_snowflake_state(last: u64, now: u64) -> u64 {
+    let last_ms = last >> SNOWFLAKE_SEQUENCE_BITS;
+
+    let (ms, sequence) = if now > last_ms {
+        (now, 0)
+    } else {
+        let sequence = (last & SNOWFLAKE_SEQUENCE_MASK) + 1;
+
+        if sequence > SNOWFLAKE_SEQUENCE_MASK {
+            (last_ms + 1, 0)
+        } else {
+            (last_ms, sequence)
+        }
+    };
+
+    (ms << SNOWFLAKE_SEQUENCE_BITS) | sequence
+}
+
+fn snowflake_id(state: u64, node_id: u64) -> BigInt {
+    let ms = state >> SNOWFLAKE_SEQUENCE_BITS;
+    let sequence = state & SNOWFLAKE_SEQUENCE_MASK;
+
+    ((ms << (SNOWFLAKE_NODE_ID_BITS + SNOWFLAKE_SEQUENCE_BITS)) | (node_id << SNOWFLAKE_SEQUENCE_BITS) | sequence)
+        as BigInt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn state(ms: u64, sequence: u64) -> u64 {
+        (ms << SNOWFLAKE_SEQUENCE_BITS) | sequence
+    }
+
+    #[test]
+    fn snowflake_state_resets_sequence_on_new_millisecond() {
+        assert_eq!(next_snowflake_state(state(10, 5), 11), state(11, 0));
+        assert_eq!(next_snowflake_state(state(10, 5), 10), state(10, 6));
+    }
+
+    #[test]
+    fn snowflake_state_keeps_last_timestamp_when_clock_goes_backwards() {
+        assert_eq!(next_snowflake_state(state(10, 5), 7), state(10, 6));
+    }
+
+    #[test]
+    fn snowflake_state_moves_to_next_millisecond_on_sequence_overflow() {
+        assert_eq!(
+            next_snowflake_state(state(10, SNOWFLAKE_SEQUENCE_MASK), 10),
+            state(11, 0)
+        );
+        assert_eq!(
+            next_snowflake_state(state(10, SNOWFLAKE_SEQUENCE_MASK), 3),
+            state(11, 0)
+        );
+    }
+
+    #[test]
+    fn snowflake_id_packs_timestamp_node_id_and_sequence() {
+        assert_eq!(snowflake_id(state(1, 2), 3), (1 << 22 | 3 << 12 | 2) as BigInt);
+    }
+
+    #[test]
+    fn snowflake_ids_are_unique_and_monotonic_across_threads() {
+        let threads = (0..4)
+            .map(|_| std::thread::spawn(|| (0..10_000).map(|_| snowflake()).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+
+        let mut ids = HashSet::new();
+
+        for thread in threads {
+            let thread_ids = thread.join().unwrap();
+
+            assert!(thread_ids.windows(2).all(|pair| pair[0] < pair[1]));
+
+            ids.extend(thread_ids);
+        }
+
+        assert_eq!(ids.len(), 40_000);
+    }
+}
@@ -1,24 +1,56 @@
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+use std::vec::IntoIter;
+
+use scylla::frame::response::result::Row;
 use scylla::transport::session::TypedRowIter;
 
-use crate::errors::CharybdisError;
+use crate::errors::{CharybdisError, ErrorContext};
 use crate::model::BaseModel;
+use crate::profiling::QueryProfile;
+
+enum Rows<T: BaseModel> {
+    Raw(IntoIter<Row>),
+    Typed(TypedRowIter<T>),
+}
 
 pub struct CharybdisModelIterator<T: BaseModel> {
-    inner: TypedRowIter<T>,
-    query_string: &'static str,
+    inner: Rows<T>,
+    context: ErrorContext,
+    profile: Option<QueryProfile>,
+    _phantom: PhantomData<fn() -> T>,
 }
 
 impl<T: BaseModel> CharybdisModelIterator<T> {
-    pub(crate) fn query_string(&mut self, query_string: &'static str) {
-        self.query_string = query_string;
+    pub(crate) fn context(&mut self, context: ErrorContext) {
+        self.context = context;
+    }
+
+    /// Starts profiling of consumed rows, `network` is time spent waiting for the page.
+    pub(crate) fn profile(&mut self, network: Duration) {
+        self.profile = QueryProfile::start(self.context.query, network);
+    }
+}
+
+impl<T: BaseModel> From<Vec<Row>> for CharybdisModelIterator<T> {
+    fn from(rows: Vec<Row>) -> Self {
+        Self {
+            inner: Rows::Raw(rows.into_iter()),
+            context: ErrorContext::model::<T>(""),
+            profile: None,
+            _phantom: PhantomData,
+        }
     }
 }
 
+/// Rows already deserialized by driver, kept for compatibility. Their deserialization isn't profiled.
 impl<T: BaseModel> From<TypedRowIter<T>> for CharybdisModelIterator<T> {
     fn from(iter: TypedRowIter<T>) -> Self {
         Self {
-            inner: iter,
-            query_string: "",
+            inner: Rows::Typed(iter),
+            context: ErrorContext::model::<T>(""),
+            profile: None,
+            _phantom: PhantomData,
         }
     }
 }
@@ -27,8 +59,36 @@ impl<T: BaseModel> Iterator for CharybdisModelIterator<T> {
     type Item = Result<T, CharybdisError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.inner
-            .next()
-            .map(|row| row.map_err(|e| CharybdisError::FromRowError(self.query_string, e)))
+        let row = match &mut self.inner {
+            Rows::Raw(rows) => rows.next()?,
+            Rows::Typed(iter) => {
+                let res = iter.next()?;
+
+                return Some(res.map_err(|e| CharybdisError::FromRowError(self.context.column_of::<T>(&e), e)));
+            }
+        };
+
+        let res = match &mut self.profile {
+            Some(profile) => {
+                let start = Instant::now();
+                let res = T::from_row(row);
+
+                profile.deserialization += start.elapsed();
+                profile.rows += 1;
+
+                res
+            }
+            None => T::from_row(row),
+        };
+
+        Some(res.map_err(|e| CharybdisError::FromRowError(self.context.column_of::<T>(&e), e)))
+    }
+}
+
+impl<T: BaseModel> Drop for CharybdisModelIterator<T> {
+    fn drop(&mut self) {
+        if let Some(profile) = &self.profile {
+            profile.report();
+        }
     }
 }
@@ -1,19 +1,48 @@
 #![allow(async_fn_in_trait)]
 
+pub mod association;
 pub mod batch;
+pub mod batcher;
 pub mod callbacks;
+pub mod changes;
+pub mod cluster;
+mod driver;
 pub mod errors;
+pub mod extensions;
+pub mod fixtures;
+pub mod fragment;
+pub mod hooks;
+pub mod id;
 pub mod iterator;
+pub mod lookup;
+pub mod metadata;
 pub mod model;
 pub mod operations;
+#[cfg(feature = "otel")]
+pub mod otel;
+pub mod profiles;
+pub mod profiling;
 pub mod query;
+pub mod registry;
+pub mod retry;
 pub mod serializers;
+pub mod session;
+pub mod sink;
+pub mod statements;
 pub mod stream;
+pub mod tenancy;
 pub mod types;
+pub mod unit_of_work;
+pub mod validation;
+
+pub use registry::prepare_registered_models;
 
 pub mod macros {
+    #[doc(hidden)]
+    pub use inventory;
+
     pub use charybdis_macros::{
-        char_model_field_attrs_gen, charybdis_model, charybdis_udt_model, charybdis_view_model,
+        char_model_field_attrs_gen, charybdis_counter_model, charybdis_model, charybdis_udt_model, charybdis_view_model,
     };
 
     pub mod scylla {
@@ -22,15 +51,23 @@ pub mod macros {
 }
 
 pub mod scylla {
+    pub use scylla::_macro_internal::{FromCqlValError, RowSerializationContext, RowWriter, SerializationError};
     pub use scylla::frame::response::cql_to_rust::{FromCqlVal, FromRow, FromRowError};
     pub use scylla::frame::response::result::{CqlValue, Row};
+    pub use scylla::serialize::row::SerializeRow;
     pub use scylla::serialize::value::SerializeValue;
     pub use scylla::statement::PagingState;
+
+    pub use crate::driver::{Batch, CachingSession, PreparedStatement, Query, QueryError, QueryResult, Session};
 }
 
 pub mod options {
     pub use scylla::execution_profile::ExecutionProfileHandle;
     pub use scylla::history::HistoryListener;
     pub use scylla::retry_policy::RetryPolicy;
+    pub use scylla::speculative_execution::{
+        PercentileSpeculativeExecutionPolicy, SimpleSpeculativeExecutionPolicy, SpeculativeExecutionPolicy,
+    };
     pub use scylla::statement::{Consistency, SerialConsistency};
+    pub use scylla::transport::Compression;
 }
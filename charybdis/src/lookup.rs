@@ -0,0 +1,64 @@
+use crate::errors::CharybdisError;
+use crate::model::Model;
+use crate::operations::{Delete, Find};
+use crate::query::{is_applied, CharybdisQuery, ModelMutation, QueryValue};
+use crate::session::CharybdisSession;
+
+/// Claims lookup row with `INSERT ... IF NOT EXISTS`.
+/// Returns `UniqueConstraintError` if row is already claimed by another record.
+/// Used by `insert_with_lookups` and `update_with_lookups` generated for `lookup_tables = [...]`.
+pub async fn claim<L: Model>(lookup: &L, session: &impl CharybdisSession) -> Result<(), CharybdisError> {
+    let res = CharybdisQuery::<L, L, ModelMutation>::new(L::INSERT_IF_NOT_EXIST_QUERY, QueryValue::Model(lookup))
+        .execute(session)
+        .await?;
+
+    if !is_applied(&res) {
+        return Err(CharybdisError::UniqueConstraintError(L::DB_MODEL_NAME));
+    }
+
+    Ok(())
+}
+
+/// Claims lookup row like [claim], but row that is already claimed by the same record is accepted, so retried
+/// `insert_with_lookups` and `update_with_lookups` succeed. `is_owner` compares primary key held by stored
+/// lookup row with primary key of the record.
+pub async fn claim_for<L: Model>(
+    lookup: &L,
+    is_owner: impl Fn(&L) -> bool,
+    session: &impl CharybdisSession,
+) -> Result<(), CharybdisError> {
+    match claim(lookup, session).await {
+        Err(CharybdisError::UniqueConstraintError(lookup_table)) => {
+            let stored = lookup.maybe_find_by_primary_key().execute(session).await?;
+
+            match stored {
+                Some(stored) if is_owner(&stored) => Ok(()),
+                _ => Err(CharybdisError::UniqueConstraintError(lookup_table)),
+            }
+        }
+        res => res,
+    }
+}
+
+/// Releases claimed lookup row, so it can be claimed by another record.
+pub async fn release<L: Model>(lookup: &L, session: &impl CharybdisSession) -> Result<(), CharybdisError> {
+    lookup.delete().execute(session).await?;
+
+    Ok(())
+}
+
+/// Releases lookup row claimed by operation that failed with `error`, and returns error to be returned to the
+/// caller. If release fails, release error is chained to `error` by `LookupReleaseError`, and row stays claimed
+/// until it's released manually.
+pub async fn release_on_error<L: Model>(
+    lookup: &L,
+    error: CharybdisError,
+    session: &impl CharybdisSession,
+) -> CharybdisError {
+    match release(lookup, session).await {
+        Ok(()) => error,
+        Err(release_error) => {
+            CharybdisError::LookupReleaseError(L::DB_MODEL_NAME, Box::new(error), Box::new(release_error))
+        }
+    }
+}
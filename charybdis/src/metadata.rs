@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use scylla::cql_to_rust::FromRowError;
+use scylla::frame::response::result::{CqlValue, Row};
+
+use crate::model::BaseModel;
+
+/// Non-key columns of model that support `TTL()` and `WRITETIME()` selectors. Collections and counters
+/// are excluded. Its automatically generated by `charybdis_model` macro.
+pub trait MetadataFields: BaseModel {
+    const METADATA_FIELDS: &'static [&'static str];
+}
+
+/// Model together with remaining TTL and write timestamp of its columns, returned by generated
+/// `select_with_metadata` function:
+/// ```rust ignore
+/// let user = User::select_with_metadata(id).execute(&session).await?;
+///
+/// // seconds until `session_token` expires, `None` if it doesn't expire
+/// let ttl: Option<i32> = user.ttl_of("session_token");
+/// // microseconds since epoch of the last write of `email`
+/// let writetime: Option<i64> = user.writetime_of("email");
+///
+/// let user: User = user.model;
+/// ```
+#[derive(Debug)]
+pub struct WithMetadata<M: MetadataFields> {
+    pub model: M,
+    ttls: HashMap<&'static str, Option<i32>>,
+    writetimes: HashMap<&'static str, Option<i64>>,
+}
+
+impl<M: MetadataFields> WithMetadata<M> {
+    /// Remaining TTL of the column in seconds. `None` if column has no TTL, is null, or doesn't support TTL.
+    pub fn ttl_of(&self, field: &str) -> Option<i32> {
+        self.ttls.get(field).copied().flatten()
+    }
+
+    /// Write timestamp of the column in microseconds since epoch. `None` if column is null, or doesn't
+    /// support WRITETIME.
+    pub fn writetime_of(&self, field: &str) -> Option<i64> {
+        self.writetimes.get(field).copied().flatten()
+    }
+
+    /// Splits row of `SELECT <fields>, TTL(f1), WRITETIME(f1), ...` into model and metadata columns.
+    pub(crate) fn from_row(mut row: Row) -> Result<Self, FromRowError> {
+        let model_columns = row.columns.len().saturating_sub(M::METADATA_FIELDS.len() * 2);
+        let metadata = row.columns.split_off(model_columns);
+        let model = M::from_row(row)?;
+
+        let mut ttls = HashMap::with_capacity(M::METADATA_FIELDS.len());
+        let mut writetimes = HashMap::with_capacity(M::METADATA_FIELDS.len());
+
+        for (field, values) in M::METADATA_FIELDS.iter().zip(metadata.chunks_exact(2)) {
+            ttls.insert(*field, values[0].as_ref().and_then(CqlValue::as_int));
+            writetimes.insert(*field, values[1].as_ref().and_then(CqlValue::as_bigint));
+        }
+
+        Ok(Self {
+            model,
+            ttls,
+            writetimes,
+        })
+    }
+}
@@ -1,18 +1,57 @@
+use std::marker::PhantomData;
+
 use scylla::serialize::row::SerializeRow;
 use scylla::FromRow;
 
-pub trait BaseModel: FromRow + Sized + SerializeRow {
+use crate::errors::CharybdisError;
+use crate::id;
+use crate::operations::Find;
+use crate::query::QueryValue;
+use crate::session::CharybdisSession;
+use crate::types::{BigInt, Int, Timeuuid};
+use crate::validation::ValidationErrors;
+
+pub trait BaseModel: FromRow + Sized + SerializeRow + Send + Sync {
     // usually tuple of primary key values
     type PrimaryKey: SerializeRow + Send + Sync;
     type PartitionKey: SerializeRow + Send + Sync;
 
     const DB_MODEL_NAME: &'static str;
+    // comma separated db columns, e.g. `id, username, email`
+    const SELECT_FIELDS_CLAUSE: &'static str;
+    // comma separated primary key columns, e.g. `category_id, id`
+    const PRIMARY_KEY_FIELDS_CLAUSE: &'static str;
+    // comma separated partition key columns, e.g. `category_id`
+    const PARTITION_KEY_FIELDS_CLAUSE: &'static str;
     const FIND_BY_PRIMARY_KEY_QUERY: &'static str;
     const FIND_BY_PARTITION_KEY_QUERY: &'static str;
+    // variants of `FIND_BY_PARTITION_KEY_QUERY` with `LIMIT ?` and ordered by first clustering key, used by
+    // `order_by_clustering` and `limit` of partition finders; ordered variants are `None` without clustering keys
+    const FIND_BY_PARTITION_KEY_LIMIT_QUERY: &'static str;
+    const FIND_BY_PARTITION_KEY_ASC_QUERY: Option<&'static str>;
+    const FIND_BY_PARTITION_KEY_DESC_QUERY: Option<&'static str>;
+    const FIND_BY_PARTITION_KEY_ASC_LIMIT_QUERY: Option<&'static str>;
+    const FIND_BY_PARTITION_KEY_DESC_LIMIT_QUERY: Option<&'static str>;
     const FIND_FIRST_BY_PARTITION_KEY_QUERY: &'static str;
+    const COUNT_BY_PARTITION_KEY_QUERY: &'static str;
+    // selects rows with partition key token in range, bound by inclusive start and end tokens
+    const FIND_BY_TOKEN_RANGE_QUERY: &'static str;
 
     fn primary_key_values(&self) -> Self::PrimaryKey;
     fn partition_key_values(&self) -> Self::PartitionKey;
+
+    /// Column of `soft_delete` field, if model declares it.
+    const SOFT_DELETE_COLUMN: Option<&'static str> = None;
+
+    /// True if model has `soft_delete` field and it's not the Unix epoch, which marks live rows. Generated
+    /// select statements filter soft deleted rows on the server, unless query is configured with
+    /// [CharybdisQuery::with_deleted](crate::query::CharybdisQuery::with_deleted).
+    fn is_soft_deleted(&self) -> bool {
+        false
+    }
+
+    /// Column of `tenant_key`, if model declares it, see [TenantScoped](crate::tenancy::TenantScoped).
+    const TENANT_COLUMN: Option<&'static str> = None;
 }
 
 ///
@@ -56,6 +95,111 @@ pub trait Model: BaseModel {
     const UPDATE_QUERY: &'static str;
     const DELETE_QUERY: &'static str;
     const DELETE_BY_PARTITION_KEY_QUERY: &'static str;
+    /// Removes the row even if model declares `soft_delete` field, in which case `DELETE_QUERY` only marks
+    /// existing row as deleted by conditional update.
+    const HARD_DELETE_QUERY: &'static str = Self::DELETE_QUERY;
+
+    /// Populates unset key fields that declare `#[charybdis(id_strategy = "...")]`.
+    /// It's called automatically by inserts that own or mutably borrow the model: `insert_cb`, `insert_with_ids`,
+    /// `insert_many`, insert sink, partition batcher and fixtures. `insert` binds the model as it is.
+    fn generate_ids(&mut self) {}
+
+    /// True if model has `#[charybdis(version)]` field. Update of versioned model is executed as LWT
+    /// conditioned on current version, and `StaleObjectError` is returned if condition is not met.
+    const VERSIONED: bool = false;
+
+    /// Values bound to `INSERT_QUERY` and `INSERT_IF_NOT_EXIST_QUERY`. Models with `soft_delete` field don't bind the
+    /// field, as inserted rows are live and statements set it to Unix epoch.
+    fn insert_values<Val: SerializeRow>(&self) -> QueryValue<'_, Val, Self> {
+        QueryValue::Model(self)
+    }
+
+    /// Values bound to `UPDATE_QUERY`. Versioned models bind next version generated for each call, that is
+    /// returned by [QueryValue::next_version].
+    fn update_values<Val: SerializeRow>(&self) -> QueryValue<'_, Val, Self> {
+        QueryValue::Model(self)
+    }
+
+    /// Sets version field to the next version written by successful update. Called automatically by `update_cb`,
+    /// `save` and [UnitOfWork](crate::unit_of_work::UnitOfWork).
+    fn increment_version(&mut self, _next_version: Version) {}
+
+    /// Runs checks declared with `#[charybdis(validate = "...")]` and collects errors of all fields.
+    /// It's called automatically by `insert_cb` and `update_cb` after `before_insert` and `before_update`
+    /// callbacks, so defaults set by callbacks are validated as well.
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Ok(())
+    }
+}
+
+/// Value of `#[charybdis(version)]` field written by update, see [Model::increment_version].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Version {
+    Int(Int),
+    BigInt(BigInt),
+    Timeuuid(Timeuuid),
+}
+
+/// Types that can be used as `#[charybdis(version)]` field.
+pub trait VersionValue: Sized {
+    /// Version written by update of the row at this version. Timeuuid versions are generated on the client, so
+    /// the same version is bound by update and assigned to the model.
+    fn next_version(&self) -> Self;
+
+    fn into_version(self) -> Version;
+
+    fn from_version(version: Version) -> Option<Self>;
+}
+
+impl VersionValue for Int {
+    fn next_version(&self) -> Self {
+        self + 1
+    }
+
+    fn into_version(self) -> Version {
+        Version::Int(self)
+    }
+
+    fn from_version(version: Version) -> Option<Self> {
+        match version {
+            Version::Int(version) => Some(version),
+            _ => None,
+        }
+    }
+}
+
+impl VersionValue for BigInt {
+    fn next_version(&self) -> Self {
+        self + 1
+    }
+
+    fn into_version(self) -> Version {
+        Version::BigInt(self)
+    }
+
+    fn from_version(version: Version) -> Option<Self> {
+        match version {
+            Version::BigInt(version) => Some(version),
+            _ => None,
+        }
+    }
+}
+
+impl VersionValue for Timeuuid {
+    fn next_version(&self) -> Self {
+        id::timeuuid()
+    }
+
+    fn into_version(self) -> Version {
+        Version::Timeuuid(self)
+    }
+
+    fn from_version(version: Version) -> Option<Self> {
+        match version {
+            Version::Timeuuid(version) => Some(version),
+            _ => None,
+        }
+    }
 }
 
 ///
@@ -93,6 +237,41 @@ pub trait Model: BaseModel {
 ///
 pub trait MaterializedView: BaseModel {}
 
+/// Counter table, declared with `charybdis_counter_model` macro. Besides the primary key, counter models
+/// hold only counter fields, which are changed by generated `increment_<field>` and `decrement_<field>`
+/// methods. Counter tables don't support inserts, and deleted counters can't be used again, so counter
+/// models don't implement [Model] and don't get `insert` and `delete` operations.
+/// ```rust ignore
+/// #[charybdis_counter_model(table_name = post_counters, partition_keys = [post_id], clustering_keys = [])]
+/// pub struct PostCounter {
+///     pub post_id: Uuid,
+///     pub likes: Counter,
+///     pub views: Counter,
+/// }
+///
+/// let counter = PostCounter { post_id, ..Default::default() };
+///
+/// CharybdisBatch::counter()
+///     .append(counter.increment_likes(1))
+///     .append(counter.increment_views(1))
+///     .execute(&session)
+///     .await?;
+///
+/// let counter = counter.read(&session).await?;
+/// ```
+pub trait CounterModel: BaseModel {
+    /// Model with primary key of `self` and default counter values, i.e. zero.
+    fn with_zero_counters(&self) -> Self;
+
+    /// Current counters of row with primary key of `self`. Row of counter table exists only after its
+    /// first update, so missing row is returned with counters set to zero.
+    async fn read(&self, session: &impl CharybdisSession) -> Result<Self, CharybdisError> {
+        let counters = self.maybe_find_by_primary_key().execute(session).await?;
+
+        Ok(counters.unwrap_or_else(|| self.with_zero_counters()))
+    }
+}
+
 /// Declare udt model as a struct within `src/models/udts` dir:
 /// ```rust
 /// use charybdis::macros::charybdis_udt_model;
@@ -159,3 +338,18 @@ pub trait TableOptions {
 pub trait AsNative<T: BaseModel> {
     fn as_native(&self) -> T;
 }
+
+/// Marks model generated by `partial_<model>!` macro as projection of base model `M`, so it
+/// can be used to select subset of `M` columns with [Find::find_columns](crate::operations::Find::find_columns).
+/// Its automatically generated by `partial_<model>!` macro.
+pub trait PartialModel<M: BaseModel>: BaseModel + AsNative<M> {}
+
+/// Refuses at compile time operations that would bypass `soft_delete` field, e.g. range deletes.
+pub(crate) struct NotSoftDeleted<M>(PhantomData<M>);
+
+impl<M: BaseModel> NotSoftDeleted<M> {
+    pub(crate) const ASSERT: () = assert!(
+        M::SOFT_DELETE_COLUMN.is_none(),
+        "Model with soft_delete field can't be deleted by partition key, use hard_delete_by_partition_key"
+    );
+}
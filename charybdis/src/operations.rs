@@ -3,6 +3,7 @@ pub use execute::*;
 pub use find::*;
 pub use insert::*;
 pub use new::*;
+pub use scan::*;
 pub use update::*;
 
 mod delete;
@@ -10,5 +11,6 @@ mod execute;
 mod find;
 mod insert;
 mod new;
+mod scan;
 mod update;
 
@@ -1,7 +1,7 @@
 use scylla::serialize::row::SerializeRow;
 
 use crate::callbacks::{Callbacks, DeleteAction};
-use crate::model::Model;
+use crate::model::{Model, NotSoftDeleted};
 use crate::query::{CharybdisCbQuery, CharybdisQuery, ModelMutation, QueryValue};
 
 pub trait Delete: Model {
@@ -12,11 +12,27 @@ pub trait Delete: Model {
         CharybdisQuery::new(query, QueryValue::Owned(values))
     }
 
+    /// For models with `soft_delete` field it marks existing row as deleted by `UPDATE ... IF EXISTS`, so it's
+    /// executed as LWT and doesn't create rows that don't exist.
     fn delete(&self) -> CharybdisQuery<Self::PrimaryKey, Self, ModelMutation> {
         CharybdisQuery::new(Self::DELETE_QUERY, QueryValue::Owned(self.primary_key_values()))
     }
 
+    /// Same as `delete` for models without `soft_delete` field.
+    fn hard_delete(&self) -> CharybdisQuery<Self::PrimaryKey, Self, ModelMutation> {
+        CharybdisQuery::new(Self::HARD_DELETE_QUERY, QueryValue::Owned(self.primary_key_values()))
+    }
+
+    /// Refused at compile time for models with `soft_delete` field, as range delete can't mark rows as deleted.
     fn delete_by_partition_key(&self) -> CharybdisQuery<Self::PartitionKey, Self, ModelMutation> {
+        #[allow(clippy::let_unit_value)]
+        let () = NotSoftDeleted::<Self>::ASSERT;
+
+        self.hard_delete_by_partition_key()
+    }
+
+    /// Removes all rows of partition, even if model declares `soft_delete` field.
+    fn hard_delete_by_partition_key(&self) -> CharybdisQuery<Self::PartitionKey, Self, ModelMutation> {
         CharybdisQuery::new(
             Self::DELETE_BY_PARTITION_KEY_QUERY,
             QueryValue::Owned(self.partition_key_values()),
@@ -33,6 +49,14 @@ pub trait DeleteWithCallbacks<'a>: Callbacks {
     ) -> CharybdisCbQuery<Self, DeleteAction<Self>, Self::PrimaryKey> {
         CharybdisCbQuery::new(Self::DELETE_QUERY, self, extension)
     }
+
+    /// Same as `delete_cb`, with extension resolved from [Extensions](crate::extensions::Extensions).
+    fn delete_cb_registered(&'a mut self) -> CharybdisCbQuery<Self, DeleteAction<Self>, Self::PrimaryKey>
+    where
+        Self::Extension: Send + Sync + 'static,
+    {
+        CharybdisCbQuery::registered(Self::DELETE_QUERY, self)
+    }
 }
 
 impl<'a, M: Callbacks> DeleteWithCallbacks<'a> for M {}
@@ -1,19 +1,18 @@
-use crate::errors::CharybdisError;
+use crate::driver::{self, CachingSession, QueryResult};
+use crate::errors::{CharybdisError, ErrorContext};
 use crate::scylla::PagingState;
 use scylla::serialize::row::SerializeRow;
 use scylla::statement::PagingStateResponse;
 use scylla::transport::iterator::RowIterator;
-use scylla::{CachingSession, QueryResult};
 
 pub async fn execute_unpaged(
     session: &CachingSession,
     query: &'static str,
     values: impl SerializeRow,
 ) -> Result<QueryResult, CharybdisError> {
-    let res = session
-        .execute_unpaged(query, values)
+    let res = driver::execute_unpaged(session, query, values)
         .await
-        .map_err(|e| CharybdisError::QueryError(query, e))?;
+        .map_err(|e| CharybdisError::QueryError(ErrorContext::new(query), e))?;
 
     Ok(res)
 }
@@ -23,10 +22,9 @@ pub async fn execute_iter(
     query: &'static str,
     values: impl SerializeRow,
 ) -> Result<RowIterator, CharybdisError> {
-    let res = session
-        .execute_iter(query, values)
+    let res = driver::execute_iter(session, query, values)
         .await
-        .map_err(|e| CharybdisError::QueryError(query, e))?;
+        .map_err(|e| CharybdisError::QueryError(ErrorContext::new(query), e))?;
 
     Ok(res)
 }
@@ -37,10 +35,9 @@ pub async fn execute_single_page(
     values: impl SerializeRow,
     paging_state: PagingState,
 ) -> Result<(QueryResult, PagingStateResponse), CharybdisError> {
-    let res = session
-        .execute_single_page(query, values, paging_state)
+    let res = driver::execute_single_page(session, query, values, paging_state)
         .await
-        .map_err(|e| CharybdisError::QueryError(query, e))?;
+        .map_err(|e| CharybdisError::QueryError(ErrorContext::new(query), e))?;
 
     Ok(res)
 }
@@ -1,5 +1,15 @@
-use crate::model::BaseModel;
-use crate::query::{CharybdisQuery, ModelPaged, ModelRow, ModelStream, OptionalModelRow, QueryValue};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use crate::fragment::first_row_statement;
+use crate::model::{BaseModel, MaterializedView, PartialModel};
+use crate::query::{
+    intern, CharybdisQuery, ModelExists, ModelPaged, ModelRow, ModelRowOrDefault, ModelStream, NamedValues,
+    OptionalModelRow, QueryValue, ScalarRow,
+};
+use charybdis_parser::statement::soft_delete_filtered;
+use scylla::cql_to_rust::FromCqlVal;
+use scylla::frame::response::result::CqlValue;
 use scylla::serialize::row::SerializeRow;
 use scylla::statement::PagingState;
 
@@ -34,6 +44,161 @@ pub trait Find: BaseModel {
         CharybdisQuery::new(query, QueryValue::Owned(values))
     }
 
+    /// Returns default model instead of `NotFoundError` if query returns no row.
+    fn find_first_or_default<'a, Val: SerializeRow>(
+        query: &'static str,
+        values: Val,
+    ) -> CharybdisQuery<'a, Val, Self, ModelRowOrDefault<Self>>
+    where
+        Self: Default,
+    {
+        CharybdisQuery::new(query, QueryValue::Owned(values))
+    }
+
+    /// Checks if any row matches the query, without reading it as model. Only primary key columns of the first row
+    /// are selected. Query is `WHERE` clause without `WHERE` keyword:
+    /// ```rust ignore
+    /// // SELECT id FROM users WHERE id = ? LIMIT 1
+    /// let exists = User::exists("id = ?", (id,)).execute(session).await?;
+    /// ```
+    /// Rows of models with `soft_delete` field that are marked as deleted are filtered out by the cluster.
+    fn exists<'a, Val: SerializeRow>(query: &'static str, values: Val) -> CharybdisQuery<'a, Val, Self, ModelExists> {
+        let statement = select_query(
+            Self::PRIMARY_KEY_FIELDS_CLAUSE,
+            Self::DB_MODEL_NAME,
+            query,
+            Self::SOFT_DELETE_COLUMN,
+            true,
+        );
+
+        CharybdisQuery::new(statement, QueryValue::Owned(values))
+    }
+
+    /// Counts rows matching the query. Query is `WHERE` clause without `WHERE` keyword:
+    /// ```rust ignore
+    /// // SELECT COUNT(*) FROM posts WHERE category_id = ? AND author_id = ? ALLOW FILTERING
+    /// let count = Post::count("category_id = ? AND author_id = ? ALLOW FILTERING", (category_id, author_id))
+    ///     .execute(session)
+    ///     .await?;
+    /// ```
+    /// Rows of models with `soft_delete` field that are marked as deleted are not counted.
+    fn count<'a, Val: SerializeRow>(query: &'static str, values: Val) -> CharybdisQuery<'a, Val, Self, ScalarRow<i64>> {
+        CharybdisQuery::new(
+            select_query("COUNT(*)", Self::DB_MODEL_NAME, query, Self::SOFT_DELETE_COLUMN, false),
+            QueryValue::Owned(values),
+        )
+    }
+
+    /// Selects only columns of partial model `P` generated by `partial_<model>!` macro, so narrow reads don't
+    /// need to fetch and deserialize whole row. Query is `WHERE` clause without `WHERE` keyword:
+    /// ```rust ignore
+    /// partial_user!(UserName, id, username);
+    ///
+    /// let users = User::find_columns::<UserName, _>("id IN ?", (ids,)).execute(session).await?;
+    /// ```
+    fn find_columns<'a, P: PartialModel<Self>, Val: SerializeRow>(
+        query: &'static str,
+        values: Val,
+    ) -> CharybdisQuery<'a, Val, P, ModelStream<P>> {
+        CharybdisQuery::new(
+            select_query(
+                P::SELECT_FIELDS_CLAUSE,
+                Self::DB_MODEL_NAME,
+                query,
+                Self::SOFT_DELETE_COLUMN,
+                false,
+            ),
+            QueryValue::Owned(values),
+        )
+    }
+
+    /// Selects columns of this model from materialized view `V`, so rows of the view are read as this model.
+    /// View has to include all columns of the model. Query is `WHERE` clause without `WHERE` keyword:
+    /// ```rust ignore
+    /// let users = User::find_via::<UsersByUsername, _>("username = ?", (username,)).execute(session).await?;
+    /// ```
+    /// Finders are generated on base model for views declared with `base_model`, e.g. `find_via_users_by_username`.
+    fn find_via<'a, V: MaterializedView, Val: SerializeRow>(
+        query: &'static str,
+        values: Val,
+    ) -> CharybdisQuery<'a, Val, V, ModelStream<Self>> {
+        CharybdisQuery::new(
+            select_query(
+                Self::SELECT_FIELDS_CLAUSE,
+                V::DB_MODEL_NAME,
+                query,
+                Self::SOFT_DELETE_COLUMN,
+                false,
+            ),
+            QueryValue::Owned(values),
+        )
+    }
+
+    fn find_first_via<'a, V: MaterializedView, Val: SerializeRow>(
+        query: &'static str,
+        values: Val,
+    ) -> CharybdisQuery<'a, Val, V, ModelRow<Self>> {
+        CharybdisQuery::new(
+            select_query(
+                Self::SELECT_FIELDS_CLAUSE,
+                V::DB_MODEL_NAME,
+                query,
+                Self::SOFT_DELETE_COLUMN,
+                false,
+            ),
+            QueryValue::Owned(values),
+        )
+    }
+
+    fn maybe_find_first_via<'a, V: MaterializedView, Val: SerializeRow>(
+        query: &'static str,
+        values: Val,
+    ) -> CharybdisQuery<'a, Val, V, OptionalModelRow<Self>> {
+        CharybdisQuery::new(
+            select_query(
+                Self::SELECT_FIELDS_CLAUSE,
+                V::DB_MODEL_NAME,
+                query,
+                Self::SOFT_DELETE_COLUMN,
+                false,
+            ),
+            QueryValue::Owned(values),
+        )
+    }
+
+    /// Binds values by name to named bind markers, so their order in query doesn't matter:
+    /// ```rust ignore
+    /// Post::find_named(
+    ///     "SELECT * FROM posts WHERE category_id = :category_id AND author_id = :author_id",
+    ///     named_values! { "author_id" => author_id, "category_id" => category_id },
+    /// )
+    /// ```
+    fn find_named<'a>(query: &'static str, values: NamedValues<'a>) -> CharybdisQuery<'a, (), Self, ModelStream<Self>> {
+        CharybdisQuery::new(query, QueryValue::Named(values))
+    }
+
+    fn find_first_named<'a>(
+        query: &'static str,
+        values: NamedValues<'a>,
+    ) -> CharybdisQuery<'a, (), Self, ModelRow<Self>> {
+        CharybdisQuery::new(query, QueryValue::Named(values))
+    }
+
+    /// Executes aggregate query and returns value of the first column, e.g.:
+    /// `Post::aggregate::<i64>("SELECT MAX(likes) FROM posts WHERE category_id = ?", (category_id,))`
+    fn aggregate<'a, T: FromCqlVal<Option<CqlValue>>>(
+        query: &'static str,
+        values: impl SerializeRow + Send + Sync + 'a,
+    ) -> CharybdisQuery<'a, (), Self, ScalarRow<T>> {
+        CharybdisQuery::new(query, QueryValue::Row(Box::new(values)))
+    }
+
+    fn count_by_partition_key<'a>(
+        value: Self::PartitionKey,
+    ) -> CharybdisQuery<'a, Self::PartitionKey, Self, ScalarRow<i64>> {
+        CharybdisQuery::new(Self::COUNT_BY_PARTITION_KEY_QUERY, QueryValue::Owned(value))
+    }
+
     fn find_by_primary_key_value<'a>(
         value: Self::PrimaryKey,
     ) -> CharybdisQuery<'a, Self::PrimaryKey, Self, ModelRow<Self>> {
@@ -87,3 +252,49 @@ pub trait Find: BaseModel {
 }
 
 impl<M: BaseModel> Find for M {}
+
+/// Statement of `find_<model>!` macros of models with `soft_delete` field, `SELECT <fields> FROM <table> WHERE <query>`
+/// that filters out rows marked as deleted. Condition is inserted before trailing clauses of the query, so it's built
+/// at runtime instead of being concatenated with the literal.
+pub fn soft_delete_find_query<M: BaseModel>(query: &'static str) -> &'static str {
+    select_query(
+        M::SELECT_FIELDS_CLAUSE,
+        M::DB_MODEL_NAME,
+        query,
+        M::SOFT_DELETE_COLUMN,
+        false,
+    )
+}
+
+/// Builds `SELECT <fields> FROM <table> WHERE <query>` once per selected fields, table and query, as queries are
+/// expected to be `&'static str`. Rows marked as deleted are filtered out by `soft_delete_column`, if it's given,
+/// and `first_row` limits statement to single row. Built statements are leaked by [intern].
+fn select_query(
+    select_fields_clause: &'static str,
+    table: &'static str,
+    query: &'static str,
+    soft_delete_column: Option<&'static str>,
+    first_row: bool,
+) -> &'static str {
+    type QueryKey = (&'static str, &'static str, &'static str, Option<&'static str>, bool);
+
+    static QUERIES: OnceLock<Mutex<HashMap<QueryKey, &'static str>>> = OnceLock::new();
+
+    let mut queries = QUERIES.get_or_init(Default::default).lock().unwrap();
+
+    *queries
+        .entry((table, select_fields_clause, query, soft_delete_column, first_row))
+        .or_insert_with(|| {
+            let query = format!("SELECT {} FROM {} WHERE {}", select_fields_clause, table, query);
+            let query = match soft_delete_column {
+                Some(soft_delete_column) => soft_delete_filtered(&query, soft_delete_column),
+                None => query,
+            };
+
+            if first_row {
+                intern(first_row_statement(&query))
+            } else {
+                intern(query)
+            }
+        })
+}
@@ -1,14 +1,53 @@
+use crate::batch::CharybdisModelBatch;
 use crate::callbacks::{Callbacks, InsertAction};
+use crate::errors::CharybdisError;
 use crate::model::Model;
-use crate::query::{CharybdisCbQuery, CharybdisQuery, ModelMutation, QueryValue};
+use crate::query::{CharybdisCbQuery, CharybdisQuery, ModelMutation};
+use crate::session::CharybdisSession;
+use crate::sink::{InsertSink, InsertSinkOptions};
 
 pub trait Insert: Model {
+    /// Inserts the model as it is. Model is borrowed immutably, so ids of `id_strategy` fields are not generated,
+    /// use [Insert::insert_with_ids] to generate them.
     fn insert(&self) -> CharybdisQuery<Self, Self, ModelMutation> {
-        CharybdisQuery::new(Self::INSERT_QUERY, QueryValue::Model(self))
+        CharybdisQuery::new(Self::INSERT_QUERY, self.insert_values())
+    }
+
+    /// Generates ids for unset `id_strategy` fields and inserts the model.
+    fn insert_with_ids(&mut self) -> CharybdisQuery<Self, Self, ModelMutation> {
+        self.generate_ids();
+
+        CharybdisQuery::new(Self::INSERT_QUERY, self.insert_values())
     }
 
     fn insert_if_not_exists(&self) -> CharybdisQuery<Self, Self, ModelMutation> {
-        CharybdisQuery::new(Self::INSERT_IF_NOT_EXIST_QUERY, QueryValue::Model(self))
+        CharybdisQuery::new(Self::INSERT_IF_NOT_EXIST_QUERY, self.insert_values())
+    }
+
+    /// Sink that inserts models written to it, see [InsertSink].
+    fn insert_sink<'a, S: CharybdisSession>(session: &'a S, options: InsertSinkOptions) -> InsertSink<'a, Self, S>
+    where
+        Self: 'a,
+    {
+        InsertSink::new(session, options)
+    }
+
+    /// Generates ids for unset `id_strategy` fields and inserts models in unlogged batches of `chunk_size` inserts,
+    /// e.g. to seed data. Batches of models with `tenant_key` are rejected, rows of all tenants can be inserted by
+    /// explicitly unscoped batch:
+    /// ```rust ignore
+    /// CharybdisModelBatch::unlogged().unscoped().chunked_insert(&session, &posts, 100).await?;
+    /// ```
+    async fn insert_many(
+        models: &mut [Self],
+        session: &impl CharybdisSession,
+        chunk_size: usize,
+    ) -> Result<(), CharybdisError> {
+        models.iter_mut().for_each(Model::generate_ids);
+
+        CharybdisModelBatch::<Self, Self>::unlogged()
+            .chunked_insert(session, models, chunk_size)
+            .await
     }
 }
 
@@ -18,6 +57,14 @@ pub trait InsertWithCallbacks<'a>: Callbacks {
     fn insert_cb(&'a mut self, extension: &'a Self::Extension) -> CharybdisCbQuery<'a, Self, InsertAction<Self>, Self> {
         CharybdisCbQuery::new(Self::INSERT_QUERY, self, extension)
     }
+
+    /// Same as `insert_cb`, with extension resolved from [Extensions](crate::extensions::Extensions).
+    fn insert_cb_registered(&'a mut self) -> CharybdisCbQuery<'a, Self, InsertAction<Self>, Self>
+    where
+        Self::Extension: Send + Sync + 'static,
+    {
+        CharybdisCbQuery::registered(Self::INSERT_QUERY, self)
+    }
 }
 
 impl<'a, M: Callbacks> InsertWithCallbacks<'a> for M {}
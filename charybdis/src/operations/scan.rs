@@ -0,0 +1,152 @@
+use std::time::{Duration, Instant};
+
+use futures::{stream, StreamExt, TryStreamExt};
+
+use crate::errors::CharybdisError;
+use crate::model::BaseModel;
+use crate::operations::Find;
+use crate::session::CharybdisSession;
+use crate::stream::CharybdisScanStream;
+
+/// Token ring is split into more ranges than are scanned concurrently, so ranges of uneven size don't leave
+/// workers idle while the largest one is scanned.
+const RANGES_PER_WORKER: usize = 16;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ScanConfig {
+    /// Number of token ranges scanned concurrently. Values lower than 1 are treated as 1.
+    pub parallelism: usize,
+    /// Max average number of rows yielded per second across all ranges, `None` for no limit.
+    pub rate_limit_rows_per_sec: Option<u32>,
+    /// Scans rows of all tenants of model with `tenant_key`, see [TenantScoped](crate::tenancy::TenantScoped).
+    /// Scan of such model fails unless it's explicitly unscoped.
+    pub unscoped: bool,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self {
+            parallelism: 4,
+            rate_limit_rows_per_sec: None,
+            unscoped: false,
+        }
+    }
+}
+
+/// Scan of entire table, e.g. for background jobs like backfills or exports.
+pub trait Scan: BaseModel {
+    /// Splits token ring into contiguous ranges and scans them with `FIND_BY_TOKEN_RANGE_QUERY`, at most
+    /// `config.parallelism` ranges at a time. Rows of all ranges are merged into single stream, so each row
+    /// is yielded once, but not in token order. Ranges are queried as stream is consumed, so slow consumer
+    /// doesn't make scan buffer the table. Failed range yields error and scan continues with other ranges.
+    /// Scan of model with `tenant_key` yields single [CharybdisError::TenantError], unless `config.unscoped` is set.
+    /// ```rust ignore
+    /// let config = ScanConfig {
+    ///     parallelism: 8,
+    ///     rate_limit_rows_per_sec: Some(5000),
+    ///     ..Default::default()
+    /// };
+    /// let mut users = User::full_scan(&session, config);
+    ///
+    /// while let Some(user) = users.next().await {
+    ///     reindex(user?).await;
+    /// }
+    /// ```
+    fn full_scan<'a, S: CharybdisSession>(session: &'a S, config: ScanConfig) -> CharybdisScanStream<'a, Self>
+    where
+        Self: 'a,
+    {
+        if let Some(tenant_column) = Self::TENANT_COLUMN.filter(|_| !config.unscoped) {
+            let error = CharybdisError::TenantError(format!(
+                "{} is scoped by {}, but scan is not unscoped",
+                Self::DB_MODEL_NAME,
+                tenant_column
+            ));
+
+            return CharybdisScanStream::from(stream::once(async move { Err(error) }).boxed());
+        }
+
+        let parallelism = config.parallelism.max(1);
+
+        let rows = stream::iter(token_ranges(parallelism * RANGES_PER_WORKER))
+            .map(move |range| {
+                stream::once(async move {
+                    // token ranges can't be scoped, scan of tenant scoped model is unscoped by config
+                    Self::find(Self::FIND_BY_TOKEN_RANGE_QUERY, range)
+                        .unscoped()
+                        .execute(session)
+                        .await
+                })
+                .try_flatten()
+                .boxed()
+            })
+            .flatten_unordered(parallelism);
+
+        let rows = match config.rate_limit_rows_per_sec {
+            Some(rows_per_sec) => {
+                let mut rate_limiter = RateLimiter::new(rows_per_sec);
+
+                rows.then(move |row| {
+                    let delay = rate_limiter.delay();
+
+                    async move {
+                        if let Some(delay) = delay {
+                            tokio::time::sleep(delay).await;
+                        }
+
+                        row
+                    }
+                })
+                .boxed()
+            }
+            None => rows.boxed(),
+        };
+
+        CharybdisScanStream::from(rows)
+    }
+}
+
+impl<M: BaseModel> Scan for M {}
+
+/// Splits Murmur3 token ring into `count` contiguous ranges with inclusive bounds, covering all tokens.
+fn token_ranges(count: usize) -> Vec<(i64, i64)> {
+    let width = (u64::MAX as i128 + 1) / count as i128;
+
+    (0..count)
+        .map(|i| {
+            let start = i64::MIN as i128 + width * i as i128;
+            let end = if i + 1 == count {
+                i64::MAX as i128
+            } else {
+                start + width - 1
+            };
+
+            (start as i64, end as i64)
+        })
+        .collect()
+}
+
+/// Spaces rows evenly, idle time doesn't accumulate into bursts.
+struct RateLimiter {
+    interval: Duration,
+    next: Option<Instant>,
+}
+
+impl RateLimiter {
+    fn new(rows_per_sec: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(1) / rows_per_sec.max(1),
+            next: None,
+        }
+    }
+
+    /// Time to wait before yielding next row.
+    fn delay(&mut self) -> Option<Duration> {
+        let now = Instant::now();
+        let next = self.next.map_or(now, |next| next.max(now));
+
+        self.next = Some(next + self.interval);
+
+        Some(next - now).filter(|delay| !delay.is_zero())
+    }
+}
@@ -1,10 +1,14 @@
 use crate::callbacks::{Callbacks, UpdateAction};
+use crate::changes::{DiffModel, TrackedModel};
+use crate::errors::CharybdisError;
 use crate::model::Model;
+use crate::operations::{Find, Insert};
 use crate::query::{CharybdisCbQuery, CharybdisQuery, ModelMutation, QueryValue};
+use crate::session::CharybdisSession;
 
 pub trait Update: Model {
     fn update(&self) -> CharybdisQuery<Self, Self, ModelMutation> {
-        CharybdisQuery::new(Self::UPDATE_QUERY, QueryValue::Model(self))
+        CharybdisQuery::new(Self::UPDATE_QUERY, self.update_values()).check_applied(Self::VERSIONED)
     }
 }
 
@@ -12,8 +16,111 @@ impl<M: Model> Update for M {}
 
 pub trait UpdateWithCallbacks<'a>: Callbacks {
     fn update_cb(&'a mut self, extension: &'a Self::Extension) -> CharybdisCbQuery<'a, Self, UpdateAction<Self>, Self> {
-        CharybdisCbQuery::new(Self::UPDATE_QUERY, self, extension)
+        CharybdisCbQuery::new(Self::UPDATE_QUERY, self, extension).check_applied(Self::VERSIONED)
+    }
+
+    /// Same as `update_cb`, with extension resolved from [Extensions](crate::extensions::Extensions).
+    fn update_cb_registered(&'a mut self) -> CharybdisCbQuery<'a, Self, UpdateAction<Self>, Self>
+    where
+        Self::Extension: Send + Sync + 'static,
+    {
+        CharybdisCbQuery::registered(Self::UPDATE_QUERY, self).check_applied(Self::VERSIONED)
     }
 }
 
 impl<'a, M: Callbacks> UpdateWithCallbacks<'a> for M {}
+
+pub trait Save: TrackedModel {
+    /// Update of changed columns, `None` if no field was changed. Changes are not cleared by executing
+    /// the query, so use [Save::save] unless query options have to be set.
+    fn update_changes(&self) -> Option<CharybdisQuery<Self, Self, ModelMutation>> {
+        let (statement, values) = self.changes_update_statement()?;
+
+        let query = CharybdisQuery::new(Self::UPDATE_QUERY, values)
+            .statement(statement)
+            .check_applied(Self::VERSIONED);
+
+        Some(query)
+    }
+
+    /// Updates changed columns and clears changes. Model without changes is not updated.
+    ///
+    /// Update is executed directly, so update callbacks and validation are not run. Use `update_cb` to run them,
+    /// which updates all columns.
+    async fn save(&mut self, session: &impl CharybdisSession) -> Result<(), CharybdisError> {
+        if let Some(query) = self.update_changes() {
+            let next_version = query.next_version();
+
+            query.execute(session).await?;
+
+            if let Some(next_version) = next_version {
+                self.increment_version(next_version);
+            }
+        }
+
+        self.changes_mut().clear();
+
+        Ok(())
+    }
+}
+
+impl<M: TrackedModel> Save for M {}
+
+pub trait SaveIfChanged: DiffModel {
+    /// Reads stored row by primary key and updates only columns that differ from it, so sync jobs don't churn
+    /// writetime and TTL of rows that are already up to date. Missing row is inserted. Returns `true` if row
+    /// was written.
+    ///
+    /// Write is executed directly, so update callbacks and validation are not run.
+    async fn save_if_changed(&self, session: &impl CharybdisSession) -> Result<bool, CharybdisError> {
+        save_diff(self, session, false).await
+    }
+
+    /// Same as [SaveIfChanged::save_if_changed], but write is executed as LWT conditioned on values that were
+    /// read, so `StaleObjectError` is returned if row was modified or inserted concurrently.
+    async fn save_if_changed_lwt(&self, session: &impl CharybdisSession) -> Result<bool, CharybdisError> {
+        save_diff(self, session, true).await
+    }
+}
+
+impl<M: DiffModel> SaveIfChanged for M {}
+
+async fn save_diff<M: DiffModel>(
+    model: &M,
+    session: &impl CharybdisSession,
+    lwt: bool,
+) -> Result<bool, CharybdisError> {
+    let current = match model.maybe_find_by_primary_key().execute(session).await? {
+        Some(current) => current,
+        None if lwt => {
+            model
+                .insert_if_not_exists()
+                .check_applied(true)
+                .execute(session)
+                .await?;
+
+            return Ok(true);
+        }
+        None => {
+            model.insert().execute(session).await?;
+
+            return Ok(true);
+        }
+    };
+
+    let changes = model.diff(&current);
+
+    if changes.is_empty() {
+        return Ok(false);
+    }
+
+    let (statement, values) = model.diff_update_statement(&changes, &current, lwt);
+
+    CharybdisQuery::<M, M, ModelMutation>::new(M::UPDATE_QUERY, QueryValue::Named(values))
+        .statement(statement)
+        .check_applied(lwt || M::VERSIONED)
+        .execute(session)
+        .await?;
+
+    Ok(true)
+}
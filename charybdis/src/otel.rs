@@ -0,0 +1,219 @@
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use scylla::history::{AttemptId, HistoryListener, QueryId, SpeculativeId};
+use scylla::retry_policy::RetryDecision;
+use tracing::field::{self, Empty};
+use tracing::{Instrument, Span};
+
+use crate::driver::QueryError;
+use crate::errors::CharybdisError;
+use crate::hooks::QueryInfo;
+
+const DB_SYSTEM: &str = "scylladb";
+
+/// Span of single execution, with attributes named by OpenTelemetry database conventions, so it's exported as
+/// client span by `tracing-opentelemetry` layer.
+fn query_span(query: &QueryInfo) -> Span {
+    let operation = query.operation.name();
+    let span_name = match query.table {
+        Some(table) => format!("{} {}", operation, table),
+        None => operation.to_string(),
+    };
+
+    tracing::info_span!(
+        "charybdis.query",
+        otel.name = %span_name,
+        otel.kind = "client",
+        otel.status_code = Empty,
+        otel.status_message = Empty,
+        db.system = DB_SYSTEM,
+        db.statement = %sanitize(&query.query),
+        db.operation = operation,
+        db.cassandra.table = query.table,
+        db.cassandra.consistency_level = query.consistency.map(field::debug),
+    )
+}
+
+/// Runs execution within span of the query and records its outcome.
+pub(crate) fn in_span<T>(
+    query: &QueryInfo,
+    execution: impl Future<Output = Result<T, CharybdisError>>,
+) -> impl Future<Output = Result<T, CharybdisError>> {
+    let span = query_span(query);
+
+    async move {
+        let res = execution.instrument(span.clone()).await;
+
+        match &res {
+            Ok(_) => span.record("otel.status_code", "OK"),
+            Err(e) => span
+                .record("otel.status_code", "ERROR")
+                .record("otel.status_message", field::display(e)),
+        };
+
+        res
+    }
+}
+
+/// Replaces literals with `?`, so values inlined into statement, e.g. by `and_where` fragments, are not exported.
+/// Identifiers and bind markers are kept.
+pub fn sanitize(statement: &str) -> String {
+    let mut sanitized = String::with_capacity(statement.len());
+    let mut chars = statement.chars().peekable();
+    let mut previous: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            // quotes within literal are escaped by doubling them
+            while let Some(c) = chars.next() {
+                if c == '\'' && chars.next_if_eq(&'\'').is_none() {
+                    break;
+                }
+            }
+
+            sanitized.push('?');
+            previous = Some('?');
+            continue;
+        }
+
+        let is_identifier_part = previous.is_some_and(|previous| previous.is_alphanumeric() || previous == '_');
+
+        if c.is_ascii_digit() && !is_identifier_part {
+            // numbers, including floats, hex blobs and uuids
+            while chars
+                .next_if(|c| c.is_ascii_alphanumeric() || *c == '.' || *c == '-')
+                .is_some()
+            {}
+
+            sanitized.push('?');
+            previous = Some('?');
+            continue;
+        }
+
+        sanitized.push(c);
+        previous = Some(c);
+    }
+
+    sanitized
+}
+
+/// History listener set on executed statements, so attempts made by the driver, i.e. retries and speculative
+/// executions, are recorded as events of current span. Listener set by user is still called.
+#[derive(Debug)]
+pub(crate) struct SpanHistoryListener {
+    inner: Option<Arc<dyn HistoryListener>>,
+    next_id: AtomicUsize,
+}
+
+impl SpanHistoryListener {
+    pub(crate) fn new(inner: Option<Arc<dyn HistoryListener>>) -> Arc<dyn HistoryListener> {
+        Arc::new(SpanHistoryListener {
+            inner,
+            next_id: AtomicUsize::new(0),
+        })
+    }
+
+    fn next_id(&self) -> usize {
+        self.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+impl HistoryListener for SpanHistoryListener {
+    fn log_query_start(&self) -> QueryId {
+        match &self.inner {
+            Some(inner) => inner.log_query_start(),
+            None => QueryId(self.next_id()),
+        }
+    }
+
+    fn log_query_success(&self, query_id: QueryId) {
+        if let Some(inner) = &self.inner {
+            inner.log_query_success(query_id);
+        }
+    }
+
+    fn log_query_error(&self, query_id: QueryId, error: &QueryError) {
+        if let Some(inner) = &self.inner {
+            inner.log_query_error(query_id, error);
+        }
+    }
+
+    fn log_new_speculative_fiber(&self, query_id: QueryId) -> SpeculativeId {
+        tracing::debug!("speculative execution started");
+
+        match &self.inner {
+            Some(inner) => inner.log_new_speculative_fiber(query_id),
+            None => SpeculativeId(self.next_id()),
+        }
+    }
+
+    fn log_attempt_start(
+        &self,
+        query_id: QueryId,
+        speculative_id: Option<SpeculativeId>,
+        node_addr: SocketAddr,
+    ) -> AttemptId {
+        let attempt_id = match &self.inner {
+            Some(inner) => inner.log_attempt_start(query_id, speculative_id, node_addr),
+            None => AttemptId(self.next_id()),
+        };
+
+        tracing::debug!(
+            attempt = attempt_id.0,
+            speculative = speculative_id.map(|id| id.0),
+            net.peer.name = %node_addr,
+            "attempt started"
+        );
+
+        attempt_id
+    }
+
+    fn log_attempt_success(&self, attempt_id: AttemptId) {
+        tracing::debug!(attempt = attempt_id.0, "attempt succeeded");
+
+        if let Some(inner) = &self.inner {
+            inner.log_attempt_success(attempt_id);
+        }
+    }
+
+    fn log_attempt_error(&self, attempt_id: AttemptId, error: &QueryError, retry_decision: &RetryDecision) {
+        tracing::debug!(
+            attempt = attempt_id.0,
+            error = %error,
+            retry_decision = ?retry_decision,
+            "attempt failed"
+        );
+
+        if let Some(inner) = &self.inner {
+            inner.log_attempt_error(attempt_id, error, retry_decision);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_literals_with_markers() {
+        assert_eq!(
+            sanitize("SELECT * FROM users WHERE name = 'O''Brien' AND age > 18 AND score < 1.5"),
+            "SELECT * FROM users WHERE name = ? AND age > ? AND score < ?"
+        );
+        assert_eq!(
+            sanitize("DELETE FROM posts WHERE id = 123e4567-e89b-12d3-a456-426614174000 AND blob = 0xcafe"),
+            "DELETE FROM posts WHERE id = ? AND blob = ?"
+        );
+    }
+
+    #[test]
+    fn keeps_identifiers_and_bind_markers() {
+        assert_eq!(
+            sanitize("SELECT v2, col_1 FROM t1 WHERE v2 = :v2 AND col_1 = ? LIMIT 10"),
+            "SELECT v2, col_1 FROM t1 WHERE v2 = :v2 AND col_1 = ? LIMIT ?"
+        );
+    }
+}
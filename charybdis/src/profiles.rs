@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use scylla::execution_profile::{ExecutionProfile, ExecutionProfileHandle};
+use scylla::load_balancing::DefaultPolicy;
+use scylla::speculative_execution::SpeculativeExecutionPolicy;
+
+/// Named execution profiles, configured once at startup and resolved by
+/// [CharybdisQuery::profile](crate::query::CharybdisQuery::profile) and
+/// [CharybdisQuery::local_dc](crate::query::CharybdisQuery::local_dc), so call sites don't have to thread
+/// `ExecutionProfileHandle` values around.
+/// ```rust ignore
+/// let analytics = ExecutionProfile::builder()
+///     .consistency(Consistency::One)
+///     .request_timeout(Some(Duration::from_secs(30)))
+///     .build();
+///
+/// charybdis::profiles::set_profile_registry(
+///     ProfileRegistry::new()
+///         .profile("analytics", analytics.into_handle())
+///         .local_dc("dc1")
+///         .local_dc("dc2"),
+/// );
+///
+/// let posts = Post::find_by_category_id(category_id).profile("analytics").execute(&session).await?;
+/// let user = User::find_by_id(id).local_dc("dc1").execute(&session).await?;
+/// ```
+#[derive(Default)]
+pub struct ProfileRegistry {
+    profiles: HashMap<String, ExecutionProfileHandle>,
+    local_dcs: HashMap<String, ExecutionProfileHandle>,
+}
+
+impl ProfileRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn profile(mut self, name: impl Into<String>, handle: ExecutionProfileHandle) -> Self {
+        self.profiles.insert(name.into(), handle);
+        self
+    }
+
+    /// Registers profile with token aware load balancing that prefers given datacenter, without
+    /// failover to remote datacenters. Other settings are driver defaults.
+    pub fn local_dc(self, datacenter: impl Into<String>) -> Self {
+        self.local_dc_from(datacenter, &ExecutionProfile::builder().build())
+    }
+
+    /// Same as `local_dc`, but other settings (consistency, timeout, retry policy...) are taken from
+    /// given base profile.
+    pub fn local_dc_from(mut self, datacenter: impl Into<String>, base: &ExecutionProfile) -> Self {
+        let datacenter = datacenter.into();
+        let policy = DefaultPolicy::builder()
+            .prefer_datacenter(datacenter.clone())
+            .token_aware(true)
+            .permit_dc_failover(false)
+            .build();
+        let handle = base.to_builder().load_balancing_policy(policy).build().into_handle();
+
+        self.local_dcs.insert(datacenter, handle);
+        self
+    }
+}
+
+static PROFILE_REGISTRY: OnceLock<ProfileRegistry> = OnceLock::new();
+
+/// Sets global profile registry. Returns `false` if registry was already set.
+pub fn set_profile_registry(registry: ProfileRegistry) -> bool {
+    PROFILE_REGISTRY.set(registry).is_ok()
+}
+
+fn registry() -> &'static ProfileRegistry {
+    PROFILE_REGISTRY
+        .get()
+        .expect("Profile registry is not set. Call `charybdis::profiles::set_profile_registry` at startup")
+}
+
+/// Unknown names are configuration errors, so they panic instead of silently falling back to
+/// the session's default profile.
+pub(crate) fn named(name: &str) -> ExecutionProfileHandle {
+    registry()
+        .profiles
+        .get(name)
+        .unwrap_or_else(|| panic!("Execution profile {} is not registered", name))
+        .clone()
+}
+
+/// Profile that query was configured with, which identifies profiles derived by
+/// [CharybdisQuery::speculative_execution](crate::query::CharybdisQuery::speculative_execution) in cache.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) enum ProfileBase {
+    /// Default profile of the session.
+    Default,
+    Named(String),
+    LocalDc(String),
+    /// Handle passed by caller, which can't be compared with other handles.
+    Custom,
+}
+
+/// Profile with given speculative execution policy, other settings are taken from query's profile or default
+/// profile of the session. Derived profiles are built once per base profile and policy, except of profiles passed
+/// by handle.
+pub(crate) fn with_speculative_execution(
+    base: &ProfileBase,
+    handle: Option<&ExecutionProfileHandle>,
+    session_default: Option<&ExecutionProfileHandle>,
+    policy: &Arc<dyn SpeculativeExecutionPolicy>,
+) -> ExecutionProfileHandle {
+    // default profiles of different sessions are told apart by their address
+    type ProfileKey = (ProfileBase, usize, usize);
+
+    static PROFILES: OnceLock<Mutex<HashMap<ProfileKey, SpeculativeProfile>>> = OnceLock::new();
+
+    let base_handle = handle.or(session_default);
+    let derive = || {
+        let builder = match base_handle {
+            Some(base) => base.pointee_to_builder(),
+            None => ExecutionProfile::builder(),
+        };
+
+        builder
+            .speculative_execution_policy(Some(policy.clone()))
+            .build()
+            .into_handle()
+    };
+
+    let session = match base {
+        ProfileBase::Default => session_default.map_or(0, |handle| handle as *const ExecutionProfileHandle as usize),
+        ProfileBase::Custom => return derive(),
+        _ => 0,
+    };
+    let key = (base.clone(), session, Arc::as_ptr(policy) as *const () as usize);
+
+    let mut profiles = PROFILES.get_or_init(Default::default).lock().unwrap();
+
+    profiles
+        .entry(key)
+        .or_insert_with(|| SpeculativeProfile {
+            _policy: policy.clone(),
+            handle: derive(),
+        })
+        .handle
+        .clone()
+}
+
+/// Cached profile keeps its policy alive, so address of the policy isn't reused by another one.
+struct SpeculativeProfile {
+    _policy: Arc<dyn SpeculativeExecutionPolicy>,
+    handle: ExecutionProfileHandle,
+}
+
+pub(crate) fn local_dc(datacenter: &str) -> ExecutionProfileHandle {
+    registry()
+        .local_dcs
+        .get(datacenter)
+        .unwrap_or_else(|| panic!("Local datacenter profile {} is not registered", datacenter))
+        .clone()
+}
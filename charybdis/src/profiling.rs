@@ -0,0 +1,85 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Time spent in single query, split between waiting for the cluster and deserializing rows into models.
+/// High `deserialization` share suggests optimizing models (fewer columns, partial models), while high
+/// `network` share points to the cluster or data model.
+///
+/// For streams and paged queries, profile is reported when stream or iterator is dropped, so it covers
+/// only rows that were actually consumed.
+#[derive(Debug, Clone, Default)]
+pub struct QueryProfile {
+    pub query: &'static str,
+    pub network: Duration,
+    pub deserialization: Duration,
+    pub rows: usize,
+}
+
+/// Receives profile of each executed query, e.g. to export it as metrics:
+/// ```rust ignore
+/// struct MetricsHook;
+///
+/// impl ProfilingHook for MetricsHook {
+///     fn on_query(&self, profile: &QueryProfile) {
+///         histogram!("query_deserialization_seconds", "query" => profile.query)
+///             .record(profile.deserialization.as_secs_f64());
+///     }
+/// }
+///
+/// charybdis::profiling::set_profiling_hook(MetricsHook);
+/// ```
+pub trait ProfilingHook: Send + Sync {
+    fn on_query(&self, profile: &QueryProfile);
+}
+
+static PROFILING_HOOK: OnceLock<Box<dyn ProfilingHook>> = OnceLock::new();
+
+/// Sets global profiling hook. Returns `false` if hook was already set.
+pub fn set_profiling_hook(hook: impl ProfilingHook + 'static) -> bool {
+    PROFILING_HOOK.set(Box::new(hook)).is_ok()
+}
+
+/// Profiling is enabled if hook is set, or if `tracing` feature is enabled, in which case each
+/// profile is emitted as `charybdis::profiling` debug event.
+pub(crate) fn is_enabled() -> bool {
+    cfg!(feature = "tracing") || PROFILING_HOOK.get().is_some()
+}
+
+impl QueryProfile {
+    pub(crate) fn start(query: &'static str, network: Duration) -> Option<Self> {
+        if !is_enabled() {
+            return None;
+        }
+
+        Some(Self {
+            query,
+            network,
+            ..Default::default()
+        })
+    }
+
+    pub(crate) fn report(&self) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            target: "charybdis::profiling",
+            query = self.query,
+            network_us = self.network.as_micros() as u64,
+            deserialization_us = self.deserialization.as_micros() as u64,
+            rows = self.rows,
+            "query profile"
+        );
+
+        if let Some(hook) = PROFILING_HOOK.get() {
+            hook.on_query(self);
+        }
+    }
+}
+
+/// Reports profile of query that deserialized its rows eagerly.
+pub(crate) fn report(query: &'static str, network: Duration, deserialization: Duration, rows: usize) {
+    if let Some(mut profile) = QueryProfile::start(query, network) {
+        profile.deserialization = deserialization;
+        profile.rows = rows;
+        profile.report();
+    }
+}
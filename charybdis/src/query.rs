@@ -1,24 +1,50 @@
-use std::sync::Arc;
-use std::time::Duration;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 use crate::callbacks::{CallbackAction, Callbacks};
-use crate::errors::CharybdisError;
+use crate::driver::{CachingSession, Query, QueryResult};
+use crate::errors::{CharybdisError, ErrorContext};
+use crate::extensions::Extensions;
+use crate::fragment::{prepared_metadata, without_soft_delete_filter, QueryFragment};
+use crate::hooks::{self, OperationKind, QueryInfo};
 use crate::iterator::CharybdisModelIterator;
-use crate::model::BaseModel;
-use crate::options::{Consistency, ExecutionProfileHandle, HistoryListener, RetryPolicy, SerialConsistency};
-use crate::stream::CharybdisModelStream;
-use scylla::query::Query;
+use crate::metadata::{MetadataFields, WithMetadata};
+use crate::model::{BaseModel, Model, Version};
+use crate::options::{
+    Consistency, ExecutionProfileHandle, HistoryListener, RetryPolicy, SerialConsistency, SpeculativeExecutionPolicy,
+};
+#[cfg(feature = "otel")]
+use crate::otel;
+use crate::profiles::{self, ProfileBase};
+use crate::profiling;
+use crate::retry::{is_retryable, ExponentialBackoff};
+use crate::session::CharybdisSession;
+use crate::stream::{CharybdisAutoPagedStream, CharybdisModelStream};
+use crate::tenancy::{self, TenantExtension, TenantGuard, TenantScoped};
+use charybdis_parser::statement::contains_keyword;
+use futures::{future, stream, StreamExt, TryStreamExt};
+use scylla::cql_to_rust::FromCqlVal;
+use scylla::frame::response::result::CqlValue;
 use scylla::serialize::row::{RowSerializationContext, SerializeRow};
+use scylla::serialize::value::SerializeValue;
 use scylla::serialize::{RowWriter, SerializationError};
 use scylla::statement::{PagingState, PagingStateResponse};
-use scylla::transport::query_result::FirstRowTypedError;
-use scylla::{CachingSession, IntoTypedRows, QueryResult};
+use scylla::transport::query_result::{FirstRowTypedError, MaybeFirstRowTypedError};
 
 pub struct ModelRow<M: BaseModel>(pub M);
 pub struct OptionalModelRow<M: BaseModel>(pub Option<M>);
 pub struct ModelStream<M: BaseModel>(pub CharybdisModelStream<M>);
 pub struct ModelPaged<M: BaseModel>(pub CharybdisModelIterator<M>, pub PagingState);
 pub struct ModelMutation(pub QueryResult);
+/// Single value of the first row, e.g. result of `SELECT COUNT(*) ...`
+pub struct ScalarRow<T>(pub T);
+/// Single model row followed by `TTL()` and `WRITETIME()` of its columns
+pub struct ModelRowWithMetadata<M: MetadataFields>(pub WithMetadata<M>);
+/// First model row, or default model if there is none
+pub struct ModelRowOrDefault<M: BaseModel + Default>(pub M);
+/// Whether query returns any row
+pub struct ModelExists(pub bool);
 
 pub trait QueryType {
     type Output;
@@ -44,130 +70,339 @@ impl QueryType for ModelMutation {
     type Output = QueryResult;
 }
 
+impl<T: FromCqlVal<Option<CqlValue>>> QueryType for ScalarRow<T> {
+    type Output = T;
+}
+
+impl<M: MetadataFields> QueryType for ModelRowWithMetadata<M> {
+    type Output = WithMetadata<M>;
+}
+
+impl<M: BaseModel + Default> QueryType for ModelRowOrDefault<M> {
+    type Output = M;
+}
+
+impl QueryType for ModelExists {
+    type Output = bool;
+}
+
 pub trait QueryExecutor: QueryType {
-    async fn execute<Val, M, Qe>(
-        query: CharybdisQuery<'_, Val, M, Qe>,
-        session: &CachingSession,
+    /// Pages are fetched by driver as rows are consumed, so they are retried by driver retry policy of
+    /// [ExponentialBackoff] instead of retrying whole execute.
+    const PAGED_BY_DRIVER: bool = false;
+
+    async fn execute<Val, M, Qe, S>(
+        query: &CharybdisQuery<'_, Val, M, Qe>,
+        session: &S,
     ) -> Result<Self::Output, CharybdisError>
     where
         M: BaseModel,
-        Val: SerializeRow,
-        Qe: QueryExecutor;
+        Val: SerializeRow + Sync,
+        Qe: QueryExecutor,
+        S: CharybdisSession;
 }
 
 impl<Bm: BaseModel> QueryExecutor for ModelRow<Bm> {
-    async fn execute<Val, M, Qe>(
-        query: CharybdisQuery<'_, Val, M, Qe>,
-        session: &CachingSession,
+    async fn execute<Val, M, Qe, S>(
+        query: &CharybdisQuery<'_, Val, M, Qe>,
+        session: &S,
     ) -> Result<Self::Output, CharybdisError>
     where
         M: BaseModel,
-        Val: SerializeRow,
+        Val: SerializeRow + Sync,
         Qe: QueryExecutor,
+        S: CharybdisSession,
     {
+        let start = Instant::now();
         let row = session
-            .execute_unpaged(query.inner, query.values)
+            .execute_unpaged(query.session_statement(session), &query.values)
             .await
-            .map_err(|e| CharybdisError::QueryError(query.query_string, e))?;
+            .map_err(|e| CharybdisError::QueryError(query.error_context(), e))?;
+        let network = start.elapsed();
+
+        let start = Instant::now();
         let res = row.first_row_typed::<Bm>().map_err(|e| match e {
-            FirstRowTypedError::RowsEmpty => CharybdisError::NotFoundError(query.query_string),
-            _ => CharybdisError::FirstRowTypedError(query.query_string, e),
+            FirstRowTypedError::RowsEmpty => CharybdisError::NotFoundError(query.error_context()),
+            FirstRowTypedError::FromRowError(ref row_error) => {
+                CharybdisError::FirstRowTypedError(query.error_context().column_of::<Bm>(row_error), e)
+            }
+            _ => CharybdisError::FirstRowTypedError(query.error_context(), e),
         })?;
 
+        profiling::report(query.query_string, network, start.elapsed(), 1);
+
         Ok(res)
     }
 }
 
 impl<Bm: BaseModel> QueryExecutor for OptionalModelRow<Bm> {
-    async fn execute<Val, M, Qe>(
-        query: CharybdisQuery<'_, Val, M, Qe>,
-        session: &CachingSession,
+    async fn execute<Val, M, Qe, S>(
+        query: &CharybdisQuery<'_, Val, M, Qe>,
+        session: &S,
     ) -> Result<Self::Output, CharybdisError>
     where
         M: BaseModel,
-        Val: SerializeRow,
+        Val: SerializeRow + Sync,
         Qe: QueryExecutor,
+        S: CharybdisSession,
     {
+        let start = Instant::now();
         let row = session
-            .execute_unpaged(query.inner, query.values)
+            .execute_unpaged(query.session_statement(session), &query.values)
             .await
-            .map_err(|e| CharybdisError::QueryError(query.query_string, e))?;
-        let res = row
-            .maybe_first_row_typed::<Bm>()
-            .map_err(|e| CharybdisError::MaybeFirstRowTypedError(query.query_string, e))?;
+            .map_err(|e| CharybdisError::QueryError(query.error_context(), e))?;
+        let network = start.elapsed();
+
+        let start = Instant::now();
+        let res = row.maybe_first_row_typed::<Bm>().map_err(|e| {
+            let context = match &e {
+                MaybeFirstRowTypedError::FromRowError(row_error) => query.error_context().column_of::<Bm>(row_error),
+                _ => query.error_context(),
+            };
+
+            CharybdisError::MaybeFirstRowTypedError(context, e)
+        })?;
+
+        profiling::report(query.query_string, network, start.elapsed(), res.is_some() as usize);
 
         Ok(res)
     }
 }
 
 impl<Bm: BaseModel> QueryExecutor for ModelStream<Bm> {
-    async fn execute<Val, M, Qe>(
-        query: CharybdisQuery<'_, Val, M, Qe>,
-        session: &CachingSession,
+    const PAGED_BY_DRIVER: bool = true;
+
+    async fn execute<Val, M, Qe, S>(
+        query: &CharybdisQuery<'_, Val, M, Qe>,
+        session: &S,
     ) -> Result<Self::Output, CharybdisError>
     where
         M: BaseModel,
-        Val: SerializeRow,
+        Val: SerializeRow + Sync,
         Qe: QueryExecutor,
+        S: CharybdisSession,
     {
+        let mut statement = query.session_statement(session);
+
+        if let Some(backoff) = query.retry.as_ref().filter(|_| statement.get_is_idempotent()) {
+            statement.set_retry_policy(Some(backoff.page_retry_policy()));
+        }
+
+        let start = Instant::now();
         let rows = session
-            .execute_iter(query.inner, query.values)
+            .execute_iter(statement, &query.values)
             .await
-            .map_err(|e| CharybdisError::QueryError(query.query_string, e))?
-            .into_typed::<Bm>();
+            .map_err(|e| CharybdisError::QueryError(query.error_context(), e))?;
 
         let mut stream = CharybdisModelStream::from(rows);
 
-        stream.query_string(query.query_string);
+        stream.context(query.error_context());
+        stream.profile(start.elapsed());
 
         Ok(stream)
     }
 }
 
 impl<Bm: BaseModel> QueryExecutor for ModelPaged<Bm> {
-    async fn execute<Val, M, Qe>(
-        query: CharybdisQuery<'_, Val, M, Qe>,
-        session: &CachingSession,
+    async fn execute<Val, M, Qe, S>(
+        query: &CharybdisQuery<'_, Val, M, Qe>,
+        session: &S,
     ) -> Result<Self::Output, CharybdisError>
     where
         M: BaseModel,
-        Val: SerializeRow,
+        Val: SerializeRow + Sync,
         Qe: QueryExecutor,
+        S: CharybdisSession,
     {
+        let start = Instant::now();
         let res = session
-            .execute_single_page(query.inner, query.values, query.paging_state)
+            .execute_single_page(
+                query.session_statement(session),
+                &query.values,
+                query.paging_state.clone(),
+            )
             .await
-            .map_err(|e| CharybdisError::QueryError(query.query_string, e))?;
+            .map_err(|e| CharybdisError::QueryError(query.error_context(), e))?;
+        let network = start.elapsed();
         let rows = res
             .0
             .rows()
-            .map_err(|e| CharybdisError::RowsExpectedError(query.query_string, e))?;
+            .map_err(|e| CharybdisError::RowsExpectedError(query.error_context(), e))?;
 
-        let mut typed_rows = CharybdisModelIterator::from(rows.into_typed());
+        let mut typed_rows = CharybdisModelIterator::from(rows);
 
-        typed_rows.query_string(query.query_string);
+        typed_rows.context(query.error_context());
+        typed_rows.profile(network);
 
         Ok((typed_rows, res.1))
     }
 }
 
+impl<T: FromCqlVal<Option<CqlValue>>> QueryExecutor for ScalarRow<T> {
+    async fn execute<Val, M, Qe, S>(
+        query: &CharybdisQuery<'_, Val, M, Qe>,
+        session: &S,
+    ) -> Result<Self::Output, CharybdisError>
+    where
+        M: BaseModel,
+        Val: SerializeRow + Sync,
+        Qe: QueryExecutor,
+        S: CharybdisSession,
+    {
+        let start = Instant::now();
+        let row = session
+            .execute_unpaged(query.session_statement(session), &query.values)
+            .await
+            .map_err(|e| CharybdisError::QueryError(query.error_context(), e))?;
+        let network = start.elapsed();
+
+        let start = Instant::now();
+        let (res,) = row.first_row_typed::<(T,)>().map_err(|e| match e {
+            FirstRowTypedError::RowsEmpty => CharybdisError::NotFoundError(query.error_context()),
+            _ => CharybdisError::FirstRowTypedError(query.error_context(), e),
+        })?;
+
+        profiling::report(query.query_string, network, start.elapsed(), 1);
+
+        Ok(res)
+    }
+}
+
+impl<Bm: MetadataFields> QueryExecutor for ModelRowWithMetadata<Bm> {
+    async fn execute<Val, M, Qe, S>(
+        query: &CharybdisQuery<'_, Val, M, Qe>,
+        session: &S,
+    ) -> Result<Self::Output, CharybdisError>
+    where
+        M: BaseModel,
+        Val: SerializeRow + Sync,
+        Qe: QueryExecutor,
+        S: CharybdisSession,
+    {
+        let start = Instant::now();
+        let res = session
+            .execute_unpaged(query.session_statement(session), &query.values)
+            .await
+            .map_err(|e| CharybdisError::QueryError(query.error_context(), e))?;
+        let network = start.elapsed();
+
+        let row = res
+            .rows()
+            .map_err(|e| CharybdisError::RowsExpectedError(query.error_context(), e))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| CharybdisError::NotFoundError(query.error_context()))?;
+
+        let start = Instant::now();
+        let res = WithMetadata::<Bm>::from_row(row)
+            .map_err(|e| CharybdisError::FromRowError(query.error_context().column_of::<Bm>(&e), e))?;
+
+        profiling::report(query.query_string, network, start.elapsed(), 1);
+
+        Ok(res)
+    }
+}
+
+impl<Bm: BaseModel + Default> QueryExecutor for ModelRowOrDefault<Bm> {
+    async fn execute<Val, M, Qe, S>(
+        query: &CharybdisQuery<'_, Val, M, Qe>,
+        session: &S,
+    ) -> Result<Self::Output, CharybdisError>
+    where
+        M: BaseModel,
+        Val: SerializeRow + Sync,
+        Qe: QueryExecutor,
+        S: CharybdisSession,
+    {
+        let res = OptionalModelRow::<Bm>::execute(query, session).await?;
+
+        Ok(res.unwrap_or_default())
+    }
+}
+
+/// Rows are read as they are, without deserialization to model.
+impl QueryExecutor for ModelExists {
+    async fn execute<Val, M, Qe, S>(
+        query: &CharybdisQuery<'_, Val, M, Qe>,
+        session: &S,
+    ) -> Result<Self::Output, CharybdisError>
+    where
+        M: BaseModel,
+        Val: SerializeRow + Sync,
+        Qe: QueryExecutor,
+        S: CharybdisSession,
+    {
+        let start = Instant::now();
+        let res = session
+            .execute_unpaged(query.session_statement(session), &query.values)
+            .await
+            .map_err(|e| CharybdisError::QueryError(query.error_context(), e))?;
+
+        let exists = res
+            .rows_num()
+            .map_err(|e| CharybdisError::RowsExpectedError(query.error_context(), e))?
+            > 0;
+
+        profiling::report(query.query_string, start.elapsed(), Duration::ZERO, exists as usize);
+
+        Ok(exists)
+    }
+}
+
 impl QueryExecutor for ModelMutation {
-    async fn execute<Val, M, Qe>(
-        query: CharybdisQuery<'_, Val, M, Qe>,
-        session: &CachingSession,
+    async fn execute<Val, M, Qe, S>(
+        query: &CharybdisQuery<'_, Val, M, Qe>,
+        session: &S,
     ) -> Result<Self::Output, CharybdisError>
     where
         M: BaseModel,
-        Val: SerializeRow,
+        Val: SerializeRow + Sync,
         Qe: QueryExecutor,
+        S: CharybdisSession,
     {
-        session
-            .execute_unpaged(query.inner, query.values)
+        let res = session
+            .execute_unpaged(query.session_statement(session), &query.values)
             .await
-            .map_err(|e| CharybdisError::QueryError(query.query_string, e))
+            .map_err(|e| CharybdisError::QueryError(query.error_context(), e))?;
+
+        if query.check_applied && !is_applied(&res) {
+            return Err(CharybdisError::StaleObjectError(query.error_context()));
+        }
+
+        Ok(res)
     }
 }
 
+/// LWT result contains `[applied]` boolean as first column
+pub(crate) fn is_applied(res: &QueryResult) -> bool {
+    res.rows
+        .as_ref()
+        .and_then(|rows| rows.first())
+        .and_then(|row| row.columns.first())
+        .and_then(|applied| applied.as_ref())
+        .and_then(|applied| applied.as_boolean())
+        .unwrap_or(true)
+}
+
+/// Values bound to named bind markers by name, e.g. `:user_id`. Use [crate::named_values] macro to build it.
+pub type NamedValues<'a> = HashMap<&'a str, Box<dyn SerializeValue + Send + Sync + 'a>>;
+
+/// Builds [NamedValues] for queries with named bind markers:
+/// ```rust ignore
+/// let values = named_values! {
+///     "category_id" => category_id,
+///     "author_id" => author_id,
+/// };
+/// ```
+#[macro_export]
+macro_rules! named_values {
+    ($($name:expr => $value:expr),* $(,)?) => {{
+        let mut values = $crate::query::NamedValues::new();
+        $(values.insert($name, Box::new($value));)*
+        values
+    }};
+}
+
 #[derive(Default)]
 pub enum QueryValue<'a, Val: SerializeRow, M: BaseModel> {
     Owned(Val),
@@ -175,6 +410,16 @@ pub enum QueryValue<'a, Val: SerializeRow, M: BaseModel> {
     PrimaryKey(M::PrimaryKey),
     PartitionKey(M::PartitionKey),
     Model(&'a M),
+    Row(Box<dyn SerializeRow + Send + Sync + 'a>),
+    Named(NamedValues<'a>),
+    /// Values of the query followed by values of appended [QueryFragment]
+    Fragment(Box<QueryValue<'a, Val, M>>, QueryFragment<'a>),
+    /// Values of the query scoped to tenant by [CharybdisQuery::scoped]
+    Tenant(Box<QueryValue<'a, Val, M>>, TenantGuard<'a>),
+    /// Values of the query followed by value of `LIMIT ?` bound by [CharybdisQuery::limit]
+    Limit(Box<QueryValue<'a, Val, M>>, i32),
+    /// Values of versioned update binding next version, see [Model::update_values](crate::model::Model::update_values)
+    Versioned(Box<QueryValue<'a, Val, M>>, Version),
     #[default]
     Empty,
 }
@@ -187,6 +432,21 @@ impl<Val: SerializeRow, M: BaseModel> SerializeRow for QueryValue<'_, Val, M> {
             QueryValue::PrimaryKey(val) => val.serialize(ctx, writer),
             QueryValue::PartitionKey(val) => val.serialize(ctx, writer),
             QueryValue::Model(val) => val.serialize(ctx, writer),
+            QueryValue::Row(val) => val.serialize(ctx, writer),
+            QueryValue::Named(val) => val.serialize(ctx, writer),
+            QueryValue::Fragment(val, fragment) => fragment.serialize_after(val.as_ref(), ctx, writer),
+            QueryValue::Tenant(val, guard) => guard.serialize_checked(val.as_ref(), ctx, writer),
+            QueryValue::Versioned(val, _) => val.serialize(ctx, writer),
+            QueryValue::Limit(val, limit) => {
+                let columns = ctx.columns();
+                let (query_columns, limit_columns) = columns.split_at(columns.len().saturating_sub(1));
+
+                let query_metadata = prepared_metadata(query_columns);
+                val.serialize(&RowSerializationContext::from_prepared(&query_metadata), writer)?;
+
+                let limit_metadata = prepared_metadata(limit_columns);
+                (*limit,).serialize(&RowSerializationContext::from_prepared(&limit_metadata), writer)
+            }
             QueryValue::Empty => Ok(()),
         }
     }
@@ -198,41 +458,411 @@ impl<Val: SerializeRow, M: BaseModel> SerializeRow for QueryValue<'_, Val, M> {
             QueryValue::PrimaryKey(val) => val.is_empty(),
             QueryValue::PartitionKey(val) => val.is_empty(),
             QueryValue::Model(val) => val.is_empty(),
+            QueryValue::Row(val) => val.is_empty(),
+            QueryValue::Named(val) => val.is_empty(),
+            QueryValue::Fragment(val, fragment) => val.is_empty() && fragment.is_empty(),
+            QueryValue::Tenant(val, _) | QueryValue::Versioned(val, _) => val.is_empty(),
+            QueryValue::Limit(_, _) => false,
             QueryValue::Empty => true,
         }
     }
 }
 
+impl<Val: SerializeRow, M: BaseModel> QueryValue<'_, Val, M> {
+    /// True if values are checked by [TenantGuard] of [CharybdisQuery::scoped].
+    fn is_scoped(&self) -> bool {
+        match self {
+            QueryValue::Tenant(_, _) => true,
+            QueryValue::Fragment(val, _) | QueryValue::Limit(val, _) | QueryValue::Versioned(val, _) => val.is_scoped(),
+            _ => false,
+        }
+    }
+
+    /// Version written by versioned update, that is assigned to the model by
+    /// [Model::increment_version](crate::model::Model::increment_version) after update is applied.
+    pub fn next_version(&self) -> Option<Version> {
+        match self {
+            QueryValue::Versioned(_, version) => Some(*version),
+            QueryValue::Fragment(val, _) | QueryValue::Tenant(val, _) | QueryValue::Limit(val, _) => val.next_version(),
+            _ => None,
+        }
+    }
+
+    /// Replaces bound value of `LIMIT ?`, if there is one.
+    fn replace_limit(&mut self, limit: i32) -> bool {
+        match self {
+            QueryValue::Limit(_, value) => {
+                *value = limit;
+                true
+            }
+            QueryValue::Tenant(val, _) => val.replace_limit(limit),
+            _ => false,
+        }
+    }
+}
+
+/// Order of rows of partition by first clustering key, see [CharybdisQuery::order_by_clustering].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClusteringOrder {
+    Asc,
+    Desc,
+}
+
+/// Orders and limits of generated `FIND_BY_PARTITION_KEY_QUERY` variants.
+const PARTITION_FINDER_VARIANTS: [(Option<ClusteringOrder>, bool); 6] = [
+    (None, false),
+    (None, true),
+    (Some(ClusteringOrder::Asc), false),
+    (Some(ClusteringOrder::Asc), true),
+    (Some(ClusteringOrder::Desc), false),
+    (Some(ClusteringOrder::Desc), true),
+];
+
+fn partition_finder_query<M: BaseModel>(order: Option<ClusteringOrder>, limit: bool) -> Option<&'static str> {
+    match (order, limit) {
+        (None, false) => Some(M::FIND_BY_PARTITION_KEY_QUERY),
+        (None, true) => Some(M::FIND_BY_PARTITION_KEY_LIMIT_QUERY),
+        (Some(ClusteringOrder::Asc), false) => M::FIND_BY_PARTITION_KEY_ASC_QUERY,
+        (Some(ClusteringOrder::Asc), true) => M::FIND_BY_PARTITION_KEY_ASC_LIMIT_QUERY,
+        (Some(ClusteringOrder::Desc), false) => M::FIND_BY_PARTITION_KEY_DESC_QUERY,
+        (Some(ClusteringOrder::Desc), true) => M::FIND_BY_PARTITION_KEY_DESC_LIMIT_QUERY,
+    }
+}
+
+/// Leaks each distinct statement once, as queries are expected to be `&'static str`.
+pub(crate) fn intern(statement: String) -> &'static str {
+    static STATEMENTS: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+    let mut statements = STATEMENTS.get_or_init(Default::default).lock().unwrap();
+
+    if let Some(interned) = statements.get(statement.as_str()) {
+        return interned;
+    }
+
+    let interned: &'static str = Box::leak(statement.into_boxed_str());
+    statements.insert(interned);
+
+    interned
+}
+
 pub struct CharybdisQuery<'a, Val: SerializeRow, M: BaseModel, Qe: QueryExecutor> {
     inner: Query,
     paging_state: PagingState,
     pub(crate) query_string: &'static str,
     pub(crate) values: QueryValue<'a, Val, M>,
+    pub(crate) check_applied: bool,
+    tenant_checked: bool,
+    partition_statement: Option<&'static str>,
+    retry: Option<ExponentialBackoff>,
+    profile_base: ProfileBase,
+    speculative_execution: Option<Arc<dyn SpeculativeExecutionPolicy>>,
+    // error of builder method, e.g. `scoped`, returned on execution so builder methods can be chained
+    error: Option<CharybdisError>,
     _phantom: std::marker::PhantomData<Qe>,
 }
 
 impl<'a, Val: SerializeRow, M: BaseModel, Qe: QueryExecutor> CharybdisQuery<'a, Val, M, Qe> {
     pub fn new(query: &'static str, values: QueryValue<'a, Val, M>) -> Self {
+        #[allow(unused_mut)]
+        let mut inner = Query::new(query);
+
+        #[cfg(feature = "otel")]
+        inner.set_history_listener(otel::SpanHistoryListener::new(None));
+
         Self {
-            inner: Query::new(query),
+            inner,
             query_string: query,
             values,
+            check_applied: false,
+            tenant_checked: false,
+            partition_statement: None,
+            retry: None,
+            profile_base: ProfileBase::Default,
+            speculative_execution: None,
+            error: None,
             paging_state: PagingState::start(),
             _phantom: Default::default(),
         }
     }
 
+    /// Query of other statement and values, with options of this one.
+    fn with_statement<V: SerializeRow>(&self, query: &'static str, values: V) -> CharybdisQuery<'a, V, M, Qe> {
+        let mut inner = self.inner.clone();
+        inner.contents = query.to_string();
+
+        CharybdisQuery {
+            inner,
+            query_string: query,
+            values: QueryValue::Owned(values),
+            check_applied: self.check_applied,
+            tenant_checked: self.tenant_checked,
+            partition_statement: None,
+            retry: self.retry.clone(),
+            profile_base: self.profile_base.clone(),
+            speculative_execution: self.speculative_execution.clone(),
+            error: None,
+            paging_state: self.paging_state.clone(),
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Executes statement built at runtime, e.g. update of changed columns, instead of `query` given to constructor.
+    pub(crate) fn statement(mut self, statement: String) -> Self {
+        self.set_statement(statement);
+
+        self
+    }
+
+    /// Replaces executed statement, so reported query stays the same as executed one. Statement of single
+    /// partition doesn't match modified statement, so it's dropped.
+    fn set_statement(&mut self, statement: String) {
+        self.query_string = intern(statement.clone());
+        self.inner.contents = statement;
+        self.partition_statement = None;
+    }
+
+    /// Statement that selects single partition of multi-get, see [Self::per_partition]. Used by generated
+    /// `find_by_<partition_key>_in` of models with single-column partition key.
+    pub fn per_partition_statement(mut self, statement: &'static str) -> Self {
+        self.partition_statement = Some(statement);
+        self
+    }
+
+    /// Statement executed by the query, including modifications, e.g. by [Self::and_where].
+    pub(crate) fn contents(&self) -> &str {
+        &self.inner.contents
+    }
+
     pub(crate) fn values(mut self, values: QueryValue<'a, Val, M>) -> Self {
         self.values = values;
 
         self
     }
 
+    /// Version written by versioned update, see [QueryValue::next_version].
+    pub fn next_version(&self) -> Option<Version> {
+        self.values.next_version()
+    }
+
+    /// Conditional mutations that are not applied will return `StaleObjectError`.
+    pub(crate) fn check_applied(mut self, check_applied: bool) -> Self {
+        self.check_applied = check_applied;
+
+        self
+    }
+
     pub fn page_size(mut self, page_size: i32) -> Self {
         self.inner.set_page_size(page_size);
         self
     }
 
+    /// Limits partition finder by user provided value clamped to `[1, max]`, so public endpoints can pass
+    /// requested size without risk of fetching whole partition. Limit is bound as [Self::limit] does, and page size
+    /// is capped to the same value.
+    /// ```rust ignore
+    /// let posts = Post::find_by_partition_key_value((category_id,))
+    ///     .limit_clamped(params.limit, 100)
+    ///     .execute(session)
+    ///     .await?;
+    /// ```
+    /// Query fails with [CharybdisError::QueryBuilderError] if it's not partition finder or its statement was
+    /// modified, e.g. by [Self::and_where].
+    pub fn limit_clamped(mut self, limit: i64, max: u32) -> Self {
+        if self.partition_finder_variant("limit_clamped").is_none() {
+            return self;
+        }
+
+        let max = max.clamp(1, i32::MAX as u32) as i64;
+        let limit = limit.clamp(1, max) as i32;
+
+        let mut query = self.limit(limit);
+
+        match query.inner.get_page_size() {
+            Some(page_size) if page_size <= limit => {}
+            _ => query.inner.set_page_size(limit),
+        }
+
+        query
+    }
+
+    /// Orders rows of partition finder, e.g. `find_by_partition_key_value` or generated
+    /// `find_by_<partition_key>`, by first clustering key, so following clustering keys keep their order relative
+    /// to it. Query is switched to generated variant of the statement, so it's prepared once like other statements:
+    /// ```rust ignore
+    /// let latest_posts = Post::find_by_category_id(category_id)
+    ///     .order_by_clustering(ClusteringOrder::Desc)
+    ///     .limit(10)
+    ///     .execute(&session)
+    ///     .await?;
+    /// ```
+    /// Query fails with [CharybdisError::QueryBuilderError] if it's not partition finder, its statement was
+    /// modified, e.g. by [Self::and_where], or model has no clustering keys.
+    pub fn order_by_clustering(mut self, order: ClusteringOrder) -> Self {
+        let limit = match self.partition_finder_variant("order_by_clustering") {
+            Some((_, limit)) => limit,
+            None => return self,
+        };
+
+        let query = match partition_finder_query::<M>(Some(order), limit) {
+            Some(query) => query,
+            None => {
+                self.error.get_or_insert(CharybdisError::QueryBuilderError(format!(
+                    "{} has no clustering keys to order by",
+                    M::DB_MODEL_NAME
+                )));
+
+                return self;
+            }
+        };
+
+        self.query_string = query;
+        self.inner.contents = query.to_string();
+
+        self
+    }
+
+    /// Limits rows of partition finder by bound `LIMIT ?`, so limit doesn't create new statement for each value.
+    /// Limit has to be positive, otherwise query is rejected by cluster, see [Self::limit_clamped] for user
+    /// provided values.
+    ///
+    /// Query fails with [CharybdisError::QueryBuilderError] if it's not partition finder or its statement was
+    /// modified, e.g. by [Self::and_where].
+    pub fn limit(mut self, limit: i32) -> Self {
+        let (order, limited) = match self.partition_finder_variant("limit") {
+            Some(variant) => variant,
+            None => return self,
+        };
+
+        if limited {
+            if !self.values.replace_limit(limit) {
+                self.error.get_or_insert(CharybdisError::QueryBuilderError(format!(
+                    "limit of query is bound by its values: {}",
+                    self.inner.contents
+                )));
+            }
+
+            return self;
+        }
+
+        let query = partition_finder_query::<M>(order, true).expect("variant of partition finder must exist");
+
+        self.query_string = query;
+        self.inner.contents = query.to_string();
+
+        let values = std::mem::replace(&mut self.values, QueryValue::Empty);
+        self.values = QueryValue::Limit(Box::new(values), limit);
+
+        self
+    }
+
+    /// Order and limit of generated partition finder executed by the query. `None` if query is not partition
+    /// finder or its statement was modified, in which case `QueryBuilderError` is returned on execution.
+    fn partition_finder_variant(&mut self, method: &str) -> Option<(Option<ClusteringOrder>, bool)> {
+        let variant = PARTITION_FINDER_VARIANTS.into_iter().find(|(order, limit)| {
+            partition_finder_query::<M>(*order, *limit).is_some_and(|query| query == self.query_string)
+        });
+
+        if variant.is_none() {
+            self.error.get_or_insert(CharybdisError::QueryBuilderError(format!(
+                "{} is supported only by unmodified partition finders, got: {}",
+                method, self.inner.contents
+            )));
+        }
+
+        variant
+    }
+
+    /// Appends conditions of [QueryFragment] to WHERE clause, with their values bound after values of
+    /// the query.
+    ///
+    /// Query fails with [CharybdisError::QueryBuilderError] if it has bind markers after WHERE clause, e.g.
+    /// `LIMIT ?`.
+    pub fn and_where(mut self, fragment: QueryFragment<'a>) -> Self {
+        let statement = match fragment.apply(&self.inner.contents) {
+            Ok(statement) => statement,
+            Err(e) => {
+                self.error.get_or_insert(e);
+                return self;
+            }
+        };
+        self.set_statement(statement);
+
+        let values = std::mem::replace(&mut self.values, QueryValue::Empty);
+        self.values = QueryValue::Fragment(Box::new(values), fragment);
+
+        self
+    }
+
+    /// Limits query to tenant of extension, see [TenantScoped]. Condition on tenant column is appended to WHERE
+    /// clause of select if query doesn't have one, e.g. query by global secondary index, while tenant bound by
+    /// query itself, e.g. as part of partition key taken from request or inserted model, is checked to be tenant
+    /// of extension. Queries of models with `tenant_key` have to be scoped, unless they are [Self::unscoped].
+    /// ```rust ignore
+    /// let post = Post::find_by_primary_key_value((org_id, category_id, id))
+    ///     .scoped(&ctx)
+    ///     .execute(&session)
+    ///     .await?;
+    /// ```
+    /// Query fails with [CharybdisError::TenantError] if extension has no tenant, query doesn't bind tenant column,
+    /// e.g. delete of model with non-key tenant column, or query restricts partition key by token, e.g.
+    /// `find_by_token_range`. Appended condition may require `ALLOW FILTERING`, as conditions appended by
+    /// [Self::and_where].
+    pub fn scoped<E: TenantExtension<M::TenantId>>(mut self, extension: &'a E) -> Self
+    where
+        M: TenantScoped,
+    {
+        let column = M::TENANT_COLUMN.expect("tenant scoped model must declare tenant column");
+        let tenant_id = extension.tenant_id();
+
+        if contains_keyword(&self.inner.contents, "TOKEN(") {
+            self.error.get_or_insert(CharybdisError::TenantError(format!(
+                "query restricted by token can't be scoped to tenant: {}",
+                self.inner.contents
+            )));
+
+            return self;
+        }
+
+        let uppercase = self.inner.contents.trim_start().to_ascii_uppercase();
+
+        if uppercase.starts_with("SELECT ") && !tenancy::restricts_column(&self.inner.contents, column) {
+            self = self.and_where(QueryFragment::new(format!("{} = ?", column), (tenant_id,)));
+        }
+
+        let values = std::mem::replace(&mut self.values, QueryValue::Empty);
+        self.values = QueryValue::Tenant(Box::new(values), TenantGuard::new(column, tenant_id));
+
+        self
+    }
+
+    /// Executes query of model with `tenant_key` without limiting it to tenant, e.g. in background jobs that
+    /// process rows of all tenants.
+    pub fn unscoped(mut self) -> Self {
+        self.tenant_checked = true;
+        self
+    }
+
+    /// Query of model authorized by callbacks, see [Callbacks::authorize](crate::callbacks::Callbacks::authorize).
+    pub(crate) fn authorized(self) -> Self {
+        self.unscoped()
+    }
+
+    /// Rejects query of model with `tenant_key`, unless it's [Self::scoped], authorized by callbacks or
+    /// [Self::unscoped].
+    fn check_tenant(&self) -> Result<(), CharybdisError> {
+        match M::TENANT_COLUMN {
+            Some(column) if !self.tenant_checked && !self.values.is_scoped() => {
+                Err(CharybdisError::TenantError(format!(
+                    "{} is scoped by {}, but query is not scoped to tenant: {}",
+                    M::DB_MODEL_NAME,
+                    column,
+                    self.inner.contents
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
     pub fn consistency(mut self, consistency: Consistency) -> Self {
         self.inner.set_consistency(consistency);
         self
@@ -274,22 +904,312 @@ impl<'a, Val: SerializeRow, M: BaseModel, Qe: QueryExecutor> CharybdisQuery<'a,
     }
 
     pub fn history_listener(mut self, history_listener: Arc<dyn HistoryListener>) -> Self {
+        #[cfg(feature = "otel")]
+        let history_listener = otel::SpanHistoryListener::new(Some(history_listener));
+
         self.inner.set_history_listener(history_listener);
         self
     }
 
+    /// Removes listener set by [Self::history_listener]. With `otel` feature, attempts are still recorded in span.
     pub fn remove_history_listener(mut self) -> Self {
         self.inner.remove_history_listener();
+
+        #[cfg(feature = "otel")]
+        self.inner.set_history_listener(otel::SpanHistoryListener::new(None));
+
         self
     }
 
     pub fn profile_handle(mut self, profile_handle: Option<ExecutionProfileHandle>) -> Self {
+        self.profile_base = match profile_handle {
+            Some(_) => ProfileBase::Custom,
+            None => ProfileBase::Default,
+        };
         self.inner.set_execution_profile_handle(profile_handle);
         self
     }
 
-    pub async fn execute(self, session: &CachingSession) -> Result<Qe::Output, CharybdisError> {
-        Qe::execute(self, session).await
+    /// Uses execution profile registered under given name in [ProfileRegistry](crate::profiles::ProfileRegistry).
+    ///
+    /// # Panics
+    /// If registry is not set or profile is not registered.
+    pub fn profile(self, name: &str) -> Self {
+        let mut query = self.profile_handle(Some(profiles::named(name)));
+        query.profile_base = ProfileBase::Named(name.to_string());
+
+        query
+    }
+
+    /// Routes query to given datacenter, using profile registered with
+    /// [ProfileRegistry::local_dc](crate::profiles::ProfileRegistry::local_dc).
+    ///
+    /// # Panics
+    /// If registry is not set or datacenter is not registered.
+    pub fn local_dc(self, datacenter: &str) -> Self {
+        let mut query = self.profile_handle(Some(profiles::local_dc(datacenter)));
+        query.profile_base = ProfileBase::LocalDc(datacenter.to_string());
+
+        query
+    }
+
+    /// Sends query to another replica if response doesn't arrive within delay given by policy, e.g.
+    /// [SimpleSpeculativeExecutionPolicy](crate::options::SimpleSpeculativeExecutionPolicy). Other settings are
+    /// taken on execution from query's execution profile or default profile of the session. Derived profile is
+    /// built once per profile and policy, so policy should be shared by queries instead of created per query.
+    /// Driver speculates only on statements marked as idempotent.
+    /// ```rust ignore
+    /// let policy = SimpleSpeculativeExecutionPolicy {
+    ///     max_retry_count: 1,
+    ///     retry_interval: Duration::from_millis(20),
+    /// };
+    ///
+    /// let user = User::find_by_id(id)
+    ///     .idempotent(true)
+    ///     .speculative_execution(Arc::new(policy))
+    ///     .execute(&session)
+    ///     .await?;
+    /// ```
+    pub fn speculative_execution(mut self, policy: Arc<dyn SpeculativeExecutionPolicy>) -> Self {
+        self.speculative_execution = Some(policy);
+        self
+    }
+
+    /// Statement executed by given session, with profile derived for speculative execution if it's set.
+    fn session_statement(&self, session: &impl CharybdisSession) -> Query {
+        let mut statement = self.inner.clone();
+
+        if let Some(policy) = &self.speculative_execution {
+            let handle = profiles::with_speculative_execution(
+                &self.profile_base,
+                self.inner.get_execution_profile_handle(),
+                session.default_profile_handle(),
+                policy,
+            );
+
+            statement.set_execution_profile_handle(Some(handle));
+        }
+
+        statement
+    }
+
+    /// Includes rows of models with `soft_delete` field that are marked as deleted, by removing condition that
+    /// filters them out from the statement. It doesn't change statements of other models.
+    pub fn with_deleted(mut self) -> Self {
+        if let Some(soft_delete_column) = M::SOFT_DELETE_COLUMN {
+            let partition_statement = self
+                .partition_statement
+                .map(|statement| intern(without_soft_delete_filter(statement, soft_delete_column)));
+
+            let statement = without_soft_delete_filter(&self.inner.contents, soft_delete_column);
+            self.set_statement(statement);
+            self.partition_statement = partition_statement;
+        }
+
+        self
+    }
+
+    /// Retries whole execute on transient errors if statement is marked as idempotent.
+    pub fn retry(mut self, backoff: ExponentialBackoff) -> Self {
+        self.retry = Some(backoff);
+        self
+    }
+
+    /// Statement as it will be executed, e.g. for logging.
+    pub fn query_info(&self) -> QueryInfo<'_> {
+        QueryInfo {
+            query: self.inner.contents.as_str().into(),
+            table: Some(M::DB_MODEL_NAME),
+            operation: OperationKind::from_query(&self.inner.contents),
+            consistency: self.inner.get_consistency(),
+            values: Some(&self.values),
+        }
+    }
+
+    /// Statement, table and operation reported in errors of this query.
+    pub(crate) fn error_context(&self) -> ErrorContext {
+        ErrorContext::model::<M>(self.query_string)
+    }
+}
+
+impl<Val: SerializeRow + Sync, M: BaseModel, Qe: QueryExecutor> CharybdisQuery<'_, Val, M, Qe> {
+    pub async fn execute(mut self, session: &impl CharybdisSession) -> Result<Qe::Output, CharybdisError> {
+        self.execute_by_ref(session).await
+    }
+
+    async fn execute_by_ref(&mut self, session: &impl CharybdisSession) -> Result<Qe::Output, CharybdisError> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+
+        self.check_tenant()?;
+
+        hooks::observe(|| self.query_info(), self.execute_with_retry(session)).await
+    }
+
+    async fn execute_with_retry(&self, session: &impl CharybdisSession) -> Result<Qe::Output, CharybdisError> {
+        let backoff = match &self.retry {
+            Some(backoff) if self.inner.get_is_idempotent() && !Qe::PAGED_BY_DRIVER => backoff,
+            _ => return Qe::execute(self, session).await,
+        };
+
+        let mut attempt = 1;
+
+        loop {
+            match Qe::execute(self, session).await {
+                Err(e) if attempt < backoff.max_attempts() && is_retryable(&e) => {
+                    tokio::time::sleep(backoff.delay(attempt)).await;
+
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+impl<'a, Val: SerializeRow + 'a, M: BaseModel + 'a, Bm: BaseModel + 'a> CharybdisQuery<'a, Val, M, ModelPaged<Bm>> {
+    /// Walks all pages, starting from configured paging state, see [AutoPagedQuery].
+    pub fn auto_pages(self) -> AutoPagedQuery<'a, Val, M, Bm> {
+        AutoPagedQuery {
+            query: self,
+            max_pages: None,
+        }
+    }
+}
+
+/// Paged query that transparently requests subsequent pages until the last one, so paging state doesn't
+/// have to be handed off manually.
+/// ```rust ignore
+/// let posts = Post::find_by_partition_key_value_paged((category_id,))
+///     .page_size(100)
+///     .auto_pages()
+///     .max_pages(50)
+///     .execute(&session)
+///     .await?
+///     .try_collect()
+///     .await?;
+/// ```
+pub struct AutoPagedQuery<'a, Val: SerializeRow, M: BaseModel, Bm: BaseModel> {
+    query: CharybdisQuery<'a, Val, M, ModelPaged<Bm>>,
+    max_pages: Option<usize>,
+}
+
+impl<'a, Val: SerializeRow + Send + Sync + 'a, M: BaseModel + 'a, Bm: BaseModel + 'a> AutoPagedQuery<'a, Val, M, Bm> {
+    /// Guard against walking unexpectedly large results. If more than `max_pages` pages would be fetched,
+    /// stream yields `MaxPagesExceededError` and ends. Values lower than 1 are treated as 1.
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = Some(max_pages.max(1));
+        self
+    }
+
+    /// Fetches the first page, subsequent pages are fetched as stream is consumed.
+    pub async fn execute<S: CharybdisSession>(
+        self,
+        session: &'a S,
+    ) -> Result<CharybdisAutoPagedStream<'a, Bm>, CharybdisError> {
+        let AutoPagedQuery { mut query, max_pages } = self;
+        let (first_page, paging_state) = query.execute_by_ref(session).await?;
+
+        let next = match paging_state {
+            PagingStateResponse::HasMorePages { state } => Some((query, state, 1)),
+            PagingStateResponse::NoMorePages => None,
+        };
+
+        let next_pages = stream::try_unfold(next, move |next| async move {
+            let Some((mut query, paging_state, fetched)) = next else {
+                return Ok(None);
+            };
+
+            if max_pages.is_some_and(|max_pages| fetched >= max_pages) {
+                return Err(CharybdisError::MaxPagesExceededError(query.error_context(), fetched));
+            }
+
+            query.paging_state = paging_state;
+
+            let (page, paging_state) = query.execute_by_ref(session).await?;
+
+            let next = match paging_state {
+                PagingStateResponse::HasMorePages { state } => Some((query, state, fetched + 1)),
+                PagingStateResponse::NoMorePages => None,
+            };
+
+            Ok(Some((page, next)))
+        });
+
+        let models = stream::once(future::ready(Ok(first_page)))
+            .chain(next_pages)
+            .map_ok(stream::iter)
+            .try_flatten()
+            .boxed();
+
+        Ok(CharybdisAutoPagedStream::from(models))
+    }
+}
+
+impl<'a, K: SerializeValue + Clone, M: BaseModel> CharybdisQuery<'a, (Vec<K>,), M, ModelStream<M>> {
+    /// Executes multi-get of generated `find_by_<partition_key>_in` as separate query per partition, at most
+    /// `concurrency` at a time, instead of single `IN` query, see [PerPartitionQuery].
+    ///
+    /// # Panics
+    /// If query is not `find_by_<partition_key>_in` of model with single-column partition key, or its statement
+    /// was modified, e.g. by [Self::and_where].
+    pub fn per_partition(self, concurrency: usize) -> PerPartitionQuery<'a, K, M> {
+        let statement = self.partition_statement.unwrap_or_else(|| {
+            panic!(
+                "per_partition is supported only by unmodified find_by_<partition_key>_in of single-column \
+                partition key, got: {}",
+                self.inner.contents
+            )
+        });
+
+        PerPartitionQuery {
+            query: self,
+            statement,
+            concurrency: concurrency.max(1),
+        }
+    }
+}
+
+/// Multi-get that queries each partition separately. Coordinator of `IN` query has to wait for replicas of all
+/// requested partitions and hold their rows, while per partition queries are token aware and spread across
+/// replicas. Rows of partitions are merged into single stream, so they are not ordered by partition.
+/// ```rust ignore
+/// let users = User::find_by_id_in(ids)
+///     .consistency(Consistency::LocalOne)
+///     .per_partition(16)
+///     .execute(&session)
+///     .await?
+///     .try_collect()
+///     .await?;
+/// ```
+pub struct PerPartitionQuery<'a, K: SerializeValue, M: BaseModel> {
+    query: CharybdisQuery<'a, (Vec<K>,), M, ModelStream<M>>,
+    statement: &'static str,
+    concurrency: usize,
+}
+
+impl<K: SerializeValue + Clone + Sync, M: BaseModel> PerPartitionQuery<'_, K, M> {
+    /// Fetches first page of each partition, subsequent pages are fetched as stream is consumed. Query with
+    /// values that were not passed as keys, e.g. extended by [CharybdisQuery::and_where], is executed as
+    /// single `IN` query.
+    pub async fn execute(self, session: &impl CharybdisSession) -> Result<CharybdisModelStream<M>, CharybdisError> {
+        let keys = match &self.query.values {
+            QueryValue::Owned((keys,)) if self.query.error.is_none() => keys,
+            _ => return self.query.execute(session).await,
+        };
+
+        let streams = stream::iter(keys)
+            .map(|key| {
+                let query = self.query.with_statement(self.statement, (key.clone(),));
+
+                async move { query.execute(session).await }
+            })
+            .buffer_unordered(self.concurrency)
+            .try_collect::<Vec<CharybdisModelStream<M>>>()
+            .await?;
+
+        Ok(CharybdisModelStream::merge(streams))
     }
 }
 
@@ -304,23 +1224,97 @@ macro_rules! delegate_inner_query_methods {
     };
 }
 
+/// Extension used by callback query, either passed by caller or resolved from
+/// [Extensions](crate::extensions::Extensions). `Registered(None)` means it was not registered, which is reported
+/// on execute.
+pub(crate) enum CbExtension<'a, E> {
+    Borrowed(&'a E),
+    Registered(Option<Arc<E>>),
+}
+
+impl<E> CbExtension<'_, E> {
+    pub(crate) fn get(&self) -> Result<&E, CharybdisError> {
+        match self {
+            CbExtension::Borrowed(extension) => Ok(*extension),
+            CbExtension::Registered(Some(extension)) => Ok(extension.as_ref()),
+            CbExtension::Registered(None) => {
+                Err(CharybdisError::ExtensionNotRegisteredError(std::any::type_name::<E>()))
+            }
+        }
+    }
+}
+
 pub struct CharybdisCbQuery<'a, M: Callbacks, CbA: CallbackAction<M>, Val: SerializeRow> {
-    inner: CharybdisQuery<'a, Val, M, ModelMutation>,
-    model: &'a mut M,
-    extension: &'a M::Extension,
-    _phantom: std::marker::PhantomData<CbA>,
+    pub(crate) inner: CharybdisQuery<'a, Val, M, ModelMutation>,
+    pub(crate) model: &'a mut M,
+    pub(crate) extension: CbExtension<'a, M::Extension>,
+    pub(crate) bind_model_values: bool,
+    _phantom: std::marker::PhantomData<fn() -> CbA>,
 }
 
 impl<'a, M: Callbacks, CbA: CallbackAction<M>, Val: SerializeRow> CharybdisCbQuery<'a, M, CbA, Val> {
     pub(crate) fn new(query: &'static str, model: &'a mut M, extension: &'a M::Extension) -> Self {
         Self {
-            inner: CharybdisQuery::new(query, QueryValue::default()),
+            inner: CharybdisQuery::new(query, QueryValue::default()).authorized(),
+            model,
+            extension: CbExtension::Borrowed(extension),
+            bind_model_values: true,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Creates callback query with extension resolved from [Extensions](crate::extensions::Extensions).
+    pub(crate) fn registered(query: &'static str, model: &'a mut M) -> Self
+    where
+        M::Extension: Send + Sync + 'static,
+    {
+        Self {
+            inner: CharybdisQuery::new(query, QueryValue::default()).authorized(),
+            model,
+            extension: CbExtension::Registered(Extensions::get::<M::Extension>()),
+            bind_model_values: true,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Creates callback query with values bound upfront, instead of values derived from the model by `CbA`.
+    /// Used by generated `push_<field>_cb` and `pull_<field>_cb` methods.
+    pub fn with_values(
+        query: &'static str,
+        values: QueryValue<'a, Val, M>,
+        model: &'a mut M,
+        extension: &'a M::Extension,
+    ) -> Self {
+        Self {
+            inner: CharybdisQuery::new(query, values).authorized(),
+            model,
+            extension: CbExtension::Borrowed(extension),
+            bind_model_values: false,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Same as `with_values`, with extension resolved from [Extensions](crate::extensions::Extensions).
+    /// Used by generated `push_<field>_cb_registered` and `pull_<field>_cb_registered` methods.
+    pub fn with_registered_values(query: &'static str, values: QueryValue<'a, Val, M>, model: &'a mut M) -> Self
+    where
+        M::Extension: Send + Sync + 'static,
+    {
+        Self {
+            inner: CharybdisQuery::new(query, values).authorized(),
             model,
-            extension,
+            extension: CbExtension::Registered(Extensions::get::<M::Extension>()),
+            bind_model_values: false,
             _phantom: Default::default(),
         }
     }
 
+    pub(crate) fn check_applied(mut self, check_applied: bool) -> Self {
+        self.inner = self.inner.check_applied(check_applied);
+
+        self
+    }
+
     delegate_inner_query_methods! {
         page_size(page_size: i32),
         consistency(consistency: Consistency),
@@ -333,16 +1327,36 @@ impl<'a, M: Callbacks, CbA: CallbackAction<M>, Val: SerializeRow> CharybdisCbQue
         retry_policy(retry_policy: Option<Arc<dyn RetryPolicy>>),
         history_listener(history_listener: Arc<dyn HistoryListener>),
         remove_history_listener(),
-        profile_handle(profile_handle: Option<ExecutionProfileHandle>)
+        profile_handle(profile_handle: Option<ExecutionProfileHandle>),
+        profile(name: &str),
+        local_dc(datacenter: &str),
+        speculative_execution(policy: Arc<dyn SpeculativeExecutionPolicy>),
+        with_deleted(),
+        retry(backoff: ExponentialBackoff)
     }
 
-    pub async fn execute(self, session: &CachingSession) -> Result<QueryResult, M::Error> {
-        CbA::before_execute(self.model, session, self.extension).await?;
+    pub async fn execute(self, session: &CachingSession) -> Result<QueryResult, M::Error>
+    where
+        Val: Sync,
+    {
+        let extension = self.extension.get()?;
+
+        CbA::before_execute(self.model, session, extension).await?;
+
+        let query = if self.bind_model_values {
+            self.inner.values(CbA::query_value(self.model))
+        } else {
+            self.inner
+        };
+        let next_version = query.next_version();
 
-        let query_value = CbA::query_value(self.model);
-        let res = self.inner.values(query_value).execute(session).await?;
+        let res = query.execute(session).await?;
+
+        if let Some(next_version) = next_version {
+            self.model.increment_version(next_version);
+        }
 
-        CbA::after_execute(self.model, session, self.extension).await?;
+        CbA::after_execute(self.model, session, extension).await?;
 
         Ok(res)
     }
@@ -0,0 +1,103 @@
+use futures::future::try_join_all;
+
+use crate::driver::Query;
+use crate::errors::{CharybdisError, ErrorContext};
+use crate::session::CharybdisSession;
+
+/// Kind of registered model, i.e. macro it was defined with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelKind {
+    Table,
+    MaterializedView,
+    Udt,
+}
+
+#[derive(Debug)]
+pub struct FieldDefinition {
+    pub name: &'static str,
+    pub cql_type: &'static str,
+    pub is_static: bool,
+    pub renamed_from: Option<&'static str>,
+}
+
+#[derive(Debug)]
+pub struct CustomIndexDefinition {
+    pub target: &'static str,
+    pub class_name: &'static str,
+    pub options: &'static [(&'static str, &'static str)],
+}
+
+/// Schema of model as declared in code. It's submitted to the global registry by `charybdis_model`,
+/// `charybdis_view_model` and `charybdis_udt_model` macros at link time, so schema of all models linked
+/// into the binary is available without parsing source files. Table models also register their
+/// lookup tables. Partial models are not registered.
+/// ```rust ignore
+/// for model in charybdis::registry::models() {
+///     println!("{:?} {}", model.kind, model.table_name);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ModelDefinition {
+    pub kind: ModelKind,
+    pub table_name: &'static str,
+    pub type_name: &'static str,
+    pub base_table: &'static str,
+    pub fields: &'static [FieldDefinition],
+    pub partition_keys: &'static [&'static str],
+    pub clustering_keys: &'static [&'static str],
+    /// declared order of clustering keys, e.g. `[("created_at", "DESC")]`
+    pub clustering_order: &'static [(&'static str, &'static str)],
+    pub global_secondary_indexes: &'static [&'static str],
+    pub local_secondary_indexes: &'static [&'static str],
+    pub custom_indexes: &'static [CustomIndexDefinition],
+    pub table_options: Option<&'static str>,
+    pub renamed_from: Option<&'static str>,
+}
+
+inventory::collect!(ModelDefinition);
+
+/// All registered models, in unspecified order.
+pub fn models() -> impl Iterator<Item = &'static ModelDefinition> {
+    inventory::iter::<ModelDefinition>.into_iter()
+}
+
+/// Statements generated for model, submitted to the registry by `charybdis_model` and `charybdis_view_model`
+/// macros. Unlike [ModelDefinition], partial and lookup models submit their statements as well.
+#[derive(Debug)]
+pub struct ModelStatements {
+    pub model: &'static str,
+    pub statements: &'static [&'static str],
+}
+
+inventory::collect!(ModelStatements);
+
+/// Prepares given statements concurrently. Used by generated `prepare_all` functions.
+pub async fn prepare_statements(
+    session: &impl CharybdisSession,
+    statements: &[&'static str],
+) -> Result<(), CharybdisError> {
+    try_join_all(statements.iter().map(|&statement| async move {
+        session
+            .prepare(Query::new(statement))
+            .await
+            .map_err(|e| CharybdisError::QueryError(ErrorContext::new(statement), e))
+    }))
+    .await?;
+
+    Ok(())
+}
+
+/// Prepares statements of all models linked into the binary, so first requests in production don't pay
+/// for preparation. It's meant to be called at startup, after session is built:
+/// ```rust ignore
+/// let session = CharybdisSessionConfig::new(["127.0.0.1:9042"]).keyspace("app").build().await?;
+///
+/// charybdis::prepare_registered_models(&session).await?;
+/// ```
+pub async fn prepare_registered_models(session: &impl CharybdisSession) -> Result<(), CharybdisError> {
+    for model in inventory::iter::<ModelStatements> {
+        prepare_statements(session, model.statements).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use scylla::retry_policy::{QueryInfo, RetryDecision, RetryPolicy, RetrySession};
+use scylla::transport::errors::DbError;
+
+use crate::driver::QueryError;
+use crate::errors::CharybdisError;
+
+/// Retry configuration for [CharybdisQuery::retry](crate::query::CharybdisQuery::retry).
+/// Unlike driver level `RetryPolicy`, whole execute is retried with delay, including preparation. Streams of
+/// `find` queries fetch pages in background as rows are consumed, so each of their pages, including the first one,
+/// is retried by driver on next node without delay, up to the same number of attempts. Only idempotent statements
+/// are retried, and only on timeout, unavailable and overloaded errors.
+/// ```rust ignore
+/// let post = Post::find_by_primary_key_value((category_id, id))
+///     .idempotent(true)
+///     .retry(ExponentialBackoff::new(Duration::from_millis(50), Duration::from_secs(2), 5))
+///     .execute(session)
+///     .await?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct ExponentialBackoff {
+    initial_delay: Duration,
+    max_delay: Duration,
+    max_attempts: u32,
+    multiplier: f64,
+    jitter: bool,
+}
+
+impl ExponentialBackoff {
+    /// `max_attempts` includes first execution, so `max_attempts = 3` means up to 2 retries.
+    pub fn new(initial_delay: Duration, max_delay: Duration, max_attempts: u32) -> Self {
+        Self {
+            initial_delay,
+            max_delay,
+            max_attempts,
+            multiplier: 2.0,
+            jitter: true,
+        }
+    }
+
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// With jitter enabled (default), each delay is randomized in range `[delay / 2, delay]`,
+    /// so clients failing at the same time don't retry in lockstep.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub(crate) fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Driver retry policy of pages fetched in background, see [PageRetryPolicy].
+    pub(crate) fn page_retry_policy(&self) -> Arc<dyn RetryPolicy> {
+        Arc::new(PageRetryPolicy {
+            max_attempts: self.max_attempts,
+        })
+    }
+
+    /// Delay before retry following given failed attempt, starting at 1.
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let delay = self.initial_delay.as_secs_f64() * self.multiplier.powi(exponent);
+        let delay = Duration::from_secs_f64(delay.min(self.max_delay.as_secs_f64()));
+
+        if self.jitter {
+            let random = uuid::Uuid::new_v4().as_u64_pair().0 as f64 / u64::MAX as f64;
+
+            delay.mul_f64(0.5 + random / 2.0)
+        } else {
+            delay
+        }
+    }
+}
+
+/// Errors that are likely transient, so repeating the same statement may succeed.
+pub(crate) fn is_retryable(error: &CharybdisError) -> bool {
+    match error {
+        CharybdisError::QueryError(_, e) => is_retryable_query_error(e),
+        _ => false,
+    }
+}
+
+fn is_retryable_query_error(error: &QueryError) -> bool {
+    matches!(
+        error,
+        QueryError::TimeoutError
+            | QueryError::RequestTimeout(_)
+            | QueryError::DbError(
+                DbError::Unavailable { .. }
+                    | DbError::ReadTimeout { .. }
+                    | DbError::WriteTimeout { .. }
+                    | DbError::Overloaded,
+                _
+            )
+    )
+}
+
+/// Retries each page fetched by driver on the same errors as [ExponentialBackoff], as driver can't wait between
+/// attempts, retry is sent to next node.
+#[derive(Debug)]
+struct PageRetryPolicy {
+    max_attempts: u32,
+}
+
+impl RetryPolicy for PageRetryPolicy {
+    fn new_session(&self) -> Box<dyn RetrySession> {
+        Box::new(PageRetrySession {
+            max_attempts: self.max_attempts,
+            attempt: 1,
+        })
+    }
+}
+
+struct PageRetrySession {
+    max_attempts: u32,
+    attempt: u32,
+}
+
+impl RetrySession for PageRetrySession {
+    fn decide_should_retry(&mut self, query_info: QueryInfo) -> RetryDecision {
+        if query_info.is_idempotent && self.attempt < self.max_attempts && is_retryable_query_error(query_info.error) {
+            self.attempt += 1;
+
+            return RetryDecision::RetryNextNode(None);
+        }
+
+        RetryDecision::DontRetry
+    }
+
+    fn reset(&mut self) {
+        self.attempt = 1;
+    }
+}
@@ -0,0 +1,353 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::hash::Hash;
+use std::net::IpAddr;
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde_json::{Map, Value};
+#[cfg(feature = "time")]
+use time::format_description::well_known::Rfc3339;
+
+use crate::errors::CharybdisError;
+use crate::types::{Counter, Duration, Timeuuid, Uuid, Varint};
+
+///
+/// JSON representation of CQL values that doesn't depend on `serde` derives of the model:
+/// - `blob` is base64 encoded string
+/// - `uuid`, `timeuuid`, `inet`, `decimal` and `varint` are strings
+/// - `duration` is object with `months`, `days` and `nanoseconds`
+/// - `timestamp` is RFC 3339 string, `date` and `time` are ISO 8601 strings
+/// - `list`, `set` and `tuple` are arrays
+/// - `map` is object, where non-string keys are stored in their JSON form, e.g. `{"1": "a"}`
+/// - UDTs and models are objects
+///
+/// It's implemented by `#[charybdis_model]`, `#[charybdis_view_model]` and `#[charybdis_udt_model]`,
+/// that also generate `to_json` and `from_json` methods on models.
+///
+pub trait CqlJson: Sized {
+    fn to_json_value(&self) -> Value;
+
+    fn from_json_value(value: Value) -> Result<Self, serde_json::Error>;
+
+    fn to_json_string(&self) -> Result<String, CharybdisError> {
+        serde_json::to_string(&self.to_json_value()).map_err(CharybdisError::JsonError)
+    }
+
+    fn from_json_str(json: &str) -> Result<Self, CharybdisError> {
+        let value = serde_json::from_str(json).map_err(CharybdisError::JsonError)?;
+
+        Self::from_json_value(value).map_err(CharybdisError::JsonError)
+    }
+}
+
+#[doc(hidden)]
+pub fn json_error(message: impl Display) -> serde_json::Error {
+    serde::de::Error::custom(message)
+}
+
+/// Prefixes error with field name, so errors of nested values point to the failing field, e.g. `address.zip`.
+#[doc(hidden)]
+pub fn json_field_error(field: &str, error: serde_json::Error) -> serde_json::Error {
+    json_error(format!("{}: {}", field, error))
+}
+
+#[doc(hidden)]
+pub fn json_object(value: Value, type_name: &str) -> Result<Map<String, Value>, serde_json::Error> {
+    match value {
+        Value::Object(object) => Ok(object),
+        other => Err(json_error(format!("expected object for {}, got {}", type_name, other))),
+    }
+}
+
+macro_rules! impl_cql_json_via_serde {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl CqlJson for $ty {
+                fn to_json_value(&self) -> Value {
+                    serde_json::to_value(self).unwrap_or_default()
+                }
+
+                fn from_json_value(value: Value) -> Result<Self, serde_json::Error> {
+                    serde_json::from_value(value)
+                }
+            }
+        )*
+    };
+}
+
+// u8 is intentionally omitted, as `Vec<u8>` is `blob`
+impl_cql_json_via_serde!(
+    bool,
+    i8,
+    i16,
+    i32,
+    i64,
+    f32,
+    f64,
+    String,
+    Uuid,
+    Timeuuid,
+    IpAddr,
+    BigDecimal,
+    NaiveDate,
+    NaiveTime,
+    DateTime<Utc>,
+    Counter,
+    Duration,
+);
+
+/// Values of `time` crate have the same representation as their `chrono` counterparts, timestamps are
+/// converted to UTC.
+#[cfg(feature = "time")]
+impl CqlJson for time::OffsetDateTime {
+    fn to_json_value(&self) -> Value {
+        self.to_offset(time::UtcOffset::UTC)
+            .format(&Rfc3339)
+            .map(Value::String)
+            .unwrap_or_default()
+    }
+
+    fn from_json_value(value: Value) -> Result<Self, serde_json::Error> {
+        match value {
+            Value::String(timestamp) => time::OffsetDateTime::parse(&timestamp, &Rfc3339).map_err(json_error),
+            other => Err(json_error(format!(
+                "expected RFC 3339 string for timestamp, got {}",
+                other
+            ))),
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+macro_rules! impl_cql_json_via_time_format {
+    ($($ty:ty => $format:literal, $cql_type:literal),* $(,)?) => {
+        $(
+            impl CqlJson for $ty {
+                fn to_json_value(&self) -> Value {
+                    time::format_description::parse($format)
+                        .ok()
+                        .and_then(|format| self.format(&format).ok())
+                        .map(Value::String)
+                        .unwrap_or_default()
+                }
+
+                fn from_json_value(value: Value) -> Result<Self, serde_json::Error> {
+                    let format = time::format_description::parse($format).map_err(json_error)?;
+
+                    match value {
+                        Value::String(value) => <$ty>::parse(&value, &format).map_err(json_error),
+                        other => Err(json_error(format!("expected ISO 8601 string for {}, got {}", $cql_type, other))),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "time")]
+impl_cql_json_via_time_format!(
+    time::Date => "[year]-[month]-[day]", "date",
+    time::Time => "[hour]:[minute]:[second].[subsecond]", "time",
+);
+
+// serde represents BigInt as array of digits
+impl CqlJson for Varint {
+    fn to_json_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+
+    fn from_json_value(value: Value) -> Result<Self, serde_json::Error> {
+        match value {
+            Value::String(varint) => varint.parse().map_err(json_error),
+            Value::Number(number) => number.to_string().parse().map_err(json_error),
+            other => Err(json_error(format!("expected string for varint, got {}", other))),
+        }
+    }
+}
+
+impl CqlJson for Vec<u8> {
+    fn to_json_value(&self) -> Value {
+        Value::String(base64_encode(self))
+    }
+
+    fn from_json_value(value: Value) -> Result<Self, serde_json::Error> {
+        match value {
+            Value::String(encoded) => base64_decode(&encoded),
+            other => Err(json_error(format!("expected base64 string for blob, got {}", other))),
+        }
+    }
+}
+
+impl<T: CqlJson> CqlJson for Option<T> {
+    fn to_json_value(&self) -> Value {
+        match self {
+            Some(value) => value.to_json_value(),
+            None => Value::Null,
+        }
+    }
+
+    fn from_json_value(value: Value) -> Result<Self, serde_json::Error> {
+        match value {
+            Value::Null => Ok(None),
+            value => T::from_json_value(value).map(Some),
+        }
+    }
+}
+
+fn json_array(value: Value) -> Result<Vec<Value>, serde_json::Error> {
+    match value {
+        Value::Array(values) => Ok(values),
+        other => Err(json_error(format!("expected array, got {}", other))),
+    }
+}
+
+impl<T: CqlJson> CqlJson for Vec<T> {
+    fn to_json_value(&self) -> Value {
+        Value::Array(self.iter().map(CqlJson::to_json_value).collect())
+    }
+
+    fn from_json_value(value: Value) -> Result<Self, serde_json::Error> {
+        json_array(value)?.into_iter().map(T::from_json_value).collect()
+    }
+}
+
+impl<T: CqlJson + Eq + Hash> CqlJson for HashSet<T> {
+    fn to_json_value(&self) -> Value {
+        Value::Array(self.iter().map(CqlJson::to_json_value).collect())
+    }
+
+    fn from_json_value(value: Value) -> Result<Self, serde_json::Error> {
+        json_array(value)?.into_iter().map(T::from_json_value).collect()
+    }
+}
+
+impl<K: CqlJson + Eq + Hash, V: CqlJson> CqlJson for HashMap<K, V> {
+    fn to_json_value(&self) -> Value {
+        let object = self
+            .iter()
+            .map(|(key, value)| {
+                let key = match key.to_json_value() {
+                    Value::String(key) => key,
+                    key => key.to_string(),
+                };
+
+                (key, value.to_json_value())
+            })
+            .collect();
+
+        Value::Object(object)
+    }
+
+    fn from_json_value(value: Value) -> Result<Self, serde_json::Error> {
+        json_object(value, "map")?
+            .into_iter()
+            .map(|(key, value)| -> Result<(K, V), serde_json::Error> {
+                // string keys are tried first, other keys are stored in their JSON form
+                let key = K::from_json_value(Value::String(key.clone())).or_else(|e| {
+                    serde_json::from_str::<Value>(&key)
+                        .map_err(|_| e)
+                        .and_then(K::from_json_value)
+                })?;
+
+                Ok((key, V::from_json_value(value)?))
+            })
+            .collect()
+    }
+}
+
+impl<T1: CqlJson, T2: CqlJson> CqlJson for (T1, T2) {
+    fn to_json_value(&self) -> Value {
+        Value::Array(vec![self.0.to_json_value(), self.1.to_json_value()])
+    }
+
+    fn from_json_value(value: Value) -> Result<Self, serde_json::Error> {
+        let mut values = json_array(value)?.into_iter();
+
+        match (values.next(), values.next(), values.next()) {
+            (Some(first), Some(second), None) => Ok((T1::from_json_value(first)?, T2::from_json_value(second)?)),
+            _ => Err(json_error("expected array of 2 elements for tuple")),
+        }
+    }
+}
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64_ALPHABET[((n >> (18 - 6 * i)) & 0x3F) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+fn base64_decode(encoded: &str) -> Result<Vec<u8>, serde_json::Error> {
+    let encoded = encoded.trim_end_matches('=').as_bytes();
+    let mut bytes = Vec::with_capacity(encoded.len() * 3 / 4);
+
+    for chunk in encoded.chunks(4) {
+        if chunk.len() == 1 {
+            return Err(json_error("invalid base64 length"));
+        }
+
+        let mut n = 0u32;
+
+        for (i, c) in chunk.iter().enumerate() {
+            let sextet = BASE64_ALPHABET
+                .iter()
+                .position(|a| a == c)
+                .ok_or_else(|| json_error(format!("invalid base64 character: {}", *c as char)))?;
+
+            n |= (sextet as u32) << (18 - 6 * i);
+        }
+
+        bytes.extend_from_slice(&n.to_be_bytes()[1..chunk.len()]);
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_round_trips_all_padding_lengths() {
+        let cases: [(&[u8], &str); 5] = [
+            (b"", ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+        ];
+
+        for (bytes, encoded) in cases {
+            assert_eq!(base64_encode(bytes), encoded);
+            assert_eq!(base64_decode(encoded).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn base64_round_trips_all_byte_values() {
+        let bytes: Vec<u8> = (0..=255).collect();
+
+        assert_eq!(base64_decode(&base64_encode(&bytes)).unwrap(), bytes);
+    }
+
+    #[test]
+    fn base64_rejects_invalid_input() {
+        assert!(base64_decode("Z").is_err());
+        assert!(base64_decode("Zm9v!").is_err());
+    }
+}
@@ -1,5 +1,8 @@
+mod cql_json;
 mod from_json;
 mod to_json;
 
+pub use cql_json::{json_error, json_field_error, json_object, CqlJson};
 pub use from_json::FromJson;
+pub use serde_json::{Error as JsonError, Map as JsonMap, Value as JsonValue};
 pub use to_json::ToJson;
@@ -0,0 +1,140 @@
+use std::future::Future;
+use std::time::Duration;
+
+use futures::stream::BoxStream;
+use scylla::execution_profile::ExecutionProfileHandle;
+use scylla::frame::response::result::Row;
+use scylla::serialize::batch::BatchValues;
+use scylla::serialize::row::SerializeRow;
+use scylla::statement::{Consistency, PagingState, PagingStateResponse};
+use scylla::transport::Compression;
+
+use crate::driver::{self, Batch, CachingSession, Query, QueryError, QueryResult};
+use crate::errors::CharybdisError;
+
+/// Rows of query executed with automatic paging.
+pub type RowStream = BoxStream<'static, Result<Row, QueryError>>;
+
+/// Session that executes statements of `CharybdisQuery` and batches. It's implemented for `CachingSession`,
+/// and can be implemented by test doubles, so code using models can be unit tested without live cluster,
+/// e.g. with `MockSession` from `charybdis-testing`.
+/// ```rust ignore
+/// async fn find_user<S: CharybdisSession>(session: &S, id: Uuid) -> Result<User, CharybdisError> {
+///     User::find_by_id(id).execute(session).await
+/// }
+/// ```
+/// Returned futures are `Send`, so queries, sinks and streams of generic session can be moved between threads,
+/// e.g. by `tokio::spawn`. Implementations can still use `async fn`.
+pub trait CharybdisSession: Sync {
+    fn execute_unpaged(
+        &self,
+        statement: Query,
+        values: impl SerializeRow + Send,
+    ) -> impl Future<Output = Result<QueryResult, QueryError>> + Send;
+
+    fn execute_iter(
+        &self,
+        statement: Query,
+        values: impl SerializeRow + Send,
+    ) -> impl Future<Output = Result<RowStream, QueryError>> + Send;
+
+    fn execute_single_page(
+        &self,
+        statement: Query,
+        values: impl SerializeRow + Send,
+        paging_state: PagingState,
+    ) -> impl Future<Output = Result<(QueryResult, PagingStateResponse), QueryError>> + Send;
+
+    fn batch(
+        &self,
+        batch: &Batch,
+        values: impl BatchValues + Send,
+    ) -> impl Future<Output = Result<QueryResult, QueryError>> + Send;
+
+    /// Prepares statement ahead of its first execution. Sessions without statement cache don't need to
+    /// implement it.
+    fn prepare(&self, _statement: Query) -> impl Future<Output = Result<(), QueryError>> + Send {
+        async { Ok(()) }
+    }
+
+    /// Default execution profile of the session, which is base of profiles derived by
+    /// [CharybdisQuery::speculative_execution](crate::query::CharybdisQuery::speculative_execution). Sessions
+    /// without execution profiles don't need to implement it.
+    fn default_profile_handle(&self) -> Option<&ExecutionProfileHandle> {
+        None
+    }
+}
+
+/// Builds `CachingSession` with charybdis defaults in one call:
+/// - statement cache of 1000 prepared statements
+/// - `LocalQuorum` consistency
+/// - `Lz4` compression
+/// - 5 seconds connection timeout
+///
+/// If keyspace is set, session is switched to it, and `KeyspaceNotFoundError` is returned if it doesn't exist.
+/// ```rust ignore
+/// let session = CharybdisSessionConfig::new(["127.0.0.1:9042"])
+///     .keyspace("app")
+///     .consistency(Consistency::One)
+///     .build()
+///     .await?;
+/// ```
+pub struct CharybdisSessionConfig {
+    pub(crate) known_nodes: Vec<String>,
+    pub(crate) keyspace: Option<String>,
+    pub(crate) cache_size: usize,
+    pub(crate) consistency: Consistency,
+    pub(crate) compression: Option<Compression>,
+    pub(crate) connection_timeout: Duration,
+    pub(crate) credentials: Option<(String, String)>,
+}
+
+impl CharybdisSessionConfig {
+    pub fn new(known_nodes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            known_nodes: known_nodes.into_iter().map(Into::into).collect(),
+            keyspace: None,
+            cache_size: 1000,
+            consistency: Consistency::LocalQuorum,
+            compression: Some(Compression::Lz4),
+            connection_timeout: Duration::from_secs(5),
+            credentials: None,
+        }
+    }
+
+    pub fn keyspace(mut self, keyspace: impl Into<String>) -> Self {
+        self.keyspace = Some(keyspace.into());
+        self
+    }
+
+    /// Max number of prepared statements kept in cache.
+    pub fn cache_size(mut self, cache_size: usize) -> Self {
+        self.cache_size = cache_size;
+        self
+    }
+
+    /// Default consistency of queries that don't set their own.
+    pub fn consistency(mut self, consistency: Consistency) -> Self {
+        self.consistency = consistency;
+        self
+    }
+
+    pub fn compression(mut self, compression: Option<Compression>) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    pub fn connection_timeout(mut self, connection_timeout: Duration) -> Self {
+        self.connection_timeout = connection_timeout;
+        self
+    }
+
+    pub fn user(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.credentials = Some((username.into(), password.into()));
+        self
+    }
+
+    pub async fn build(self) -> Result<CachingSession, CharybdisError> {
+        driver::connect(self).await
+    }
+}
@@ -0,0 +1,168 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use futures::{FutureExt, Sink, StreamExt};
+
+use crate::batch::CharybdisModelBatch;
+use crate::errors::CharybdisError;
+use crate::model::Model;
+use crate::operations::Insert;
+use crate::session::CharybdisSession;
+
+#[derive(Debug, Clone, Copy)]
+pub struct InsertSinkOptions {
+    chunk_size: usize,
+    max_in_flight: usize,
+    unscoped: bool,
+}
+
+impl Default for InsertSinkOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: 1,
+            max_in_flight: 16,
+            unscoped: false,
+        }
+    }
+}
+
+impl InsertSinkOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of models inserted by single unlogged batch. With default `1`, each model is inserted by
+    /// its own statement.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Maximum number of requests executed concurrently. Sink is not ready to accept next model while
+    /// limit is reached.
+    pub fn max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = max_in_flight.max(1);
+        self
+    }
+
+    /// Inserts models with `tenant_key` without limiting them to tenant, see
+    /// [TenantScoped](crate::tenancy::TenantScoped). Inserts of such models fail unless sink is explicitly unscoped.
+    pub fn unscoped(mut self) -> Self {
+        self.unscoped = true;
+        self
+    }
+}
+
+/// Sink that inserts models written to it, with bounded number of concurrent requests. It's write-side
+/// counterpart of `CharybdisModelStream`, so data can be moved between tables with `forward`:
+/// ```rust ignore
+/// let options = InsertSinkOptions::new().chunk_size(20).max_in_flight(8);
+///
+/// Post::find_by_category_id(category_id)
+///     .execute(&session)
+///     .await?
+///     .map(|post| post.map(ArchivedPost::from))
+///     .forward(ArchivedPost::insert_sink(&session, options))
+///     .await?;
+/// ```
+/// Flushing or closing the sink waits for all pending inserts. First failed insert is returned as sink
+/// error, and models sent after it might not be inserted.
+pub struct InsertSink<'a, M: Model, S: CharybdisSession> {
+    session: &'a S,
+    options: InsertSinkOptions,
+    buffer: Vec<M>,
+    in_flight: FuturesUnordered<BoxFuture<'a, Result<(), CharybdisError>>>,
+}
+
+impl<'a, M: Model + 'a, S: CharybdisSession> InsertSink<'a, M, S> {
+    pub fn new(session: &'a S, options: InsertSinkOptions) -> Self {
+        Self {
+            session,
+            options,
+            buffer: Vec::with_capacity(options.chunk_size),
+            in_flight: FuturesUnordered::new(),
+        }
+    }
+
+    fn send_buffer(&mut self) {
+        let session = self.session;
+        let unscoped = self.options.unscoped;
+        let models = std::mem::replace(&mut self.buffer, Vec::with_capacity(self.options.chunk_size));
+
+        let insert = async move {
+            if let [model] = models.as_slice() {
+                let query = model.insert();
+                let query = if unscoped { query.unscoped() } else { query };
+
+                return query.execute(session).await.map(|_| ());
+            }
+
+            let mut batch = CharybdisModelBatch::<M, M>::unlogged();
+
+            if unscoped {
+                batch = batch.unscoped();
+            }
+
+            batch.append_inserts(&models);
+
+            batch.execute(session).await.map(|_| ())
+        };
+
+        self.in_flight.push(insert.boxed());
+    }
+
+    /// Polls pending inserts until fewer than `capacity` are left, or one of them fails.
+    fn poll_in_flight(&mut self, cx: &mut Context<'_>, capacity: usize) -> Poll<Result<(), CharybdisError>> {
+        loop {
+            match self.in_flight.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(()))) => continue,
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Err(e)),
+                Poll::Ready(None) => return Poll::Ready(Ok(())),
+                Poll::Pending if self.in_flight.len() < capacity => return Poll::Ready(Ok(())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<'a, M: Model + 'a, S: CharybdisSession> Sink<M> for InsertSink<'a, M, S> {
+    type Error = CharybdisError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let max_in_flight = self.options.max_in_flight;
+
+        self.get_mut().poll_in_flight(cx, max_in_flight)
+    }
+
+    fn start_send(self: Pin<&mut Self>, mut model: M) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+
+        model.generate_ids();
+        this.buffer.push(model);
+
+        if this.buffer.len() >= this.options.chunk_size {
+            this.send_buffer();
+        }
+
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+
+        if !this.buffer.is_empty() {
+            let max_in_flight = this.options.max_in_flight;
+
+            futures::ready!(this.poll_in_flight(cx, max_in_flight))?;
+            this.send_buffer();
+        }
+
+        this.poll_in_flight(cx, 0)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.poll_flush(cx)
+    }
+}
@@ -0,0 +1,344 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+use futures::future::try_join_all;
+use uuid::Uuid;
+
+use crate::driver::{self, CachingSession, PreparedStatement, Query};
+use crate::errors::{CharybdisError, ErrorContext};
+use crate::registry::ModelStatements;
+
+const SCHEMA_AGREEMENT_QUERY: &str = "schema agreement";
+
+/// Statement registered by model or pinned, as returned by [usage].
+#[derive(Debug, Clone)]
+pub struct StatementUsage {
+    pub statement: String,
+    /// model that generated statement, `None` for pinned statements built at runtime or written by hand
+    pub model: Option<&'static str>,
+    pub executions: u64,
+    pub last_executed: Option<Instant>,
+    /// statement was prepared on session, by execution, `prepare_all`, `prepare_registered_models` or pinning.
+    /// Statements that are not pinned may have been evicted from cache of `CachingSession` since.
+    pub prepared: bool,
+    pub pinned: bool,
+}
+
+/// Size of `CachingSession` cache compared to tracked statements prepared on it. Cache evicts arbitrary statement
+/// once it's full, so if more statements are prepared than it can hold, evicted ones are prepared again on next
+/// execution. Only registered and pinned statements are tracked, so statements built at runtime are not counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    pub capacity: usize,
+    /// distinct registered statements prepared on session that are not pinned
+    pub prepared: usize,
+    pub pinned: usize,
+}
+
+impl CacheStats {
+    pub fn is_evicting(&self) -> bool {
+        self.prepared > self.capacity
+    }
+}
+
+/// Usage of tracked statement, updated without locks on execution.
+#[derive(Default)]
+pub(crate) struct StatementEntry {
+    executions: AtomicU64,
+    /// nanoseconds since [started] of the last execution, `0` if statement was not executed
+    last_executed: AtomicU64,
+    prepared: AtomicBool,
+}
+
+impl StatementEntry {
+    pub(crate) fn record_execution(&self, is_ok: bool) {
+        let elapsed = started().elapsed().as_nanos().max(1) as u64;
+
+        self.executions.fetch_add(1, Ordering::Relaxed);
+        self.last_executed.store(elapsed, Ordering::Relaxed);
+
+        if is_ok {
+            self.prepared.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn last_executed(&self) -> Option<Instant> {
+        match self.last_executed.load(Ordering::Relaxed) {
+            0 => None,
+            elapsed => Some(*started() + Duration::from_nanos(elapsed)),
+        }
+    }
+}
+
+struct PinnedStatement {
+    prepared: PreparedStatement,
+    /// usage of pinned statement, if it's not registered
+    entry: Arc<StatementEntry>,
+}
+
+fn started() -> &'static Instant {
+    static STARTED: OnceLock<Instant> = OnceLock::new();
+
+    STARTED.get_or_init(Instant::now)
+}
+
+/// Statements registered by models, with their models. Registry is complete at link time, so entries are built
+/// once and looked up without locks.
+fn registered() -> &'static HashMap<&'static str, (&'static str, Arc<StatementEntry>)> {
+    static REGISTERED: OnceLock<HashMap<&'static str, (&'static str, Arc<StatementEntry>)>> = OnceLock::new();
+
+    REGISTERED.get_or_init(|| {
+        inventory::iter::<ModelStatements>
+            .into_iter()
+            .flat_map(|model| {
+                model
+                    .statements
+                    .iter()
+                    .map(|&statement| (statement, (model.model, Arc::default())))
+            })
+            .collect()
+    })
+}
+
+/// Number of pinned statements, so executions don't take lock of [pinned_statements] unless some are pinned.
+static PINNED_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+fn pinned_statements() -> &'static RwLock<HashMap<String, PinnedStatement>> {
+    static PINNED: OnceLock<RwLock<HashMap<String, PinnedStatement>>> = OnceLock::new();
+
+    PINNED.get_or_init(Default::default)
+}
+
+/// Usage entry of registered or pinned statement, looked up before statement is executed by driver. Statements
+/// built at runtime or written by hand are not tracked, unless they are pinned.
+pub(crate) fn tracked(statement: &str) -> Option<Arc<StatementEntry>> {
+    if let Some((_, entry)) = registered().get(statement) {
+        return Some(entry.clone());
+    }
+
+    if PINNED_COUNT.load(Ordering::Relaxed) == 0 {
+        return None;
+    }
+
+    pinned_statements()
+        .read()
+        .unwrap()
+        .get(statement)
+        .map(|pinned| pinned.entry.clone())
+}
+
+pub(crate) fn record_prepare(statement: &str) {
+    if let Some((_, entry)) = registered().get(statement) {
+        entry.prepared.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Pinned statement with options of given query, i.e. consistency, timeout, history listener...
+pub(crate) fn pinned(query: &mut Query) -> Option<PreparedStatement> {
+    if PINNED_COUNT.load(Ordering::Relaxed) == 0 {
+        return None;
+    }
+
+    let mut prepared = pinned_statements()
+        .read()
+        .unwrap()
+        .get(&query.contents)?
+        .prepared
+        .clone();
+
+    if let Some(consistency) = query.get_consistency() {
+        prepared.set_consistency(consistency);
+    }
+
+    prepared.set_serial_consistency(query.get_serial_consistency());
+    prepared.set_is_idempotent(query.get_is_idempotent());
+    prepared.set_tracing(query.get_tracing());
+    prepared.set_timestamp(query.get_timestamp());
+    prepared.set_request_timeout(query.get_request_timeout());
+    prepared.set_retry_policy(query.get_retry_policy().cloned());
+    prepared.set_execution_profile_handle(query.get_execution_profile_handle().cloned());
+    prepared.set_page_size(query.get_page_size());
+
+    if let Some(history_listener) = query.remove_history_listener() {
+        prepared.set_history_listener(history_listener);
+    }
+
+    Some(prepared)
+}
+
+/// True if any of given statements is pinned, so batch has to be cloned to execute their prepared statements.
+pub(crate) fn any_pinned<'b>(mut statements: impl Iterator<Item = &'b str>) -> bool {
+    if PINNED_COUNT.load(Ordering::Relaxed) == 0 {
+        return false;
+    }
+
+    let pinned = pinned_statements().read().unwrap();
+
+    statements.any(|statement| pinned.contains_key(statement))
+}
+
+fn usage_of(statement: &str, model: Option<&'static str>, entry: &StatementEntry, pinned: bool) -> StatementUsage {
+    StatementUsage {
+        statement: statement.to_string(),
+        model,
+        executions: entry.executions.load(Ordering::Relaxed),
+        last_executed: entry.last_executed(),
+        prepared: entry.prepared.load(Ordering::Relaxed),
+        pinned,
+    }
+}
+
+/// Statements registered by models and pinned statements, e.g. to find registered statements that were not
+/// prepared at startup:
+/// ```rust ignore
+/// for usage in charybdis::statements::usage() {
+///     if usage.model.is_some() && !usage.prepared {
+///         println!("{:?} is not prepared: {}", usage.model, usage.statement);
+///     }
+/// }
+/// ```
+/// Statements built at runtime or written by hand are not tracked unless they are pinned, so tracking doesn't
+/// grow with number of distinct statements.
+pub fn usage() -> Vec<StatementUsage> {
+    let pinned = pinned_statements().read().unwrap();
+
+    let mut usage = registered()
+        .iter()
+        .map(|(statement, (model, entry))| usage_of(statement, Some(*model), entry, pinned.contains_key(*statement)))
+        .collect::<Vec<StatementUsage>>();
+
+    usage.extend(
+        pinned
+            .iter()
+            .filter(|(statement, _)| !registered().contains_key(statement.as_str()))
+            .map(|(statement, pinned)| usage_of(statement, None, &pinned.entry, true)),
+    );
+
+    usage
+}
+
+pub fn cache_stats(session: &CachingSession) -> CacheStats {
+    let pinned = pinned_statements().read().unwrap();
+
+    let prepared = registered()
+        .iter()
+        .filter(|(statement, (_, entry))| !pinned.contains_key(*statement) && entry.prepared.load(Ordering::Relaxed))
+        .count();
+
+    CacheStats {
+        capacity: driver::cache_capacity(session),
+        prepared,
+        pinned: pinned.len(),
+    }
+}
+
+async fn prepare_pinned(session: &CachingSession, statement: &str) -> Result<(), CharybdisError> {
+    let prepared = driver::prepare_uncached(session, statement)
+        .await
+        .map_err(|e| CharybdisError::QueryError(ErrorContext::new("PREPARE"), e))?;
+
+    record_prepare(statement);
+
+    let mut pinned = pinned_statements().write().unwrap();
+
+    match pinned.get_mut(statement) {
+        Some(pinned) => pinned.prepared = prepared,
+        None => {
+            let entry = StatementEntry::default();
+            entry.prepared.store(true, Ordering::Relaxed);
+
+            pinned.insert(
+                statement.to_string(),
+                PinnedStatement {
+                    prepared,
+                    entry: Arc::new(entry),
+                },
+            );
+            PINNED_COUNT.store(pinned.len(), Ordering::Relaxed);
+        }
+    }
+
+    Ok(())
+}
+
+/// Prepares statements and keeps them outside of `CachingSession` cache, so hot statements are never evicted and
+/// prepared again on the hot path:
+/// ```rust ignore
+/// charybdis::statements::pin(&session, &[User::FIND_BY_PRIMARY_KEY_QUERY, Post::FIND_BY_PARTITION_KEY_QUERY]).await?;
+/// ```
+/// Pinned statements are executed with options of the query, including its history listener.
+pub async fn pin(session: &CachingSession, statements: &[&str]) -> Result<(), CharybdisError> {
+    try_join_all(statements.iter().map(|statement| prepare_pinned(session, statement))).await?;
+
+    Ok(())
+}
+
+/// Statement is executed through `CachingSession` cache again.
+pub fn unpin(statement: &str) {
+    let mut pinned = pinned_statements().write().unwrap();
+
+    pinned.remove(statement);
+    PINNED_COUNT.store(pinned.len(), Ordering::Relaxed);
+}
+
+/// Prepares pinned statements again, e.g. after schema change, so their metadata is up to date, or after nodes
+/// joined the cluster, so statements are prepared on them before first execution. Statements that are not pinned are
+/// prepared by `CachingSession` on their next execution after eviction.
+pub async fn reprepare(session: &CachingSession) -> Result<(), CharybdisError> {
+    let pinned = pinned_statements()
+        .read()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect::<Vec<String>>();
+
+    try_join_all(pinned.iter().map(|statement| prepare_pinned(session, statement))).await?;
+
+    Ok(())
+}
+
+/// Checks schema version and nodes of the cluster each `interval`, and calls [reprepare] once they change. It runs
+/// until error, so it's meant to be spawned at startup:
+/// ```rust ignore
+/// let session = Arc::new(session);
+///
+/// tokio::spawn({
+///     let session = session.clone();
+///
+///     async move { charybdis::statements::reprepare_on_changes(&session, Duration::from_secs(10)).await }
+/// });
+/// ```
+pub async fn reprepare_on_changes(session: &CachingSession, interval: Duration) -> Result<(), CharybdisError> {
+    let mut previous = cluster_state(session).await?;
+
+    loop {
+        tokio::time::sleep(interval).await;
+
+        let current = cluster_state(session).await?;
+
+        // schema is not in agreement while change is propagated, so statements are prepared once it's settled
+        if current.0.is_some() && current != previous {
+            reprepare(session).await?;
+
+            previous = current;
+        }
+    }
+}
+
+/// Schema version and host ids of known nodes.
+async fn cluster_state(session: &CachingSession) -> Result<(Option<Uuid>, Vec<Uuid>), CharybdisError> {
+    let schema_version = driver::check_schema_agreement(driver::base_session(session))
+        .await
+        .map_err(|e| CharybdisError::QueryError(ErrorContext::new(SCHEMA_AGREEMENT_QUERY), e))?;
+
+    let mut nodes = driver::known_nodes(driver::base_session(session))
+        .into_iter()
+        .map(|(host_id, _)| host_id)
+        .collect::<Vec<Uuid>>();
+
+    nodes.sort();
+
+    Ok((schema_version, nodes))
+}
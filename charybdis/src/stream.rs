@@ -1,28 +1,121 @@
+use std::marker::PhantomData;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 
+use futures::stream::{self, BoxStream};
 use futures::{Stream, StreamExt, TryStreamExt};
-use scylla::transport::iterator::{NextRowError, TypedRowIterator};
+use scylla::frame::response::result::Row;
+use scylla::transport::iterator::{NextRowError, RowIterator, TypedRowIterator};
 
-use crate::errors::CharybdisError;
+use crate::errors::{CharybdisError, ErrorContext};
 use crate::model::BaseModel;
+use crate::profiling::QueryProfile;
+use crate::session::RowStream;
+
+enum Rows<T> {
+    Raw(RowStream),
+    Typed(BoxStream<'static, Result<T, NextRowError>>),
+}
 
 pub struct CharybdisModelStream<T: BaseModel> {
-    inner: TypedRowIterator<T>,
-    query_string: &'static str,
+    inner: Rows<T>,
+    context: ErrorContext,
+    profile: Option<QueryProfile>,
+    pending_since: Option<Instant>,
+    _phantom: PhantomData<fn() -> T>,
 }
 
 impl<T: BaseModel> CharybdisModelStream<T> {
-    pub(crate) fn query_string(&mut self, query_string: &'static str) {
-        self.query_string = query_string;
+    pub(crate) fn context(&mut self, context: ErrorContext) {
+        self.context = context;
+    }
+
+    /// Starts profiling of consumed rows, `network` is time spent waiting for the first page.
+    pub(crate) fn profile(&mut self, network: Duration) {
+        self.profile = QueryProfile::start(self.context.query, network);
+    }
+
+    /// Interleaves rows of streams of the same model, e.g. of [PerPartitionQuery](crate::query::PerPartitionQuery).
+    /// Merged stream is profiled as single query.
+    pub(crate) fn merge(streams: Vec<CharybdisModelStream<T>>) -> Self {
+        let mut merged = CharybdisModelStream::from(stream::empty().boxed() as RowStream);
+        let mut rows = Vec::with_capacity(streams.len());
+
+        for mut model_stream in streams {
+            merged.context = model_stream.context;
+
+            // profiles of merged streams would be reported on drop without rows
+            let profile = model_stream.profile.take();
+            if merged.profile.is_none() {
+                merged.profile = profile;
+            }
+
+            match std::mem::replace(&mut model_stream.inner, Rows::Raw(stream::empty().boxed())) {
+                Rows::Raw(model_rows) => rows.push(model_rows),
+                Rows::Typed(_) => unreachable!("merged streams are executed by charybdis, so they are not typed"),
+            }
+        }
+
+        merged.inner = Rows::Raw(stream::select_all(rows).boxed());
+
+        merged
+    }
+
+    fn from_row(&mut self, row: Row) -> Result<T, CharybdisError> {
+        let res = match &mut self.profile {
+            Some(profile) => {
+                let start = Instant::now();
+                let res = T::from_row(row);
+
+                profile.deserialization += start.elapsed();
+                profile.rows += 1;
+
+                res
+            }
+            None => T::from_row(row),
+        };
+
+        res.map_err(|e| CharybdisError::NextRowError(self.context.column_of::<T>(&e), NextRowError::FromRowError(e)))
+    }
+
+    fn typed_error(&self, error: NextRowError) -> CharybdisError {
+        let context = match &error {
+            NextRowError::FromRowError(e) => self.context.column_of::<T>(e),
+            NextRowError::QueryError(_) => self.context,
+        };
+
+        CharybdisError::NextRowError(context, error)
     }
 }
 
-impl<T: BaseModel> From<TypedRowIterator<T>> for CharybdisModelStream<T> {
+impl<T: BaseModel> From<RowIterator> for CharybdisModelStream<T> {
+    fn from(iter: RowIterator) -> Self {
+        CharybdisModelStream::from(iter.boxed())
+    }
+}
+
+/// Rows already deserialized by driver, kept for compatibility. Their deserialization isn't profiled.
+impl<T: BaseModel + 'static> From<TypedRowIterator<T>> for CharybdisModelStream<T> {
     fn from(iter: TypedRowIterator<T>) -> Self {
         CharybdisModelStream {
-            inner: iter,
-            query_string: "",
+            inner: Rows::Typed(iter.boxed()),
+            context: ErrorContext::model::<T>(""),
+            profile: None,
+            pending_since: None,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: BaseModel> From<RowStream> for CharybdisModelStream<T> {
+    fn from(rows: RowStream) -> Self {
+        CharybdisModelStream {
+            inner: Rows::Raw(rows),
+            context: ErrorContext::model::<T>(""),
+            profile: None,
+            pending_since: None,
+            _phantom: PhantomData,
         }
     }
 }
@@ -31,16 +124,99 @@ impl<T: BaseModel> Stream for CharybdisModelStream<T> {
     type Item = Result<T, CharybdisError>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.inner
-            .poll_next_unpin(cx)
-            .map_err(|e| CharybdisError::NextRowError(self.query_string, e))
+        let this = &mut *self;
+
+        let poll = match &mut this.inner {
+            Rows::Raw(rows) => rows.poll_next_unpin(cx),
+            Rows::Typed(iter) => {
+                let poll = iter.poll_next_unpin(cx);
+
+                return poll.map(|typed| typed.map(|res| res.map_err(|e| this.typed_error(e))));
+            }
+        };
+
+        // time between first pending poll and ready poll is spent waiting for next page
+        if this.profile.is_some() {
+            if poll.is_pending() {
+                this.pending_since.get_or_insert_with(Instant::now);
+            } else if let (Some(profile), Some(since)) = (&mut this.profile, this.pending_since.take()) {
+                profile.network += since.elapsed();
+            }
+        }
+
+        match poll {
+            Poll::Ready(Some(Ok(row))) => Poll::Ready(Some(this.from_row(row))),
+            Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(CharybdisError::NextRowError(
+                this.context,
+                NextRowError::QueryError(e),
+            )))),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
     }
 }
 
 impl<T: BaseModel> CharybdisModelStream<T> {
     pub async fn try_collect(self) -> Result<Vec<T>, CharybdisError> {
-        let results: Result<Vec<T>, NextRowError> = self.inner.try_collect().await;
+        TryStreamExt::try_collect(self).await
+    }
+}
+
+/// Models of all pages of paged query, see [AutoPagedQuery](crate::query::AutoPagedQuery).
+pub struct CharybdisAutoPagedStream<'a, T: BaseModel> {
+    inner: BoxStream<'a, Result<T, CharybdisError>>,
+}
+
+impl<'a, T: BaseModel> From<BoxStream<'a, Result<T, CharybdisError>>> for CharybdisAutoPagedStream<'a, T> {
+    fn from(inner: BoxStream<'a, Result<T, CharybdisError>>) -> Self {
+        CharybdisAutoPagedStream { inner }
+    }
+}
+
+impl<T: BaseModel> Stream for CharybdisAutoPagedStream<'_, T> {
+    type Item = Result<T, CharybdisError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+impl<T: BaseModel> CharybdisAutoPagedStream<'_, T> {
+    pub async fn try_collect(self) -> Result<Vec<T>, CharybdisError> {
+        TryStreamExt::try_collect(self).await
+    }
+}
 
-        results.map_err(|e| CharybdisError::NextRowError(self.query_string, e))
+/// Models of all token ranges of full table scan, see [Scan::full_scan](crate::operations::Scan::full_scan).
+/// Rows of concurrently scanned ranges are interleaved, so they are not ordered by token.
+pub struct CharybdisScanStream<'a, T: BaseModel> {
+    inner: BoxStream<'a, Result<T, CharybdisError>>,
+}
+
+impl<'a, T: BaseModel> From<BoxStream<'a, Result<T, CharybdisError>>> for CharybdisScanStream<'a, T> {
+    fn from(inner: BoxStream<'a, Result<T, CharybdisError>>) -> Self {
+        CharybdisScanStream { inner }
+    }
+}
+
+impl<T: BaseModel> Stream for CharybdisScanStream<'_, T> {
+    type Item = Result<T, CharybdisError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+impl<T: BaseModel> CharybdisScanStream<'_, T> {
+    pub async fn try_collect(self) -> Result<Vec<T>, CharybdisError> {
+        TryStreamExt::try_collect(self).await
+    }
+}
+
+impl<T: BaseModel> Drop for CharybdisModelStream<T> {
+    fn drop(&mut self) {
+        if let Some(profile) = &self.profile {
+            profile.report();
+        }
     }
 }
@@ -0,0 +1,286 @@
+use std::error::Error;
+use std::fmt;
+
+use charybdis_parser::statement::find_keyword;
+use scylla::frame::response::result::ColumnType;
+use scylla::serialize::row::{RowSerializationContext, SerializeRow};
+use scylla::serialize::value::SerializeValue;
+use scylla::serialize::{CellWriter, RowWriter, SerializationError};
+
+use crate::errors::CharybdisError;
+use crate::model::Model;
+
+/// Implemented by callback extension that carries tenant of current request, e.g. request context built by
+/// authentication middleware.
+pub trait TenantExtension<T> {
+    /// `None` if extension is not bound to tenant, e.g. in background jobs, so operations of tenant scoped
+    /// models are rejected.
+    fn tenant_id(&self) -> Option<&T>;
+}
+
+/// Implemented by `charybdis_model` for models that declare `tenant_key`:
+/// ```rust ignore
+/// #[charybdis_model(
+///     table_name = posts,
+///     partition_keys = [org_id, category_id],
+///     clustering_keys = [id],
+///     tenant_key = "org_id"
+/// )]
+/// pub struct Post {
+///     pub org_id: Uuid,
+///     pub category_id: Uuid,
+///     pub id: Uuid,
+///     pub title: Text,
+/// }
+///
+/// impl TenantExtension<Uuid> for RequestContext {
+///     fn tenant_id(&self) -> Option<&Uuid> {
+///         self.org_id.as_ref()
+///     }
+/// }
+///
+/// impl Callbacks for Post {
+///     type Extension = RequestContext;
+///     type Error = CharybdisError;
+///
+///     fn authorize(&self, extension: &RequestContext) -> Result<(), CharybdisError> {
+///         self.authorize_tenant(extension)
+///     }
+/// }
+/// ```
+/// Callback operations of the model are rejected if model doesn't belong to tenant of extension. Other queries of
+/// the model are rejected unless they are limited to tenant of extension by
+/// [CharybdisQuery::scoped](crate::query::CharybdisQuery::scoped), or explicitly
+/// [unscoped](crate::query::CharybdisQuery::unscoped).
+pub trait TenantScoped: Model {
+    type TenantId: SerializeValue + PartialEq + fmt::Debug + Send + Sync;
+
+    fn tenant_id(&self) -> &Self::TenantId;
+
+    /// Checks that model belongs to tenant of extension.
+    fn authorize_tenant<E: TenantExtension<Self::TenantId>>(&self, extension: &E) -> Result<(), CharybdisError> {
+        match extension.tenant_id() {
+            Some(tenant_id) if tenant_id == self.tenant_id() => Ok(()),
+            Some(tenant_id) => Err(CharybdisError::TenantError(format!(
+                "{} of tenant {:?} is not accessible by tenant {:?}",
+                Self::DB_MODEL_NAME,
+                self.tenant_id(),
+                tenant_id
+            ))),
+            None => Err(CharybdisError::TenantError(format!(
+                "{} is tenant scoped, but extension has no tenant",
+                Self::DB_MODEL_NAME
+            ))),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TenantMismatchError(String);
+
+impl fmt::Display for TenantMismatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TenantError: {}", self.0)
+    }
+}
+
+impl Error for TenantMismatchError {}
+
+/// Tenant bound by [CharybdisQuery::scoped](crate::query::CharybdisQuery::scoped). Values of the query are checked
+/// on serialization, so query fails if it binds other tenant to tenant column, e.g. partition key given by request.
+/// Each tenant of list bound by `IN ?` marker of tenant column has to be tenant of the guard.
+pub struct TenantGuard<'a> {
+    column: &'static str,
+    tenant_id: Option<&'a (dyn SerializeValue + Send + Sync)>,
+}
+
+impl<'a> TenantGuard<'a> {
+    pub(crate) fn new<T: SerializeValue + Send + Sync>(column: &'static str, tenant_id: Option<&'a T>) -> Self {
+        Self {
+            column,
+            tenant_id: tenant_id.map(|tenant_id| tenant_id as &(dyn SerializeValue + Send + Sync)),
+        }
+    }
+
+    /// Serializes values of the query, if tenant column is bound and each value bound to it is tenant of the guard.
+    pub(crate) fn serialize_checked(
+        &self,
+        query_values: &impl SerializeRow,
+        ctx: &RowSerializationContext<'_>,
+        writer: &mut RowWriter,
+    ) -> Result<(), SerializationError> {
+        let tenant_id = self.tenant_id.ok_or_else(|| {
+            SerializationError::new(TenantMismatchError(format!(
+                "query is scoped by {}, but extension has no tenant",
+                self.column
+            )))
+        })?;
+
+        let mut buffer = vec![];
+        query_values.serialize(ctx, &mut RowWriter::new(&mut buffer))?;
+
+        let mut cells = buffer.as_slice();
+        let mut bound = false;
+
+        for column in ctx.columns() {
+            let cell = next_cell(&mut cells);
+
+            // `IN ?` marker is bound to list of column values and is named `in(<column>)`
+            let (typ, tenant_cells) = match (in_marker_column(&column.name), &column.typ) {
+                (Some(name), ColumnType::List(typ)) if is_column(name, self.column) => (typ.as_ref(), list_cells(cell)),
+                _ if is_column(&column.name, self.column) => (&column.typ, vec![cell]),
+                _ => continue,
+            };
+
+            bound = true;
+
+            let mut expected = vec![];
+            tenant_id.serialize(typ, CellWriter::new(&mut expected))?;
+
+            if tenant_cells.iter().any(|cell| *cell != expected.as_slice()) {
+                return Err(SerializationError::new(TenantMismatchError(format!(
+                    "query binds {} of other tenant",
+                    self.column
+                ))));
+            }
+        }
+
+        if !bound {
+            return Err(SerializationError::new(TenantMismatchError(format!(
+                "query is scoped by {}, but doesn't bind it",
+                self.column
+            ))));
+        }
+
+        query_values.serialize(ctx, writer)
+    }
+}
+
+/// Splits first cell, including its length, off serialized values.
+fn next_cell<'b>(cells: &mut &'b [u8]) -> &'b [u8] {
+    let len = match cells.get(..4) {
+        Some(len) => i32::from_be_bytes([len[0], len[1], len[2], len[3]]),
+        None => return &[],
+    };
+
+    // null and unset cells are only length
+    let end = (4 + len.max(0) as usize).min(cells.len());
+    let (cell, rest) = cells.split_at(end);
+
+    *cells = rest;
+
+    cell
+}
+
+/// Splits serialized list into cells of its elements.
+fn list_cells(cell: &[u8]) -> Vec<&[u8]> {
+    let Some(count) = cell.get(4..8) else {
+        return vec![];
+    };
+
+    let count = i32::from_be_bytes([count[0], count[1], count[2], count[3]]).max(0);
+    let mut elements = &cell[8..];
+
+    (0..count).map(|_| next_cell(&mut elements)).collect()
+}
+
+/// Column of `IN ?` marker, e.g. `in(org_id)` -> `org_id`.
+fn in_marker_column(name: &str) -> Option<&str> {
+    name.strip_prefix("in(")?.strip_suffix(')')
+}
+
+/// True if WHERE clause of statement references given column.
+pub(crate) fn restricts_column(statement: &str, column: &str) -> bool {
+    let conditions = match find_keyword(statement, " WHERE ") {
+        Some(position) => &statement[position + " WHERE ".len()..],
+        None => return false,
+    };
+
+    // every other part split by quote is string literal, doubled quotes within literal split off empty part
+    conditions
+        .split('\'')
+        .step_by(2)
+        .flat_map(|part| part.split(|c: char| !c.is_alphanumeric() && c != '_' && c != '"'))
+        .any(|identifier| is_column(identifier, column))
+}
+
+/// Compares column names, either of them may be quoted, e.g. `TENANT_COLUMN` of case sensitive column.
+fn is_column(name: &str, column: &str) -> bool {
+    name.trim_matches('"') == column.trim_matches('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_cell_splits_cells_with_their_length() {
+        let values = [0, 0, 0, 2, 7, 8, 255, 255, 255, 255, 0, 0, 0, 1, 9];
+        let mut cells = values.as_slice();
+
+        assert_eq!(next_cell(&mut cells), &[0, 0, 0, 2, 7, 8]);
+        // null cell is only its length
+        assert_eq!(next_cell(&mut cells), &[255, 255, 255, 255]);
+        assert_eq!(next_cell(&mut cells), &[0, 0, 0, 1, 9]);
+        assert_eq!(next_cell(&mut cells), &[] as &[u8]);
+    }
+
+    #[test]
+    fn next_cell_stops_at_truncated_values() {
+        let values = [0, 0, 0, 4, 7];
+        let mut cells = values.as_slice();
+
+        assert_eq!(next_cell(&mut cells), &[0, 0, 0, 4, 7]);
+        assert!(cells.is_empty());
+    }
+
+    #[test]
+    fn list_cells_splits_elements_of_list() {
+        let list = [0, 0, 0, 14, 0, 0, 0, 2, 0, 0, 0, 1, 7, 0, 0, 0, 1, 8];
+
+        assert_eq!(list_cells(&list), vec![&[0, 0, 0, 1, 7][..], &[0, 0, 0, 1, 8][..]]);
+        // empty list and null cell
+        assert!(list_cells(&[0, 0, 0, 4, 0, 0, 0, 0]).is_empty());
+        assert!(list_cells(&[255, 255, 255, 255]).is_empty());
+    }
+
+    #[test]
+    fn in_marker_column_is_column_of_in_restriction() {
+        assert_eq!(in_marker_column("in(org_id)"), Some("org_id"));
+        assert_eq!(in_marker_column(r#"in("orgId")"#), Some(r#""orgId""#));
+        assert_eq!(in_marker_column("org_id"), None);
+        assert_eq!(in_marker_column("inside"), None);
+    }
+
+    #[test]
+    fn restricts_column_checks_only_where_clause() {
+        assert!(restricts_column(
+            "SELECT id FROM posts WHERE org_id = ? AND id = ?",
+            "org_id"
+        ));
+        assert!(restricts_column(
+            "SELECT id FROM posts WHERE id = ? AND org_id IN ?",
+            "org_id"
+        ));
+        assert!(!restricts_column("SELECT org_id FROM posts WHERE id = ?", "org_id"));
+        assert!(!restricts_column(
+            "SELECT id FROM posts WHERE parent_org_id = ?",
+            "org_id"
+        ));
+        assert!(!restricts_column("SELECT id FROM posts", "org_id"));
+        assert!(!restricts_column(
+            r#"SELECT id FROM posts WHERE title = ' WHERE org_id = '"#,
+            "org_id"
+        ));
+    }
+
+    #[test]
+    fn restricts_column_compares_unquoted_names() {
+        assert!(restricts_column(
+            r#"SELECT id FROM posts WHERE "orgId" = ?"#,
+            r#""orgId""#
+        ));
+        assert!(restricts_column(r#"SELECT id FROM posts WHERE "orgId" = ?"#, "orgId"));
+        assert!(is_column("orgId", r#""orgId""#));
+    }
+}
@@ -27,7 +27,7 @@ pub type Timestamp = chrono::DateTime<Utc>;
 pub type TinyInt = i8;
 pub type Uuid = uuid::Uuid;
 pub type Varchar = String;
-pub type Varint = BigInt;
+pub type Varint = num_bigint::BigInt;
 // collections
 pub type Map<K, V> = HashMap<K, V>;
 pub type List<T> = Vec<T>;
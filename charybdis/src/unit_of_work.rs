@@ -0,0 +1,175 @@
+use futures::future::BoxFuture;
+use scylla::serialize::row::SerializeRow;
+
+use crate::batch::CharybdisBatch;
+use crate::callbacks::{CallbackAction, Callbacks};
+use crate::driver::CachingSession;
+use crate::errors::{CharybdisError, ErrorContext};
+use crate::model::{Model, Version};
+use crate::query::{is_applied, CharybdisCbQuery};
+
+/// Callback query registered in [UnitOfWork], with type of its model erased.
+trait PendingWrite<E>: Send {
+    fn before<'b>(&'b mut self, session: &'b CachingSession) -> BoxFuture<'b, Result<(), E>>;
+
+    /// Appends statement to the batch and returns version written by versioned update.
+    fn append<'b>(&'b self, batch: &mut CharybdisBatch<'b>) -> Option<Version>;
+
+    fn after<'b>(
+        &'b mut self,
+        session: &'b CachingSession,
+        next_version: Option<Version>,
+    ) -> BoxFuture<'b, Result<(), E>>;
+
+    /// Error context of conditional statement, e.g. update of versioned model.
+    fn conditional(&self) -> Option<ErrorContext>;
+}
+
+impl<M, CbA, Val, E> PendingWrite<E> for CharybdisCbQuery<'_, M, CbA, Val>
+where
+    M: Callbacks + Sync + Send,
+    M::Error: Into<E>,
+    CbA: CallbackAction<M>,
+    Val: SerializeRow + Sync + Send,
+    E: From<CharybdisError>,
+{
+    fn before<'b>(&'b mut self, session: &'b CachingSession) -> BoxFuture<'b, Result<(), E>> {
+        Box::pin(async move {
+            let extension = self.extension.get()?;
+
+            CbA::before_execute(self.model, session, extension)
+                .await
+                .map_err(Into::into)
+        })
+    }
+
+    fn append<'b>(&'b self, batch: &mut CharybdisBatch<'b>) -> Option<Version> {
+        if self.bind_model_values {
+            let values = CbA::query_value::<Val>(self.model);
+            let next_version = values.next_version();
+
+            batch.append_values(self.inner.contents(), values);
+
+            next_version
+        } else {
+            batch.append_values(self.inner.contents(), &self.inner.values);
+
+            self.inner.values.next_version()
+        }
+    }
+
+    fn after<'b>(
+        &'b mut self,
+        session: &'b CachingSession,
+        next_version: Option<Version>,
+    ) -> BoxFuture<'b, Result<(), E>> {
+        Box::pin(async move {
+            let extension = self.extension.get()?;
+
+            if let Some(next_version) = next_version {
+                self.model.increment_version(next_version);
+            }
+
+            CbA::after_execute(self.model, session, extension)
+                .await
+                .map_err(Into::into)
+        })
+    }
+
+    fn conditional(&self) -> Option<ErrorContext> {
+        self.inner.check_applied.then(|| self.inner.error_context())
+    }
+}
+
+/// Collects callback queries of models, so they are written by single logged batch:
+/// ```rust ignore
+/// let mut unit = UnitOfWork::new();
+///
+/// unit.add(post.insert_cb(&extension))
+///     .add(category.update_cb(&extension))
+///     .add(draft.delete_cb(&extension));
+///
+/// unit.commit(&session).await?;
+/// ```
+/// `commit` runs `before_<action>` callbacks of all queries in order they were added, executes their statements
+/// by single logged batch, and runs `after_<action>` callbacks once batch is applied. If any of `before_<action>`
+/// callbacks fails, nothing is written.
+///
+/// Logged batch is applied atomically, i.e. either all or none of its statements are eventually applied, but it's
+/// isolated only if all of them write the same partition, so unit of work is meant for multi-row changes of single
+/// partition. Versioned updates make batch conditional, which requires all statements to write the same partition,
+/// and `StaleObjectError` is returned if batch is not applied.
+///
+/// Options set on registered queries, e.g. consistency, are not applied to batch. Models of different callback error
+/// types can be added to the same unit, as long as their errors convert to error of the unit.
+pub struct UnitOfWork<'a, E = CharybdisError> {
+    writes: Vec<Box<dyn PendingWrite<E> + 'a>>,
+}
+
+impl<'a, E: From<CharybdisError> + 'a> UnitOfWork<'a, E> {
+    pub fn new() -> Self {
+        Self { writes: vec![] }
+    }
+
+    pub fn add<M, CbA, Val>(&mut self, query: CharybdisCbQuery<'a, M, CbA, Val>) -> &mut Self
+    where
+        M: Callbacks + Sync + Send + 'a,
+        M::Extension: 'a,
+        M::Error: Into<E>,
+        CbA: CallbackAction<M> + 'a,
+        Val: SerializeRow + Sync + Send + 'a,
+    {
+        self.writes.push(Box::new(query));
+
+        self
+    }
+
+    /// Number of registered queries.
+    pub fn len(&self) -> usize {
+        self.writes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    pub async fn commit(mut self, session: &CachingSession) -> Result<(), E> {
+        if self.writes.is_empty() {
+            return Ok(());
+        }
+
+        for write in self.writes.iter_mut() {
+            write.before(session).await?;
+        }
+
+        let mut next_versions = Vec::with_capacity(self.writes.len());
+
+        let res = {
+            let mut batch = CharybdisBatch::new();
+
+            for write in &self.writes {
+                next_versions.push(write.append(&mut batch));
+            }
+
+            batch.execute(session).await?
+        };
+
+        if let Some(context) = self.writes.iter().find_map(|write| write.conditional()) {
+            if !is_applied(&res) {
+                return Err(CharybdisError::StaleObjectError(context).into());
+            }
+        }
+
+        for (write, next_version) in self.writes.iter_mut().zip(next_versions) {
+            write.after(session, next_version).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, E: From<CharybdisError> + 'a> Default for UnitOfWork<'a, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
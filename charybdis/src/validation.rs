@@ -0,0 +1,231 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+
+use crate::types::{Date, Timestamp};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub field: &'static str,
+    /// name of failed validator, e.g. `email`, `length` or `custom`
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// Errors of all fields that failed validation, returned by generated `validate` method.
+/// ```rust ignore
+/// match user.insert_cb(&None).execute(&session).await {
+///     Err(CharybdisError::ValidationError(errors)) => {
+///         for error in errors.field_errors("email") {
+///             println!("{}: {}", error.code, error.message);
+///         }
+///     }
+///     res => res?,
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationErrors {
+    errors: Vec<ValidationError>,
+}
+
+impl ValidationErrors {
+    pub fn add(&mut self, field: &'static str, code: &'static str, message: impl Into<String>) {
+        self.errors.push(ValidationError {
+            field,
+            code,
+            message: message.into(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn errors(&self) -> &[ValidationError] {
+        &self.errors
+    }
+
+    pub fn field_errors<'a>(&'a self, field: &'a str) -> impl Iterator<Item = &'a ValidationError> {
+        self.errors.iter().filter(move |error| error.field == field)
+    }
+
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let errors = self
+            .errors
+            .iter()
+            .map(|error| format!("{} {}", error.field, error.message))
+            .collect::<Vec<String>>();
+
+        write!(f, "{}", errors.join(", "))
+    }
+}
+
+impl Error for ValidationErrors {}
+
+/// Text value checked by `email` validator. `None` values are not validated.
+pub trait ValidatedStr {
+    fn validated_str(&self) -> Option<&str>;
+}
+
+/// Length of text or collection checked by `not_empty` and `length` validators. Text length is counted in
+/// characters. `None` values are not validated.
+pub trait ValidatedLength {
+    fn validated_length(&self) -> Option<usize>;
+}
+
+/// Value checked by `range` validator. `None` values are not validated.
+pub trait ValidatedValue {
+    type Value: PartialOrd + fmt::Display;
+
+    fn validated_value(&self) -> Option<&Self::Value>;
+}
+
+impl ValidatedStr for String {
+    fn validated_str(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+impl<T: ValidatedStr> ValidatedStr for Option<T> {
+    fn validated_str(&self) -> Option<&str> {
+        self.as_ref().and_then(ValidatedStr::validated_str)
+    }
+}
+
+impl ValidatedLength for String {
+    fn validated_length(&self) -> Option<usize> {
+        Some(self.chars().count())
+    }
+}
+
+impl<T> ValidatedLength for Vec<T> {
+    fn validated_length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T> ValidatedLength for HashSet<T> {
+    fn validated_length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T> ValidatedLength for BTreeSet<T> {
+    fn validated_length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<K, V> ValidatedLength for HashMap<K, V> {
+    fn validated_length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<K, V> ValidatedLength for BTreeMap<K, V> {
+    fn validated_length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T: ValidatedLength> ValidatedLength for Option<T> {
+    fn validated_length(&self) -> Option<usize> {
+        self.as_ref().and_then(ValidatedLength::validated_length)
+    }
+}
+
+macro_rules! impl_validated_value {
+    ($($ty:ty),*) => {
+        $(
+            impl ValidatedValue for $ty {
+                type Value = $ty;
+
+                fn validated_value(&self) -> Option<&Self::Value> {
+                    Some(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_validated_value!(i8, i16, i32, i64, f32, f64, Date, Timestamp);
+
+#[cfg(feature = "time")]
+impl_validated_value!(time::Date, time::OffsetDateTime);
+
+impl<T: ValidatedValue> ValidatedValue for Option<T> {
+    type Value = T::Value;
+
+    fn validated_value(&self) -> Option<&Self::Value> {
+        self.as_ref().and_then(ValidatedValue::validated_value)
+    }
+}
+
+/// Basic structural check: single `@` with non-empty local part and dotted domain, without whitespace.
+pub fn email(value: &impl ValidatedStr) -> Result<(), String> {
+    let value = match value.validated_str() {
+        Some(value) => value,
+        None => return Ok(()),
+    };
+
+    let is_valid = match value.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !value.chars().any(char::is_whitespace)
+        }
+        None => false,
+    };
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err("must be a valid email address".to_string())
+    }
+}
+
+pub fn not_empty(value: &impl ValidatedLength) -> Result<(), String> {
+    match value.validated_length() {
+        Some(0) => Err("must not be empty".to_string()),
+        _ => Ok(()),
+    }
+}
+
+pub fn length(value: &impl ValidatedLength, min: Option<usize>, max: Option<usize>) -> Result<(), String> {
+    let length = match value.validated_length() {
+        Some(length) => length,
+        None => return Ok(()),
+    };
+
+    match (min, max) {
+        (Some(min), _) if length < min => Err(format!("length must be at least {}", min)),
+        (_, Some(max)) if length > max => Err(format!("length must be at most {}", max)),
+        _ => Ok(()),
+    }
+}
+
+pub fn range<V: ValidatedValue>(value: &V, min: Option<V::Value>, max: Option<V::Value>) -> Result<(), String> {
+    let value = match value.validated_value() {
+        Some(value) => value,
+        None => return Ok(()),
+    };
+
+    match (min, max) {
+        (Some(min), _) if *value < min => Err(format!("must be at least {}", min)),
+        (_, Some(max)) if *value > max => Err(format!("must be at most {}", max)),
+        _ => Ok(()),
+    }
+}